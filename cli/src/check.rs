@@ -1,6 +1,14 @@
-use crate::api::check;
-use crate::{cli_input::CheckCommand, errors::CliError, report};
+use crate::api::{check, graphql::mutations::SchemaCheckStep};
+use crate::{
+    cli_input::{CheckCommand, DiagnosticsFormat, FullGraphRef},
+    compose::diagnostics::{self, Report},
+    dev::fetch_remote_subgraphs,
+    errors::CliError,
+    report,
+};
+use graphql_composition::diagnostics::Diagnostic;
 use std::{
+    collections::HashMap,
     fs,
     io::{IsTerminal, Read},
     process::Command,
@@ -14,8 +22,11 @@ pub(crate) async fn check(command: CheckCommand) -> Result<(), CliError> {
         graph_ref,
         subgraph_name,
         schema,
+        diagnostics_format,
     } = command;
 
+    let schema_path = schema.clone();
+
     let git_commit = find_git_commit();
 
     let schema = match schema {
@@ -34,7 +45,9 @@ pub(crate) async fn check(command: CheckCommand) -> Result<(), CliError> {
         }
     };
 
-    report::checking();
+    if diagnostics_format == DiagnosticsFormat::Human {
+        report::checking();
+    }
 
     let result = check::check(
         graph_ref.account(),
@@ -58,19 +71,113 @@ pub(crate) async fn check(command: CheckCommand) -> Result<(), CliError> {
         }
     };
 
-    if diagnostics.is_empty() {
-        report::check_success();
-    } else {
-        report::check_errors(error_count != 0, &diagnostics);
+    let (local_diagnostics, sources) = compose_locally(&graph_ref, &subgraph_name, &schema).await;
+
+    // The API decides whether the check passes. Local composition only locates its composition
+    // diagnostics in the subgraph schemas.
+    let diagnostics: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let location = local_diagnostics
+                .iter()
+                .filter(|_| diagnostic.step == SchemaCheckStep::Composition)
+                .find(|local| local.message() == diagnostic.message)
+                .and_then(Diagnostic::location);
+
+            (diagnostic, location)
+        })
+        .collect();
+
+    match diagnostics_format {
+        DiagnosticsFormat::Human => {
+            if diagnostics.is_empty() {
+                report::check_success();
+                return Ok(());
+            }
 
-        if error_count > 0 {
-            std::process::exit(FAILED_CHECK_EXIT_STATUS);
+            let has_errors = diagnostics
+                .iter()
+                .any(|(diagnostic, _)| matches!(diagnostic.severity, check::SchemaCheckErrorSeverity::Error));
+
+            report::check_errors(has_errors, &diagnostics, &sources);
+        }
+        DiagnosticsFormat::Json | DiagnosticsFormat::Sarif => {
+            let reports = diagnostics.iter().map(|(diagnostic, location)| Report {
+                severity: match diagnostic.severity {
+                    check::SchemaCheckErrorSeverity::Error => "error",
+                    check::SchemaCheckErrorSeverity::Warning => "warning",
+                },
+                message: &diagnostic.message,
+                code: Some(format!("{:?}", diagnostic.step)),
+                location: *location,
+            });
+
+            let output = if diagnostics_format == DiagnosticsFormat::Json {
+                diagnostics::to_json(reports, &sources)
+            } else {
+                diagnostics::to_sarif(reports, &sources, |name| {
+                    schema_path.clone().filter(|_| name == subgraph_name)
+                })
+            };
+
+            println!("{output:#}");
         }
     }
 
+    if error_count > 0 {
+        std::process::exit(FAILED_CHECK_EXIT_STATUS);
+    }
+
     Ok(())
 }
 
+/// Composes the checked schema with the other subgraphs of the branch, returning the composition
+/// diagnostics along with the subgraph schemas they point to. This is best-effort: if the
+/// subgraphs can't be fetched or ingested, nothing is returned and the API diagnostics are
+/// reported without locations.
+async fn compose_locally(
+    graph_ref: &FullGraphRef,
+    subgraph_name: &str,
+    schema: &str,
+) -> (Vec<Diagnostic>, HashMap<String, String>) {
+    let remote_subgraphs = match fetch_remote_subgraphs(graph_ref).await {
+        Ok(remote_subgraphs) => remote_subgraphs,
+        Err(error) => {
+            tracing::warn!("Could not fetch the subgraphs to locate composition diagnostics: {error}");
+            return Default::default();
+        }
+    };
+
+    let mut sources = HashMap::from([(subgraph_name.to_owned(), schema.to_owned())]);
+    let mut subgraphs = graphql_composition::Subgraphs::default();
+    let mut url = None;
+
+    for subgraph in remote_subgraphs {
+        if subgraph.name == subgraph_name {
+            url = subgraph.url;
+            continue;
+        }
+
+        if subgraphs
+            .ingest_str(&subgraph.schema, &subgraph.name, subgraph.url.as_deref())
+            .is_err()
+        {
+            return Default::default();
+        }
+
+        sources.insert(subgraph.name, subgraph.schema);
+    }
+
+    if subgraphs.ingest_str(schema, subgraph_name, url.as_deref()).is_err() {
+        return Default::default();
+    }
+
+    let result = graphql_composition::compose(&mut subgraphs);
+    let diagnostics = result.diagnostics().iter().cloned().collect();
+
+    (diagnostics, sources)
+}
+
 fn find_git_commit() -> Option<check::SchemaCheckGitCommitInput> {
     let git_author = git_author();
     let git_sha = git_sha();
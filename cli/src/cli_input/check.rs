@@ -1,4 +1,4 @@
-use super::FullGraphRef;
+use super::{DiagnosticsFormat, FullGraphRef};
 
 #[derive(Debug, clap::Args)]
 pub struct CheckCommand {
@@ -12,4 +12,8 @@ pub struct CheckCommand {
     /// from stdin.
     #[arg(long)]
     pub schema: Option<String>,
+
+    /// The format of the check warnings and errors
+    #[arg(long, value_enum, default_value_t = DiagnosticsFormat::Human)]
+    pub(crate) diagnostics_format: DiagnosticsFormat,
 }
@@ -13,6 +13,21 @@ pub(crate) struct ComposeCommand {
     /// The path of the gateway configuration file
     #[arg(short('c'), long("config"))]
     config_path: Option<PathBuf>,
+    /// The format of the composition warnings and errors. When composition succeeds, the schema is
+    /// printed to stdout and the json or sarif warnings to stderr
+    #[arg(long, value_enum, default_value_t = DiagnosticsFormat::Human)]
+    pub(crate) diagnostics_format: DiagnosticsFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum DiagnosticsFormat {
+    /// Messages with annotated subgraph schema snippets
+    Human,
+    /// A JSON array of diagnostics
+    Json,
+    /// A SARIF 2.1.0 log, for code scanning tools
+    Sarif,
 }
 
 impl ComposeCommand {
@@ -6,4 +6,7 @@ use std::path::PathBuf;
 pub struct LintCommand {
     /// The path of the schema to lint
     pub schema: Option<PathBuf>,
+    /// The path of a TOML file enabling, disabling or setting the severity of lint rules
+    #[arg(long)]
+    pub config: Option<PathBuf>,
 }
@@ -1,4 +1,12 @@
-use crate::{cli_input::ComposeCommand, dev::SubgraphCache, output::report};
+pub(crate) mod diagnostics;
+pub(crate) mod satisfiability;
+
+use self::diagnostics::Report;
+use crate::{
+    cli_input::{ComposeCommand, DiagnosticsFormat},
+    dev::SubgraphCache,
+    output::report,
+};
 
 #[tokio::main]
 pub(crate) async fn compose(args: ComposeCommand) -> anyhow::Result<()> {
@@ -8,20 +16,64 @@ pub(crate) async fn compose(args: ComposeCommand) -> anyhow::Result<()> {
         return Err(anyhow::anyhow!("No subgraphs found"));
     }
 
-    let (warnings_sender, _warnings_receiver) = tokio::sync::mpsc::channel(1);
+    // The warnings are reported from the composition result.
+    let (warnings_sender, _) = tokio::sync::mpsc::channel(1);
 
     let subgraph_cache = SubgraphCache::new(args.graph_ref.as_ref(), &config, warnings_sender).await?;
 
     let result = subgraph_cache.compose().await?;
+    let sources = subgraph_cache.subgraph_sdls().await;
+
+    let schema_path = |subgraph_name: &str| {
+        config
+            .subgraphs
+            .get(subgraph_name)
+            .and_then(|subgraph| subgraph.schema_path.as_ref())
+            .map(|path| path.display().to_string())
+    };
 
     match result {
-        Ok(schema) => {
-            println!("{schema}");
+        Ok(composed_graph) => {
+            println!("{}", composed_graph.federated_sdl);
+
+            let unsatisfiable_fields = satisfiability::check(&composed_graph.federated_sdl).await;
+
+            // The schema is on stdout, so the warnings go to stderr.
+            let warnings = composed_graph
+                .warnings
+                .iter()
+                .chain(unsatisfiable_fields.iter())
+                .map(Report::from);
+
+            match args.diagnostics_format {
+                DiagnosticsFormat::Human => {
+                    for warning in unsatisfiable_fields.iter_warnings() {
+                        eprintln!("⚠️ Warning: {warning}\n");
+                    }
+                }
+                DiagnosticsFormat::Json => {
+                    eprintln!("{:#}", diagnostics::to_json(warnings, &sources));
+                }
+                DiagnosticsFormat::Sarif => {
+                    eprintln!("{:#}", diagnostics::to_sarif(warnings, &sources, schema_path));
+                }
+            }
 
             Ok(())
         }
         Err(diagnostics) => {
-            report::composition_diagnostics(&diagnostics);
+            let reports = diagnostics.iter().map(Report::from);
+
+            match args.diagnostics_format {
+                DiagnosticsFormat::Human => report::composition_diagnostics(diagnostics.iter(), &sources),
+                DiagnosticsFormat::Json => {
+                    println!("{:#}", diagnostics::to_json(reports, &sources));
+                }
+                DiagnosticsFormat::Sarif => {
+                    println!("{:#}", diagnostics::to_sarif(reports, &sources, schema_path));
+                }
+            }
+
             std::process::exit(1)
         }
     }
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use graphql_composition::diagnostics::{Diagnostic, DiagnosticLocation, Severity};
+use serde_json::{Value, json};
+
+/// A diagnostic rendered as JSON or SARIF, from composition or from another source like the schema
+/// check API.
+pub(crate) struct Report<'a> {
+    pub(crate) severity: &'static str,
+    pub(crate) message: &'a str,
+    pub(crate) code: Option<String>,
+    pub(crate) location: Option<&'a DiagnosticLocation>,
+}
+
+impl<'a> From<&'a Diagnostic> for Report<'a> {
+    fn from(diagnostic: &'a Diagnostic) -> Self {
+        Report {
+            severity: match diagnostic.severity() {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            },
+            message: diagnostic.message(),
+            code: diagnostic
+                .composite_schemas_error_code()
+                .map(|code| format!("{code:?}")),
+            location: diagnostic.location(),
+        }
+    }
+}
+
+/// A range of lines and columns in a subgraph schema, all 1-based.
+pub(crate) struct Region {
+    pub(crate) start_line: usize,
+    pub(crate) start_column: usize,
+    pub(crate) end_line: usize,
+    pub(crate) end_column: usize,
+}
+
+/// The region of the diagnostic location in the SDL of its subgraph, if both are known.
+pub(crate) fn region(location: &DiagnosticLocation, sources: &HashMap<String, String>) -> Option<Region> {
+    let source = sources.get(location.subgraph_name())?;
+    let span = location.span()?;
+
+    let (start_line, start_column) = line_column(source, span.start);
+    let (end_line, end_column) = line_column(source, span.end);
+
+    Some(Region {
+        start_line,
+        start_column,
+        end_line,
+        end_column,
+    })
+}
+
+/// The 1-based line and column of a byte offset in the source.
+pub(crate) fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |line| line.chars().count()) + 1;
+
+    (line, column)
+}
+
+pub(crate) fn to_json<'a>(reports: impl Iterator<Item = Report<'a>>, sources: &HashMap<String, String>) -> Value {
+    let diagnostics = reports
+        .map(|report| {
+            let location = report.location;
+
+            json!({
+                "severity": report.severity,
+                "message": report.message,
+                "code": report.code,
+                "subgraph": location.map(|location| location.subgraph_name()),
+                "coordinate": location.and_then(|location| location.schema_coordinate()),
+                "region": location.and_then(|location| region(location, sources)).map(|region| json!({
+                    "startLine": region.start_line,
+                    "startColumn": region.start_column,
+                    "endLine": region.end_line,
+                    "endColumn": region.end_column,
+                })),
+            })
+        })
+        .collect();
+
+    Value::Array(diagnostics)
+}
+
+/// See https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+///
+/// `schema_path` returns the path of the schema file of a subgraph, when it has one.
+pub(crate) fn to_sarif<'a>(
+    reports: impl Iterator<Item = Report<'a>>,
+    sources: &HashMap<String, String>,
+    schema_path: impl Fn(&str) -> Option<String>,
+) -> Value {
+    let results = reports
+        .map(|report| {
+            let mut result = json!({
+                "ruleId": report.code.unwrap_or_else(|| "composition".to_owned()),
+                "level": report.severity,
+                "message": { "text": report.message },
+            });
+
+            if let Some(location) = report.location {
+                // Point to the schema file when the subgraph has one.
+                let uri = schema_path(location.subgraph_name()).unwrap_or_else(|| location.subgraph_name().to_owned());
+
+                let mut physical_location = json!({ "artifactLocation": { "uri": uri } });
+
+                if let Some(region) = region(location, sources) {
+                    physical_location["region"] = json!({
+                        "startLine": region.start_line,
+                        "startColumn": region.start_column,
+                        "endLine": region.end_line,
+                        "endColumn": region.end_column,
+                    });
+                }
+
+                let mut sarif_location = json!({ "physicalLocation": physical_location });
+
+                if let Some(coordinate) = location.schema_coordinate() {
+                    sarif_location["logicalLocations"] = json!([{ "fullyQualifiedName": coordinate }]);
+                }
+
+                result["locations"] = json!([sarif_location]);
+            }
+
+            result
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "grafbase",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://grafbase.com",
+                }
+            },
+            "results": results,
+        }],
+    })
+}
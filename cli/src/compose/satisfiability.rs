@@ -0,0 +1,24 @@
+/// Checks that the query planner can resolve every field of the federated graph, reporting the
+/// fields it can't resolve from any root as composition warnings. Only `grafbase compose` runs it,
+/// it's too slow for every `grafbase dev` reload.
+pub(crate) async fn check(federated_schema: &str) -> graphql_composition::Diagnostics {
+    let mut diagnostics = graphql_composition::Diagnostics::default();
+
+    let schema = match engine::Schema::builder(federated_schema).build().await {
+        Ok(schema) => schema,
+        Err(errors) => {
+            tracing::debug!("Skipping satisfiability check: {}", errors.join("\n"));
+            return diagnostics;
+        }
+    };
+
+    for unsatisfiable_field in engine::check_satisfiability(&schema) {
+        diagnostics.push_unsatisfiable_field(
+            &unsatisfiable_field.field,
+            &unsatisfiable_field.error,
+            &unsatisfiable_field.example_query,
+        );
+    }
+
+    diagnostics
+}
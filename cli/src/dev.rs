@@ -1,9 +1,10 @@
 mod assets;
 mod data_json;
 mod hot_reload;
+mod mock_subgraphs;
 mod subgraphs;
 
-pub(crate) use self::subgraphs::SubgraphCache;
+pub(crate) use self::subgraphs::{SubgraphCache, fetch_remote_subgraphs};
 
 use super::errors::BackendError;
 use crate::{
@@ -104,7 +105,7 @@ async fn start(args: DevCommand, logging_filter: String) -> anyhow::Result<()> {
     let composition_result = subgraph_cache.compose().await?;
 
     let federated_sdl = match composition_result {
-        Ok(federated_schema) => federated_schema,
+        Ok(composed_graph) => composed_graph.federated_sdl,
         Err(diagnostics) => {
             return Err(BackendError::Composition(diagnostics.iter_errors().collect::<Vec<_>>().join("\n")).into());
         }
@@ -115,7 +116,7 @@ async fn start(args: DevCommand, logging_filter: String) -> anyhow::Result<()> {
         .await
         .expect("this really has to succeed");
 
-    let (config_sender, config_receiver) = watch::channel(config.clone());
+    let (config_sender, config_receiver) = watch::channel(mock_subgraphs::without_mocked_subgraph_urls(config.clone()));
 
     let config = Arc::new(config);
     tokio::spawn(hot_reload(
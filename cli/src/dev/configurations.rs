@@ -38,10 +38,12 @@ pub(crate) async fn get_and_merge_configurations(
             &subgraph.introspection_url,
             &subgraph.introspection_headers,
             &subgraph.schema_path,
+            subgraph.mock,
         ) {
-            (Some(_), _, _) => return Err(BackendError::DevOptionsInGatewayConfig("introspection_url")),
-            (_, Some(_), _) => return Err(BackendError::DevOptionsInGatewayConfig("introspection_headers")),
-            (_, _, Some(_)) => return Err(BackendError::DevOptionsInGatewayConfig("schema_path")),
+            (Some(_), _, _, _) => return Err(BackendError::DevOptionsInGatewayConfig("introspection_url")),
+            (_, Some(_), _, _) => return Err(BackendError::DevOptionsInGatewayConfig("introspection_headers")),
+            (_, _, Some(_), _) => return Err(BackendError::DevOptionsInGatewayConfig("schema_path")),
+            (_, _, _, true) => return Err(BackendError::DevOptionsInGatewayConfig("mock")),
             _ => {}
         }
     }
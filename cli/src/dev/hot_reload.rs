@@ -1,7 +1,7 @@
 mod subgraph_watcher;
 
 use self::subgraph_watcher::*;
-use super::subgraphs::SubgraphCache;
+use super::{mock_subgraphs::without_mocked_subgraph_urls, subgraphs::SubgraphCache};
 use crate::errors::BackendError;
 use gateway_config::Config;
 use notify_debouncer_full::{
@@ -114,7 +114,7 @@ pub(crate) async fn hot_reload(
             }
         };
 
-        if let Err(err) = config_sender.send(config.clone()) {
+        if let Err(err) = config_sender.send(without_mocked_subgraph_urls(config.clone())) {
             tracing::error!("Could not update config: {err}");
             continue;
         };
@@ -144,7 +144,7 @@ async fn reload_subgraphs(
     let composition_result = subgraph_cache.compose().await?;
 
     let federated_sdl = match composition_result {
-        Ok(result) => result,
+        Ok(composed_graph) => composed_graph.federated_sdl,
         Err(diagnostics) => {
             return Err(BackendError::Composition(
                 diagnostics.iter_messages().collect::<Vec<_>>().join("\n"),
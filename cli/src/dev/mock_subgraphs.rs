@@ -0,0 +1,77 @@
+use gateway_config::Config;
+use graphql_mocks::{MockGraphQlServer, dynamic::DynamicSchema};
+use std::collections::{HashMap, HashSet};
+
+/// Local servers responding with generated data for the subgraphs with `mock = true`.
+#[derive(Default)]
+pub(crate) struct MockSubgraphs {
+    /// The names of the subgraphs to mock.
+    enabled: HashSet<String>,
+    /// subgraph name -> running mock server
+    servers: HashMap<String, MockSubgraph>,
+}
+
+struct MockSubgraph {
+    sdl: String,
+    server: MockGraphQlServer,
+}
+
+impl MockSubgraphs {
+    /// Take the mocked subgraphs from the configuration, stopping the servers of the subgraphs
+    /// that aren't mocked anymore.
+    pub(crate) fn configure(&mut self, config: &Config) {
+        self.enabled = config
+            .subgraphs
+            .iter()
+            .filter(|(_, subgraph)| subgraph.mock)
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        self.servers.retain(|name, _| self.enabled.contains(name));
+    }
+
+    /// The URL of the mock server for the subgraph, or `None` if it isn't mocked. The server is
+    /// started on first use and restarted when the subgraph SDL changes.
+    pub(crate) async fn url(&mut self, name: &str, sdl: &str) -> anyhow::Result<Option<String>> {
+        if !self.enabled.contains(name) {
+            return Ok(None);
+        }
+
+        if let Some(mock) = self.servers.get(name)
+            && mock.sdl == sdl
+        {
+            return Ok(Some(mock.server.url().to_string()));
+        }
+
+        let schema = DynamicSchema::builder(sdl)
+            .with_fake_data()
+            .try_finish()
+            .map_err(|error| anyhow::anyhow!("Could not mock the subgraph {name}:\n{error}"))?;
+
+        // A dev session can run for long, the requests aren't kept as they are in tests.
+        let server = MockGraphQlServer::builder(schema).without_recording().build().await;
+        let url = server.url().to_string();
+
+        self.servers.insert(
+            name.to_owned(),
+            MockSubgraph {
+                sdl: sdl.to_owned(),
+                server,
+            },
+        );
+
+        Ok(Some(url))
+    }
+}
+
+/// The gateway configuration with the URLs of the mocked subgraphs removed, so that the gateway
+/// uses the mock server URLs from the federated schema instead.
+pub(crate) fn without_mocked_subgraph_urls(mut config: Config) -> Config {
+    for subgraph in config.subgraphs.values_mut().filter(|subgraph| subgraph.mock) {
+        subgraph.url = None;
+        subgraph.websocket_url = None;
+        subgraph.load_balancing = None;
+    }
+
+    config
+}
@@ -1,6 +1,7 @@
 use super::{
     FullGraphRef,
     data_json::{self, Error, Schemas},
+    mock_subgraphs::MockSubgraphs,
 };
 use crate::{
     api::{
@@ -15,7 +16,6 @@ use crate::{
 };
 use chrono::{DateTime, Utc};
 use cynic::{QueryBuilder, http::ReqwestExt};
-use futures::{StreamExt as _, TryStreamExt as _};
 use gateway_config::{Config, SubgraphConfig};
 use grafbase_graphql_introspection::introspect;
 use std::{
@@ -48,6 +48,13 @@ pub(crate) struct SubgraphOwner {
     pub(crate) name: String,
 }
 
+/// A successful composition of the cached subgraphs.
+pub(crate) struct ComposedGraph {
+    pub(crate) federated_sdl: String,
+    /// The composition warnings.
+    pub(crate) warnings: graphql_composition::Diagnostics,
+}
+
 pub(crate) struct SubgraphCache {
     /// Urls from remote subgraphs (subgraphs fetched from the API with the graph ref).
     ///
@@ -61,6 +68,11 @@ pub(crate) struct SubgraphCache {
     local_from_file: Mutex<Box<[Arc<CachedSubgraph>]>>,
     /// For the app. Regenerated on every call to `compose()`.
     data_json_schemas: Mutex<(DateTime<Utc>, data_json::Schemas)>,
+    /// The subgraphs of the last call to `compose()`, kept parsed so that only changed subgraphs
+    /// are validated and parsed again.
+    composition: Mutex<graphql_composition::ParsedSubgraphs>,
+    /// Servers for the subgraphs with `mock = true`, composed with the URL of their server.
+    mock_subgraphs: Mutex<MockSubgraphs>,
 
     /// The handler for composition warnings.
     composition_warnings_sender: mpsc::Sender<Vec<String>>,
@@ -117,6 +129,8 @@ impl SubgraphCache {
             remote_urls,
             remote,
             data_json_schemas: Mutex::new((Utc::now(), data_json::Schemas::default())),
+            composition: Default::default(),
+            mock_subgraphs: Default::default(),
             composition_warnings_sender,
             local_from_introspection: Default::default(),
             local_from_file: Default::default(),
@@ -156,50 +170,51 @@ impl SubgraphCache {
         }
     }
 
+    /// The SDL of each cached subgraph, by subgraph name.
+    pub(crate) async fn subgraph_sdls(&self) -> HashMap<String, String> {
+        let mut sdls = HashMap::new();
+
+        self.for_each_subgraph(|subgraph| {
+            sdls.insert(subgraph.name.clone(), subgraph.sdl.clone());
+        })
+        .await;
+
+        sdls
+    }
+
     /// Compose all cached subgraphs.
-    pub(crate) async fn compose(&self) -> anyhow::Result<Result<String, graphql_composition::Diagnostics>> {
-        let mut futs = futures::stream::FuturesOrdered::new();
+    pub(crate) async fn compose(&self) -> anyhow::Result<Result<ComposedGraph, graphql_composition::Diagnostics>> {
         let mut all_subgraphs = Vec::with_capacity(self.remote.len());
 
         self.for_each_subgraph(|subgraph| {
             all_subgraphs.push(subgraph.clone());
+        })
+        .await;
 
-            let subgraph = subgraph.clone();
-            futs.push_back(async move {
+        let mut composition = self.composition.lock().await;
+        let mut mock_subgraphs = self.mock_subgraphs.lock().await;
+        let mut validation_errors = Vec::new();
+
+        for subgraph in &all_subgraphs {
+            // Unchanged subgraphs were already validated.
+            if composition.sdl(&subgraph.name) != Some(subgraph.sdl.as_str()) {
                 let diagnostics = graphql_schema_validation::validate(&subgraph.sdl);
 
                 if diagnostics.has_errors() {
-                    let errors = diagnostics
-                        .iter()
-                        .map(|diagnostic| format!("[{}] {}\n", subgraph.name, diagnostic))
-                        .collect::<Vec<_>>();
-                    return Err((subgraph.name.clone(), errors));
+                    validation_errors.extend(
+                        diagnostics
+                            .iter()
+                            .map(|diagnostic| format!("[{}] {}\n", subgraph.name, diagnostic)),
+                    );
+                    continue;
                 }
+            }
 
-                let parsed_schema = cynic_parser::parse_type_system_document(&subgraph.sdl).map_err(|err| {
-                    (
-                        subgraph.name.clone(),
-                        vec![format!("[{}] Failed to parse subgraph SDL: {err}\n", subgraph.name)],
-                    )
-                })?;
-
-                Ok((subgraph, parsed_schema))
-            });
-        })
-        .await;
-
-        let mut stream = futs.into_stream();
-        let mut subgraphs = graphql_composition::Subgraphs::default();
-        let mut validation_errors = Vec::new();
+            let mock_url = mock_subgraphs.url(&subgraph.name, &subgraph.sdl).await?;
+            let url = mock_url.as_deref().or(subgraph.url.as_deref());
 
-        while let Some(result) = stream.next().await {
-            match result {
-                Ok((subgraph, parsed_schema)) => {
-                    subgraphs.ingest(&parsed_schema, &subgraph.name, subgraph.url.as_deref());
-                }
-                Err((_, mut errors)) => {
-                    validation_errors.append(&mut errors);
-                }
+            if let Err(err) = composition.replace_subgraph(&subgraph.name, &subgraph.sdl, url) {
+                validation_errors.push(format!("[{}] Failed to parse subgraph SDL: {err}\n", subgraph.name));
             }
         }
 
@@ -210,49 +225,46 @@ impl SubgraphCache {
             ));
         }
 
-        let result = graphql_composition::compose(&mut subgraphs);
+        let subgraph_names: HashSet<&str> = all_subgraphs.iter().map(|subgraph| subgraph.name.as_str()).collect();
+        composition.retain(|name| subgraph_names.contains(name));
+
+        let result = composition.compose();
+        drop(composition);
+        drop(mock_subgraphs);
 
         {
             let mut warnings = result.diagnostics().iter_warnings().peekable();
 
             if warnings.peek().is_some() {
+                // Nobody listens for the warnings in `grafbase compose`, it reports them from the
+                // composed graph.
                 self.composition_warnings_sender
                     .send(warnings.map(ToOwned::to_owned).collect())
                     .await
-                    .unwrap();
+                    .ok();
             }
         }
 
+        let warnings = result.diagnostics().clone();
         let result = result.into_result();
 
         let (schemas, result) = match result {
             Ok(graph) => {
                 let federated_schema = graphql_composition::render_federated_sdl(&graph)?;
+
                 (
                     Schemas::Data(SchemasData {
                         api_schema: Some(graphql_composition::render_api_sdl(&graph)),
                         federated_schema: Some(federated_schema.clone()),
                         subgraphs: all_subgraphs,
                     }),
-                    Ok(federated_schema),
+                    Ok(ComposedGraph {
+                        federated_sdl: federated_schema,
+                        warnings,
+                    }),
                 )
             }
-            Err(diagnostics) => (
-                Schemas::Errors(SchemasErrors {
-                    errors: diagnostics
-                        .iter_warnings()
-                        .map(|warning| Error {
-                            message: warning.to_owned(),
-                            severity: Severity::Warning,
-                        })
-                        .chain(diagnostics.iter_errors().map(|err| Error {
-                            message: err.to_owned(),
-                            severity: Severity::Error,
-                        }))
-                        .collect(),
-                }),
-                Err(diagnostics),
-            ),
+            Err(diagnostics) => (schema_errors(&diagnostics), Err(diagnostics)),
         };
 
         *self.data_json_schemas.lock().await = (Utc::now(), schemas);
@@ -287,6 +299,7 @@ impl SubgraphCache {
             }
         }
 
+        self.mock_subgraphs.lock().await.configure(config);
         *self.local_from_introspection.lock().await = local_from_introspection;
         *self.local_from_file.lock().await = local_from_file.into_boxed_slice();
 
@@ -360,7 +373,7 @@ async fn handle_overridden_subgraph(
     }
 }
 
-async fn fetch_remote_subgraphs(graph_ref: &FullGraphRef) -> Result<Vec<Subgraph>, BackendError> {
+pub(crate) async fn fetch_remote_subgraphs(graph_ref: &FullGraphRef) -> Result<Vec<Subgraph>, BackendError> {
     let platform_data = PlatformData::get();
 
     let client = create_client().map_err(BackendError::ApiError)?;
@@ -387,3 +400,19 @@ async fn fetch_remote_subgraphs(graph_ref: &FullGraphRef) -> Result<Vec<Subgraph
 
     Ok(branch.subgraphs)
 }
+
+fn schema_errors(diagnostics: &graphql_composition::Diagnostics) -> Schemas {
+    Schemas::Errors(SchemasErrors {
+        errors: diagnostics
+            .iter_warnings()
+            .map(|warning| Error {
+                message: warning.to_owned(),
+                severity: Severity::Warning,
+            })
+            .chain(diagnostics.iter_errors().map(|err| Error {
+                message: err.to_owned(),
+                severity: Severity::Error,
+            }))
+            .collect(),
+    })
+}
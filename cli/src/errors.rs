@@ -60,6 +60,12 @@ pub(crate) enum CliError {
     /// returned if an unsupported extension is passed to lint
     #[error("attempted to lint a file with an unsupported extension: '{0}'")]
     LintUnsupportedFileExtension(String),
+    /// returned if the lint configuration could not be read
+    #[error("could not read the lint configuration '{0}'\nCaused by: {1}")]
+    ReadLintConfig(PathBuf, io::Error),
+    /// returned if the lint configuration is not valid
+    #[error("could not parse the lint configuration '{0}'\nCaused by: {1}")]
+    ParseLintConfig(PathBuf, toml::de::Error),
     #[error(transparent)]
     GenericError(#[from] anyhow::Error),
 }
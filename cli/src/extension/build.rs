@@ -306,6 +306,7 @@ fn parse_manifest(source_dir: &Path, wasm_path: &Path) -> anyhow::Result<Manifes
         ExtensionType::Hooks => Type::Hooks(if let Some(cfg) = toml.hooks {
             extension::HooksType {
                 event_filter: cfg.events.map(Into::into),
+                on_graphql_response: cfg.on_graphql_response,
             }
         } else {
             Default::default()
@@ -199,6 +199,8 @@ pub struct ContractsType {
 pub struct HooksType {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub events: Option<EventFilterWrapper>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub on_graphql_response: bool,
 }
 
 #[derive(Clone)]
@@ -1,5 +1,5 @@
 use crate::{errors::CliError, output::report};
-use graphql_lint::Severity;
+use graphql_lint::{LintConfig, Severity};
 use std::{
     borrow::Borrow,
     fs,
@@ -9,7 +9,17 @@ use std::{
 
 const ALLOWED_EXTENSIONS: [&str; 4] = ["gql", "graphql", "graphqls", "sdl"];
 
-pub fn lint(schema_path: Option<PathBuf>) -> Result<(), CliError> {
+pub fn lint(schema_path: Option<PathBuf>, config_path: Option<PathBuf>) -> Result<(), CliError> {
+    let config = match config_path {
+        Some(config_path) => {
+            let config = fs::read_to_string(&config_path)
+                .map_err(|error| CliError::ReadLintConfig(config_path.clone(), error))?;
+
+            toml::from_str::<LintConfig>(&config).map_err(|error| CliError::ParseLintConfig(config_path, error))?
+        }
+        None => LintConfig::default(),
+    };
+
     let schema = match schema_path {
         Some(schema_path) => {
             let extension = schema_path
@@ -37,17 +47,22 @@ pub fn lint(schema_path: Option<PathBuf>) -> Result<(), CliError> {
         }
     };
 
-    let diagnostics = graphql_lint::lint(&schema)?;
+    let diagnostics = graphql_lint::lint_with_config(&schema, &config)?;
 
     if diagnostics.is_empty() {
         report::lint_success();
         return Ok(());
     }
 
-    for (message, severity) in diagnostics {
-        match severity {
-            Severity::Warning => report::lint_warning(message),
-        }
+    for diagnostic in &diagnostics {
+        report::lint_diagnostic(diagnostic, &schema);
+    }
+
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity() == Severity::Error)
+    {
+        std::process::exit(1);
     }
 
     Ok(())
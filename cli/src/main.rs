@@ -170,7 +170,7 @@ fn try_main(args: Args) -> Result<(), CliError> {
             }
             upgrade::install_grafbase().map_err(Into::into)
         }
-        SubCommand::Lint(cmd) => lint::lint(cmd.schema),
+        SubCommand::Lint(cmd) => lint::lint(cmd.schema, cmd.config),
         SubCommand::Plugins => Ok(plugins::list()?),
         SubCommand::Branch(cmd) => match cmd.command {
             BranchSubCommand::Delete(cmd) => branch::delete(cmd.branch_ref),
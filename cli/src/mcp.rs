@@ -40,6 +40,7 @@ pub(crate) async fn run(args: McpCommand) -> anyhow::Result<()> {
         name: NameOrPattern::Pattern(Regex::new(r".*").unwrap().into()),
         default: None,
         rename: None,
+        when: None,
     }));
     for (name, value) in args.headers() {
         config.headers.push(HeaderRule::Insert(HeaderInsert {
@@ -49,6 +50,7 @@ pub(crate) async fn run(args: McpCommand) -> anyhow::Result<()> {
             value: value
                 .parse()
                 .map_err(|err| anyhow::anyhow!("Invalid header value '{value}': {err}"))?,
+            when: None,
         }));
     }
 
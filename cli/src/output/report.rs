@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::{
     api::{
@@ -32,18 +32,70 @@ pub fn error(error: &CliError) {
     }
 }
 
-pub(crate) fn composition_diagnostics(diagnostics: &graphql_composition::Diagnostics) {
-    for diagnostic in diagnostics.iter_warnings() {
-        watercolor::output!("- ⚠️ Warning: {}", diagnostic, @BrightYellow);
+pub(crate) fn composition_diagnostics<'a>(
+    diagnostics: impl IntoIterator<Item = &'a graphql_composition::diagnostics::Diagnostic>,
+    sources: &HashMap<String, String>,
+) {
+    let (warnings, errors): (Vec<_>, Vec<_>) = diagnostics
+        .into_iter()
+        .partition(|diagnostic| diagnostic.severity().is_warning());
+
+    for diagnostic in warnings {
+        watercolor::output!("- ⚠️ Warning: {}", diagnostic.message(), @BrightYellow);
+        composition_diagnostic_snippet(diagnostic, sources);
         println!();
     }
 
-    for diagnostic in diagnostics.iter_errors() {
-        watercolor::output!("- ❌ Error: {}", diagnostic, @BrightRed);
+    for diagnostic in errors {
+        watercolor::output!("- ❌ Error: {}", diagnostic.message(), @BrightRed);
+        composition_diagnostic_snippet(diagnostic, sources);
         println!();
     }
 }
 
+/// Prints the subgraph schema lines the diagnostic points to, with the span underlined.
+fn composition_diagnostic_snippet(
+    diagnostic: &graphql_composition::diagnostics::Diagnostic,
+    sources: &HashMap<String, String>,
+) {
+    if let Some(location) = diagnostic.location() {
+        location_snippet(location, sources);
+    }
+}
+
+fn location_snippet(
+    location: &graphql_composition::diagnostics::DiagnosticLocation,
+    sources: &HashMap<String, String>,
+) {
+    let Some(region) = crate::compose::diagnostics::region(location, sources) else {
+        return;
+    };
+
+    let subgraph_name = location.subgraph_name();
+    watercolor::output!("  --> {subgraph_name}:{}:{}", region.start_line, region.start_column, @BrightBlue);
+
+    let Some(line) = sources[subgraph_name].lines().nth(region.start_line - 1) else {
+        return;
+    };
+
+    let underline_length = if region.end_line == region.start_line {
+        region.end_column.saturating_sub(region.start_column).max(1)
+    } else {
+        line.chars().count().saturating_sub(region.start_column - 1).max(1)
+    };
+
+    let gutter = region.start_line.to_string();
+    let padding = " ".repeat(gutter.len());
+
+    println!("  {padding} |");
+    println!("  {gutter} | {line}");
+    println!(
+        "  {padding} | {}{}",
+        " ".repeat(region.start_column - 1),
+        "^".repeat(underline_length)
+    );
+}
+
 pub fn warnings(warnings: &[Warning]) {
     for warning in warnings {
         let msg = warning.message();
@@ -119,17 +171,27 @@ pub(crate) fn check_success() {
     watercolor::output!("\n✨ Successful check!", @BrightBlue);
 }
 
-pub(crate) fn check_errors(has_errors: bool, diagnostics: &[SchemaCheckDiagnostic]) {
+pub(crate) fn check_errors(
+    has_errors: bool,
+    diagnostics: &[(
+        &SchemaCheckDiagnostic,
+        Option<&graphql_composition::diagnostics::DiagnosticLocation>,
+    )],
+    sources: &HashMap<String, String>,
+) {
     if has_errors {
         watercolor::output!("\nErrors were found in your schema check:", @BrightRed);
     } else {
         watercolor::output!("\nWarnings were found in your schema check:", @BrightYellow);
     }
 
-    let mut sections: BTreeMap<SchemaCheckStep, Vec<&SchemaCheckDiagnostic>> = BTreeMap::new();
+    let mut sections: BTreeMap<SchemaCheckStep, Vec<_>> = BTreeMap::new();
 
-    for diagnostic in diagnostics {
-        sections.entry(diagnostic.step).or_default().push(diagnostic);
+    for (diagnostic, location) in diagnostics {
+        sections
+            .entry(diagnostic.step)
+            .or_default()
+            .push((diagnostic, location));
     }
 
     for (step, diagnostics) in sections {
@@ -144,7 +206,7 @@ pub(crate) fn check_errors(has_errors: bool, diagnostics: &[SchemaCheckDiagnosti
 
         watercolor::output!("\n{step_name}\n", @BrightBlue);
 
-        for diagnostic in diagnostics {
+        for (diagnostic, location) in diagnostics {
             let error = &diagnostic.message;
 
             match diagnostic.severity {
@@ -155,6 +217,10 @@ pub(crate) fn check_errors(has_errors: bool, diagnostics: &[SchemaCheckDiagnosti
                     watercolor::output!("⚠️ [Warning] {error}", @BrightYellow);
                 }
             }
+
+            if let Some(location) = location {
+                location_snippet(location, sources);
+            }
         }
     }
 }
@@ -291,8 +357,19 @@ pub(crate) fn lint_success() {
     watercolor::output!("✅ No issues found in your schema", @BrightGreen)
 }
 
-pub(crate) fn lint_warning(warning: String) {
-    watercolor::output!("⚠️ [Warning] {warning}", @BrightYellow);
+pub(crate) fn lint_diagnostic(diagnostic: &graphql_lint::Diagnostic, schema: &str) {
+    let (line, column) = crate::compose::diagnostics::line_column(schema, diagnostic.span().start);
+    let message = diagnostic.message();
+    let rule = diagnostic.rule();
+
+    match diagnostic.severity() {
+        graphql_lint::Severity::Warning => {
+            watercolor::output!("⚠️ [Warning] {line}:{column} {message} ({rule})", @BrightYellow);
+        }
+        graphql_lint::Severity::Error => {
+            watercolor::output!("❌ [Error] {line}:{column} {message} ({rule})", @BrightRed);
+        }
+    }
 }
 
 pub(crate) fn extension_build_start() {
@@ -94,6 +94,22 @@ pub(crate) enum BindError {
         directive: String,
         span: Span,
     },
+    #[error("Unknown argument named '{name}' for directive '{directive}'")]
+    UnknownDirectiveArgument {
+        name: String,
+        directive: String,
+        span: Span,
+    },
+    #[error("Directive '@{directive}' can only be used on {expected}.")]
+    InvalidDirectiveLocation {
+        directive: String,
+        expected: &'static str,
+        span: Span,
+    },
+    #[error("Directive '@{directive}' is not supported on subscriptions.")]
+    IncrementalDeliveryNotSupported { directive: String, span: Span },
+    #[error("Argument 'initialCount' of directive '@stream' must be non-negative, found {count}.")]
+    NegativeStreamInitialCount { count: i32, span: Span },
 }
 
 impl BindError {
@@ -110,7 +126,11 @@ impl BindError {
             | BindError::InvalidVariableType { span, .. }
             | BindError::LeafMustBeAScalarOrEnum { span, .. }
             | BindError::MissingArgument { span, .. }
-            | BindError::MissingDirectiveArgument { span, .. } => Some(operation.span_to_location(*span)),
+            | BindError::MissingDirectiveArgument { span, .. }
+            | BindError::UnknownDirectiveArgument { span, .. }
+            | BindError::InvalidDirectiveLocation { span, .. }
+            | BindError::IncrementalDeliveryNotSupported { span, .. }
+            | BindError::NegativeStreamInitialCount { span, .. } => Some(operation.span_to_location(*span)),
             BindError::DuplicateVariable { location, .. } | BindError::UnusedVariable { location, .. } => {
                 Some(*location)
             }
@@ -133,6 +153,10 @@ impl BindError {
             | BindError::NoSubscriptionDefined
             | BindError::InvalidVariableType { .. }
             | BindError::MissingDirectiveArgument { .. }
+            | BindError::UnknownDirectiveArgument { .. }
+            | BindError::InvalidDirectiveLocation { .. }
+            | BindError::IncrementalDeliveryNotSupported { .. }
+            | BindError::NegativeStreamInitialCount { .. }
             | BindError::UnknownType { .. }
             | BindError::UnknownFragment { .. }
             | BindError::DuplicateVariable { .. }
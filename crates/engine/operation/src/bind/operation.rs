@@ -9,8 +9,9 @@ use std::collections::HashSet;
 use walker::Walk;
 
 use crate::{
-    ExecutableDirectiveId, FieldArgumentId, IncludeDirectiveRecord, InlineFragmentId, InlineFragmentRecord,
-    QueryInputValueRecord, SelectionSetRecord, SkipDirectiveRecord, VariableDefinitionRecord,
+    DeferDirectiveRecord, ExecutableDirectiveId, FieldArgumentId, IncludeDirectiveRecord, InlineFragmentId,
+    InlineFragmentRecord, QueryInputValueId, QueryInputValueRecord, SelectionSetRecord, SkipDirectiveRecord,
+    StreamDirectiveRecord, VariableDefinitionRecord,
 };
 
 use super::{
@@ -93,7 +94,8 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
     }
 
     fn bind_typename_field(&mut self, field: FieldSelection<'p>) -> BindResult<crate::TypenameFieldId> {
-        let directive_ids = self.bind_executable_directive(field.directives());
+        let directive_ids =
+            self.bind_executable_directive(DirectiveLocation::Field { is_list: false }, field.directives());
         let response_key = self.response_keys.get_or_intern(field.alias().unwrap_or(field.name()));
         self.typename_fields.push(crate::TypenameFieldRecord {
             response_key,
@@ -157,7 +159,12 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
         };
 
         let sorted_argument_ids = self.bind_field_arguments_sorted(definition, field.name_span(), field.arguments());
-        let directive_ids = self.bind_executable_directive(field.directives());
+        let directive_ids = self.bind_executable_directive(
+            DirectiveLocation::Field {
+                is_list: definition.ty().wrapping.is_list(),
+            },
+            field.directives(),
+        );
         let response_key = self.response_keys.get_or_intern(field.alias().unwrap_or(field.name()));
 
         self.data_fields.push(crate::DataFieldRecord {
@@ -240,7 +247,7 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
             .transpose()?;
         let selection_set_record =
             self.bind_selection_set(type_condition.unwrap_or(parent_output_type), fragment.selection_set())?;
-        let directive_ids = self.bind_executable_directive(fragment.directives());
+        let directive_ids = self.bind_executable_directive(DirectiveLocation::Fragment, fragment.directives());
 
         self.inline_fragments.push(InlineFragmentRecord {
             type_condition_id: type_condition.map(|ty| ty.id()),
@@ -279,7 +286,7 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
                 id
             }
         };
-        let directive_ids = self.bind_executable_directive(spread.directives());
+        let directive_ids = self.bind_executable_directive(DirectiveLocation::Fragment, spread.directives());
         self.fragment_spreads.push(crate::FragmentSpreadRecord {
             fragment_id,
             directive_ids,
@@ -342,16 +349,23 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
         })
     }
 
-    fn bind_executable_directive(&mut self, directives: Iter<'p, Directive<'p>>) -> Vec<ExecutableDirectiveId> {
+    fn bind_executable_directive(
+        &mut self,
+        location: DirectiveLocation,
+        directives: Iter<'p, Directive<'p>>,
+    ) -> Vec<ExecutableDirectiveId> {
         let mut out = Vec::new();
         for directive in directives {
-            if matches!(directive.name(), "skip" | "include") {
-                match self.bind_skip_or_include_executable_directive(directive) {
-                    Ok(directive_id) => out.push(directive_id),
-                    Err(err) => {
-                        self.errors.push(err);
-                        continue;
-                    }
+            let result = match directive.name() {
+                "skip" | "include" => self.bind_skip_or_include_executable_directive(directive),
+                "defer" | "stream" => self.bind_incremental_delivery_directive(location, directive),
+                _ => continue,
+            };
+            match result {
+                Ok(directive_id) => out.push(directive_id),
+                Err(err) => {
+                    self.errors.push(err);
+                    continue;
                 }
             }
         }
@@ -373,13 +387,7 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
                 directive: directive.name().to_string(),
             })?;
 
-        let ty = TypeRecord {
-            definition_id: self.schema.type_definition_by_name("Boolean").expect("must exist").id(),
-            wrapping: schema::Wrapping::default().non_null(),
-        }
-        .walk(self.schema);
-
-        let condition = coerce_query_value(self, ty, argument.value());
+        let condition = self.bind_directive_argument_value(argument, "Boolean", true);
 
         Ok(if directive.name() == "skip" {
             ExecutableDirectiveId::Skip(SkipDirectiveRecord { condition })
@@ -388,6 +396,99 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
         })
     }
 
+    /// Binds `@defer(label: String, if: Boolean! = true)` and
+    /// `@stream(label: String, initialCount: Int! = 0, if: Boolean! = true)`.
+    fn bind_incremental_delivery_directive(
+        &mut self,
+        location: DirectiveLocation,
+        directive: Directive<'p>,
+    ) -> BindResult<ExecutableDirectiveId> {
+        let is_defer = directive.name() == "defer";
+        let is_valid_location = match location {
+            DirectiveLocation::Fragment => is_defer,
+            DirectiveLocation::Field { is_list } => !is_defer && is_list,
+        };
+        if !is_valid_location {
+            return Err(BindError::InvalidDirectiveLocation {
+                directive: directive.name().to_string(),
+                expected: if is_defer { "fragments" } else { "list fields" },
+                span: directive.name_span(),
+            });
+        }
+        if matches!(
+            self.parsed_operation.operation().operation_type(),
+            OperationType::Subscription
+        ) {
+            return Err(BindError::IncrementalDeliveryNotSupported {
+                directive: directive.name().to_string(),
+                span: directive.name_span(),
+            });
+        }
+
+        let mut label = None;
+        let mut condition = None;
+        let mut initial_count = None;
+        for argument in directive.arguments() {
+            match argument.name() {
+                "label" => label = Some(self.bind_directive_argument_value(argument, "String", false)),
+                "if" => condition = Some(self.bind_directive_argument_value(argument, "Boolean", true)),
+                "initialCount" if !is_defer => {
+                    let id = self.bind_directive_argument_value(argument, "Int", true);
+                    // Variables are checked when planning the operation.
+                    if let QueryInputValueRecord::Int(count) = self.query_input_values[id]
+                        && count < 0
+                    {
+                        return Err(BindError::NegativeStreamInitialCount {
+                            count,
+                            span: argument.name_span(),
+                        });
+                    }
+                    initial_count = Some(id)
+                }
+                _ => {
+                    return Err(BindError::UnknownDirectiveArgument {
+                        directive: directive.name().to_string(),
+                        name: argument.name().to_string(),
+                        span: argument.name_span(),
+                    });
+                }
+            }
+        }
+        let condition =
+            condition.unwrap_or_else(|| self.query_input_values.push_value(QueryInputValueRecord::Boolean(true)));
+
+        Ok(if is_defer {
+            ExecutableDirectiveId::Defer(DeferDirectiveRecord { label, condition })
+        } else {
+            ExecutableDirectiveId::Stream(StreamDirectiveRecord {
+                label,
+                initial_count: initial_count
+                    .unwrap_or_else(|| self.query_input_values.push_value(QueryInputValueRecord::Int(0))),
+                condition,
+            })
+        })
+    }
+
+    fn bind_directive_argument_value(
+        &mut self,
+        argument: Argument<'p>,
+        scalar: &str,
+        required: bool,
+    ) -> QueryInputValueId {
+        let wrapping = if required {
+            schema::Wrapping::default().non_null()
+        } else {
+            schema::Wrapping::default()
+        };
+        let ty = TypeRecord {
+            definition_id: self.schema.type_definition_by_name(scalar).expect("must exist").id(),
+            wrapping,
+        }
+        .walk(self.schema);
+
+        coerce_query_value(self, ty, argument.value())
+    }
+
     fn bind_variable_definitions(
         &mut self,
         variables: cynic_parser::executable::Iter<'_, cynic_parser::executable::VariableDefinition<'_>>,
@@ -501,3 +602,9 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
         })
     }
 }
+
+#[derive(Clone, Copy)]
+enum DirectiveLocation {
+    Field { is_list: bool },
+    Fragment,
+}
@@ -0,0 +1,71 @@
+use walker::Walk;
+
+use crate::{OperationContext, QueryInputValueId, QueryInputValueRecord, VariableDefinitionId};
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct DeferDirectiveRecord {
+    pub label: Option<QueryInputValueId>,
+    /// Defaults to `true` if not specified.
+    pub condition: QueryInputValueId,
+}
+
+#[derive(Clone, Copy)]
+pub struct DeferDirective<'a> {
+    pub(in crate::model) ctx: OperationContext<'a>,
+    pub(in crate::model) item: DeferDirectiveRecord,
+}
+
+impl std::ops::Deref for DeferDirective<'_> {
+    type Target = DeferDirectiveRecord;
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl DeferDirective<'_> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn as_ref(&self) -> &DeferDirectiveRecord {
+        &self.item
+    }
+}
+
+impl<'a> Walk<OperationContext<'a>> for DeferDirectiveRecord {
+    type Walker<'w>
+        = DeferDirective<'w>
+    where
+        'a: 'w;
+    fn walk<'w>(self, ctx: impl Into<OperationContext<'a>>) -> Self::Walker<'w>
+    where
+        Self: 'w,
+        'a: 'w,
+    {
+        DeferDirective {
+            ctx: ctx.into(),
+            item: self,
+        }
+    }
+}
+
+impl std::fmt::Debug for DeferDirective<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut f = f.debug_struct("DeferDirective");
+        if let Some(label) = self.item.label
+            && let QueryInputValueRecord::String(label) = &self.ctx.operation.query_input_values[label]
+        {
+            f.field("label", label);
+        }
+        match self.ctx.operation.query_input_values[self.item.condition] {
+            QueryInputValueRecord::Boolean(b) => f.field("condition", &b).finish(),
+            QueryInputValueRecord::Variable(id) => f
+                .field(
+                    "condition",
+                    &format!(
+                        "${}",
+                        <VariableDefinitionId as Walk<OperationContext<'_>>>::walk(id, self.ctx).name
+                    ),
+                )
+                .finish(),
+            _ => f.field("condition", &"???").finish(),
+        }
+    }
+}
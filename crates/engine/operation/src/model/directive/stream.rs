@@ -0,0 +1,76 @@
+use walker::Walk;
+
+use crate::{OperationContext, QueryInputValueId, QueryInputValueRecord, VariableDefinitionId};
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct StreamDirectiveRecord {
+    pub label: Option<QueryInputValueId>,
+    /// Defaults to `0` if not specified.
+    pub initial_count: QueryInputValueId,
+    /// Defaults to `true` if not specified.
+    pub condition: QueryInputValueId,
+}
+
+#[derive(Clone, Copy)]
+pub struct StreamDirective<'a> {
+    pub(in crate::model) ctx: OperationContext<'a>,
+    pub(in crate::model) item: StreamDirectiveRecord,
+}
+
+impl std::ops::Deref for StreamDirective<'_> {
+    type Target = StreamDirectiveRecord;
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl StreamDirective<'_> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn as_ref(&self) -> &StreamDirectiveRecord {
+        &self.item
+    }
+}
+
+impl<'a> Walk<OperationContext<'a>> for StreamDirectiveRecord {
+    type Walker<'w>
+        = StreamDirective<'w>
+    where
+        'a: 'w;
+    fn walk<'w>(self, ctx: impl Into<OperationContext<'a>>) -> Self::Walker<'w>
+    where
+        Self: 'w,
+        'a: 'w,
+    {
+        StreamDirective {
+            ctx: ctx.into(),
+            item: self,
+        }
+    }
+}
+
+impl std::fmt::Debug for StreamDirective<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut f = f.debug_struct("StreamDirective");
+        if let Some(label) = self.item.label
+            && let QueryInputValueRecord::String(label) = &self.ctx.operation.query_input_values[label]
+        {
+            f.field("label", label);
+        }
+        let variable_name = |id: VariableDefinitionId| {
+            format!(
+                "${}",
+                <VariableDefinitionId as Walk<OperationContext<'_>>>::walk(id, self.ctx).name
+            )
+        };
+        match self.ctx.operation.query_input_values[self.item.initial_count] {
+            QueryInputValueRecord::Int(n) => f.field("initial_count", &n),
+            QueryInputValueRecord::Variable(id) => f.field("initial_count", &variable_name(id)),
+            _ => f.field("initial_count", &"???"),
+        };
+        match self.ctx.operation.query_input_values[self.item.condition] {
+            QueryInputValueRecord::Boolean(b) => f.field("condition", &b).finish(),
+            QueryInputValueRecord::Variable(id) => f.field("condition", &variable_name(id)).finish(),
+            _ => f.field("condition", &"???").finish(),
+        }
+    }
+}
@@ -97,11 +97,7 @@ fn field_complexity(
     field: DataField<'_>,
     preset_list_size: Option<usize>,
 ) -> Result<usize, ComplexityError> {
-    let type_cost = field
-        .definition()
-        .cost()
-        .map(|cost| cost.weight)
-        .unwrap_or_else(|| cost_for_type(field.definition().ty().definition())) as usize;
+    let type_cost = field_definition_cost(field.definition());
 
     let list_size_directive = field.definition().list_size();
 
@@ -120,6 +116,15 @@ fn field_complexity(
     Ok(this_field_count * (type_cost + argument_cost + child_cost))
 }
 
+/// Cost of a single occurrence of a field, ignoring its arguments and selection set: the `@cost`
+/// weight if any, or a default depending on its type.
+pub fn field_definition_cost(definition: schema::FieldDefinition<'_>) -> usize {
+    definition
+        .cost()
+        .map(|cost| cost.weight)
+        .unwrap_or_else(|| cost_for_type(definition.ty().definition())) as usize
+}
+
 fn cost_for_argument(argument: FieldArgument<'_>, variables: &Variables) -> usize {
     let def = argument.definition();
     let argument_type = def.ty().definition();
@@ -10,17 +10,23 @@ pub(crate) mod dot_graph;
 mod error;
 mod post_process;
 mod query;
+mod satisfiability;
 mod solution_space;
 pub(crate) mod solve;
 pub use error::*;
 use operation::Operation;
 pub use petgraph;
 pub use query::*;
-use schema::Schema;
+pub use satisfiability::*;
+use schema::{EnabledOverrideLabels, Schema};
 pub(crate) use solution_space::*;
 
-pub fn solve(schema: &Schema, operation: &mut Operation) -> Result<QuerySolution> {
-    let query_solution_space = Query::generate_solution_space(schema, operation)?;
+pub fn solve(
+    schema: &Schema,
+    operation: &mut Operation,
+    override_labels: &EnabledOverrideLabels,
+) -> Result<QuerySolution> {
+    let query_solution_space = Query::generate_solution_space(schema, operation, override_labels)?;
     let solution = solve::Solver::initialize(schema, operation, query_solution_space)?.solve()?;
     let crude_solved_query = solution.into_query(schema, operation)?;
     let solved_query = post_process::post_process(schema, operation, crude_solved_query);
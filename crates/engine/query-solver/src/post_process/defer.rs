@@ -0,0 +1,87 @@
+use operation::DeferDirectiveRecord;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use crate::{
+    query::{Edge, Node},
+    solve::QuerySteinerSolution,
+};
+
+/// Fields within a `@defer` fragment must not delay the initial response. So whenever a query
+/// partition mixes deferred and non-deferred fields, or fields from different deferred fragments,
+/// we move each deferred fragment into its own partition with the same resolver. The new
+/// partition has the same parent and requirements as the original one, so it can be executed
+/// independently.
+///
+/// Deferred fields nested within a partition are retrieved with an entity lookup whenever the
+/// schema allows it, see `create_providable_fields`. Only those without any alternative resolver
+/// are still retrieved with their parent field.
+pub(super) fn split_deferred_fields_into_own_query_partitions(
+    query: &mut QuerySteinerSolution,
+    starting_nodes: Vec<NodeIndex>,
+) {
+    let mut partitions = Vec::new();
+    let mut stack = starting_nodes;
+    while let Some(node_ix) = stack.pop() {
+        for edge in query.graph.edges(node_ix) {
+            match edge.weight() {
+                Edge::QueryPartition => {
+                    partitions.push((node_ix, edge.target()));
+                    stack.push(edge.target());
+                }
+                Edge::Field => stack.push(edge.target()),
+                _ => (),
+            }
+        }
+    }
+
+    let mut deferred_fields: Vec<(DeferDirectiveRecord, NodeIndex)> = Vec::new();
+    for (parent_node_ix, partition_node_ix) in partitions {
+        let mut has_non_deferred_fields = false;
+        for edge in query.graph.edges(partition_node_ix) {
+            if !matches!(edge.weight(), Edge::Field) {
+                continue;
+            }
+            let Node::Field(node) = query.graph[edge.target()] else {
+                continue;
+            };
+            match query[node.id].defer {
+                Some(defer) => deferred_fields.push((defer, edge.target())),
+                None => has_non_deferred_fields = true,
+            }
+        }
+
+        deferred_fields.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        let mut groups = deferred_fields.chunk_by(|a, b| a.0 == b.0);
+
+        // If everything is deferred, the first group can stay in the original partition.
+        if !has_non_deferred_fields {
+            groups.next();
+        }
+
+        for group in groups {
+            let weight = query.graph[partition_node_ix];
+            let new_partition_node_ix = query.graph.add_node(weight);
+            query
+                .graph
+                .add_edge(parent_node_ix, new_partition_node_ix, Edge::QueryPartition);
+
+            let mut neighbors = query.graph.neighbors(partition_node_ix).detach();
+            while let Some((edge_ix, node_ix)) = neighbors.next(&query.graph) {
+                let weight = query.graph[edge_ix];
+                if matches!(weight, Edge::RequiredBySubgraph | Edge::MutationExecutedAfter) {
+                    query.graph.add_edge(new_partition_node_ix, node_ix, weight);
+                }
+            }
+
+            for (_, field_node_ix) in group {
+                if let Some(id) = query.graph.find_edge(partition_node_ix, *field_node_ix) {
+                    query.graph.remove_edge(id);
+                }
+                query.graph.add_edge(new_partition_node_ix, *field_node_ix, Edge::Field);
+            }
+        }
+
+        deferred_fields.clear();
+    }
+}
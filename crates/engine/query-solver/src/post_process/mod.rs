@@ -1,3 +1,4 @@
+mod defer;
 mod mutation_order;
 mod partition_cycles;
 mod response_key;
@@ -17,19 +18,24 @@ pub(crate) fn post_process(
 
     if Some(operation.root_object_id) == schema.graph.root_operation_types_record.mutation_id {
         let root_fields = mutation_order::ensure_mutation_execution_order(&mut query);
-        // We already handled query partitions in a more specific way, so we don't want this
-        // function to touch them. So it starts from the root field's selection sets instead of
+        // We already handled query partitions in a more specific way, so we don't want these
+        // functions to touch them. So they start from the root field's selection sets instead of
         // the root selection set.
+        defer::split_deferred_fields_into_own_query_partitions(&mut query, root_fields.clone());
         partition_cycles::split_query_partition_dependency_cycles(&mut query, root_fields);
     } else {
         let starting_nodes = vec![query.root_node_id];
+        defer::split_deferred_fields_into_own_query_partitions(&mut query, starting_nodes.clone());
         partition_cycles::split_query_partition_dependency_cycles(&mut query, starting_nodes);
     }
 
     root_typename::assign_root_typename_fields(schema, operation, &mut query);
 
     let query = QuerySolution {
-        step: Solution { field_to_subgraph_key },
+        step: Solution {
+            field_to_subgraph_key,
+            cost: query.step.cost,
+        },
         graph: query.graph,
         root_node_id: query.root_node_id,
         fields: query.fields,
@@ -4,7 +4,10 @@ use std::{collections::HashMap, num::NonZero};
 
 use bitflags::bitflags;
 use id_newtypes::IdRange;
-use operation::{FieldArgumentId, Location, OperationContext, QueryPosition, ResponseKey};
+use operation::{
+    DeferDirectiveRecord, FieldArgumentId, Location, OperationContext, QueryPosition, ResponseKey,
+    StreamDirectiveRecord,
+};
 use petgraph::{Graph, visit::GraphBase};
 use schema::{
     CompositeTypeId, EntityDefinitionId, FieldDefinitionId, ResolverDefinitionId, SchemaFieldArgumentId, SchemaFieldId,
@@ -86,15 +89,27 @@ pub(crate) mod steps {
 
     pub(crate) struct SolutionSpace {}
 
-    pub(crate) struct SteinerSolution {}
+    pub(crate) struct SteinerSolution {
+        pub cost: u32,
+    }
 
     pub struct Solution {
         // If necessary we generate a new subgraph key for a field.
         pub field_to_subgraph_key: Vec<Option<operation::ResponseKey>>,
+        /// Total weight of the Steiner tree, the cost the solver minimized.
+        pub cost: u32,
     }
 }
 
 pub type QuerySolution = Query<SolutionGraph, steps::Solution>;
+
+impl QuerySolution {
+    /// Total weight of the Steiner tree the solver settled on. Only meaningful to compare plans
+    /// of the same operation, it mostly reflects the number and cost of subgraph requests.
+    pub fn cost(&self) -> u32 {
+        self.step.cost
+    }
+}
 pub type SolutionGraph = Graph<Node, Edge>;
 
 #[derive(id_derives::IndexedFields)]
@@ -150,6 +165,11 @@ pub struct QueryField {
     pub sorted_argument_ids: QueryOrSchemaSortedFieldArgumentIds,
     pub location: Location,
     pub flat_directive_id: Option<DeduplicatedFlatExecutableDirectivesId>,
+    /// Innermost `@defer` fragment this field belongs to within its parent selection set. It's
+    /// kept apart from the flat directives so that a field requested both inside and outside of
+    /// a deferred fragment is only retrieved once, without being deferred.
+    pub defer: Option<DeferDirectiveRecord>,
+    pub stream: Option<StreamDirectiveRecord>,
 }
 
 /// Sorted by input value definition id
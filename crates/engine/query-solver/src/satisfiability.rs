@@ -0,0 +1,283 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Write as _,
+    rc::Rc,
+};
+
+use operation::Operation;
+use schema::{
+    CompositeType, CompositeTypeId, FieldDefinition, FieldDefinitionId, ObjectDefinition, Schema, TypeDefinition,
+};
+
+use crate::Error;
+
+/// A field of the supergraph that can't be planned when reached through `example_query`.
+#[derive(Debug)]
+pub struct UnsatisfiableField {
+    /// The unsatisfiable field as `Type.field`.
+    pub field: String,
+    /// An operation selecting the field which the query solver fails to plan.
+    pub example_query: String,
+    /// The query solver error for `example_query`.
+    pub error: Error,
+}
+
+/// Checks that every accessible field of the supergraph can be planned from a root type.
+///
+/// A composite type is visited once for every field leading to it, and again for every `@provides`
+/// above it on the path, as those change which subgraph can resolve its fields. Each visit goes
+/// through the shortest path from the query or mutation root reaching it this way, and builds the
+/// solution space for an operation selecting all the fields of the type. Only if that fails are the
+/// fields checked one by one to find the unsatisfiable ones, each field being reported once.
+///
+/// Paths that only differ further up, without any `@provides`, aren't checked: if `A.b` is reached
+/// both through `Query.x` and `Query.y`, the output type of `A.b` is only visited through the first
+/// one.
+pub fn check_satisfiability(schema: &Schema) -> Vec<UnsatisfiableField> {
+    let mut unsatisfiable_fields = Vec::new();
+    let mut reported_field_ids = HashSet::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    let roots = [("query", Some(schema.query())), ("mutation", schema.mutation())];
+    for (operation_type, root) in roots {
+        let Some(root) = root else {
+            continue;
+        };
+        visited.insert(VisitKey {
+            ty: CompositeTypeId::Object(root.id),
+            field_id: None,
+            provides_field_id: None,
+        });
+        queue.push_back(Visit {
+            operation_type,
+            parent: CompositeType::Object(root),
+            path: None,
+            provides_field_id: None,
+        });
+    }
+
+    while let Some(visit) = queue.pop_front() {
+        let fields = match visit.parent {
+            CompositeType::Object(object) => object.fields().collect::<Vec<_>>(),
+            CompositeType::Interface(interface) => interface.fields().collect(),
+            CompositeType::Union(_) => Vec::new(),
+        };
+        let fields = fields
+            .into_iter()
+            .filter(|field| {
+                !field.name().starts_with("__")
+                    && !field.is_inaccessible()
+                    && !field.ty().definition().is_inaccessible()
+            })
+            .collect::<Vec<_>>();
+
+        let mut unsatisfiable_field_ids = HashSet::new();
+        if !fields.is_empty() && check(schema, &visit.example_query(&fields)).is_err() {
+            for field in &fields {
+                let example_query = visit.example_query(std::slice::from_ref(field));
+                if let Err(error) = check(schema, &example_query) {
+                    unsatisfiable_field_ids.insert(field.id);
+                    if !reported_field_ids.insert(field.id) {
+                        continue;
+                    }
+                    unsatisfiable_fields.push(UnsatisfiableField {
+                        field: format!("{}.{}", visit.parent.name(), field.name()),
+                        example_query,
+                        error,
+                    });
+                }
+            }
+        }
+
+        // Types only reachable through unsatisfiable fields would only repeat the same error.
+        for field in fields {
+            if unsatisfiable_field_ids.contains(&field.id) {
+                continue;
+            }
+            let Some(output) = field.ty().definition().as_composite_type() else {
+                continue;
+            };
+            let path = Rc::new(PathSegment {
+                parent: visit.path.clone(),
+                field,
+                type_condition: None,
+            });
+            let provides_field_id = if field.provides().next().is_some() {
+                Some(field.id)
+            } else {
+                visit.provides_field_id
+            };
+            let key = |ty| VisitKey {
+                ty,
+                field_id: Some(field.id),
+                provides_field_id,
+            };
+
+            let possible_types: &[ObjectDefinition<'_>] = &match output {
+                CompositeType::Object(_) => Vec::new(),
+                CompositeType::Interface(interface) => interface.possible_types().collect(),
+                CompositeType::Union(union) => union.possible_types().collect(),
+            };
+
+            if visited.insert(key(output.id())) {
+                queue.push_back(Visit {
+                    operation_type: visit.operation_type,
+                    parent: output,
+                    path: Some(path.clone()),
+                    provides_field_id,
+                });
+            }
+
+            for &object in possible_types {
+                if TypeDefinition::Object(object).is_inaccessible()
+                    || !visited.insert(key(CompositeTypeId::Object(object.id)))
+                {
+                    continue;
+                }
+                queue.push_back(Visit {
+                    operation_type: visit.operation_type,
+                    parent: CompositeType::Object(object),
+                    path: Some(Rc::new(PathSegment {
+                        parent: path.parent.clone(),
+                        field,
+                        type_condition: Some(object),
+                    })),
+                    provides_field_id,
+                });
+            }
+        }
+    }
+
+    unsatisfiable_fields
+}
+
+pub(crate) fn check(schema: &Schema, query: &str) -> Result<(), Error> {
+    // Operations that can't be parsed, e.g. because of arguments we can't provide, say nothing
+    // about satisfiability.
+    let Ok(mut operation) = Operation::parse(schema, None, query) else {
+        tracing::debug!("Skipping satisfiability check for unparseable operation:\n{query}");
+        return Ok(());
+    };
+
+    crate::solve(schema, &mut operation, &Default::default()).map(|_| ())
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct VisitKey {
+    ty: CompositeTypeId,
+    /// The field leading to the type, `None` for root types.
+    field_id: Option<FieldDefinitionId>,
+    /// The closest field with a `@provides` on the path.
+    provides_field_id: Option<FieldDefinitionId>,
+}
+
+struct Visit<'a> {
+    operation_type: &'static str,
+    parent: CompositeType<'a>,
+    path: Option<Rc<PathSegment<'a>>>,
+    provides_field_id: Option<FieldDefinitionId>,
+}
+
+/// A field leading to the visited type, linked to the one before it.
+struct PathSegment<'a> {
+    parent: Option<Rc<PathSegment<'a>>>,
+    field: FieldDefinition<'a>,
+    /// Set when the visited type is a possible type of the field output.
+    type_condition: Option<ObjectDefinition<'a>>,
+}
+
+impl Visit<'_> {
+    /// Renders an operation selecting `fields` of the visited type. Required arguments are
+    /// provided through variables.
+    fn example_query(&self, fields: &[FieldDefinition<'_>]) -> String {
+        let mut segments = Vec::new();
+        let mut current = self.path.as_deref();
+        while let Some(segment) = current {
+            segments.push(segment);
+            current = segment.parent.as_deref();
+        }
+        segments.reverse();
+
+        let mut variables = Vec::new();
+        let mut selection = String::new();
+
+        let mut depth = 1;
+        for segment in &segments {
+            indent(&mut selection, depth);
+            write_field(&mut selection, segment.field, &mut variables);
+            selection.push_str(" {\n");
+            depth += 1;
+            if let Some(object) = segment.type_condition {
+                indent(&mut selection, depth);
+                writeln!(selection, "... on {} {{", object.name()).unwrap();
+                depth += 1;
+            }
+        }
+
+        for field in fields {
+            indent(&mut selection, depth);
+            write_field(&mut selection, *field, &mut variables);
+            if field.ty().definition().is_composite_type() {
+                selection.push_str(" { __typename }");
+            }
+            selection.push('\n');
+        }
+
+        for segment in segments.iter().rev() {
+            if segment.type_condition.is_some() {
+                depth -= 1;
+                indent(&mut selection, depth);
+                selection.push_str("}\n");
+            }
+            depth -= 1;
+            indent(&mut selection, depth);
+            selection.push_str("}\n");
+        }
+
+        let mut query = String::from(self.operation_type);
+        if !variables.is_empty() {
+            query.push('(');
+            query.push_str(&variables.join(", "));
+            query.push(')');
+        }
+        query.push_str(" {\n");
+        query.push_str(&selection);
+        query.push('}');
+        query
+    }
+}
+
+fn write_field(out: &mut String, field: FieldDefinition<'_>, variables: &mut Vec<String>) {
+    out.push_str(field.name());
+
+    let required_arguments = field
+        .arguments()
+        .filter(|argument| {
+            argument.is_internal_in_id.is_none()
+                && argument.default_value_id.is_none()
+                && argument.ty_record.is_required()
+        })
+        .collect::<Vec<_>>();
+
+    if required_arguments.is_empty() {
+        return;
+    }
+
+    out.push('(');
+    for (i, argument) in required_arguments.into_iter().enumerate() {
+        let variable = format!("v{}", variables.len());
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write!(out, "{}: ${variable}", argument.name()).unwrap();
+        variables.push(format!("${variable}: {}", argument.ty()));
+    }
+    out.push(')');
+}
+
+fn indent(out: &mut String, depth: usize) {
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+}
@@ -5,7 +5,7 @@ mod prune;
 
 use petgraph::stable_graph::NodeIndex;
 use providable_fields::{CreateProvidableFieldsTask, CreateRequirementTask, UnplannableField};
-use schema::{CompositeTypeId, Schema, TypeDefinitionId};
+use schema::{CompositeTypeId, EnabledOverrideLabels, Schema, TypeDefinitionId};
 use walker::Walk;
 
 use crate::{FieldFlags, FieldNode, QueryFieldId, SplitId, steps::SolutionSpace};
@@ -16,6 +16,7 @@ use super::*;
 pub(super) struct QuerySolutionSpaceBuilder<'schema, 'op> {
     schema: &'schema Schema,
     operation: &'op Operation,
+    override_labels: &'op EnabledOverrideLabels,
     query: QuerySolutionSpace<'schema>,
     create_provideable_fields_task_stack: Vec<CreateProvidableFieldsTask>,
     create_requirement_task_stack: Vec<CreateRequirementTask<'schema>>,
@@ -28,6 +29,7 @@ impl<'schema> QuerySolutionSpace<'schema> {
     pub(super) fn builder<'op>(
         schema: &'schema Schema,
         operation: &'op Operation,
+        override_labels: &'op EnabledOverrideLabels,
     ) -> QuerySolutionSpaceBuilder<'schema, 'op>
     where
         'schema: 'op,
@@ -39,6 +41,7 @@ impl<'schema> QuerySolutionSpace<'schema> {
         QuerySolutionSpaceBuilder {
             schema,
             operation,
+            override_labels,
             query: Query {
                 step: SolutionSpace {},
                 root_node_id,
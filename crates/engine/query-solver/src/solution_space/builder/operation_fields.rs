@@ -5,7 +5,7 @@ use std::{
 
 use fxhash::FxHasher32;
 use id_newtypes::IdRange;
-use operation::{ExecutableDirectiveId, OperationContext, QueryPosition};
+use operation::{DeferDirectiveRecord, ExecutableDirectiveId, OperationContext, QueryPosition, StreamDirectiveRecord};
 use petgraph::{Direction, stable_graph::NodeIndex};
 use schema::{CompositeTypeId, TypeSystemDirective};
 use walker::Walk;
@@ -241,7 +241,11 @@ where
                 .extend_from_slice(&self.parent_type_conditions);
             (start..query.shared_type_conditions.len()).into()
         };
-        let flat_directive_id = self.ingest_directives(field.directive_ids());
+        let IngestedDirectives {
+            flat_directive_id,
+            defer,
+            stream,
+        } = self.ingest_directives(field.directive_ids());
         let response_key = field.response_key();
         let (definition_id, output_ty) = field.definition().map(|def| (def.id, def.ty())).unzip();
 
@@ -284,13 +288,22 @@ where
                 if self.builder.query[query_field.type_conditions] == self.builder.query[type_conditions]
                     && query_field.flat_directive_id == flat_directive_id
                 {
-                    existing_query_field_node_ix = Some(node_ix);
+                    existing_query_field_node_ix = Some((node_ix, node.id));
                     break;
                 }
             }
         }
         self.response_key_bloom_filter |= bloom_bit_mask;
 
+        // If any occurrence isn't deferred or streamed, the field is part of the initial response
+        // anyway.
+        let existing_query_field_node_ix = existing_query_field_node_ix.map(|(node_ix, id)| {
+            let query_field = &mut self.builder.query[id];
+            query_field.defer = query_field.defer.and(defer);
+            query_field.stream = query_field.stream.and(stream);
+            node_ix
+        });
+
         let query_field_node_ix = existing_query_field_node_ix.unwrap_or_else(|| {
             let (query_field_id, edge_weight) = {
                 let (query_field, edge_weight) = match field {
@@ -304,6 +317,8 @@ where
                             sorted_argument_ids: QueryOrSchemaSortedFieldArgumentIds::Query(field.sorted_argument_ids),
                             location: field.location,
                             flat_directive_id,
+                            defer,
+                            stream,
                         },
                         SpaceEdge::Field,
                     ),
@@ -317,6 +332,8 @@ where
                             sorted_argument_ids: QueryOrSchemaSortedFieldArgumentIds::Query(IdRange::empty()),
                             location: field.location,
                             flat_directive_id,
+                            defer,
+                            stream: None,
                         },
                         SpaceEdge::TypenameField,
                     ),
@@ -411,16 +428,24 @@ where
         Ok(())
     }
 
-    fn ingest_directives(
-        &mut self,
-        field_directive_ids: &[ExecutableDirectiveId],
-    ) -> Option<DeduplicatedFlatExecutableDirectivesId> {
+    fn ingest_directives(&mut self, field_directive_ids: &[ExecutableDirectiveId]) -> IngestedDirectives {
+        let mut ingested = IngestedDirectives::default();
         if self.parent_directive_ids.is_empty() && field_directive_ids.is_empty() {
-            return None;
+            return ingested;
         }
         let mut directives = Vec::with_capacity(self.parent_directive_ids.len() + field_directive_ids.len());
-        directives.extend_from_slice(&self.parent_directive_ids);
-        directives.extend_from_slice(field_directive_ids);
+        // Parent directives are ordered from the outermost fragment to the innermost one, so the
+        // last @defer wins.
+        for directive in self.parent_directive_ids.iter().chain(field_directive_ids) {
+            match directive {
+                ExecutableDirectiveId::Defer(defer) => ingested.defer = Some(*defer),
+                ExecutableDirectiveId::Stream(stream) => ingested.stream = Some(*stream),
+                ExecutableDirectiveId::Include(_) | ExecutableDirectiveId::Skip(_) => directives.push(*directive),
+            }
+        }
+        if directives.is_empty() {
+            return ingested;
+        }
         directives.sort_unstable();
 
         let next_id = self
@@ -429,13 +454,21 @@ where
             .deduplicated_flat_sorted_executable_directives
             .len()
             .into();
-        Some(
+        ingested.flat_directive_id = Some(
             *self
                 .builder
                 .query
                 .deduplicated_flat_sorted_executable_directives
                 .entry(directives)
                 .or_insert(next_id),
-        )
+        );
+        ingested
     }
 }
+
+#[derive(Default)]
+struct IngestedDirectives {
+    flat_directive_id: Option<DeduplicatedFlatExecutableDirectivesId>,
+    defer: Option<DeferDirectiveRecord>,
+    stream: Option<StreamDirectiveRecord>,
+}
@@ -81,17 +81,28 @@ where
         let field_definition = definition_id.walk(self.schema);
 
         // --
-        // If providable by parent, we don't need to find for a resolver.
+        // If providable by parent, we don't need to find for a resolver. Unless the field starts a
+        // @defer fragment, it must then be retrieved separately if possible to not delay its
+        // parent.
         // --
+        let is_deferred = query_field.defer.is_some();
         let provide_result = self.query.graph[parent.providable_field_or_root_ix]
             .as_providable_field()
             .map(|parent_providable_field| {
-                self.provide_field_from_parent(
+                let result = self.provide_field_from_parent(
                     parent_providable_field,
                     parent.output_type,
                     query_field_id,
                     field_definition,
-                )
+                );
+                match result {
+                    ParentProvideResult::Providable(_)
+                        if is_deferred && self.has_dedicated_resolver(parent.output_type, field_definition) =>
+                    {
+                        ParentProvideResult::NotProvidable
+                    }
+                    result => result,
+                }
             })
             .unwrap_or_default();
         let could_be_provided_from_parent = match provide_result {
@@ -146,6 +157,10 @@ where
                 continue;
             };
 
+            if field_definition.is_overridden_in(resolver_definition.subgraph_id(), self.override_labels) {
+                continue;
+            }
+
             if resolver_definition.is_lookup()
                 && !self
                     .is_field_connected_to_parent_resolver(
@@ -293,6 +308,20 @@ where
         }
     }
 
+    fn has_dedicated_resolver(&self, parent_output: CompositeTypeId, field_definition: FieldDefinition<'_>) -> bool {
+        field_definition.resolvers().any(|resolver_definition| {
+            !field_definition.is_overridden_in(resolver_definition.subgraph_id(), self.override_labels)
+                && (!resolver_definition.is_lookup()
+                    || self
+                        .is_field_connected_to_parent_resolver(
+                            resolver_definition.subgraph_id(),
+                            parent_output,
+                            field_definition,
+                        )
+                        .is_yes())
+        })
+    }
+
     fn provide_field_from_parent(
         &self,
         parent: &ProvidableField<'schema>,
@@ -433,6 +462,9 @@ where
     }
 
     fn is_field_providable_in_subgraph(&self, subgraph_id: SubgraphId, field_definition: FieldDefinition<'_>) -> bool {
+        if field_definition.is_overridden_in(subgraph_id, self.override_labels) {
+            return false;
+        }
         match field_definition.parent_entity() {
             EntityDefinition::Interface(_) => field_definition.exists_in_subgraph_ids.contains(&subgraph_id),
             EntityDefinition::Object(obj) => {
@@ -572,6 +604,8 @@ where
                         sorted_argument_ids,
                         location: self.query[petitioner_field_id].location,
                         flat_directive_id,
+                        defer: None,
+                        stream: None,
                     });
 
                     let query_field_node_ix = self.push_query_field_node(
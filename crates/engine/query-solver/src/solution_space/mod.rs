@@ -6,7 +6,7 @@ pub(crate) use edge::*;
 pub(crate) use node::*;
 
 use operation::{Operation, OperationContext};
-use schema::Schema;
+use schema::{EnabledOverrideLabels, Schema};
 use tracing::{Level, instrument};
 
 use petgraph::{
@@ -24,16 +24,22 @@ pub(crate) type QuerySolutionSpace<'schema> = Query<SolutionSpaceGraph<'schema>,
 
 impl<'schema> QuerySolutionSpace<'schema> {
     #[instrument(skip_all, level = Level::DEBUG)]
-    pub fn generate_solution_space<'op>(schema: &'schema Schema, operation: &'op Operation) -> crate::Result<Self>
+    pub fn generate_solution_space<'op>(
+        schema: &'schema Schema,
+        operation: &'op Operation,
+        override_labels: &'op EnabledOverrideLabels,
+    ) -> crate::Result<Self>
     where
         'schema: 'op,
     {
-        QuerySolutionSpace::builder(schema, operation).build().inspect(|query| {
-            tracing::debug!(
-                "OperationGraph created:\n{}",
-                query.to_pretty_dot_graph(OperationContext { schema, operation })
-            );
-        })
+        QuerySolutionSpace::builder(schema, operation, override_labels)
+            .build()
+            .inspect(|query| {
+                tracing::debug!(
+                    "OperationGraph created:\n{}",
+                    query.to_pretty_dot_graph(OperationContext { schema, operation })
+                );
+            })
     }
 
     /// Use https://dreampuf.github.io/GraphvizOnline
@@ -82,6 +82,7 @@ impl DeduplicationMap {
             RecordDiscriminants::Field.hash(&mut hasher);
             field.type_conditions.hash(&mut hasher);
             field.flat_directive_id.hash(&mut hasher);
+            field.defer.hash(&mut hasher);
             field.response_key.hash(&mut hasher);
             field.definition_id.hash(&mut hasher);
             field.sorted_argument_ids.len().hash(&mut hasher);
@@ -109,7 +110,8 @@ impl DeduplicationMap {
                     ((existing.type_conditions == field.type_conditions)
                         & (existing.response_key == field.response_key)
                         & (existing.definition_id == field.definition_id)
-                        & (existing.flat_directive_id == field.flat_directive_id))
+                        & (existing.flat_directive_id == field.flat_directive_id)
+                        & (existing.defer == field.defer))
                         && are_arguments_equivalent(ctx, existing.sorted_argument_ids, field.sorted_argument_ids)
                 }
                 _ => false,
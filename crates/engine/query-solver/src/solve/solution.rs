@@ -3,7 +3,7 @@ use id_newtypes::{IdRange, IdToMany};
 use operation::{Operation, OperationContext};
 use petgraph::{
     Direction, Graph,
-    graph::NodeIndex,
+    graph::{EdgeIndex, NodeIndex},
     visit::{EdgeIndexable, EdgeRef},
 };
 use schema::{FieldDefinitionId, Schema};
@@ -47,6 +47,7 @@ struct Builder<'schema, 'op> {
     included_space_edges: FixedBitSet,
     steiner_tree: SteinerTree,
     steiner_input_map: SteinerInputMap,
+    cost: u32,
 }
 
 impl<'schema, 'op> Builder<'schema, 'op> {
@@ -57,11 +58,21 @@ impl<'schema, 'op> Builder<'schema, 'op> {
                     space,
                     map: steiner_input_map,
                     space_node_is_terminal,
+                    graph: steiner_graph,
                     ..
                 },
             steiner_tree,
         } = solution;
 
+        // The tree tracks its weight while growing, but it's reset whenever the weights are
+        // updated, so summing the final edges instead.
+        let cost = steiner_tree
+            .edges
+            .ones()
+            .filter_map(|ix| steiner_graph.edge_weight(EdgeIndex::new(ix)))
+            .map(|weight| *weight as u32)
+            .sum();
+
         let mut deduplication_map = DeduplicationMap::with_capacity(space.fields.len());
         let ctx = OperationContext { schema, operation };
         let field_to_dedup_id = (0..space.fields.len())
@@ -109,6 +120,7 @@ impl<'schema, 'op> Builder<'schema, 'op> {
             included_space_edges,
             steiner_tree,
             steiner_input_map,
+            cost,
         }
     }
 
@@ -116,7 +128,7 @@ impl<'schema, 'op> Builder<'schema, 'op> {
         self.ingest_nodes()?;
 
         let query = QuerySteinerSolution {
-            step: crate::query::steps::SteinerSolution {},
+            step: crate::query::steps::SteinerSolution { cost: self.cost },
             root_node_id: self.root_node_id,
             graph: self.graph,
             fields: self.space.fields,
@@ -372,6 +384,8 @@ impl<'schema, 'op> Builder<'schema, 'op> {
                 sorted_argument_ids: QueryOrSchemaSortedFieldArgumentIds::Query(IdRange::empty()),
                 location: self.space[node.id].location,
                 flat_directive_id: None,
+                defer: None,
+                stream: None,
             };
             self.space.fields.push(field);
             let id = QueryFieldId::from(self.space.fields.len() - 1);
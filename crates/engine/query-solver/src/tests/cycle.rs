@@ -81,7 +81,7 @@ fn requirements_cycle() {
     )
     .unwrap();
 
-    let query = Query::generate_solution_space(&schema, &operation).unwrap();
+    let query = Query::generate_solution_space(&schema, &operation, &Default::default()).unwrap();
     let ctx = OperationContext {
         schema: &schema,
         operation: &operation,
@@ -14,6 +14,7 @@ mod mutation;
 mod provides;
 mod requirements;
 mod root_type;
+mod satisfiability;
 mod shared_root;
 mod sibling_dependencies;
 mod tea_shop;
@@ -66,7 +67,8 @@ macro_rules! assert_solving_snapshots {
             operation: &operation,
         };
 
-        let query_solution_space = $crate::Query::generate_solution_space(&schema, &operation).unwrap();
+        let query_solution_space =
+            $crate::Query::generate_solution_space(&schema, &operation, &Default::default()).unwrap();
         insta::assert_snapshot!(
             format!("{name}-graph"),
             query_solution_space.to_dot_graph(ctx),
@@ -121,7 +123,8 @@ macro_rules! assert_solution_snapshots {
         let query = $query;
         let mut operation = ::operation::Operation::parse(&schema, None, query).unwrap();
 
-        let query_solution_space = $crate::Query::generate_solution_space(&schema, &operation).unwrap();
+        let query_solution_space =
+            $crate::Query::generate_solution_space(&schema, &operation, &Default::default()).unwrap();
         let mut solver = $crate::solve::Solver::initialize(&schema, &operation, query_solution_space).unwrap();
 
         solver.execute().unwrap();
@@ -0,0 +1,330 @@
+use crate::check_satisfiability;
+
+use super::{IntoSchema, runtime};
+
+#[test]
+fn satisfiable_entities() {
+    let schema = runtime().block_on(
+        IntoSchema::from(
+            r###"
+            enum join__Graph {
+              CATEGORY @join__graph(name: "category", url: "http://localhost:4200/category")
+              REVIEW @join__graph(name: "review", url: "http://localhost:4200/review")
+            }
+
+            type Product
+              @join__type(graph: CATEGORY, key: "id")
+              @join__type(graph: REVIEW, key: "id")
+            {
+              id: ID!
+              category: String @join__field(graph: CATEGORY)
+              reviews(first: Int!): [Review] @join__field(graph: REVIEW)
+            }
+
+            type Review @join__type(graph: REVIEW)
+            {
+              stars: Int @join__field(graph: REVIEW)
+            }
+
+            type Query @join__type(graph: CATEGORY)
+            {
+              product: Product
+            }
+            "###,
+        )
+        .into_schema(),
+    );
+
+    assert!(check_satisfiability(&schema).is_empty());
+}
+
+#[test]
+fn entity_without_key() {
+    let schema = runtime().block_on(
+        IntoSchema::from(
+            r###"
+            enum join__Graph {
+              CATEGORY @join__graph(name: "category", url: "http://localhost:4200/category")
+              REVIEW @join__graph(name: "review", url: "http://localhost:4200/review")
+            }
+
+            type Product
+              @join__type(graph: CATEGORY, key: "id")
+              @join__type(graph: REVIEW)
+            {
+              id: ID!
+              category: String @join__field(graph: CATEGORY)
+              reviews: [Review] @join__field(graph: REVIEW)
+            }
+
+            type Review @join__type(graph: REVIEW)
+            {
+              stars: Int @join__field(graph: REVIEW)
+            }
+
+            type Query @join__type(graph: CATEGORY)
+            {
+              product: Product
+            }
+            "###,
+        )
+        .into_schema(),
+    );
+
+    let unsatisfiable_fields = check_satisfiability(&schema);
+    let [unsatisfiable_field] = unsatisfiable_fields.as_slice() else {
+        panic!("Expected a single unsatisfiable field: {unsatisfiable_fields:#?}");
+    };
+
+    assert_eq!(unsatisfiable_field.field, "Product.reviews");
+    insta::assert_snapshot!(unsatisfiable_field.example_query, @r"
+    query {
+      product {
+        reviews { __typename }
+      }
+    }
+    ");
+    insta::assert_snapshot!(unsatisfiable_field.error, @"Could not plan field: Product.reviews");
+}
+
+#[test]
+fn type_reached_through_several_fields() {
+    let schema = runtime().block_on(
+        IntoSchema::from(
+            r###"
+            enum join__Graph {
+              REVIEW @join__graph(name: "review", url: "http://localhost:4200/review")
+              STORE @join__graph(name: "store", url: "http://localhost:4200/store")
+            }
+
+            type Product
+              @join__type(graph: REVIEW)
+              @join__type(graph: STORE, key: "id")
+            {
+              id: ID!
+              reviews: [Review] @join__field(graph: REVIEW)
+            }
+
+            type Review @join__type(graph: REVIEW)
+            {
+              stars: Int @join__field(graph: REVIEW)
+            }
+
+            type Query
+              @join__type(graph: REVIEW)
+              @join__type(graph: STORE)
+            {
+              fromReviews: Product @join__field(graph: REVIEW)
+              fromStore: Product @join__field(graph: STORE)
+            }
+            "###,
+        )
+        .into_schema(),
+    );
+
+    let unsatisfiable_fields = check_satisfiability(&schema);
+    let [unsatisfiable_field] = unsatisfiable_fields.as_slice() else {
+        panic!("Expected a single unsatisfiable field: {unsatisfiable_fields:#?}");
+    };
+
+    assert_eq!(unsatisfiable_field.field, "Product.reviews");
+    insta::assert_snapshot!(unsatisfiable_field.example_query, @r"
+    query {
+      fromStore {
+        reviews { __typename }
+      }
+    }
+    ");
+}
+
+#[test]
+fn unsatisfiable_field_is_reported_once() {
+    let schema = runtime().block_on(
+        IntoSchema::from(
+            r###"
+            enum join__Graph {
+              CATEGORY @join__graph(name: "category", url: "http://localhost:4200/category")
+              REVIEW @join__graph(name: "review", url: "http://localhost:4200/review")
+            }
+
+            type Product
+              @join__type(graph: CATEGORY, key: "id")
+              @join__type(graph: REVIEW)
+            {
+              id: ID!
+              reviews: [Review] @join__field(graph: REVIEW)
+            }
+
+            type Review @join__type(graph: REVIEW)
+            {
+              stars: Int @join__field(graph: REVIEW)
+            }
+
+            type Query @join__type(graph: CATEGORY)
+            {
+              product: Product
+              topProduct: Product
+            }
+            "###,
+        )
+        .into_schema(),
+    );
+
+    let unsatisfiable_fields = check_satisfiability(&schema);
+    let [unsatisfiable_field] = unsatisfiable_fields.as_slice() else {
+        panic!("Expected a single unsatisfiable field: {unsatisfiable_fields:#?}");
+    };
+
+    assert_eq!(unsatisfiable_field.field, "Product.reviews");
+}
+
+// Pins a known gap: `Wrapper.product` is only followed through the first field returning `Wrapper`.
+#[test]
+fn only_the_first_path_to_a_field_is_checked() {
+    let schema = runtime().block_on(
+        IntoSchema::from(
+            r###"
+            enum join__Graph {
+              REVIEW @join__graph(name: "review", url: "http://localhost:4200/review")
+              STORE @join__graph(name: "store", url: "http://localhost:4200/store")
+            }
+
+            type Wrapper
+              @join__type(graph: REVIEW)
+              @join__type(graph: STORE)
+            {
+              product: Product
+            }
+
+            type Product
+              @join__type(graph: REVIEW)
+              @join__type(graph: STORE, key: "id")
+            {
+              id: ID!
+              reviews: [Review] @join__field(graph: REVIEW)
+            }
+
+            type Review @join__type(graph: REVIEW)
+            {
+              stars: Int @join__field(graph: REVIEW)
+            }
+
+            type Query
+              @join__type(graph: REVIEW)
+              @join__type(graph: STORE)
+            {
+              fromReviews: Wrapper @join__field(graph: REVIEW)
+              fromStore: Wrapper @join__field(graph: STORE)
+            }
+            "###,
+        )
+        .into_schema(),
+    );
+
+    assert!(check_satisfiability(&schema).is_empty());
+    assert!(
+        crate::satisfiability::check(&schema, "query { fromStore { product { reviews { __typename } } } }").is_err()
+    );
+}
+
+#[test]
+fn possible_types_of_abstract_fields() {
+    let schema = runtime().block_on(
+        IntoSchema::from(
+            r###"
+            enum join__Graph {
+              CATEGORY @join__graph(name: "category", url: "http://localhost:4200/category")
+              REVIEW @join__graph(name: "review", url: "http://localhost:4200/review")
+            }
+
+            union Media
+              @join__type(graph: CATEGORY)
+              @join__unionMember(graph: CATEGORY, member: "Book")
+            = Book
+
+            type Book
+              @join__type(graph: CATEGORY, key: "id")
+              @join__type(graph: REVIEW)
+            {
+              id: ID!
+              title: String @join__field(graph: CATEGORY)
+              rating: Int @join__field(graph: REVIEW)
+            }
+
+            type Query @join__type(graph: CATEGORY)
+            {
+              media: Media
+            }
+            "###,
+        )
+        .into_schema(),
+    );
+
+    let unsatisfiable_fields = check_satisfiability(&schema);
+    let [unsatisfiable_field] = unsatisfiable_fields.as_slice() else {
+        panic!("Expected a single unsatisfiable field: {unsatisfiable_fields:#?}");
+    };
+
+    assert_eq!(unsatisfiable_field.field, "Book.rating");
+    insta::assert_snapshot!(unsatisfiable_field.example_query, @r"
+    query {
+      media {
+        ... on Book {
+          rating
+        }
+      }
+    }
+    ");
+}
+
+#[test]
+fn mutation_with_required_arguments() {
+    let schema = runtime().block_on(
+        IntoSchema::from(
+            r###"
+            enum join__Graph {
+              CATEGORY @join__graph(name: "category", url: "http://localhost:4200/category")
+              REVIEW @join__graph(name: "review", url: "http://localhost:4200/review")
+            }
+
+            type Product
+              @join__type(graph: CATEGORY, key: "id")
+              @join__type(graph: REVIEW)
+            {
+              id: ID!
+              reviews: [Review] @join__field(graph: REVIEW)
+            }
+
+            type Review @join__type(graph: REVIEW)
+            {
+              stars: Int @join__field(graph: REVIEW)
+            }
+
+            type Query @join__type(graph: CATEGORY)
+            {
+              version: String
+            }
+
+            type Mutation @join__type(graph: CATEGORY)
+            {
+              createProduct(name: String!): Product
+            }
+            "###,
+        )
+        .into_schema(),
+    );
+
+    let unsatisfiable_fields = check_satisfiability(&schema);
+    let [unsatisfiable_field] = unsatisfiable_fields.as_slice() else {
+        panic!("Expected a single unsatisfiable field: {unsatisfiable_fields:#?}");
+    };
+
+    assert_eq!(unsatisfiable_field.field, "Product.reviews");
+    insta::assert_snapshot!(unsatisfiable_field.example_query, @r"
+    mutation($v0: String!) {
+      createProduct(name: $v0) {
+        reviews { __typename }
+      }
+    }
+    ");
+}
@@ -80,6 +80,7 @@ pub(crate) fn ingest_definitions(
         templates: Vec::new(),
         lookup_resolver_definitions: Vec::new(),
         derive_definitions: Vec::new(),
+        override_labels: Vec::new(),
     };
 
     let builder = GraphBuilder {
@@ -246,6 +247,7 @@ impl<'a> Ingester<'a> {
                 requires_records: Default::default(),
                 directive_ids: Default::default(),
                 derive_ids: Default::default(),
+                progressive_override_records: Default::default(),
             });
         }
         let end = self.graph.field_definitions.len();
@@ -605,6 +607,7 @@ impl<'a> Ingester<'a> {
                 requires_records: Default::default(),
                 directive_ids: Default::default(),
                 derive_ids: Default::default(),
+                progressive_override_records: Default::default(),
             });
         }
     }
@@ -0,0 +1,38 @@
+use cynic_parser_deser::ConstDeserializer as _;
+
+use crate::{
+    CacheControlDirectiveRecord, TypeSystemDirectiveId,
+    builder::{Error, graph::directives::DirectivesIngester, sdl},
+};
+
+impl<'sdl> DirectivesIngester<'_, 'sdl> {
+    pub fn create_cache_control_directive(
+        &mut self,
+        def: sdl::SdlDefinition<'sdl>,
+        directive: sdl::Directive<'sdl>,
+    ) -> Result<TypeSystemDirectiveId, Error> {
+        if !matches!(
+            def,
+            sdl::SdlDefinition::FieldDefinition(_)
+                | sdl::SdlDefinition::Object(_)
+                | sdl::SdlDefinition::Interface(_)
+                | sdl::SdlDefinition::Union(_)
+        ) {
+            return Err(Error::new(
+                "@cacheControl can only be used on field definitions, objects, interfaces and unions.",
+            )
+            .span(directive.name_span()));
+        }
+        let dir = directive.deserialize::<sdl::CacheControlDirective>().map_err(|err| {
+            (
+                format!("Invalid @cacheControl directive: {err}"),
+                directive.arguments_span(),
+            )
+        })?;
+        Ok(TypeSystemDirectiveId::CacheControl(CacheControlDirectiveRecord {
+            max_age: dir.max_age,
+            scope: dir.scope,
+            inherit_max_age: dir.inherit_max_age,
+        }))
+    }
+}
@@ -1,3 +1,4 @@
+mod cache_control;
 mod cost;
 mod deprecated;
 mod list_size;
@@ -29,6 +30,10 @@ impl<'sdl> DirectivesIngester<'_, 'sdl> {
                     Ok(id) => directive_ids.push(id),
                     Err(err) => self.errors.push(err),
                 },
+                "cacheControl" => match self.create_cache_control_directive(def, directive) {
+                    Ok(id) => directive_ids.push(id),
+                    Err(err) => self.errors.push(err),
+                },
                 "cost" => match self.create_cost_directive(def, directive) {
                     Ok(id) => directive_ids.push(id),
                     Err(err) => self.errors.push(err),
@@ -4,8 +4,8 @@ use itertools::Itertools;
 
 use crate::{
     EnumDefinitionId, FieldProvidesRecord, FieldRequiresRecord, Graph, InputObjectDefinitionId, InterfaceDefinitionId,
-    JoinImplementsDefinitionRecord, JoinMemberDefinitionRecord, ScalarDefinitionId, SubgraphId, SubgraphTypeRecord,
-    UnionDefinitionId,
+    JoinImplementsDefinitionRecord, JoinMemberDefinitionRecord, OverrideLabelRecord, ProgressiveOverrideRecord,
+    ScalarDefinitionId, StringId, SubgraphId, SubgraphTypeRecord, UnionDefinitionId,
     builder::{
         Error,
         sdl::{self, GraphName},
@@ -259,6 +259,7 @@ impl<'sdl> DirectivesIngester<'_, 'sdl> {
 
         let mut has_join_field = false;
         let mut overrides = Vec::new();
+        let mut progressive_override_records = Vec::new();
         for result in directives.iter().filter_map(sdl::as_join_field) {
             let (dir, span) = match result {
                 Ok(v) => v,
@@ -356,7 +357,19 @@ impl<'sdl> DirectivesIngester<'_, 'sdl> {
             if let Some(name) = dir.r#override
                 && let Ok(graph) = self.subgraphs.try_get(GraphName(name), span)
             {
-                overrides.push(graph);
+                // With a label the field is resolvable by both subgraphs, the query planning
+                // decides which one to use depending on whether the label is enabled.
+                match (dir.override_label, subgraph_id) {
+                    (Some(label), Some(to_id)) => {
+                        let label_id = self.ingest_override_label(label);
+                        progressive_override_records.push(ProgressiveOverrideRecord {
+                            label_id,
+                            from_id: graph,
+                            to_id,
+                        });
+                    }
+                    _ => overrides.push(graph),
+                }
             }
         }
 
@@ -397,6 +410,18 @@ impl<'sdl> DirectivesIngester<'_, 'sdl> {
         field.exists_in_subgraph_ids = exists_in_subgraph_ids;
         field.provides_records = provides_records;
         field.requires_records = requires_records;
+        field.progressive_override_records = progressive_override_records;
+    }
+
+    fn ingest_override_label(&mut self, label: sdl::OverrideLabel) -> StringId {
+        let (id, percent) = match label {
+            sdl::OverrideLabel::Percent(percent) => (self.ingest_str(format!("percent({percent})")), Some(percent)),
+            sdl::OverrideLabel::Unknown(label) => (self.ingest_str(label), None),
+        };
+        if !self.graph.override_labels.iter().any(|label| label.id == id) {
+            self.graph.override_labels.push(OverrideLabelRecord { id, percent });
+        }
+        id
     }
 }
 
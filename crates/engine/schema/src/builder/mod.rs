@@ -171,6 +171,26 @@ impl BuildContext<'_> {
     }
 }
 
+fn complexity_control(config: &Config) -> ComplexityControl {
+    let complexity_control = ComplexityControl::from(&config.complexity_control);
+
+    // Complexity rate limits need the cost of every operation.
+    let rate_limited = config
+        .gateway
+        .rate_limit
+        .as_ref()
+        .is_some_and(|rate_limit| rate_limit.complexity.is_some());
+
+    if rate_limited && complexity_control.is_disabled() {
+        return ComplexityControl::Measure {
+            limit: None,
+            list_size: config.complexity_control.list_size.unwrap_or(10),
+        };
+    }
+
+    complexity_control
+}
+
 fn build_settings(config: &Config) -> PartialConfig {
     PartialConfig {
         timeout: config.gateway.timeout,
@@ -178,7 +198,7 @@ fn build_settings(config: &Config) -> PartialConfig {
         disable_introspection: !config.graph.introspection.unwrap_or_default(),
         retry: config.gateway.retry.enabled.then_some(config.gateway.retry.into()),
         batching: config.gateway.batching.clone(),
-        complexity_control: (&config.complexity_control).into(),
+        complexity_control: complexity_control(config),
         response_extension: config
             .telemetry
             .exporters
@@ -186,7 +206,10 @@ fn build_settings(config: &Config) -> PartialConfig {
             .clone()
             .unwrap_or_default()
             .into(),
+        response_caching: (&config.response_caching).into(),
         apq_enabled: config.apq.enabled,
+        file_uploads_enabled: config.file_uploads.enabled,
+        file_uploads_required_header: config.csrf.header_name.clone(),
         executable_document_limit_bytes: config
             .executable_document_limit
             .bytes()
@@ -196,5 +219,6 @@ fn build_settings(config: &Config) -> PartialConfig {
         websocket_forward_connection_init_payload: config.websockets.forward_connection_init_payload,
         contract_cache_max_size: config.graph.contracts.cache.max_size,
         error_code_mapping: config.graph.error_code_mapping.clone(),
+        response_header_rules: config.response_headers.iter().map(Into::into).collect(),
     }
 }
@@ -98,6 +98,43 @@ pub struct ListSizeDirective<'a> {
     pub require_one_slicing_argument: bool,
 }
 
+/// ```ignore,graphql
+/// enum CacheControlScope {
+///   PUBLIC
+///   PRIVATE
+/// }
+///
+/// directive @cacheControl(
+///   maxAge: Int
+///   scope: CacheControlScope
+///   inheritMaxAge: Boolean
+/// ) on FIELD_DEFINITION | OBJECT | INTERFACE | UNION
+/// ```
+#[derive(ValueDeserialize)]
+pub struct CacheControlDirective {
+    #[deser(rename = "maxAge")]
+    pub max_age: Option<u32>,
+    pub scope: Option<crate::CacheControlScope>,
+    #[deser(default = false, rename = "inheritMaxAge")]
+    pub inherit_max_age: bool,
+}
+
+impl<'de> ValueDeserialize<'de> for crate::CacheControlScope {
+    fn deserialize(input: DeserValue<'de>) -> Result<Self, cynic_parser_deser::Error> {
+        match input {
+            DeserValue::Enum(enum_value) => match enum_value.name() {
+                "PUBLIC" => Ok(crate::CacheControlScope::Public),
+                "PRIVATE" => Ok(crate::CacheControlScope::Private),
+                _ => Err(cynic_parser_deser::Error::custom(
+                    "Unknown cache control scope",
+                    input.span(),
+                )),
+            },
+            other => Err(cynic_parser_deser::Error::unexpected_type(ValueType::Enum, other)),
+        }
+    }
+}
+
 #[derive(ValueDeserialize)]
 pub struct DeprecatedDirective<'a> {
     pub reason: Option<&'a str>,
@@ -180,12 +217,10 @@ pub(crate) struct JoinFieldDirective<'a> {
     pub external: bool,
     #[deser(rename = "override")]
     pub r#override: Option<&'a str>,
-    #[allow(unused)]
     #[deser(rename = "overrideLabel")]
     pub override_label: Option<OverrideLabel>,
 }
 
-#[allow(unused)]
 #[derive(Debug)]
 pub enum OverrideLabel {
     Percent(u8),
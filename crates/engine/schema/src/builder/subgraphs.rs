@@ -3,8 +3,9 @@ use id_newtypes::IdRange;
 use rapidhash::fast::RapidHashMap;
 
 use crate::{
-    ForwardHeaderRuleRecord, HeaderRuleId, HeaderRuleRecord, InsertHeaderRuleRecord, NameOrPatternId,
-    RemoveHeaderRuleRecord, RenameDuplicateHeaderRuleRecord, SubGraphs, introspection::IntrospectionSubgraph,
+    ForwardHeaderRuleRecord, HeaderRuleCondition, HeaderRuleConditionId, HeaderRuleId, HeaderRuleRecord,
+    HeaderTemplate, HeaderTemplateId, InsertHeaderRuleRecord, NameOrPatternId, RemoveHeaderRuleRecord,
+    RenameDuplicateHeaderRuleRecord, SubGraphs, introspection::IntrospectionSubgraph,
 };
 
 use super::{
@@ -24,6 +25,8 @@ pub(crate) struct SubgraphsBuilder<'sdl> {
     pub virtual_subgraphs: Vec<VirtualSubgraphRecord>,
     pub default_header_rules: IdRange<HeaderRuleId>,
     pub header_rules: Vec<HeaderRuleRecord>,
+    pub header_rule_conditions: Vec<HeaderRuleCondition>,
+    pub header_templates: Vec<HeaderTemplate>,
 }
 
 impl<'sdl> SubgraphsBuilder<'sdl> {
@@ -34,10 +37,18 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
             graphql_endpoints: Vec::new(),
             virtual_subgraphs: Vec::new(),
             header_rules: Vec::new(),
+            header_rule_conditions: Vec::new(),
+            header_templates: Vec::new(),
             default_header_rules: IdRange::default(),
         };
 
-        subgraphs.default_header_rules = ingest_header_rules(&mut subgraphs.header_rules, &config.headers, interners);
+        subgraphs.default_header_rules = ingest_header_rules(
+            &mut subgraphs.header_rules,
+            &mut subgraphs.header_rule_conditions,
+            &mut subgraphs.header_templates,
+            &config.headers,
+            interners,
+        );
 
         let default_cache_ttl = if config.entity_caching.enabled {
             Some(config.entity_caching.ttl)
@@ -58,16 +69,33 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
             let SubgraphConfig {
                 url,
                 headers,
+                response_headers,
                 websocket_url,
                 timeout,
                 retry,
                 entity_caching,
+                entity_batching,
+                circuit_breaker,
+                load_balancing,
                 subscription_protocol,
                 ..
             } = config.subgraphs.get(name).cloned().unwrap_or_default();
-            let url = url.or(subgraph.url.clone());
-
-            let header_rule_ids = ingest_header_rules(&mut subgraphs.header_rules, &headers, interners);
+            // The fetcher balances the requests across the endpoints, but one of them is needed
+            // as the subgraph URL if none is provided.
+            let url = url.or(subgraph.url.clone()).or_else(|| {
+                load_balancing
+                    .as_ref()
+                    .and_then(|cfg| cfg.endpoints.first())
+                    .map(|endpoint| endpoint.url.clone())
+            });
+
+            let header_rule_ids = ingest_header_rules(
+                &mut subgraphs.header_rules,
+                &mut subgraphs.header_rule_conditions,
+                &mut subgraphs.header_templates,
+                &headers,
+                interners,
+            );
             let subgraph_id = if let Some(url) = url {
                 subgraphs.graphql_endpoints.push(GraphqlSubgraphRecord {
                     name_id: subgraph_name_id,
@@ -91,6 +119,9 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
                                     .flatten()
                             })
                             .or(default_cache_ttl),
+                        entity_batching: entity_batching.filter(|cfg| cfg.enabled).map(Into::into),
+                        circuit_breaker: circuit_breaker.filter(|cfg| cfg.enabled).map(Into::into),
+                        response_header_rules: response_headers.iter().map(Into::into).collect(),
                     },
                     schema_directive_ids: Vec::new(),
                 });
@@ -123,6 +154,8 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
             virtual_subgraphs,
             default_header_rules,
             header_rules,
+            header_rule_conditions,
+            header_templates,
             ..
         } = self;
         SubGraphs {
@@ -131,16 +164,25 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
             introspection,
             default_header_rules,
             header_rules,
+            header_rule_conditions,
+            header_templates,
         }
     }
 }
 
 fn ingest_header_rules(
     header_rules: &mut Vec<HeaderRuleRecord>,
+    header_rule_conditions: &mut Vec<HeaderRuleCondition>,
+    header_templates: &mut Vec<HeaderTemplate>,
     rules: &[gateway_config::HeaderRule],
     interners: &mut Interners,
 ) -> IdRange<HeaderRuleId> {
     use gateway_config::*;
+    let mut ingest_condition = |condition: &Option<HeaderRuleCondition>| -> Option<HeaderRuleConditionId> {
+        let condition = condition.clone()?;
+        header_rule_conditions.push(condition);
+        Some((header_rule_conditions.len() - 1).into())
+    };
     let start = header_rules.len();
     header_rules.extend(rules.iter().map(|rule| -> HeaderRuleRecord {
         match rule {
@@ -159,13 +201,19 @@ fn ingest_header_rules(
                     name_id,
                     default_id,
                     rename_id,
+                    condition_id: ingest_condition(&rule.when),
                 })
             }
             HeaderRule::Insert(rule) => {
                 let name_id = interners.strings.get_or_new(rule.name.as_str());
-                let value_id = interners.strings.get_or_new(rule.value.as_str());
+                header_templates.push(rule.value.clone());
+                let value_id = HeaderTemplateId::from(header_templates.len() - 1);
 
-                HeaderRuleRecord::Insert(InsertHeaderRuleRecord { name_id, value_id })
+                HeaderRuleRecord::Insert(InsertHeaderRuleRecord {
+                    name_id,
+                    value_id,
+                    condition_id: ingest_condition(&rule.when),
+                })
             }
             HeaderRule::Remove(rule) => {
                 let name_id = match &rule.name {
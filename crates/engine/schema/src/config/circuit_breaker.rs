@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Ratio of failed requests opening the breaker.
+    pub failure_rate_threshold: f32,
+    /// Minimum number of requests in the window before the failure rate is considered.
+    pub minimum_requests: u32,
+    /// Time window over which the failure rate is computed.
+    pub window: Duration,
+    /// How long the breaker stays open before letting a probe request through.
+    pub half_open_after: Duration,
+}
+
+impl From<gateway_config::CircuitBreakerConfig> for CircuitBreakerConfig {
+    fn from(config: gateway_config::CircuitBreakerConfig) -> Self {
+        CircuitBreakerConfig {
+            failure_rate_threshold: config.failure_rate_threshold,
+            minimum_requests: config.minimum_requests,
+            window: config.window,
+            half_open_after: config.half_open_after,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct EntityBatchingConfig {
+    /// Maximum number of entity requests in a single batch.
+    pub max_size: usize,
+    /// How long to wait for other entity requests before sending a batch.
+    pub max_wait: Duration,
+}
+
+impl From<gateway_config::EntityBatchingConfig> for EntityBatchingConfig {
+    fn from(config: gateway_config::EntityBatchingConfig) -> Self {
+        EntityBatchingConfig {
+            max_size: config.max_size,
+            max_wait: config.max_wait,
+        }
+    }
+}
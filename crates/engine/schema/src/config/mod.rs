@@ -1,10 +1,18 @@
+mod circuit_breaker;
 mod complexity_control;
+mod entity_batching;
+mod response_caching;
 mod response_extensions;
+mod response_headers;
 mod retry;
 mod trusted_documents;
 
+pub use circuit_breaker::*;
 pub use complexity_control::*;
+pub use entity_batching::*;
+pub use response_caching::*;
 pub use response_extensions::*;
+pub use response_headers::*;
 pub use retry::*;
 pub use trusted_documents::*;
 
@@ -17,10 +25,17 @@ pub struct PartialConfig {
     pub batching: gateway_config::BatchingConfig,
     pub complexity_control: ComplexityControl,
     pub response_extension: ResponseExtensionConfig,
+    pub response_caching: ResponseCachingConfig,
     pub apq_enabled: bool,
+    pub file_uploads_enabled: bool,
+    /// Header multipart requests must carry. Browsers send them cross-site without a CORS
+    /// preflight, so they're always subject to the CSRF check.
+    pub file_uploads_required_header: String,
     pub executable_document_limit_bytes: usize,
     pub trusted_documents: TrustedDocumentsConfig,
     pub websocket_forward_connection_init_payload: bool,
     pub contract_cache_max_size: usize,
     pub error_code_mapping: gateway_config::ErrorCodeMapping,
+    /// Global rules propagating subgraph response headers to the client.
+    pub response_header_rules: Vec<ResponseHeaderRule>,
 }
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ResponseCachingConfig {
+    pub enabled: bool,
+    /// Maximum time to live of a cached response.
+    pub ttl: Duration,
+    /// Lowercase names of the request headers partitioning the cache.
+    pub key_headers: Vec<String>,
+    /// Authentication token claims partitioning the cache. If empty, authenticated requests
+    /// are never cached.
+    pub key_jwt_claims: Vec<String>,
+}
+
+impl From<&gateway_config::ResponseCachingConfig> for ResponseCachingConfig {
+    fn from(config: &gateway_config::ResponseCachingConfig) -> Self {
+        ResponseCachingConfig {
+            enabled: config.enabled,
+            ttl: config.ttl,
+            key_headers: config
+                .key
+                .headers
+                .iter()
+                .map(|name| name.as_str().to_ascii_lowercase())
+                .collect(),
+            key_jwt_claims: config.key.jwt_claims.clone(),
+        }
+    }
+}
@@ -8,6 +8,9 @@ pub struct ResponseExtensionConfig {
     pub include_trace_id: bool,
     /// Whether the query plan is exposed in the grafbase response extension. Defaults to true.
     pub include_query_plan: bool,
+    /// Whether the `x-grafbase-explain` header can be used to explain the query plan instead of
+    /// executing the operation. Defaults to false.
+    pub allow_explain: bool,
     /// Defines under which conditions the grafbase response extension will be added.
     /// Defaults to a simple header rule, the presence of `x-grafbase-telemetry` is enough.
     pub access_control: Vec<AccessControl>,
@@ -24,6 +27,7 @@ impl From<ResponseExtensionExporterConfig> for ResponseExtensionConfig {
         ResponseExtensionConfig {
             include_trace_id: config.trace_id,
             include_query_plan: config.query_plan,
+            allow_explain: config.explain,
             access_control: config
                 .access_control
                 .into_iter()
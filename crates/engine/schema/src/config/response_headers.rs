@@ -0,0 +1,61 @@
+pub use gateway_config::HeaderMergeStrategy;
+use gateway_config::NameOrPattern;
+use regex::Regex;
+
+/// Rule applied to the subgraph response headers, see [gateway_config::ResponseHeaderRule].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum ResponseHeaderRule {
+    Propagate {
+        name: HeaderNameMatcher,
+        rename: Option<String>,
+        merge: HeaderMergeStrategy,
+    },
+    Remove {
+        name: HeaderNameMatcher,
+    },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum HeaderNameMatcher {
+    /// Lowercase header name.
+    Name(String),
+    Pattern(#[serde(with = "serde_regex")] Regex),
+}
+
+impl HeaderNameMatcher {
+    /// Header names are expected to be lowercase, as they are in `http::HeaderMap`.
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            HeaderNameMatcher::Name(expected) => expected == name,
+            HeaderNameMatcher::Pattern(regex) => regex.is_match(name),
+        }
+    }
+}
+
+impl From<&NameOrPattern> for HeaderNameMatcher {
+    fn from(name: &NameOrPattern) -> Self {
+        match name {
+            NameOrPattern::Name(name) => HeaderNameMatcher::Name(name.as_str().to_ascii_lowercase()),
+            // The case insensitive flag is embedded in the pattern to survive the serialization
+            // of the schema.
+            NameOrPattern::Pattern(pattern) => HeaderNameMatcher::Pattern(
+                Regex::new(&format!("(?i){}", pattern.0.as_str())).expect("pattern was already validated"),
+            ),
+        }
+    }
+}
+
+impl From<&gateway_config::ResponseHeaderRule> for ResponseHeaderRule {
+    fn from(rule: &gateway_config::ResponseHeaderRule) -> Self {
+        match rule {
+            gateway_config::ResponseHeaderRule::Propagate(rule) => ResponseHeaderRule::Propagate {
+                name: (&rule.name).into(),
+                rename: rule.rename.as_ref().map(|name| name.as_str().to_ascii_lowercase()),
+                merge: rule.merge,
+            },
+            gateway_config::ResponseHeaderRule::Remove(rule) => ResponseHeaderRule::Remove {
+                name: (&rule.name).into(),
+            },
+        }
+    }
+}
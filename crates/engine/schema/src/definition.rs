@@ -1,8 +1,8 @@
 use walker::Walk;
 
 use crate::{
-    CompositeType, CompositeTypeId, DeprecatedDirective, EntityDefinition, EntityDefinitionId, TypeDefinition,
-    TypeDefinitionId, TypeSystemDirective, TypeSystemDirectiveId,
+    CacheControlDirective, CompositeType, CompositeTypeId, DeprecatedDirective, EntityDefinition, EntityDefinitionId,
+    TypeDefinition, TypeDefinitionId, TypeSystemDirective, TypeSystemDirectiveId,
 };
 
 impl<'a> TypeDefinition<'a> {
@@ -86,6 +86,10 @@ impl<'a> TypeDefinition<'a> {
         })
     }
 
+    pub fn cache_control(&self) -> Option<CacheControlDirective<'a>> {
+        self.directives().find_map(|directive| directive.as_cache_control())
+    }
+
     pub fn is_inaccessible(&self) -> bool {
         match self {
             TypeDefinition::Enum(enm) => enm.is_inaccessible(),
@@ -1,10 +1,12 @@
 mod derive;
+mod progressive_override;
 
 pub use derive::*;
+pub use progressive_override::*;
 
 use crate::{
-    CostDirective, DeprecatedDirective, FieldDefinition, FieldRequires, FieldSet, InputValueDefinition,
-    ListSizeDirective, SubgraphId, TypeSystemDirective,
+    CacheControlDirective, CostDirective, DeprecatedDirective, FieldDefinition, FieldRequires, FieldSet,
+    InputValueDefinition, ListSizeDirective, SubgraphId, TypeSystemDirective,
 };
 
 impl std::fmt::Display for FieldDefinition<'_> {
@@ -44,6 +46,10 @@ impl<'a> FieldDefinition<'a> {
         })
     }
 
+    pub fn cache_control(&self) -> Option<CacheControlDirective<'a>> {
+        self.directives().find_map(|directive| directive.as_cache_control())
+    }
+
     pub fn list_size(&self) -> Option<ListSizeDirective<'a>> {
         self.directives().find_map(|directive| match directive {
             TypeSystemDirective::ListSize(list_size_directive) => Some(list_size_directive),
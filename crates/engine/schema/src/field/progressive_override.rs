@@ -0,0 +1,58 @@
+use crate::{FieldDefinition, Schema, StringId, SubgraphId};
+
+/// Label used by at least one progressive override, `@override(label: "...")`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OverrideLabelRecord {
+    pub id: StringId,
+    /// Present for `percent(N)` labels, enabled for N% of the requests. Any other label must be
+    /// explicitly enabled for a request.
+    pub percent: Option<u8>,
+}
+
+/// Progressive override labels enabled for a given request.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct EnabledOverrideLabels(Vec<StringId>);
+
+impl FromIterator<StringId> for EnabledOverrideLabels {
+    fn from_iter<T: IntoIterator<Item = StringId>>(iter: T) -> Self {
+        let mut ids = iter.into_iter().collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        Self(ids)
+    }
+}
+
+impl EnabledOverrideLabels {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, id: StringId) -> bool {
+        self.0.binary_search(&id).is_ok()
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = StringId> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl Schema {
+    pub fn override_labels(&self) -> &[OverrideLabelRecord] {
+        &self.graph.override_labels
+    }
+}
+
+impl FieldDefinition<'_> {
+    /// Whether a progressive override prevents the subgraph from resolving this field for the
+    /// given labels. With an enabled label the field is only resolved by the overriding subgraph,
+    /// otherwise only by the overridden one.
+    pub fn is_overridden_in(&self, subgraph_id: SubgraphId, labels: &EnabledOverrideLabels) -> bool {
+        self.progressive_override_records.iter().any(|record| {
+            if labels.contains(record.label_id) {
+                record.from_id == subgraph_id
+            } else {
+                record.to_id == subgraph_id
+            }
+        })
+    }
+}
@@ -3,11 +3,13 @@
 //! ===================
 //! Generated with: `cargo run -p engine-codegen`
 //! Source file: <engine-codegen dir>/domain/schema.graphql
+mod cache_control;
 mod complexity_control;
 mod deprecated;
 mod extension;
 
 use crate::prelude::*;
+pub use cache_control::*;
 pub use complexity_control::*;
 pub use deprecated::*;
 pub use extension::*;
@@ -21,10 +23,12 @@ use walker::{Iter, Walk};
 ///   | DeprecatedDirective
 ///   | CostDirective
 ///   | ListSizeDirective
+///   | CacheControlDirective
 ///   | ExtensionDirective
 /// ```
 #[derive(serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum TypeSystemDirectiveId {
+    CacheControl(CacheControlDirectiveRecord),
     Cost(CostDirectiveId),
     Deprecated(DeprecatedDirectiveRecord),
     Extension(ExtensionDirectiveId),
@@ -34,6 +38,7 @@ pub enum TypeSystemDirectiveId {
 impl std::fmt::Debug for TypeSystemDirectiveId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            TypeSystemDirectiveId::CacheControl(variant) => variant.fmt(f),
             TypeSystemDirectiveId::Cost(variant) => variant.fmt(f),
             TypeSystemDirectiveId::Deprecated(variant) => variant.fmt(f),
             TypeSystemDirectiveId::Extension(variant) => variant.fmt(f),
@@ -42,6 +47,11 @@ impl std::fmt::Debug for TypeSystemDirectiveId {
     }
 }
 
+impl From<CacheControlDirectiveRecord> for TypeSystemDirectiveId {
+    fn from(value: CacheControlDirectiveRecord) -> Self {
+        TypeSystemDirectiveId::CacheControl(value)
+    }
+}
 impl From<CostDirectiveId> for TypeSystemDirectiveId {
     fn from(value: CostDirectiveId) -> Self {
         TypeSystemDirectiveId::Cost(value)
@@ -64,6 +74,15 @@ impl From<ListSizeDirectiveId> for TypeSystemDirectiveId {
 }
 
 impl TypeSystemDirectiveId {
+    pub fn is_cache_control(&self) -> bool {
+        matches!(self, TypeSystemDirectiveId::CacheControl(_))
+    }
+    pub fn as_cache_control(&self) -> Option<CacheControlDirectiveRecord> {
+        match self {
+            TypeSystemDirectiveId::CacheControl(item) => Some(*item),
+            _ => None,
+        }
+    }
     pub fn is_cost(&self) -> bool {
         matches!(self, TypeSystemDirectiveId::Cost(_))
     }
@@ -104,6 +123,7 @@ impl TypeSystemDirectiveId {
 
 #[derive(Clone, Copy)]
 pub enum TypeSystemDirective<'a> {
+    CacheControl(CacheControlDirective<'a>),
     Cost(CostDirective<'a>),
     Deprecated(DeprecatedDirective<'a>),
     Extension(ExtensionDirective<'a>),
@@ -113,6 +133,7 @@ pub enum TypeSystemDirective<'a> {
 impl std::fmt::Debug for TypeSystemDirective<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            TypeSystemDirective::CacheControl(variant) => variant.fmt(f),
             TypeSystemDirective::Cost(variant) => variant.fmt(f),
             TypeSystemDirective::Deprecated(variant) => variant.fmt(f),
             TypeSystemDirective::Extension(variant) => variant.fmt(f),
@@ -121,6 +142,11 @@ impl std::fmt::Debug for TypeSystemDirective<'_> {
     }
 }
 
+impl<'a> From<CacheControlDirective<'a>> for TypeSystemDirective<'a> {
+    fn from(item: CacheControlDirective<'a>) -> Self {
+        TypeSystemDirective::CacheControl(item)
+    }
+}
 impl<'a> From<CostDirective<'a>> for TypeSystemDirective<'a> {
     fn from(item: CostDirective<'a>) -> Self {
         TypeSystemDirective::Cost(item)
@@ -154,6 +180,7 @@ impl<'a> Walk<&'a Schema> for TypeSystemDirectiveId {
     {
         let schema: &'a Schema = schema.into();
         match self {
+            TypeSystemDirectiveId::CacheControl(item) => TypeSystemDirective::CacheControl(item.walk(schema)),
             TypeSystemDirectiveId::Cost(id) => TypeSystemDirective::Cost(id.walk(schema)),
             TypeSystemDirectiveId::Deprecated(item) => TypeSystemDirective::Deprecated(item.walk(schema)),
             TypeSystemDirectiveId::Extension(id) => TypeSystemDirective::Extension(id.walk(schema)),
@@ -165,12 +192,22 @@ impl<'a> Walk<&'a Schema> for TypeSystemDirectiveId {
 impl<'a> TypeSystemDirective<'a> {
     pub fn id(&self) -> TypeSystemDirectiveId {
         match self {
+            TypeSystemDirective::CacheControl(walker) => TypeSystemDirectiveId::CacheControl(walker.item),
             TypeSystemDirective::Cost(walker) => TypeSystemDirectiveId::Cost(walker.id),
             TypeSystemDirective::Deprecated(walker) => TypeSystemDirectiveId::Deprecated(walker.item),
             TypeSystemDirective::Extension(walker) => TypeSystemDirectiveId::Extension(walker.id),
             TypeSystemDirective::ListSize(walker) => TypeSystemDirectiveId::ListSize(walker.id),
         }
     }
+    pub fn is_cache_control(&self) -> bool {
+        matches!(self, TypeSystemDirective::CacheControl(_))
+    }
+    pub fn as_cache_control(&self) -> Option<CacheControlDirective<'a>> {
+        match self {
+            TypeSystemDirective::CacheControl(item) => Some(*item),
+            _ => None,
+        }
+    }
     pub fn is_cost(&self) -> bool {
         matches!(self, TypeSystemDirective::Cost(_))
     }
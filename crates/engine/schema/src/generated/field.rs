@@ -4,6 +4,7 @@
 //! Generated with: `cargo run -p engine-codegen`
 //! Source file: <engine-codegen dir>/domain/schema.graphql
 mod derive;
+mod progressive_override;
 mod provides;
 mod requires;
 mod subgraph_type;
@@ -17,6 +18,7 @@ use crate::{
     prelude::*,
 };
 pub use derive::*;
+pub use progressive_override::*;
 pub use provides::*;
 pub use requires::*;
 pub use subgraph_type::*;
@@ -41,6 +43,8 @@ use walker::{Iter, Walk};
 ///   arguments: [InputValueDefinition!]!
 ///   directives: [TypeSystemDirective!]!
 ///   derives: [DeriveDefinition!]!
+///   "@override with a label, the field is resolved by either subgraph depending on whether the label is enabled"
+///   progressive_overrides: [ProgressiveOverride!]!
 /// }
 /// ```
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
@@ -59,6 +63,8 @@ pub struct FieldDefinitionRecord {
     pub argument_ids: IdRange<InputValueDefinitionId>,
     pub directive_ids: Vec<TypeSystemDirectiveId>,
     pub derive_ids: IdRange<DeriveDefinitionId>,
+    /// @override with a label, the field is resolved by either subgraph depending on whether the label is enabled
+    pub progressive_override_records: Vec<ProgressiveOverrideRecord>,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, serde::Serialize, serde::Deserialize, id_derives::Id)]
@@ -121,6 +127,10 @@ impl<'a> FieldDefinition<'a> {
     pub fn derives(&self) -> impl Iter<Item = DeriveDefinition<'a>> + 'a {
         self.as_ref().derive_ids.walk(self.schema)
     }
+    /// @override with a label, the field is resolved by either subgraph depending on whether the label is enabled
+    pub fn progressive_overrides(&self) -> impl Iter<Item = ProgressiveOverride<'a>> + 'a {
+        self.as_ref().progressive_override_records.walk(self.schema)
+    }
 }
 
 impl<'a> Walk<&'a Schema> for FieldDefinitionId {
@@ -0,0 +1,90 @@
+//! ===================
+//! !!! DO NOT EDIT !!!
+//! ===================
+//! Generated with: `cargo run -p engine-codegen`
+//! Source file: <engine-codegen dir>/domain/schema.graphql
+use crate::{
+    StringId,
+    generated::{Subgraph, SubgraphId},
+    prelude::*,
+};
+#[allow(unused_imports)]
+use walker::{Iter, Walk};
+
+/// Generated from:
+///
+/// ```custom,{.language-graphql}
+/// type ProgressiveOverride @meta(module: "field/progressive_override") @copy {
+///   label: String!
+///   "Subgraph resolving the field when the label is disabled"
+///   from: Subgraph!
+///   "Subgraph resolving the field when the label is enabled"
+///   to: Subgraph!
+/// }
+/// ```
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
+pub struct ProgressiveOverrideRecord {
+    pub label_id: StringId,
+    /// Subgraph resolving the field when the label is disabled
+    pub from_id: SubgraphId,
+    /// Subgraph resolving the field when the label is enabled
+    pub to_id: SubgraphId,
+}
+
+#[derive(Clone, Copy)]
+pub struct ProgressiveOverride<'a> {
+    pub(crate) schema: &'a Schema,
+    pub(crate) item: ProgressiveOverrideRecord,
+}
+
+impl std::ops::Deref for ProgressiveOverride<'_> {
+    type Target = ProgressiveOverrideRecord;
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<'a> ProgressiveOverride<'a> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn as_ref(&self) -> &ProgressiveOverrideRecord {
+        &self.item
+    }
+    pub fn label(&self) -> &'a str {
+        self.label_id.walk(self.schema)
+    }
+    /// Subgraph resolving the field when the label is disabled
+    pub fn from(&self) -> Subgraph<'a> {
+        self.from_id.walk(self.schema)
+    }
+    /// Subgraph resolving the field when the label is enabled
+    pub fn to(&self) -> Subgraph<'a> {
+        self.to_id.walk(self.schema)
+    }
+}
+
+impl<'a> Walk<&'a Schema> for ProgressiveOverrideRecord {
+    type Walker<'w>
+        = ProgressiveOverride<'w>
+    where
+        'a: 'w;
+    fn walk<'w>(self, schema: impl Into<&'a Schema>) -> Self::Walker<'w>
+    where
+        Self: 'w,
+        'a: 'w,
+    {
+        ProgressiveOverride {
+            schema: schema.into(),
+            item: self,
+        }
+    }
+}
+
+impl std::fmt::Debug for ProgressiveOverride<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressiveOverride")
+            .field("label", &self.label())
+            .field("from", &self.from())
+            .field("to", &self.to())
+            .finish()
+    }
+}
@@ -4,7 +4,7 @@
 //! Generated with: `cargo run -p engine-codegen`
 //! Source file: <engine-codegen dir>/domain/schema.graphql
 use crate::{
-    StringId,
+    HeaderRuleCondition, HeaderRuleConditionId, StringId,
     generated::{NameOrPattern, NameOrPatternId},
     prelude::*,
 };
@@ -18,6 +18,7 @@ use walker::{Iter, Walk};
 ///   name: NameOrPattern!
 ///   default: String
 ///   rename: String
+///   condition: HeaderRuleCondition
 /// }
 /// ```
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
@@ -25,6 +26,7 @@ pub struct ForwardHeaderRuleRecord {
     pub name_id: NameOrPatternId,
     pub default_id: Option<StringId>,
     pub rename_id: Option<StringId>,
+    pub condition_id: Option<HeaderRuleConditionId>,
 }
 
 #[derive(Clone, Copy)]
@@ -54,6 +56,9 @@ impl<'a> ForwardHeaderRule<'a> {
     pub fn rename(&self) -> Option<&'a str> {
         self.rename_id.walk(self.schema)
     }
+    pub fn condition(&self) -> Option<&'a HeaderRuleCondition> {
+        self.condition_id.walk(self.schema)
+    }
 }
 
 impl<'a> Walk<&'a Schema> for ForwardHeaderRuleRecord {
@@ -79,6 +84,7 @@ impl std::fmt::Debug for ForwardHeaderRule<'_> {
             .field("name", &self.name())
             .field("default", &self.default())
             .field("rename", &self.rename())
+            .field("condition", &self.condition())
             .finish()
     }
 }
@@ -3,7 +3,7 @@
 //! ===================
 //! Generated with: `cargo run -p engine-codegen`
 //! Source file: <engine-codegen dir>/domain/schema.graphql
-use crate::{StringId, prelude::*};
+use crate::{HeaderRuleCondition, HeaderRuleConditionId, HeaderTemplate, HeaderTemplateId, StringId, prelude::*};
 #[allow(unused_imports)]
 use walker::{Iter, Walk};
 
@@ -12,13 +12,15 @@ use walker::{Iter, Walk};
 /// ```custom,{.language-graphql}
 /// type InsertHeaderRule @meta(module: "header_rule/insert") @copy {
 ///   name: String!
-///   value: String!
+///   value: HeaderTemplate!
+///   condition: HeaderRuleCondition
 /// }
 /// ```
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
 pub struct InsertHeaderRuleRecord {
     pub name_id: StringId,
-    pub value_id: StringId,
+    pub value_id: HeaderTemplateId,
+    pub condition_id: Option<HeaderRuleConditionId>,
 }
 
 #[derive(Clone, Copy)]
@@ -42,9 +44,12 @@ impl<'a> InsertHeaderRule<'a> {
     pub fn name(&self) -> &'a str {
         self.name_id.walk(self.schema)
     }
-    pub fn value(&self) -> &'a str {
+    pub fn value(&self) -> &'a HeaderTemplate {
         self.value_id.walk(self.schema)
     }
+    pub fn condition(&self) -> Option<&'a HeaderRuleCondition> {
+        self.condition_id.walk(self.schema)
+    }
 }
 
 impl<'a> Walk<&'a Schema> for InsertHeaderRuleRecord {
@@ -69,6 +74,7 @@ impl std::fmt::Debug for InsertHeaderRule<'_> {
         f.debug_struct("InsertHeaderRule")
             .field("name", &self.name())
             .field("value", &self.value())
+            .field("condition", &self.condition())
             .finish()
     }
 }
@@ -4,7 +4,7 @@ use regex::Regex;
 use url::Url;
 use walker::Walk;
 
-use crate::Schema;
+use crate::{HeaderRuleCondition, HeaderTemplate, Schema};
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, serde::Serialize, serde::Deserialize, id_derives::Id)]
 #[max(MAX_ID)]
@@ -61,3 +61,41 @@ impl<'a> Walk<&'a Schema> for RegexId {
         &schema.into()[self]
     }
 }
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, serde::Serialize, serde::Deserialize, id_derives::Id)]
+#[max(MAX_ID)]
+pub struct HeaderRuleConditionId(NonZero<u32>);
+
+impl<'a> Walk<&'a Schema> for HeaderRuleConditionId {
+    type Walker<'w>
+        = &'w HeaderRuleCondition
+    where
+        'a: 'w;
+
+    fn walk<'w>(self, schema: impl Into<&'a Schema>) -> Self::Walker<'w>
+    where
+        Self: 'w,
+        'a: 'w,
+    {
+        &schema.into()[self]
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, serde::Serialize, serde::Deserialize, id_derives::Id)]
+#[max(MAX_ID)]
+pub struct HeaderTemplateId(NonZero<u32>);
+
+impl<'a> Walk<&'a Schema> for HeaderTemplateId {
+    type Walker<'w>
+        = &'w HeaderTemplate
+    where
+        'a: 'w;
+
+    fn walk<'w>(self, schema: impl Into<&'a Schema>) -> Self::Walker<'w>
+    where
+        Self: 'w,
+        'a: 'w,
+    {
+        &schema.into()[self]
+    }
+}
@@ -696,6 +696,7 @@ impl GraphBuilder<'_> {
                 argument_ids: IdRange::empty(),
                 subgraph_type_records: Vec::new(),
                 derive_ids: Default::default(),
+                progressive_override_records: Default::default(),
             });
 
             out_fields.push((id, tag));
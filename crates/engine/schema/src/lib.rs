@@ -42,7 +42,10 @@ pub use extension::*;
 use extension_catalog::ExtensionId;
 pub use field::*;
 pub use field_set::*;
-pub use gateway_config::SubscriptionProtocol;
+pub use gateway_config::{
+    HeaderRuleCondition, HeaderRuleOperationType, HeaderTemplate, HeaderTemplatePart, HeaderTemplateVariable,
+    SubscriptionProtocol,
+};
 pub use generated::*;
 use id_newtypes::{BitSet, IdRange};
 pub use ids::*;
@@ -129,6 +132,8 @@ id_newtypes::forward_with_range! {
     impl Index<GraphqlSubgraphId, Output = GraphqlSubgraphRecord> for Schema.subgraphs,
     impl Index<VirtualSubgraphId, Output = VirtualSubgraphRecord> for Schema.subgraphs,
     impl Index<HeaderRuleId, Output = HeaderRuleRecord> for Schema.subgraphs,
+    impl Index<HeaderRuleConditionId, Output = HeaderRuleCondition> for Schema.subgraphs,
+    impl Index<HeaderTemplateId, Output = HeaderTemplate> for Schema.subgraphs,
     impl Index<SchemaFieldId, Output = SchemaFieldRecord> for Schema.selections,
     impl Index<SchemaFieldArgumentId, Output = SchemaFieldArgumentRecord> for Schema.selections,
     impl Index<KeyValueInjectionId, Output = KeyValueInjectionRecord> for Schema.selections,
@@ -190,6 +195,9 @@ pub struct Graph {
 
     #[indexed_by(TemplateId)]
     templates: Vec<TemplateRecord>,
+
+    /// Distinct labels of all progressive overrides.
+    override_labels: Vec<OverrideLabelRecord>,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -217,6 +225,10 @@ pub struct SubGraphs {
     /// Headers we might want to send to a subgraph
     #[indexed_by(HeaderRuleId)]
     header_rules: Vec<HeaderRuleRecord>,
+    #[indexed_by(HeaderRuleConditionId)]
+    header_rule_conditions: Vec<HeaderRuleCondition>,
+    #[indexed_by(HeaderTemplateId)]
+    header_templates: Vec<HeaderTemplate>,
 }
 
 impl Schema {
@@ -309,6 +321,13 @@ pub enum ScalarType {
     Unknown,
 }
 
+/// Scope of a `@cacheControl` hint. A private response may only be cached for a specific user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+pub enum CacheControlScope {
+    Public,
+    Private,
+}
+
 impl ScalarType {
     pub fn from_scalar_name(name: &str) -> ScalarType {
         match name {
@@ -2,7 +2,10 @@ use std::time::Duration;
 
 use walker::{Iter, Walk};
 
-use crate::{ExtensionDirective, ExtensionDirectiveId, HeaderRule, RetryConfig, Subgraph};
+use crate::{
+    CircuitBreakerConfig, EntityBatchingConfig, ExtensionDirective, ExtensionDirectiveId, HeaderRule,
+    ResponseHeaderRule, RetryConfig, Subgraph,
+};
 
 impl<'a> Subgraph<'a> {
     pub fn name(&self) -> &'a str {
@@ -43,4 +46,10 @@ pub struct SubgraphConfig {
     // The ttl to use for caching for this subgraph.
     // If None then caching is disabled for this subgraph
     pub cache_ttl: Option<Duration>,
+    // Concurrent entity requests are sent in a single batch if enabled.
+    pub entity_batching: Option<EntityBatchingConfig>,
+    // Requests are short-circuited while the subgraph is failing if enabled.
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    // Rules propagating the response headers to the client, applied after the global ones.
+    pub response_header_rules: Vec<ResponseHeaderRule>,
 }
@@ -1,14 +1,17 @@
 pub(crate) mod cache;
+pub(crate) mod circuit_breaker;
 pub mod mcp;
 mod retry_budget;
 mod runtime;
 
 use ::runtime::{
+    entity_cache::CacheTag,
     extension::{ContractsExtension as _, Token},
     operation_cache::OperationCache,
 };
 use bytes::Bytes;
 use cache::CacheKey;
+use circuit_breaker::CircuitBreakers;
 use error::{ErrorCode, ErrorResponse, GraphqlError};
 use event_queue::EventQueue;
 use futures::{StreamExt, TryFutureExt};
@@ -17,7 +20,7 @@ use graphql_tools::{parser::parse_schema, static_graphql::schema::Document};
 use hive_console_sdk::agent::usage_agent::{UsageAgent, UsageAgentExt};
 use retry_budget::RetryBudgets;
 use schema::Schema;
-use std::{borrow::Cow, env, future::Future, sync::Arc};
+use std::{borrow::Cow, env, future::Future, net::IpAddr, sync::Arc};
 use tokio_util::sync::CancellationToken;
 
 use crate::{
@@ -44,6 +47,10 @@ pub struct RequestExtensions {
     pub contract_key: Option<String>,
     pub event_queue: Arc<EventQueue>,
     pub hooks_context: Arc<[u8]>,
+    /// Custom progressive override labels to enable for this request.
+    pub override_labels: Vec<String>,
+    /// IP address of the client connection.
+    pub ip: Option<IpAddr>,
 }
 
 impl Default for RequestExtensions {
@@ -53,6 +60,8 @@ impl Default for RequestExtensions {
             contract_key: None,
             event_queue: Arc::new(EventQueue::default()),
             hooks_context: Arc::new([]),
+            override_labels: Vec::new(),
+            ip: None,
         }
     }
 }
@@ -101,6 +110,7 @@ impl<R: Runtime> ContractAwareEngine<R> {
             response_format: ResponseFormat::Streaming(StreamingResponseFormat::GraphQLOverWebSocket),
             include_grafbase_response_extension: false,
             include_mcp_response_extension: false,
+            explain: false,
             content_type: ContentType::Json,
         };
         let parts = Parts {
@@ -136,6 +146,25 @@ impl<R: Runtime> ContractAwareEngine<R> {
         }
     }
 
+    /// Purges all entity cache entries with at least one of the given tags, including the ones of
+    /// the contracts.
+    pub async fn purge_entity_cache(&self, tags: &[CacheTag]) -> Result<(), String> {
+        let engines = std::iter::once(self.no_contract.clone())
+            .chain(self.by_contract_key.iter().map(|(_, engine)| engine))
+            .collect::<Vec<_>>();
+
+        for engine in engines {
+            engine
+                .runtime
+                .entity_cache()
+                .purge(tags)
+                .await
+                .map_err(|err| err.to_string())?;
+        }
+
+        Ok(())
+    }
+
     async fn get_engine_for_contract(&self, key: &str) -> Result<Arc<Engine<R>>, ErrorResponse> {
         match self.by_contract_key.get_value_or_guard_async(key).await {
             Ok(engine) => Ok(engine),
@@ -175,6 +204,7 @@ pub struct Engine<R: Runtime> {
     pub schema: Arc<Schema>,
     pub runtime: R,
     pub(crate) retry_budgets: RetryBudgets,
+    pub(crate) circuit_breakers: CircuitBreakers,
     pub hive_usage_reporter: Option<HiveUsageReporter>,
 }
 
@@ -218,6 +248,7 @@ impl<R: Runtime> Engine<R> {
         }
         Self {
             retry_budgets: RetryBudgets::build(&schema),
+            circuit_breakers: CircuitBreakers::build(&schema),
             schema,
             runtime,
             hive_usage_reporter,
@@ -235,7 +266,7 @@ impl<R: Runtime> Engine<R> {
         for document in documents {
             let document: OperationDocument<'_> = document.into();
             let name = document.operation_name().map(|s| s.to_owned());
-            let cache_key = CacheKey::document(&self.schema, &document.key).to_string();
+            let cache_key = CacheKey::document(&self.schema, &document.key, &Default::default()).to_string();
 
             match self.warm_operation(document) {
                 Ok(cached) => {
@@ -284,7 +315,11 @@ impl<R: Runtime> Engine<R> {
                     self.schema.config.error_code_mapping.clone(),
                 ))
             }) {
-            Ok((request_context, request)) => self.execute_well_formed_graphql_request(request_context, request).await,
+            Ok((mut request_context, (request, file_uploads))) => {
+                request_context.file_uploads = file_uploads;
+                self.execute_well_formed_graphql_request(Arc::new(request_context), request)
+                    .await
+            }
             Err(response) => Http::error(ctx.response_format, response),
         }
     }
@@ -311,7 +346,7 @@ impl<R: Runtime> Engine<R> {
 
         Ok(WebsocketSession {
             engine: self.clone(),
-            request_context,
+            request_context: Arc::new(request_context),
         })
     }
 
@@ -2,10 +2,11 @@ use std::borrow::Cow;
 
 use base64::{display::Base64Display, engine::general_purpose::URL_SAFE_NO_PAD};
 use operation::extensions::PersistedQueryRequestExtension;
-use schema::Schema;
+use schema::{EnabledOverrideLabels, Schema};
 
 mod namespaces {
     pub const OPERATION: &str = "op";
+    pub const RESPONSE: &str = "resp";
 }
 
 /// Unique cache key that generates a URL-safe string.
@@ -13,12 +14,36 @@ pub(crate) enum CacheKey<'a> {
     Operation {
         schema: &'a Schema,
         document: &'a DocumentKey<'a>,
+        override_labels: &'a EnabledOverrideLabels,
+    },
+    Response {
+        operation_key: &'a str,
+        partition: &'a blake3::Hash,
     },
 }
 
 impl CacheKey<'_> {
-    pub(crate) fn document(schema: &Schema, document: &DocumentKey<'_>) -> String {
-        CacheKey::Operation { schema, document }.to_string()
+    pub(crate) fn document(
+        schema: &Schema,
+        document: &DocumentKey<'_>,
+        override_labels: &EnabledOverrideLabels,
+    ) -> String {
+        CacheKey::Operation {
+            schema,
+            document,
+            override_labels,
+        }
+        .to_string()
+    }
+
+    /// Key of a whole response, `partition` being the hash of the variables and every other
+    /// request part the response depends on.
+    pub(crate) fn response(operation_key: &str, partition: &blake3::Hash) -> String {
+        CacheKey::Response {
+            operation_key,
+            partition,
+        }
+        .to_string()
     }
 }
 
@@ -71,7 +96,11 @@ impl std::fmt::Display for CacheKey<'_> {
         match self {
             // Schema version + Commit SHA ensures we don't need to care about
             // backwards-compatibility
-            CacheKey::Operation { schema, document } => {
+            CacheKey::Operation {
+                schema,
+                document,
+                override_labels,
+            } => {
                 let mut hasher = blake3::Hasher::new();
                 hasher.update(&schema.hash);
 
@@ -117,6 +146,15 @@ impl std::fmt::Display for CacheKey<'_> {
                         hasher.update(document.as_bytes());
                     }
                 }
+                // Progressive overrides change the query plan, so each combination of enabled
+                // labels gets its own cache entry.
+                if !override_labels.is_empty() {
+                    hasher.update(b"override_labels");
+                    hasher.update(&override_labels.len().to_ne_bytes());
+                    for id in override_labels.iter() {
+                        hasher.update(&usize::from(id).to_ne_bytes());
+                    }
+                }
                 let hash = hasher.finalize();
 
                 f.write_fmt(format_args!(
@@ -125,6 +163,22 @@ impl std::fmt::Display for CacheKey<'_> {
                     Base64Display::new(hash.as_bytes(), &URL_SAFE_NO_PAD)
                 ))
             }
+            CacheKey::Response {
+                operation_key,
+                partition,
+            } => {
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(operation_key.as_bytes());
+                hasher.update(&[0x00]);
+                hasher.update(partition.as_bytes());
+                let hash = hasher.finalize();
+
+                f.write_fmt(format_args!(
+                    "{}.blake3.{}",
+                    namespaces::RESPONSE,
+                    Base64Display::new(hash.as_bytes(), &URL_SAFE_NO_PAD)
+                ))
+            }
         }
     }
 }
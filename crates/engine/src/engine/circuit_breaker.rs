@@ -0,0 +1,224 @@
+use std::{sync::Mutex, time::Instant};
+
+use event_queue::CircuitBreakerState;
+use schema::{CircuitBreakerConfig, GraphqlSubgraphId, Schema};
+
+use super::Runtime;
+
+#[derive(id_derives::IndexedFields)]
+pub(crate) struct CircuitBreakers {
+    #[indexed_by(GraphqlSubgraphId)]
+    by_graphql_endpoints: Vec<Option<CircuitBreaker>>,
+}
+
+impl CircuitBreakers {
+    pub fn build(schema: &Schema) -> Self {
+        Self {
+            by_graphql_endpoints: schema
+                .graphql_subgraphs()
+                .map(|subgraph| subgraph.config.circuit_breaker.map(CircuitBreaker::new))
+                .collect(),
+        }
+    }
+}
+
+impl<R: Runtime> super::Engine<R> {
+    pub(crate) fn get_circuit_breaker(&self, subgraph_id: GraphqlSubgraphId) -> Option<&CircuitBreaker> {
+        self.circuit_breakers[subgraph_id].as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct StateChange {
+    pub from: CircuitBreakerState,
+    pub to: CircuitBreakerState,
+}
+
+/// The breaker rejected the request, the subgraph is considered unavailable.
+#[derive(Debug)]
+pub(crate) struct CircuitOpen;
+
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<State>,
+}
+
+enum State {
+    Closed {
+        window_start: Instant,
+        requests: u32,
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// A single probe request is in flight. If it never reports back, for example because the
+    /// operation was cancelled, another probe is allowed after `half_open_after`.
+    HalfOpen {
+        probe_started_at: Instant,
+    },
+}
+
+impl State {
+    fn closed(now: Instant) -> Self {
+        State::Closed {
+            window_start: now,
+            requests: 0,
+            failures: 0,
+        }
+    }
+
+    fn kind(&self) -> CircuitBreakerState {
+        match self {
+            State::Closed { .. } => CircuitBreakerState::Closed,
+            State::Open { .. } => CircuitBreakerState::Open,
+            State::HalfOpen { .. } => CircuitBreakerState::HalfOpen,
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::closed(Instant::now())),
+        }
+    }
+
+    /// Decides whether a request may be sent to the subgraph. Once the breaker has been open for
+    /// long enough, the first caller becomes the probe and moves the breaker to half-open.
+    pub fn try_acquire(&self) -> Result<Option<StateChange>, CircuitOpen> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    /// Records the outcome of a request allowed by `try_acquire`.
+    pub fn record(&self, success: bool) -> Option<StateChange> {
+        self.record_at(Instant::now(), success)
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Result<Option<StateChange>, CircuitOpen> {
+        let mut state = self.state.lock().unwrap();
+
+        match *state {
+            State::Closed { .. } => Ok(None),
+            State::Open { until } if now < until => Err(CircuitOpen),
+            State::HalfOpen { probe_started_at } if now < probe_started_at + self.config.half_open_after => {
+                Err(CircuitOpen)
+            }
+            State::Open { .. } | State::HalfOpen { .. } => {
+                Ok(self.transition(&mut state, State::HalfOpen { probe_started_at: now }))
+            }
+        }
+    }
+
+    fn record_at(&self, now: Instant, success: bool) -> Option<StateChange> {
+        let mut state = self.state.lock().unwrap();
+
+        match &mut *state {
+            State::Closed {
+                window_start,
+                requests,
+                failures,
+            } => {
+                if now.duration_since(*window_start) >= self.config.window {
+                    *window_start = now;
+                    *requests = 0;
+                    *failures = 0;
+                }
+
+                *requests += 1;
+                if !success {
+                    *failures += 1;
+                }
+
+                let failure_rate = *failures as f32 / *requests as f32;
+                if *requests >= self.config.minimum_requests && failure_rate >= self.config.failure_rate_threshold {
+                    let until = now + self.config.half_open_after;
+                    self.transition(&mut state, State::Open { until })
+                } else {
+                    None
+                }
+            }
+            // Requests allowed before the breaker opened don't change anything anymore.
+            State::Open { .. } => None,
+            State::HalfOpen { .. } if success => self.transition(&mut state, State::closed(now)),
+            State::HalfOpen { .. } => {
+                let until = now + self.config.half_open_after;
+                self.transition(&mut state, State::Open { until })
+            }
+        }
+    }
+
+    fn transition(&self, state: &mut State, next: State) -> Option<StateChange> {
+        let from = state.kind();
+        let to = next.kind();
+        *state = next;
+
+        (from != to).then_some(StateChange { from, to })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_rate_threshold: 0.5,
+            minimum_requests: 4,
+            window: Duration::from_secs(10),
+            half_open_after: Duration::from_secs(30),
+        })
+    }
+
+    fn change(from: CircuitBreakerState, to: CircuitBreakerState) -> Option<StateChange> {
+        Some(StateChange { from, to })
+    }
+
+    #[test]
+    fn opens_above_failure_rate_and_recovers_through_probe() {
+        use CircuitBreakerState::*;
+
+        let breaker = breaker();
+        let start = Instant::now();
+
+        assert_eq!(breaker.record_at(start, true), None);
+        assert_eq!(breaker.record_at(start, false), None);
+        assert_eq!(breaker.record_at(start, true), None);
+        assert_eq!(breaker.record_at(start, false), change(Closed, Open));
+
+        assert!(breaker.try_acquire_at(start + Duration::from_secs(29)).is_err());
+
+        let probe = start + Duration::from_secs(30);
+        assert_eq!(breaker.try_acquire_at(probe).unwrap(), change(Open, HalfOpen));
+        // Only one probe at a time.
+        assert!(breaker.try_acquire_at(probe).is_err());
+
+        assert_eq!(breaker.record_at(probe, false), change(HalfOpen, Open));
+        assert!(breaker.try_acquire_at(probe + Duration::from_secs(1)).is_err());
+
+        let probe = probe + Duration::from_secs(30);
+        assert_eq!(breaker.try_acquire_at(probe).unwrap(), change(Open, HalfOpen));
+        assert_eq!(breaker.record_at(probe, true), change(HalfOpen, Closed));
+        assert_eq!(breaker.try_acquire_at(probe).unwrap(), None);
+    }
+
+    #[test]
+    fn failures_are_counted_per_window() {
+        let breaker = breaker();
+        let start = Instant::now();
+
+        assert_eq!(breaker.record_at(start, false), None);
+        assert_eq!(breaker.record_at(start, false), None);
+        assert_eq!(breaker.record_at(start, false), None);
+
+        let next_window = start + Duration::from_secs(10);
+        assert_eq!(breaker.record_at(next_window, false), None);
+        assert_eq!(breaker.record_at(next_window, true), None);
+        assert_eq!(breaker.record_at(next_window, true), None);
+        assert_eq!(breaker.record_at(next_window, true), None);
+        assert!(breaker.try_acquire_at(next_window).unwrap().is_none());
+    }
+}
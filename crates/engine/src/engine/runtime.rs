@@ -1,7 +1,9 @@
 use std::{future::Future, sync::Arc};
 
 use grafbase_telemetry::metrics::EngineMetrics;
-use runtime::{entity_cache::EntityCache, extension::EngineExtensions, rate_limiting::RateLimiter};
+use runtime::{
+    entity_cache::EntityCache, extension::EngineExtensions, health::DependencyHealth, rate_limiting::RateLimiter,
+};
 use schema::Schema;
 
 use crate::{CachedOperation, EngineOperationContext, EngineRequestContext};
@@ -21,6 +23,16 @@ pub trait Runtime: Send + Sync + Sized + 'static {
     fn extensions(&self) -> &Self::Extensions;
 
     fn clone_and_adjust_for_contract(&self, schema: &Arc<Schema>) -> impl Future<Output = Result<Self, String>> + Send;
+
+    /// Version of the graph the engine was built from, when loaded from a graph reference.
+    fn graph_version(&self) -> Option<String> {
+        None
+    }
+
+    /// Checks the external dependencies, such as the Redis backends, for the readiness endpoint.
+    fn dependencies_health(&self) -> impl Future<Output = Vec<DependencyHealth>> + Send {
+        async { Vec::new() }
+    }
 }
 
 pub(crate) trait RuntimeExt: Runtime {
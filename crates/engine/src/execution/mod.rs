@@ -2,4 +2,5 @@ mod operation;
 mod request;
 
 pub(crate) use operation::*;
+pub use request::subgraph_probe_headers;
 pub(crate) use request::*;
@@ -7,8 +7,9 @@ use schema::{HeaderRule, Schema};
 
 use crate::{
     Engine, Runtime,
-    execution::{RequestContext, apply_header_rules},
+    execution::{HeaderRuleContext, RequestContext, apply_header_rules},
     prepare::{CachedOperationContext, OperationPlanContext, PreparedOperation, Shapes},
+    resolver::EntityBatches,
 };
 
 /// Context for a single prepared operation that only needs to be executed.
@@ -16,6 +17,7 @@ pub(crate) struct ExecutionContext<'ctx, R: Runtime> {
     pub engine: &'ctx Arc<Engine<R>>,
     pub request_context: &'ctx Arc<RequestContext>,
     pub operation: &'ctx Arc<PreparedOperation>,
+    pub entity_batches: &'ctx EntityBatches,
 }
 
 impl<R: Runtime> Clone for ExecutionContext<'_, R> {
@@ -41,7 +43,14 @@ impl<'ctx, R: Runtime> ExecutionContext<'ctx, R> {
             .as_ref()
             .unwrap_or(&self.request_context.subgraph_default_headers)
             .clone();
-        apply_header_rules(&self.request_context.headers, rules, &mut subgraph_headers);
+        let attributes = &self.operation.cached.operation.attributes;
+        let header_rule_context = HeaderRuleContext::new(
+            &self.request_context.headers,
+            &self.request_context.token,
+            self.request_context.ip,
+        )
+        .with_operation(attributes.ty, attributes.name.original());
+        apply_header_rules(&header_rule_context, rules, &mut subgraph_headers);
         subgraph_headers
     }
 
@@ -1,7 +1,7 @@
 use std::{collections::VecDeque, sync::Arc};
 
 use event_queue::{ExecutedOperation, ExecutedOperationBuilder};
-use futures::{Future, FutureExt, Stream, stream::FuturesOrdered};
+use futures::{Future, FutureExt, Stream, channel::mpsc, stream::FuturesOrdered};
 use futures_util::{StreamExt, future::BoxFuture, stream::FuturesUnordered};
 use grafbase_telemetry::graphql::{GraphqlResponseStatus, OperationType};
 use tracing::Instrument;
@@ -11,17 +11,24 @@ use crate::{
     Runtime,
     execution::ExecutionContext,
     prepare::{Executable, Plan, PlanId, PrepareContext, PreparedOperation},
-    resolver::ResolverResult,
+    resolver::{EntityBatches, ResolverResult},
     response::{GraphqlError, PartIngestionResult, Response, ResponseBuilder, ResponsePartBuilder},
 };
 
-use super::state::OperationExecutionState;
+use super::{incremental::IncrementalDelivery, state::OperationExecutionState};
 
 pub(crate) trait ResponseSender: Send {
     type Error;
     fn send(&mut self, response: Response) -> impl Future<Output = Result<(), Self::Error>> + Send;
 }
 
+impl<S: ResponseSender> ResponseSender for &mut S {
+    type Error = S::Error;
+    fn send(&mut self, response: Response) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        (**self).send(response)
+    }
+}
+
 impl<R: Runtime> PrepareContext<'_, R> {
     pub async fn execute_query_or_mutation(mut self, operation: PreparedOperation) -> Response {
         let background_futures: FuturesUnordered<_> =
@@ -29,11 +36,13 @@ impl<R: Runtime> PrepareContext<'_, R> {
 
         let background_fut = background_futures.collect::<Vec<_>>();
         let operation = Arc::new(operation);
+        let entity_batches = EntityBatches::default();
 
         let ctx = ExecutionContext {
             engine: self.engine,
             request_context: self.request_context,
             operation: &operation,
+            entity_batches: &entity_batches,
         };
 
         tracing::trace!("Starting execution...");
@@ -51,17 +60,52 @@ impl<R: Runtime> PrepareContext<'_, R> {
         }
     }
 
+    /// Sends the response in multiple payloads for `@defer` and `@stream`.
+    pub async fn execute_query_or_mutation_incrementally(
+        mut self,
+        operation: PreparedOperation,
+        mut responses: impl ResponseSender,
+    ) {
+        let background_futures: FuturesUnordered<_> =
+            std::mem::take(&mut self.background_futures).into_iter().collect();
+
+        let background_fut = background_futures.collect::<Vec<_>>();
+        let operation = Arc::new(operation);
+        let entity_batches = EntityBatches::default();
+
+        let ctx = ExecutionContext {
+            engine: self.engine,
+            request_context: self.request_context,
+            operation: &operation,
+            entity_batches: &entity_batches,
+        };
+
+        tracing::trace!("Starting incremental execution...");
+
+        if operation.plan.query_modifications.root_error_ids.is_empty() {
+            let execution_fut = ctx.execute_incrementally(self.executed_operation_builder, responses);
+            futures_util::join!(execution_fut, background_fut);
+        } else {
+            let response_fut = ctx.response_for_root_errors(self.executed_operation_builder);
+            let (response, _) = futures_util::join!(response_fut, background_fut);
+
+            responses.send(response).await.ok();
+        }
+    }
+
     pub async fn execute_subscription(mut self, operation: PreparedOperation, mut responses: impl ResponseSender) {
         let background_futures: FuturesUnordered<_> =
             std::mem::take(&mut self.background_futures).into_iter().collect();
 
         let background_fut = background_futures.collect::<Vec<_>>();
         let operation = Arc::new(operation);
+        let entity_batches = EntityBatches::default();
 
         let ctx = ExecutionContext {
             engine: self.engine,
             request_context: self.request_context,
             operation: &operation,
+            entity_batches: &entity_batches,
         };
 
         tracing::trace!("Starting execution...");
@@ -120,12 +164,46 @@ impl<'ctx, R: Runtime> ExecutionContext<'ctx, R> {
             state: self.new_execution_state(),
             executed_operation_builder: builder,
             response: ResponseBuilder::new(&self.engine.schema, self.operation),
+            incremental_delivery: None,
             ctx: self,
         }
         .run(VecDeque::new())
         .await
     }
 
+    async fn execute_incrementally(self, builder: ExecutedOperationBuilder<'_>, mut responses: impl ResponseSender) {
+        assert!(
+            !matches!(self.operation.cached.ty(), OperationType::Subscription),
+            "execute_incrementally shouldn't be called for subscriptions"
+        );
+
+        // Payloads are sent as soon as they're ready from within the execution, so we forward
+        // them concurrently. The last one is returned at the end of the execution.
+        let (payloads_sender, mut payloads_receiver) = mpsc::unbounded();
+        let execution_fut = OperationExecution {
+            state: self.new_execution_state(),
+            executed_operation_builder: builder,
+            response: ResponseBuilder::new(&self.engine.schema, self.operation),
+            incremental_delivery: Some(IncrementalDelivery::new(self.operation, payloads_sender)),
+            ctx: self,
+        }
+        .run(VecDeque::new());
+
+        let forward_fut = async {
+            while let Some(payload) = payloads_receiver.next().await {
+                if responses.send(payload).await.is_err() {
+                    return false;
+                }
+            }
+            true
+        };
+
+        let (last_payload, is_receiving) = futures_util::join!(execution_fut, forward_fut);
+        if is_receiving {
+            responses.send(last_payload).await.ok();
+        }
+    }
+
     async fn execute_subscription(
         self,
         executed_operation_builder: ExecutedOperationBuilder<'_>,
@@ -225,6 +303,7 @@ where
                         executed_operation_builder,
                         state: self.initial_state.clone(),
                         response,
+                        incremental_delivery: None,
                     };
 
                     response_futures.push_back(operation_execution.run(results));
@@ -245,6 +324,7 @@ struct OperationExecution<'ctx, R: Runtime> {
     executed_operation_builder: ExecutedOperationBuilder<'ctx>,
     state: OperationExecutionState<'ctx, R>,
     response: ResponseBuilder<'ctx>,
+    incremental_delivery: Option<IncrementalDelivery<'ctx>>,
 }
 
 impl<'ctx, R: Runtime> std::ops::Deref for OperationExecution<'ctx, R> {
@@ -277,6 +357,10 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
             }
         }
 
+        if let Some(incremental_delivery) = &mut self.incremental_delivery {
+            incremental_delivery.send_ready_payloads(&mut self.response);
+        }
+
         let mut state = State::Execution(self);
         futures_util::pin_mut!(futures);
 
@@ -337,7 +421,10 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
 
         event_queue.push_operation(this.executed_operation_builder);
 
-        this.response.build(operation.attributes())
+        match this.incremental_delivery {
+            Some(incremental_delivery) => incremental_delivery.into_last_payload(&mut this.response),
+            None => this.response.build(operation.attributes()),
+        }
     }
 
     async fn ingest_execution_result<'exec>(
@@ -349,6 +436,7 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
     {
         let PartIngestionResult::Data { response_object_sets } = self.response.ingest(response_part) else {
             tracing::trace!(%plan_id, "Failed");
+            self.plan_finished(plan_id);
             return (self, Vec::new());
         };
 
@@ -380,9 +468,17 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
             }
         }
 
+        self.plan_finished(plan_id);
         (self, next_futures)
     }
 
+    fn plan_finished(&mut self, plan_id: PlanId) {
+        if let Some(incremental_delivery) = &mut self.incremental_delivery {
+            incremental_delivery.plan_finished(plan_id);
+            incremental_delivery.send_ready_payloads(&mut self.response);
+        }
+    }
+
     fn create_plan_execution_future<'exec>(
         &mut self,
         plan: Plan<'ctx>,
@@ -417,6 +513,10 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
                 response_part,
             });
 
+        if let Some(incremental_delivery) = &mut self.incremental_delivery {
+            incremental_delivery.plan_started(plan.id);
+        }
+
         let span = span.exit();
         Some(fut.instrument(span).boxed())
     }
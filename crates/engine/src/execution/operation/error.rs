@@ -10,6 +10,8 @@ pub enum ExecutionError {
     Internal(Cow<'static, str>),
     #[error("Request to subgraph '{subgraph_name}' failed.")]
     Fetch { subgraph_name: String, error: FetchError },
+    #[error("Subgraph '{subgraph_name}' is unavailable.")]
+    CircuitOpen { subgraph_name: String },
     #[error(transparent)]
     RateLimit(#[from] runtime::rate_limiting::Error),
     #[error("{0}")]
@@ -38,7 +40,7 @@ impl From<ExecutionError> for GraphqlError {
         let message = err.to_string();
         let code = match &err {
             ExecutionError::Internal(_) => ErrorCode::InternalServerError,
-            ExecutionError::Fetch { .. } => ErrorCode::SubgraphRequestError,
+            ExecutionError::Fetch { .. } | ExecutionError::CircuitOpen { .. } => ErrorCode::SubgraphRequestError,
             ExecutionError::RateLimit(_) => ErrorCode::RateLimited,
             ExecutionError::Graphql(err) => err.code,
         };
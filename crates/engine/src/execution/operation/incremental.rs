@@ -0,0 +1,135 @@
+use futures::channel::mpsc;
+
+use crate::{
+    prepare::{DeferredFragmentId, PlanId, PreparedOperation},
+    response::{IncrementalResponseWriter, Response, ResponseBuilder},
+};
+
+/// Keeps track of the plans running for the initial payload and each deferred fragment to send
+/// the incremental delivery payloads as soon as they're ready. A plan always belongs to the same
+/// deferred fragment, or a descendant of it, as its parents. So once a fragment has no running
+/// plans and its parent was delivered, nothing can be added to it anymore.
+pub(super) struct IncrementalDelivery<'ctx> {
+    operation: &'ctx PreparedOperation,
+    payloads: mpsc::UnboundedSender<Response>,
+    writer: IncrementalResponseWriter,
+    /// Initial payload first, followed by each deferred fragment.
+    running_plans_count: Vec<usize>,
+    is_initial_payload_sent: bool,
+    is_fragment_delivered: Vec<bool>,
+}
+
+impl<'ctx> IncrementalDelivery<'ctx> {
+    pub fn new(operation: &'ctx PreparedOperation, payloads: mpsc::UnboundedSender<Response>) -> Self {
+        let fragments_count = operation.cached.query_plan.deferred_fragments.len();
+        Self {
+            operation,
+            payloads,
+            writer: IncrementalResponseWriter::default(),
+            running_plans_count: vec![0; fragments_count + 1],
+            is_initial_payload_sent: false,
+            is_fragment_delivered: vec![false; fragments_count],
+        }
+    }
+
+    pub fn plan_started(&mut self, plan_id: PlanId) {
+        let ix = self.payload_index(plan_id);
+        self.running_plans_count[ix] += 1;
+    }
+
+    pub fn plan_finished(&mut self, plan_id: PlanId) {
+        let ix = self.payload_index(plan_id);
+        self.running_plans_count[ix] -= 1;
+    }
+
+    /// Sends all the payloads which can't change anymore.
+    pub fn send_ready_payloads(&mut self, response: &mut ResponseBuilder<'ctx>) {
+        if !self.is_initial_payload_sent {
+            if self.running_plans_count[0] > 0 {
+                return;
+            }
+            self.send_initial_payload(response);
+        }
+
+        while let Some(incremental) = self.next_incremental_results(response, false) {
+            let payload = self
+                .writer
+                .subsequent_payload(response, self.operation.attributes(), incremental, true);
+            self.payloads.unbounded_send(payload).ok();
+        }
+    }
+
+    /// Sends everything that is left once all plans have finished. The last payload is returned
+    /// rather than sent.
+    pub fn into_last_payload(mut self, response: &mut ResponseBuilder<'ctx>) -> Response {
+        if !self.is_initial_payload_sent {
+            self.send_initial_payload(response);
+        }
+
+        let mut last = None;
+        while let Some(incremental) = self.next_incremental_results(response, true) {
+            if let Some(previous) = last.replace(incremental) {
+                let payload = self
+                    .writer
+                    .subsequent_payload(response, self.operation.attributes(), previous, true);
+                self.payloads.unbounded_send(payload).ok();
+            }
+        }
+
+        self.writer
+            .subsequent_payload(response, self.operation.attributes(), last.unwrap_or_default(), false)
+    }
+
+    fn send_initial_payload(&mut self, response: &mut ResponseBuilder<'ctx>) {
+        let payload = self.writer.initial_payload(response, self.operation.attributes());
+        self.is_initial_payload_sent = true;
+        self.payloads.unbounded_send(payload).ok();
+    }
+
+    fn next_incremental_results(
+        &mut self,
+        response: &ResponseBuilder<'ctx>,
+        all_plans_finished: bool,
+    ) -> Option<Vec<serde_json::Value>> {
+        let mut incremental = Vec::new();
+        self.writer.write_streamed_lists(response, &mut incremental);
+
+        // Streamed lists must be sent before delivering any nested deferred fragment.
+        while !self.writer.has_streamed_lists()
+            && let Some(fragment_id) = self.next_ready_fragment_id(all_plans_finished)
+        {
+            self.is_fragment_delivered[usize::from(fragment_id)] = true;
+            self.writer
+                .write_deferred_fragment(response, fragment_id, &mut incremental);
+        }
+
+        if incremental.is_empty() {
+            None
+        } else {
+            Some(incremental)
+        }
+    }
+
+    fn next_ready_fragment_id(&self, all_plans_finished: bool) -> Option<DeferredFragmentId> {
+        self.operation
+            .plan
+            .incremental_delivery
+            .delivered_fragments
+            .iter()
+            .find(|(id, parent_id)| {
+                !self.is_fragment_delivered[usize::from(*id)]
+                    && parent_id.is_none_or(|parent_id| self.is_fragment_delivered[usize::from(parent_id)])
+                    && (all_plans_finished || self.running_plans_count[usize::from(*id) + 1] == 0)
+            })
+            .map(|(id, _)| *id)
+    }
+
+    fn payload_index(&self, plan_id: PlanId) -> usize {
+        self.operation
+            .plan
+            .incremental_delivery
+            .plan_deferred_fragment_id(plan_id)
+            .map(|id| usize::from(id) + 1)
+            .unwrap_or_default()
+    }
+}
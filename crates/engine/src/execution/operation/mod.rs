@@ -1,6 +1,7 @@
 mod context;
 mod coordinator;
 mod error;
+mod incremental;
 mod response_modifier;
 mod state;
 
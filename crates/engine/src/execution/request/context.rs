@@ -1,10 +1,11 @@
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc};
 
 use event_queue::EventQueue;
 use grafbase_telemetry::grafbase_client::Client;
 use runtime::extension::Token;
+use schema::EnabledOverrideLabels;
 
-use crate::graphql_over_http::{ContentType, ResponseFormat};
+use crate::graphql_over_http::{ContentType, FileUploads, ResponseFormat};
 
 /// Context only used early in the request processing before generating the RequestContext used
 /// everywhere else. Contrary to the RequestContext this one never fails to be created.
@@ -16,6 +17,9 @@ pub(crate) struct EarlyHttpContext {
     pub content_type: ContentType,
     pub include_grafbase_response_extension: bool,
     pub include_mcp_response_extension: bool,
+    /// Only explain the query plan in the grafbase response extension, without executing the
+    /// operation.
+    pub explain: bool,
 }
 
 /// Context associated with the HTTP request. For batch requests and a websocket session, a single RequestContext is
@@ -30,6 +34,14 @@ pub(crate) struct RequestContext {
     pub subgraph_default_headers: http::HeaderMap,
     pub include_grafbase_response_extension: bool,
     pub include_mcp_response_extension: bool,
+    pub explain: bool,
     pub event_queue: Arc<EventQueue>,
     pub hooks_context: Arc<[u8]>,
+    /// Progressive override labels enabled for this request, they're decided once for all
+    /// operations of a batch or a websocket session.
+    pub override_labels: EnabledOverrideLabels,
+    /// IP address of the client connection.
+    pub ip: Option<IpAddr>,
+    /// Files sent with a multipart request, referenced by placeholders in the variables.
+    pub file_uploads: FileUploads,
 }
@@ -47,6 +47,23 @@ pub(crate) fn unsupported_content_type(
     )
 }
 
+pub(crate) fn missing_file_uploads_header(
+    error_code_mapping: ErrorCodeMapping,
+    format: ResponseFormat,
+    header_name: &str,
+) -> http::Response<Body> {
+    Http::error(
+        format,
+        refuse_request_with(
+            error_code_mapping,
+            http::StatusCode::FORBIDDEN,
+            format!(
+                "Multipart requests must include the '{header_name}' header to prevent cross-site request forgery."
+            ),
+        ),
+    )
+}
+
 pub(crate) fn method_not_allowed(
     error_code_mapping: ErrorCodeMapping,
     format: ResponseFormat,
@@ -84,6 +101,8 @@ pub(crate) fn refuse_request_with(
 }
 
 pub(crate) mod response {
+    use std::time::Duration;
+
     use gateway_config::ErrorCodeMapping;
 
     use crate::{
@@ -91,12 +110,23 @@ pub(crate) mod response {
         response::{GraphqlError, Response},
     };
 
-    pub(crate) fn gateway_rate_limited(error_code_mapping: ErrorCodeMapping) -> Response {
+    pub(crate) fn gateway_rate_limited(
+        error_code_mapping: ErrorCodeMapping,
+        retry_after: Option<Duration>,
+    ) -> Response {
+        let mut headers = http::HeaderMap::new();
+
+        if let Some(retry_after) = retry_after {
+            // Retry-After is in whole seconds, rounded up so that clients don't retry too early.
+            let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            headers.insert(http::header::RETRY_AFTER, seconds.into());
+        }
+
         Response::refused_request(
             error_code_mapping,
             http::StatusCode::TOO_MANY_REQUESTS,
             [GraphqlError::new("Rate limited", ErrorCode::RateLimited)],
-            Default::default(),
+            headers,
         )
     }
 
@@ -1,23 +1,118 @@
-use std::{borrow::Cow, str::FromStr, sync::OnceLock};
+use std::{borrow::Cow, net::IpAddr, str::FromStr, sync::OnceLock};
 
+use grafbase_telemetry::graphql::OperationType;
 use http::{HeaderName, header};
+use runtime::extension::Token;
 use schema::{
-    ForwardHeaderRule, HeaderRule, HeaderRuleVariant, InsertHeaderRule, NameOrPattern, RemoveHeaderRule,
-    RenameDuplicateHeaderRule,
+    ForwardHeaderRule, GraphqlSubgraphId, HeaderRule, HeaderRuleCondition, HeaderRuleOperationType, HeaderRuleVariant,
+    HeaderTemplate, HeaderTemplatePart, HeaderTemplateVariable, InsertHeaderRule, NameOrPattern, RemoveHeaderRule,
+    RenameDuplicateHeaderRule, Schema,
 };
+use walker::Walk as _;
+
+/// Request data available to the header rule conditions and value templates.
+pub(crate) struct HeaderRuleContext<'a> {
+    pub headers: &'a http::HeaderMap,
+    pub token: &'a Token,
+    pub ip: Option<IpAddr>,
+    /// Not known yet when applying the global header rules.
+    pub operation: Option<(OperationType, Option<&'a str>)>,
+    claims: OnceLock<Option<serde_json::Map<String, serde_json::Value>>>,
+}
+
+impl<'a> HeaderRuleContext<'a> {
+    pub fn new(headers: &'a http::HeaderMap, token: &'a Token, ip: Option<IpAddr>) -> Self {
+        Self {
+            headers,
+            token,
+            ip,
+            operation: None,
+            claims: OnceLock::new(),
+        }
+    }
+
+    pub fn with_operation(mut self, ty: OperationType, name: Option<&'a str>) -> Self {
+        self.operation = Some((ty, name));
+        self
+    }
+
+    fn jwt_claim(&self, key: &str) -> Option<&serde_json::Value> {
+        self.claims
+            .get_or_init(|| match self.token {
+                Token::Anonymous => None,
+                Token::Bytes(bytes) => match serde_json::from_slice(bytes) {
+                    Ok(serde_json::Value::Object(claims)) => Some(claims),
+                    _ => None,
+                },
+            })
+            .as_ref()
+            .and_then(|claims| claims.get(key))
+    }
+
+    fn matches(&self, condition: &HeaderRuleCondition) -> bool {
+        match condition {
+            HeaderRuleCondition::HeaderPresent(name) => self.headers.contains_key(name.as_str()),
+            HeaderRuleCondition::JwtClaimPresent(claim) => self.jwt_claim(claim).is_some(),
+            HeaderRuleCondition::OperationType(expected) => self.operation.is_some_and(|(ty, _)| match expected {
+                HeaderRuleOperationType::Query => ty.is_query(),
+                HeaderRuleOperationType::Mutation => ty.is_mutation(),
+                HeaderRuleOperationType::Subscription => ty.is_subscription(),
+            }),
+            HeaderRuleCondition::All(conditions) => conditions.iter().all(|condition| self.matches(condition)),
+            HeaderRuleCondition::Any(conditions) => conditions.iter().any(|condition| self.matches(condition)),
+            HeaderRuleCondition::Not(condition) => !self.matches(condition),
+        }
+    }
+
+    fn resolve(&self, variable: &HeaderTemplateVariable) -> Option<Cow<'_, str>> {
+        match variable {
+            HeaderTemplateVariable::JwtClaim(claim) => match self.jwt_claim(claim)? {
+                serde_json::Value::String(value) => Some(Cow::Borrowed(value)),
+                serde_json::Value::Null => None,
+                value => Some(Cow::Owned(value.to_string())),
+            },
+            HeaderTemplateVariable::Header(name) => self.headers.get(name)?.to_str().ok().map(Cow::Borrowed),
+            HeaderTemplateVariable::ClientIp => self.ip.map(|ip| Cow::Owned(ip.to_string())),
+            HeaderTemplateVariable::OperationName => self.operation?.1.map(Cow::Borrowed),
+            HeaderTemplateVariable::OperationType => self.operation.map(|(ty, _)| Cow::Borrowed(ty.as_str())),
+        }
+    }
+
+    /// Renders a header value template, `None` if any of the variables isn't available.
+    fn render(&self, template: &HeaderTemplate) -> Option<http::HeaderValue> {
+        if let Some(value) = template.as_static() {
+            return http::HeaderValue::from_str(value).ok();
+        }
+
+        let mut value = String::with_capacity(template.as_str().len());
+        for part in template.parts() {
+            match part {
+                HeaderTemplatePart::Static(s) => value.push_str(s),
+                HeaderTemplatePart::Variable(variable) => value.push_str(&self.resolve(variable)?),
+            }
+        }
+
+        http::HeaderValue::try_from(value).ok()
+    }
+}
 
 pub(crate) fn apply_header_rules<'ctx>(
-    gateway_headers: &http::HeaderMap,
+    ctx: &HeaderRuleContext<'_>,
     rules: impl Iterator<Item = HeaderRule<'ctx>>,
     subgraph_headers: &mut http::HeaderMap,
 ) {
+    let gateway_headers = ctx.headers;
     for rule in rules {
         match rule.variant() {
             HeaderRuleVariant::Forward(rule) => {
-                handle_forward(gateway_headers, rule, subgraph_headers);
+                if rule.condition().is_none_or(|condition| ctx.matches(condition)) {
+                    handle_forward(gateway_headers, rule, subgraph_headers);
+                }
             }
             HeaderRuleVariant::Insert(rule) => {
-                handle_insert(rule, subgraph_headers);
+                if rule.condition().is_none_or(|condition| ctx.matches(condition)) {
+                    handle_insert(ctx, rule, subgraph_headers);
+                }
             }
             HeaderRuleVariant::Remove(rule) => handle_remove(rule, subgraph_headers),
             HeaderRuleVariant::RenameDuplicate(rule) => {
@@ -27,6 +122,19 @@ pub(crate) fn apply_header_rules<'ctx>(
     }
 }
 
+/// Headers of the requests sent to a subgraph outside of any client request, such as health
+/// checks. The header rules are applied as for an anonymous request without any header.
+pub fn subgraph_probe_headers(schema: &Schema, subgraph_id: GraphqlSubgraphId) -> http::HeaderMap {
+    let headers = http::HeaderMap::new();
+    let token = Token::Anonymous;
+    let ctx = HeaderRuleContext::new(&headers, &token, None);
+
+    let mut subgraph_headers = http::HeaderMap::new();
+    apply_header_rules(&ctx, schema.default_header_rules(), &mut subgraph_headers);
+    apply_header_rules(&ctx, subgraph_id.walk(schema).header_rules(), &mut subgraph_headers);
+    subgraph_headers
+}
+
 fn handle_rename_duplicate(
     gateway_headers: &http::HeaderMap,
     rule: RenameDuplicateHeaderRule<'_>,
@@ -76,9 +184,9 @@ fn handle_remove(rule: RemoveHeaderRule<'_>, subgraph_headers: &mut http::Header
     }
 }
 
-fn handle_insert(rule: InsertHeaderRule<'_>, subgraph_headers: &mut http::HeaderMap) {
+fn handle_insert(ctx: &HeaderRuleContext<'_>, rule: InsertHeaderRule<'_>, subgraph_headers: &mut http::HeaderMap) {
     let name = http::HeaderName::from_bytes(rule.name().as_bytes()).ok();
-    let value = http::HeaderValue::from_str(rule.value()).ok();
+    let value = ctx.render(rule.value());
 
     if let Some((name, value)) = name.zip(value) {
         if is_header_denied(&name) {
@@ -188,3 +296,101 @@ pub(crate) fn find_matching_denied_header(name: &HeaderName) -> Option<&'static
     });
     blacklist.iter().find(|denied| **denied == name.as_str()).copied()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn claims_token() -> Token {
+        Token::Bytes(
+            serde_json::to_vec(&serde_json::json!({
+                "sub": "user-1",
+                "admin": true,
+                "groups": ["a", "b"],
+                "email": null
+            }))
+            .unwrap()
+            .into(),
+        )
+    }
+
+    fn render(ctx: &HeaderRuleContext<'_>, template: &str) -> Option<String> {
+        let template: HeaderTemplate = template.parse().unwrap();
+        ctx.render(&template).map(|value| value.to_str().unwrap().to_owned())
+    }
+
+    #[test]
+    fn renders_jwt_claims_and_client_ip() {
+        let headers = http::HeaderMap::new();
+        let token = claims_token();
+        let ctx = HeaderRuleContext::new(&headers, &token, Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))));
+
+        assert_eq!(render(&ctx, "user:{{ jwt.sub }}").as_deref(), Some("user:user-1"));
+        assert_eq!(render(&ctx, "{{ jwt.admin }}").as_deref(), Some("true"));
+        assert_eq!(render(&ctx, "{{ jwt.groups }}").as_deref(), Some(r#"["a","b"]"#));
+        assert_eq!(render(&ctx, "{{ client.ip }}").as_deref(), Some("10.0.0.1"));
+        assert_eq!(
+            render(&ctx, "{{ jwt.sub }}@{{ client.ip }}").as_deref(),
+            Some("user-1@10.0.0.1")
+        );
+    }
+
+    #[test]
+    fn missing_variables_skip_the_header() {
+        let headers = http::HeaderMap::new();
+        let token = claims_token();
+        let ctx = HeaderRuleContext::new(&headers, &token, None);
+
+        assert_eq!(render(&ctx, "{{ jwt.email }}"), None);
+        assert_eq!(render(&ctx, "{{ jwt.unknown }}"), None);
+        assert_eq!(render(&ctx, "{{ client.ip }}"), None);
+        assert_eq!(render(&ctx, "{{ operation.name }}"), None);
+
+        let anonymous = Token::Anonymous;
+        let ctx = HeaderRuleContext::new(&headers, &anonymous, None);
+        assert_eq!(render(&ctx, "{{ jwt.sub }}"), None);
+
+        let not_json = Token::Bytes(b"opaque".as_slice().into());
+        let ctx = HeaderRuleContext::new(&headers, &not_json, None);
+        assert_eq!(render(&ctx, "{{ jwt.sub }}"), None);
+    }
+
+    #[test]
+    fn renders_operation_data() {
+        let headers = http::HeaderMap::new();
+        let token = Token::Anonymous;
+        let ctx =
+            HeaderRuleContext::new(&headers, &token, None).with_operation(OperationType::Mutation, Some("Create"));
+
+        assert_eq!(
+            render(&ctx, "{{ operation.type }}:{{ operation.name }}").as_deref(),
+            Some("mutation:Create")
+        );
+    }
+
+    #[test]
+    fn conditions() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("x-debug", http::HeaderValue::from_static("1"));
+        let token = claims_token();
+        let ctx = HeaderRuleContext::new(&headers, &token, None).with_operation(OperationType::Query, None);
+
+        assert!(ctx.matches(&HeaderRuleCondition::JwtClaimPresent("sub".into())));
+        assert!(!ctx.matches(&HeaderRuleCondition::JwtClaimPresent("unknown".into())));
+        assert!(ctx.matches(&HeaderRuleCondition::All(vec![
+            HeaderRuleCondition::HeaderPresent("x-debug".parse().unwrap()),
+            HeaderRuleCondition::OperationType(HeaderRuleOperationType::Query),
+        ])));
+        assert!(ctx.matches(&HeaderRuleCondition::Any(vec![
+            HeaderRuleCondition::HeaderPresent("x-missing".parse().unwrap()),
+            HeaderRuleCondition::Not(Box::new(HeaderRuleCondition::OperationType(
+                HeaderRuleOperationType::Mutation
+            ))),
+        ])));
+
+        let ctx = HeaderRuleContext::new(&headers, &token, None);
+        assert!(!ctx.matches(&HeaderRuleCondition::OperationType(HeaderRuleOperationType::Query)));
+    }
+}
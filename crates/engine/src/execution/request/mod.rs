@@ -1,15 +1,20 @@
 mod context;
 pub(crate) mod errors;
 mod header_rule;
+mod override_labels;
+mod rate_limit;
+mod response_cache;
 mod response_extension;
 mod single;
 mod stream;
 mod well_formed_graphql_request;
 
 pub(crate) use context::*;
+pub use header_rule::subgraph_probe_headers;
 pub(crate) use header_rule::*;
-use response_extension::should_include_grafbase_response_extension;
+pub(crate) use rate_limit::*;
 pub(crate) use response_extension::*;
+use response_extension::{should_explain, should_include_grafbase_response_extension};
 pub(crate) use stream::*;
 
 use ::runtime::rate_limiting::RateLimitKey;
@@ -20,7 +25,7 @@ use std::{future::Future, sync::Arc};
 
 use crate::{
     Body, ContractAwareEngine, Engine, RequestExtensions, Runtime,
-    graphql_over_http::{ContentType, ResponseFormat},
+    graphql_over_http::{ContentType, FileUploads, ResponseFormat, parse_multipart_request},
     mcp::McpRequestContext,
     response::Response,
     websocket::InitPayload,
@@ -46,12 +51,31 @@ impl<R: Runtime> ContractAwareEngine<R> {
             // GraphQL-over-HTTP spec:
             //   If the client does not supply a Content-Type header with a POST request,
             //   the server SHOULD reject the request using the appropriate 4xx status code.
-            ContentType::extract(&parts.headers).ok_or_else(|| {
-                errors::unsupported_content_type(
+            let content_type = ContentType::extract(&parts.headers)
+                .filter(|content_type| {
+                    !matches!(content_type, ContentType::MultipartFormData { .. })
+                        || self.no_contract.schema.config.file_uploads_enabled
+                })
+                .ok_or_else(|| {
+                    errors::unsupported_content_type(
+                        self.no_contract.schema.config.error_code_mapping.clone(),
+                        response_format,
+                    )
+                })?;
+
+            if matches!(content_type, ContentType::MultipartFormData { .. })
+                && !parts
+                    .headers
+                    .contains_key(self.no_contract.schema.config.file_uploads_required_header.as_str())
+            {
+                return Err(errors::missing_file_uploads_header(
                     self.no_contract.schema.config.error_code_mapping.clone(),
                     response_format,
-                )
-            })?
+                    &self.no_contract.schema.config.file_uploads_required_header,
+                ));
+            }
+
+            content_type
         } else {
             if parts.method != http::Method::GET {
                 return Err(errors::method_not_allowed(
@@ -66,6 +90,8 @@ impl<R: Runtime> ContractAwareEngine<R> {
         // Config doesn't depend on the contract.
         let include_grafbase_response_extension =
             should_include_grafbase_response_extension(&self.no_contract.schema.config, &parts.headers);
+        let explain =
+            include_grafbase_response_extension && should_explain(&self.no_contract.schema.config, &parts.headers);
 
         let mut ctx = EarlyHttpContext {
             can_mutate: !parts.method.is_safe(),
@@ -75,6 +101,7 @@ impl<R: Runtime> ContractAwareEngine<R> {
             content_type,
             include_grafbase_response_extension,
             include_mcp_response_extension: false,
+            explain,
         };
 
         if let Some(mcp) = parts.extensions.get::<McpRequestContext>() {
@@ -105,19 +132,20 @@ impl<R: Runtime> Engine<R> {
         headers: http::HeaderMap,
         extensions: RequestExtensions,
         websocket_init_payload: Option<InitPayload>,
-    ) -> Result<Arc<RequestContext>, Response> {
+    ) -> Result<RequestContext, Response> {
         let client = Client::extract_from(&headers);
 
         // Currently it doesn't rely on authentication, but likely will at some point.
-        if self.runtime.rate_limiter().limit(&RateLimitKey::Global).await.is_err() {
+        if let Err(err) = self.runtime.rate_limiter().limit(&RateLimitKey::Global).await {
             return Err(errors::response::gateway_rate_limited(
                 self.schema.config.error_code_mapping.clone(),
+                err.retry_after(),
             ));
         }
 
         let mut subgraph_default_headers = http::HeaderMap::new();
         apply_header_rules(
-            &headers,
+            &HeaderRuleContext::new(&headers, &extensions.token, extensions.ip),
             self.schema.default_header_rules(),
             &mut subgraph_default_headers,
         );
@@ -132,18 +160,22 @@ impl<R: Runtime> Engine<R> {
             subgraph_default_headers,
             include_grafbase_response_extension: ctx.include_grafbase_response_extension,
             include_mcp_response_extension: ctx.include_mcp_response_extension,
+            explain: ctx.explain,
             event_queue: extensions.event_queue,
             hooks_context: extensions.hooks_context,
+            override_labels: self.sample_override_labels(&extensions.override_labels),
+            ip: extensions.ip,
+            file_uploads: Default::default(),
         };
 
-        Ok(Arc::new(request_context))
+        Ok(request_context)
     }
 
     pub(crate) async fn extract_well_formed_graphql_over_http_request<F>(
         &self,
         ctx: &EarlyHttpContext,
         body: F,
-    ) -> Result<BatchRequest, Response>
+    ) -> Result<(BatchRequest, FileUploads), Response>
     where
         F: Future<Output = Result<Bytes, (http::StatusCode, String)>> + Send,
     {
@@ -154,25 +186,37 @@ impl<R: Runtime> Engine<R> {
 
             self.runtime.metrics().record_request_body_size(body.len());
 
-            match ctx.content_type {
-                ContentType::Json => sonic_rs::from_slice(&body).map_err(|err| {
-                    errors::not_well_formed_graphql_over_http_request(
-                        self.schema.config.error_code_mapping.clone(),
-                        format_args!("JSON deserialization failure: {err}",),
-                    )
-                }),
-                ContentType::Cbor => minicbor_serde::from_slice(&body).map_err(|err| {
-                    errors::not_well_formed_graphql_over_http_request(
-                        self.schema.config.error_code_mapping.clone(),
-                        format_args!("CBOR deserialization failure: {err}"),
-                    )
-                }),
+            match &ctx.content_type {
+                ContentType::Json => sonic_rs::from_slice(&body)
+                    .map(|request| (request, FileUploads::default()))
+                    .map_err(|err| {
+                        errors::not_well_formed_graphql_over_http_request(
+                            self.schema.config.error_code_mapping.clone(),
+                            format_args!("JSON deserialization failure: {err}",),
+                        )
+                    }),
+                ContentType::Cbor => minicbor_serde::from_slice(&body)
+                    .map(|request| (request, FileUploads::default()))
+                    .map_err(|err| {
+                        errors::not_well_formed_graphql_over_http_request(
+                            self.schema.config.error_code_mapping.clone(),
+                            format_args!("CBOR deserialization failure: {err}"),
+                        )
+                    }),
+                ContentType::MultipartFormData { boundary } => {
+                    parse_multipart_request(boundary, body).await.map_err(|err| {
+                        errors::not_well_formed_graphql_over_http_request(
+                            self.schema.config.error_code_mapping.clone(),
+                            format_args!("Multipart request failure: {err}"),
+                        )
+                    })
+                }
             }
         } else {
             let query = ctx.uri.query().unwrap_or_default();
 
             serde_urlencoded::from_str::<QueryParamsRequest>(query)
-                .map(|request| BatchRequest::Single(request.into()))
+                .map(|request| (BatchRequest::Single(request.into()), FileUploads::default()))
                 .map_err(|err| {
                     errors::not_well_formed_graphql_over_http_request(
                         self.schema.config.error_code_mapping.clone(),
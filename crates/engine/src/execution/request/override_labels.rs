@@ -0,0 +1,19 @@
+use schema::EnabledOverrideLabels;
+
+use crate::{Engine, Runtime};
+
+impl<R: Runtime> Engine<R> {
+    /// Decides which progressive override labels are enabled for a request. `percent(N)` labels
+    /// are enabled N% of the time, any other label only if the gateway hooks provided it.
+    pub(super) fn sample_override_labels(&self, custom_labels: &[String]) -> EnabledOverrideLabels {
+        self.schema
+            .override_labels()
+            .iter()
+            .filter(|label| match label.percent {
+                Some(percent) => rand::random_range(0..100u8) < percent,
+                None => custom_labels.iter().any(|custom| *custom == self.schema[label.id]),
+            })
+            .map(|label| label.id)
+            .collect()
+    }
+}
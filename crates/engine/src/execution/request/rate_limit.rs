@@ -0,0 +1,111 @@
+use std::{net::IpAddr, sync::OnceLock};
+
+use runtime::{
+    extension::Token,
+    rate_limiting::{RateLimitKey, RateLimiterContext},
+};
+
+use super::RequestContext;
+
+/// Exposes the request to the rate limiter, so that limits can be applied per client.
+pub(crate) struct RequestRateLimitContext<'a> {
+    key: RateLimitKey<'a>,
+    headers: &'a http::HeaderMap,
+    token: &'a Token,
+    ip: Option<IpAddr>,
+    operation_name: Option<&'a str>,
+    claims: OnceLock<Option<serde_json::Map<String, serde_json::Value>>>,
+}
+
+impl<'a> RequestRateLimitContext<'a> {
+    pub fn new(key: RateLimitKey<'a>, request_context: &'a RequestContext, operation_name: Option<&'a str>) -> Self {
+        Self {
+            key,
+            headers: &request_context.headers,
+            token: &request_context.token,
+            ip: request_context.ip,
+            operation_name,
+            claims: OnceLock::new(),
+        }
+    }
+}
+
+impl RateLimiterContext for RequestRateLimitContext<'_> {
+    fn header(&self, name: http::HeaderName) -> Option<&http::HeaderValue> {
+        self.headers.get(name)
+    }
+
+    fn graphql_operation_name(&self) -> Option<&str> {
+        self.operation_name
+    }
+
+    fn ip(&self) -> Option<IpAddr> {
+        self.ip
+    }
+
+    fn jwt_claim(&self, key: &str) -> Option<&serde_json::Value> {
+        self.claims
+            .get_or_init(|| match self.token {
+                Token::Anonymous => None,
+                Token::Bytes(bytes) => match serde_json::from_slice(bytes) {
+                    Ok(serde_json::Value::Object(claims)) => Some(claims),
+                    _ => None,
+                },
+            })
+            .as_ref()
+            .and_then(|claims| claims.get(key))
+    }
+
+    fn key(&self) -> Option<&RateLimitKey<'_>> {
+        Some(&self.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv6Addr;
+
+    use super::*;
+
+    fn context<'a>(headers: &'a http::HeaderMap, token: &'a Token, ip: Option<IpAddr>) -> RequestRateLimitContext<'a> {
+        RequestRateLimitContext {
+            key: RateLimitKey::Operation,
+            headers,
+            token,
+            ip,
+            operation_name: None,
+            claims: OnceLock::new(),
+        }
+    }
+
+    #[test]
+    fn exposes_jwt_claims_and_client_ip() {
+        let headers = http::HeaderMap::new();
+        let token = Token::Bytes(
+            serde_json::to_vec(&serde_json::json!({"sub": "user-1", "plan": {"tier": "pro"}}))
+                .unwrap()
+                .into(),
+        );
+        let ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+        let ctx = context(&headers, &token, Some(ip));
+
+        assert_eq!(ctx.jwt_claim("sub"), Some(&serde_json::json!("user-1")));
+        assert_eq!(ctx.jwt_claim("plan"), Some(&serde_json::json!({"tier": "pro"})));
+        assert_eq!(ctx.jwt_claim("unknown"), None);
+        assert_eq!(ctx.ip(), Some(ip));
+    }
+
+    #[test]
+    fn tokens_without_json_claims_have_no_claims() {
+        let headers = http::HeaderMap::new();
+
+        let anonymous = Token::Anonymous;
+        assert_eq!(context(&headers, &anonymous, None).jwt_claim("sub"), None);
+
+        let opaque = Token::Bytes(b"opaque".as_slice().into());
+        assert_eq!(context(&headers, &opaque, None).jwt_claim("sub"), None);
+
+        let array = Token::Bytes(b"[\"sub\"]".as_slice().into());
+        assert_eq!(context(&headers, &array, None).jwt_claim("sub"), None);
+    }
+}
@@ -0,0 +1,355 @@
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use grafbase_telemetry::graphql::{GraphqlResponseStatus, OperationType};
+use itertools::Itertools as _;
+use operation::RawVariables;
+use runtime::{entity_cache::CacheTag, extension::Token};
+use schema::{CacheControlScope, ResponseCachingConfig};
+use walker::Walk;
+
+use crate::{
+    Runtime,
+    engine::cache::CacheKey,
+    prepare::{PrepareContext, PreparedOperation},
+    response::Response,
+};
+
+/// Whole-response cache entry, stored in the entity cache storage.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry<'a> {
+    /// Unix timestamp in seconds.
+    expires_at: u64,
+    private: bool,
+    #[serde(borrow)]
+    data: &'a serde_json::value::RawValue,
+    /// Subgraph response headers propagated to the client.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    headers: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+}
+
+/// Hash of everything besides the operation itself a cached response depends on. `None` if the
+/// request must bypass the cache.
+pub(super) fn request_partition(
+    config: &ResponseCachingConfig,
+    headers: &http::HeaderMap,
+    token: &Token,
+    variables: &RawVariables,
+) -> Option<blake3::Hash> {
+    if !config.enabled {
+        return None;
+    }
+
+    let mut hasher = blake3::Hasher::new();
+
+    hasher.update(b"variables");
+    hasher.update(&serde_json::to_vec(variables).ok()?);
+
+    hasher.update(b"headers");
+    for name in &config.key_headers {
+        hasher.update(&name.len().to_le_bytes());
+        hasher.update(name.as_bytes());
+        // Multiple values are all part of the key, in order.
+        for value in headers.get_all(name) {
+            hasher.update(&value.len().to_le_bytes());
+            hasher.update(value.as_bytes());
+        }
+        hasher.update(&[0x00]);
+    }
+
+    match token {
+        Token::Anonymous => {
+            hasher.update(b"anonymous");
+        }
+        Token::Bytes(bytes) => {
+            // Authenticated responses may depend on the user, so we only cache them when they're
+            // partitioned on at least one claim.
+            if config.key_jwt_claims.is_empty() {
+                return None;
+            }
+            let serde_json::Value::Object(claims) = serde_json::from_slice::<serde_json::Value>(bytes).ok()? else {
+                return None;
+            };
+
+            hasher.update(b"claims");
+            for name in &config.key_jwt_claims {
+                let value = claims.get(name).unwrap_or(&serde_json::Value::Null);
+                let value = serde_json::to_vec(value).ok()?;
+                hasher.update(&name.len().to_le_bytes());
+                hasher.update(name.as_bytes());
+                hasher.update(&value.len().to_le_bytes());
+                hasher.update(&value);
+            }
+        }
+    }
+
+    Some(hasher.finalize())
+}
+
+pub(super) struct ResponseCacheEntry {
+    key: String,
+    max_age: Duration,
+    private: bool,
+    /// Tags of every subgraph involved, so that purging a subgraph also purges the responses
+    /// relying on it.
+    tags: Vec<CacheTag>,
+}
+
+impl ResponseCacheEntry {
+    /// Determines the cache entry for a prepared operation based on the `@cacheControl` hints of
+    /// its fields. `None` if the operation isn't cacheable.
+    pub(super) fn new<R: Runtime>(
+        ctx: &PrepareContext<'_, R>,
+        operation: &PreparedOperation,
+        partition: &blake3::Hash,
+    ) -> Option<Self> {
+        if !matches!(operation.cached.ty(), OperationType::Query) {
+            return None;
+        }
+
+        let schema = ctx.schema();
+        let mut max_age = schema.config.response_caching.ttl;
+        let mut private = false;
+
+        for field in &operation.cached.operation.data_fields {
+            let definition = field.definition_id.walk(schema);
+            let output = definition.ty().definition();
+            let field_hint = definition.cache_control();
+            let output_hint = output.is_composite_type().then(|| output.cache_control()).flatten();
+
+            // With `inheritMaxAge` the field only gets the max-age of its parent, which is already
+            // accounted for. A max-age on the field itself still applies.
+            let inherits_max_age = match field_hint {
+                Some(hint) if hint.max_age.is_some() => false,
+                Some(hint) if hint.inherit_max_age => true,
+                _ => output_hint.is_some_and(|hint| hint.inherit_max_age),
+            };
+
+            let max_ages = field_hint
+                .and_then(|hint| hint.max_age)
+                .into_iter()
+                .chain(output_hint.filter(|_| !inherits_max_age).and_then(|hint| hint.max_age));
+            for hint_max_age in max_ages {
+                max_age = max_age.min(Duration::from_secs(hint_max_age as u64));
+            }
+
+            private |= field_hint
+                .into_iter()
+                .chain(output_hint)
+                .any(|hint| matches!(hint.scope, Some(CacheControlScope::Private)));
+        }
+
+        // Private responses are only cached when partitioned by the user.
+        if max_age.is_zero() || (private && matches!(ctx.request_context.token, Token::Anonymous)) {
+            return None;
+        }
+
+        let operation_key = CacheKey::document(
+            schema,
+            &operation.cached.document.key,
+            &ctx.request_context.override_labels,
+        );
+
+        let tags = operation
+            .cached
+            .query_plan
+            .partitions
+            .iter()
+            .map(|partition| partition.resolver_definition_id.walk(schema).subgraph().name())
+            .unique()
+            .map(|name| CacheTag::Subgraph(name.to_string()))
+            .collect();
+
+        Some(Self {
+            key: CacheKey::response(&operation_key, partition),
+            max_age,
+            private,
+            tags,
+        })
+    }
+
+    pub(super) async fn get<R: Runtime>(
+        &self,
+        runtime: &R,
+    ) -> Option<(serde_json::Value, headers::CacheControl, http::HeaderMap)> {
+        let bytes = runtime
+            .entity_cache()
+            .get(&self.key)
+            .await
+            .inspect_err(|err| tracing::warn!("Failed to read the cache key {}: {err}", self.key))
+            .ok()
+            .flatten()?;
+
+        let entry: CacheEntry<'_> = serde_json::from_slice(&bytes)
+            .inspect_err(|err| tracing::warn!("Invalid cached response for key {}: {err}", self.key))
+            .ok()?;
+
+        let remaining = entry.expires_at.checked_sub(now().as_secs()).filter(|secs| *secs > 0)?;
+        let data = serde_json::from_str(entry.data.get()).ok()?;
+
+        let mut headers = http::HeaderMap::new();
+        for (name, value) in entry.headers {
+            let (Ok(name), Ok(value)) = (
+                http::HeaderName::try_from(name.as_ref()),
+                http::HeaderValue::try_from(value.as_ref()),
+            ) else {
+                tracing::warn!("Invalid cached header for key {}", self.key);
+                return None;
+            };
+            headers.append(name, value);
+        }
+
+        Some((
+            data,
+            cache_control(Duration::from_secs(remaining), entry.private),
+            headers,
+        ))
+    }
+
+    /// Stores the response if it's complete and returns it with the appropriate Cache-Control header.
+    pub(super) async fn put<R: Runtime>(self, runtime: &R, response: Response) -> Response {
+        if !matches!(response.graphql_status(), GraphqlResponseStatus::Success) {
+            return response.with_cache_control(no_store());
+        }
+
+        let max_age = response
+            .subgraph_cache_max_age()
+            .map(|subgraph_max_age| subgraph_max_age.min(self.max_age))
+            .unwrap_or(self.max_age);
+
+        // Sub-second precision isn't worth keeping.
+        let max_age = Duration::from_secs(max_age.as_secs());
+        if max_age.is_zero() {
+            return response.with_cache_control(no_store());
+        }
+
+        let data = match serde_json::to_value(&response) {
+            Ok(serde_json::Value::Object(mut fields)) => fields.remove("data").unwrap_or_default(),
+            _ => serde_json::Value::Null,
+        };
+        if data.is_null() {
+            return response.with_cache_control(no_store());
+        }
+
+        let Ok(data) = serde_json::value::to_raw_value(&data) else {
+            return response.with_cache_control(no_store());
+        };
+
+        let mut headers = Vec::new();
+        for (name, value) in response.propagated_headers().into_iter().flatten() {
+            // Cookies are specific to a client, they must never be replayed to others.
+            if name == http::header::SET_COOKIE {
+                return response.with_cache_control(no_store());
+            }
+            let Ok(value) = value.to_str() else {
+                return response.with_cache_control(no_store());
+            };
+            headers.push((Cow::Borrowed(name.as_str()), Cow::Borrowed(value)));
+        }
+
+        let entry = CacheEntry {
+            expires_at: (now() + max_age).as_secs(),
+            private: self.private,
+            data: &data,
+            headers,
+        };
+
+        match serde_json::to_vec(&entry) {
+            Ok(bytes) => {
+                runtime
+                    .entity_cache()
+                    .put(&self.key, Cow::Owned(bytes), max_age, &self.tags)
+                    .await
+                    .inspect_err(|err| tracing::warn!("Failed to write the cache key {}: {err}", self.key))
+                    .ok();
+            }
+            Err(err) => tracing::warn!("Failed to serialize the response for the cache key {}: {err}", self.key),
+        }
+
+        response.with_cache_control(cache_control(max_age, self.private))
+    }
+}
+
+pub(super) fn no_store() -> headers::CacheControl {
+    headers::CacheControl::new().with_no_store()
+}
+
+fn cache_control(max_age: Duration, private: bool) -> headers::CacheControl {
+    let cache_control = headers::CacheControl::new().with_max_age(max_age);
+    if private {
+        cache_control.with_private()
+    } else {
+        cache_control.with_public()
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(key_jwt_claims: &[&str]) -> ResponseCachingConfig {
+        ResponseCachingConfig {
+            enabled: true,
+            ttl: Duration::from_secs(60),
+            key_headers: vec!["x-tenant".into()],
+            key_jwt_claims: key_jwt_claims.iter().map(|claim| claim.to_string()).collect(),
+        }
+    }
+
+    fn token(claims: serde_json::Value) -> Token {
+        Token::Bytes(serde_json::to_vec(&claims).unwrap().into())
+    }
+
+    fn partition(config: &ResponseCachingConfig, token: &Token) -> Option<blake3::Hash> {
+        request_partition(config, &http::HeaderMap::new(), token, &RawVariables::default())
+    }
+
+    #[test]
+    fn authenticated_requests_are_partitioned_on_claims() {
+        let config = config(&["sub"]);
+
+        let alice = partition(&config, &token(serde_json::json!({"sub": "alice", "iat": 1})));
+        let alice_again = partition(&config, &token(serde_json::json!({"sub": "alice", "iat": 2})));
+        let bob = partition(&config, &token(serde_json::json!({"sub": "bob", "iat": 1})));
+        let no_sub = partition(&config, &token(serde_json::json!({"iat": 1})));
+        let anonymous = partition(&config, &Token::Anonymous);
+
+        assert!(alice.is_some());
+        assert_eq!(alice, alice_again);
+        assert_ne!(alice, bob);
+        assert!(no_sub.is_some());
+        assert_ne!(alice, no_sub);
+        assert_ne!(no_sub, anonymous);
+    }
+
+    #[test]
+    fn authenticated_requests_bypass_the_cache_without_claims() {
+        let sub = token(serde_json::json!({"sub": "alice"}));
+        assert_eq!(partition(&config(&[]), &sub), None);
+        assert!(partition(&config(&[]), &Token::Anonymous).is_some());
+
+        let opaque = Token::Bytes(b"opaque".as_slice().into());
+        assert_eq!(partition(&config(&["sub"]), &opaque), None);
+    }
+
+    #[test]
+    fn key_headers_partition_the_cache() {
+        let config = config(&[]);
+        let mut headers = http::HeaderMap::new();
+        let without = request_partition(&config, &headers, &Token::Anonymous, &RawVariables::default());
+        headers.insert("x-tenant", http::HeaderValue::from_static("a"));
+        let tenant_a = request_partition(&config, &headers, &Token::Anonymous, &RawVariables::default());
+        headers.insert("x-other", http::HeaderValue::from_static("b"));
+        let with_other = request_partition(&config, &headers, &Token::Anonymous, &RawVariables::default());
+
+        assert_ne!(without, tenant_a);
+        assert_eq!(tenant_a, with_other);
+    }
+}
@@ -8,6 +8,8 @@ use crate::{
 
 use super::RequestContext;
 
+const EXPLAIN_HEADER: &str = "x-grafbase-explain";
+
 pub(crate) fn should_include_grafbase_response_extension(config: &PartialConfig, headers: &http::HeaderMap) -> bool {
     config
         .response_extension
@@ -31,6 +33,12 @@ pub(crate) fn should_include_grafbase_response_extension(config: &PartialConfig,
         })
 }
 
+/// Explaining relies on the grafbase response extension, so it's subject to the same access
+/// control.
+pub(crate) fn should_explain(config: &PartialConfig, headers: &http::HeaderMap) -> bool {
+    config.response_extension.allow_explain && headers.contains_key(EXPLAIN_HEADER)
+}
+
 pub(crate) fn default_response_extensions(schema: &Schema, ctx: &RequestContext) -> ResponseExtensions {
     let mut ext = ResponseExtensions::default();
     if ctx.include_grafbase_response_extension {
@@ -53,5 +61,8 @@ pub(crate) fn response_extension_for_prepared_operation(
     if ctx.include_grafbase_response_extension && schema.config.response_extension.include_query_plan {
         ext.grafbase = Some(ext.grafbase.unwrap_or_default().with_query_plan(schema, operation))
     };
+    if ctx.explain {
+        ext.grafbase = Some(ext.grafbase.unwrap_or_default().with_explain(schema, operation))
+    }
     ext
 }
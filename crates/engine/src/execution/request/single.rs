@@ -1,5 +1,6 @@
 use std::{sync::Arc, time::Instant};
 
+use futures::future::join_all;
 use grafbase_telemetry::{
     graphql::OperationType,
     metrics::{GraphqlErrorAttributes, GraphqlRequestMetricsAttributes},
@@ -14,7 +15,11 @@ use crate::{
     response::{ErrorCode, GraphqlError, Response},
 };
 
-use super::{RequestContext, default_response_extensions, response_extension_for_prepared_operation};
+use super::{
+    RequestContext, default_response_extensions,
+    response_cache::{self, ResponseCacheEntry},
+    response_extension_for_prepared_operation,
+};
 
 impl<R: Runtime> Engine<R> {
     pub(super) async fn execute_single(
@@ -66,6 +71,18 @@ impl<R: Runtime> Engine<R> {
 
 impl<R: Runtime> PrepareContext<'_, R> {
     async fn execute_single(mut self, request: Request) -> Response {
+        // Variables only hold placeholders for uploaded files, so they can't partition the cache.
+        let cache_partition = if self.request_context.file_uploads.is_empty() {
+            response_cache::request_partition(
+                &self.schema().config.response_caching,
+                &self.request_context.headers,
+                &self.request_context.token,
+                &request.variables,
+            )
+        } else {
+            None
+        };
+
         let operation = match self.prepare_operation(request).await {
             Ok(operation) => operation,
             Err(response) => {
@@ -77,6 +94,13 @@ impl<R: Runtime> PrepareContext<'_, R> {
 
         let extensions = response_extension_for_prepared_operation(self.schema(), self.request_context, &operation);
 
+        if self.request_context.explain {
+            join_all(std::mem::take(&mut self.background_futures)).await;
+            return Response::not_executed(&self.engine.schema, operation)
+                .with_operation_attributes(attributes)
+                .with_extensions(extensions);
+        }
+
         if matches!(operation.cached.ty(), OperationType::Subscription) {
             let error = GraphqlError::new(
                 "Subscriptions are only suported on streaming transports. Try making a request with SSE or WebSockets",
@@ -88,7 +112,38 @@ impl<R: Runtime> PrepareContext<'_, R> {
                 .with_extensions(extensions);
         }
 
-        self.execute_query_or_mutation(operation)
+        if !self.schema().config.response_caching.enabled {
+            return self
+                .execute_query_or_mutation(operation)
+                .await
+                .with_operation_attributes(attributes)
+                .with_extensions(extensions);
+        }
+
+        let Some(cache_entry) = cache_partition
+            .as_ref()
+            .and_then(|partition| ResponseCacheEntry::new(&self, &operation, partition))
+        else {
+            return self
+                .execute_query_or_mutation(operation)
+                .await
+                .with_cache_control(response_cache::no_store())
+                .with_operation_attributes(attributes)
+                .with_extensions(extensions);
+        };
+
+        if let Some((data, cache_control, propagated_headers)) = cache_entry.get(self.runtime()).await {
+            join_all(std::mem::take(&mut self.background_futures)).await;
+            return Response::cached(attributes, data, cache_control, propagated_headers)
+                .with_rate_limit_budget(operation.rate_limit_budget)
+                .with_extensions(extensions);
+        }
+
+        let runtime = self.runtime();
+        let response = self.execute_query_or_mutation(operation).await;
+
+        cache_entry
+            .put(runtime, response)
             .await
             .with_operation_attributes(attributes)
             .with_extensions(extensions)
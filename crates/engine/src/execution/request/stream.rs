@@ -3,6 +3,7 @@ use std::{sync::Arc, time::Instant};
 use futures::{
     StreamExt as _,
     channel::{mpsc, oneshot},
+    future::join_all,
     stream::BoxStream,
 };
 use futures_util::SinkExt;
@@ -149,9 +150,39 @@ impl<R: Runtime> PrepareContext<'_, R> {
                     }
                 };
 
+                if self.request_context.explain {
+                    let attributes = operation.attributes();
+                    let extensions =
+                        response_extension_for_prepared_operation(self.schema(), self.request_context, &operation);
+                    join_all(std::mem::take(&mut self.background_futures)).await;
+                    sender
+                        .send(
+                            Response::not_executed(&self.engine.schema, operation)
+                                .with_operation_attributes(attributes.clone())
+                                .with_extensions(extensions),
+                        )
+                        .await
+                        .ok();
+                    return Err(Some(attributes));
+                }
+
                 if matches!(operation.cached.ty(), OperationType::Query | OperationType::Mutation) {
                     let extensions =
                         response_extension_for_prepared_operation(self.schema(), self.request_context, &operation);
+
+                    if operation.plan.has_incremental_delivery() {
+                        let attributes = operation.attributes();
+                        self.execute_query_or_mutation_incrementally(
+                            operation,
+                            AddExtToFirstResponse {
+                                sender: &mut sender,
+                                extensions: Some(extensions),
+                            },
+                        )
+                        .await;
+                        return Err(Some(attributes));
+                    }
+
                     let response = self.execute_query_or_mutation(operation).await;
 
                     let attributes = response.operation_attributes().cloned();
@@ -183,23 +214,6 @@ impl<R: Runtime> PrepareContext<'_, R> {
 
         let attributes = operation.attributes();
 
-        struct AddExtToFirstResponse<Sender> {
-            sender: Sender,
-            extensions: Option<ResponseExtensions>,
-        }
-
-        impl<S: ResponseSender> ResponseSender for AddExtToFirstResponse<S> {
-            type Error = S::Error;
-            async fn send(&mut self, response: Response) -> Result<(), Self::Error> {
-                let response = if let Some(extensions) = self.extensions.take() {
-                    response.with_extensions(extensions)
-                } else {
-                    response
-                };
-                self.sender.send(response).await
-            }
-        }
-
         let extensions = response_extension_for_prepared_operation(schema, request_context, &operation);
         ctx.execute_subscription(
             operation,
@@ -213,3 +227,20 @@ impl<R: Runtime> PrepareContext<'_, R> {
         Some(attributes)
     }
 }
+
+struct AddExtToFirstResponse<Sender> {
+    sender: Sender,
+    extensions: Option<ResponseExtensions>,
+}
+
+impl<S: ResponseSender> ResponseSender for AddExtToFirstResponse<S> {
+    type Error = S::Error;
+    async fn send(&mut self, response: Response) -> Result<(), Self::Error> {
+        let response = if let Some(extensions) = self.extensions.take() {
+            response.with_extensions(extensions)
+        } else {
+            response
+        };
+        self.sender.send(response).await
+    }
+}
@@ -5,12 +5,14 @@ use std::{
 
 use error::{ErrorCode, GraphqlError};
 use futures::StreamExt;
+use headers::HeaderMapExt as _;
 use hive_console_sdk::agent::usage_agent::{ExecutionReport, UsageAgentExt};
 use operation::{BatchRequest, Request};
+use runtime::extension::EngineHooksExtension as _;
 
 use crate::{
     Body, Engine,
-    graphql_over_http::{Http, ResponseFormat},
+    graphql_over_http::{CompleteResponseFormat, Http, ResponseFormat},
     response::Response,
 };
 
@@ -61,7 +63,8 @@ impl<R: Runtime> Engine<R> {
                         }
                     }
 
-                    Http::single(format, response)
+                    let http_response = Http::single(format, response);
+                    self.on_graphql_response(&request_context, format, http_response).await
                 }
             },
             BatchRequest::Batch(requests) => {
@@ -101,7 +104,8 @@ impl<R: Runtime> Engine<R> {
                     return self.gateway_timeout_error(&request_context);
                 };
 
-                Http::batch(format, responses)
+                let http_response = Http::batch(format, responses);
+                self.on_graphql_response(&request_context, format, http_response).await
             }
         }
     }
@@ -114,6 +118,40 @@ impl<R: Runtime> Engine<R> {
         self.execute_stream(request_context, request)
     }
 
+    /// Lets the hooks extension rewrite the serialized body of complete responses.
+    async fn on_graphql_response(
+        &self,
+        request_context: &RequestContext,
+        format: CompleteResponseFormat,
+        http_response: http::Response<Body>,
+    ) -> http::Response<Body> {
+        let (mut parts, body) = http_response.into_parts();
+        let Body::Bytes(bytes) = body else {
+            return http::Response::from_parts(parts, body);
+        };
+
+        match self
+            .runtime
+            .extensions()
+            .on_graphql_response(
+                request_context.event_queue.clone(),
+                request_context.hooks_context.clone(),
+                bytes,
+            )
+            .await
+        {
+            Ok(bytes) => {
+                parts.headers.typed_insert(headers::ContentLength(bytes.len() as u64));
+                http::Response::from_parts(parts, Body::Bytes(bytes))
+            }
+            Err(err) => Http::error(
+                ResponseFormat::Complete(format),
+                Response::request_error(self.schema.config.error_code_mapping.clone(), [err])
+                    .with_extensions(default_response_extensions(&self.schema, request_context)),
+            ),
+        }
+    }
+
     fn bad_request_but_well_formed_graphql_over_http_request(
         &self,
         request_context: &RequestContext,
@@ -37,6 +37,7 @@ pub enum StreamingResponseFormat {
 mod content_types {
     pub const APPLICATION_JSON: http::HeaderValue = http::HeaderValue::from_static("application/json");
     pub const APPLICATION_CBOR: http::HeaderValue = http::HeaderValue::from_static("application/cbor");
+    pub const MULTIPART_FORM_DATA: http::HeaderValue = http::HeaderValue::from_static("multipart/form-data");
     pub const APPLICATION_GRAPHQL_RESPONSE_JSON: http::HeaderValue =
         http::HeaderValue::from_static("application/graphql-response+json");
 
@@ -46,6 +47,12 @@ mod content_types {
 pub(crate) enum ContentType {
     Json,
     Cbor,
+    /// Follow the [GraphQL multipart request spec][1]
+    ///
+    /// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+    MultipartFormData {
+        boundary: String,
+    },
 }
 
 impl ContentType {
@@ -59,6 +66,10 @@ impl ContentType {
             Some(ContentType::Json)
         } else if bytes == content_types::APPLICATION_CBOR.as_bytes() {
             Some(ContentType::Cbor)
+        } else if bytes.eq_ignore_ascii_case(content_types::MULTIPART_FORM_DATA.as_bytes()) {
+            let value = headers.get(http::header::CONTENT_TYPE)?.to_str().ok()?;
+            let boundary = multer::parse_boundary(value).ok()?;
+            Some(ContentType::MultipartFormData { boundary })
         } else {
             None
         }
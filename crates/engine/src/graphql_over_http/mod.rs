@@ -4,6 +4,7 @@
 //!
 mod format;
 mod response;
+mod upload;
 
 use bytes::Bytes;
 use error::ErrorCode;
@@ -11,6 +12,7 @@ pub use format::*;
 use futures_util::stream::BoxStream;
 use grafbase_telemetry::graphql::GraphqlExecutionTelemetry;
 pub(crate) use response::*;
+pub(crate) use upload::*;
 
 pub enum Body {
     Bytes(Bytes),
@@ -3,6 +3,7 @@ use futures::StreamExt;
 use futures_util::Stream;
 use grafbase_telemetry::graphql::GraphqlExecutionTelemetry;
 use headers::HeaderMapExt;
+use runtime::rate_limiting::RateLimitBudget;
 
 use crate::{
     execution::StreamResponse,
@@ -18,6 +19,9 @@ const INITIAL_MAX_CAPACITY: usize = 1 << 18;
 const APPLICATION_JSON: http::HeaderValue = http::HeaderValue::from_static("application/json");
 const APPLICATION_GRAPHQL_RESPONSE_JSON: http::HeaderValue =
     http::HeaderValue::from_static("application/graphql-response+json");
+const X_RATELIMIT_LIMIT: http::HeaderName = http::HeaderName::from_static("x-ratelimit-limit");
+const X_RATELIMIT_REMAINING: http::HeaderName = http::HeaderName::from_static("x-ratelimit-remaining");
+const X_RATELIMIT_RESET: http::HeaderName = http::HeaderName::from_static("x-ratelimit-reset");
 
 pub(crate) struct Http;
 
@@ -172,6 +176,9 @@ impl Http {
         };
 
         let status_code = compute_status_code(ResponseFormat::Complete(format), &response);
+        let cache_control = response.cache_control().cloned();
+        let rate_limit_budget = response.rate_limit_budget();
+        let propagated_headers = response.propagated_headers().cloned();
 
         let mut headers = if let Response::RefusedRequest(response) = response {
             response.headers
@@ -179,6 +186,16 @@ impl Http {
             Default::default()
         };
 
+        // Headers set by the gateway below take precedence over the subgraph ones.
+        if let Some(propagated_headers) = propagated_headers {
+            headers.extend(propagated_headers);
+        }
+        if let Some(cache_control) = cache_control {
+            headers.typed_insert(cache_control);
+        }
+        if let Some(budget) = rate_limit_budget {
+            insert_rate_limit_headers(&mut headers, budget);
+        }
         headers.insert(http::header::CONTENT_TYPE, format.to_content_type_header_value());
         headers.typed_insert(headers::ContentLength(bytes.len() as u64));
 
@@ -190,6 +207,15 @@ impl Http {
     }
 }
 
+/// Remaining complexity budget of the client, the reset being in seconds.
+fn insert_rate_limit_headers(headers: &mut http::HeaderMap, budget: RateLimitBudget) {
+    let reset = budget.reset.as_secs() + u64::from(budget.reset.subsec_nanos() > 0);
+
+    headers.insert(X_RATELIMIT_LIMIT, budget.limit.into());
+    headers.insert(X_RATELIMIT_REMAINING, budget.remaining.into());
+    headers.insert(X_RATELIMIT_RESET, reset.into());
+}
+
 fn compute_status_code(format: ResponseFormat, response: &Response) -> http::StatusCode {
     match response {
         // GraphQL-over-HTTP spec:
@@ -237,7 +263,7 @@ fn compute_status_code(format: ResponseFormat, response: &Response) -> http::Sta
                 }
             }
         }
-        Response::Executed(_) => {
+        Response::Executed(_) | Response::Incremental(_) | Response::Cached(_) => {
             // GraphQL-over-HTTP spec:
             //   If the GraphQL response contains the {data} entry and it is {null}, then the server SHOULD
             //   reply with a 2xx status code and it is RECOMMENDED it replies with 200 status code.
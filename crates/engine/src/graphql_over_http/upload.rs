@@ -0,0 +1,252 @@
+use std::{collections::BTreeMap, convert::Infallible, fmt::Write as _};
+
+use bytes::{BufMut as _, Bytes, BytesMut};
+use fxhash::FxHashMap;
+use operation::BatchRequest;
+use serde_json::Value;
+
+// Files are referenced in the variables by a placeholder string until they're sent to a subgraph.
+// The GraphQL multipart request spec uses null instead, but it wouldn't pass the validation of
+// non-null `Upload!` variables. Placeholders include a random nonce generated for each request,
+// so only the values at the paths of the `map` field can ever be resolved to a file.
+const PLACEHOLDER_PREFIX: &str = "grafbase-file-upload";
+
+/// Files sent with a multipart request following the [GraphQL multipart request spec][1].
+///
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+#[derive(Default)]
+pub(crate) struct FileUploads {
+    /// `grafbase-file-upload:<nonce>:`, the file index follows it.
+    placeholder_prefix: String,
+    files: Vec<FileUpload>,
+}
+
+struct FileUpload {
+    filename: Option<String>,
+    content_type: Option<String>,
+    bytes: Bytes,
+}
+
+impl FileUploads {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Index of the file if the value is one of our placeholders.
+    fn file_index(&self, value: &str) -> Option<usize> {
+        if self.files.is_empty() {
+            return None;
+        }
+        value
+            .strip_prefix(self.placeholder_prefix.as_str())?
+            .parse::<usize>()
+            .ok()
+            .filter(|index| *index < self.files.len())
+    }
+
+    /// Indices of all the files referenced within the value.
+    pub fn referenced_files(&self, value: &Value) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut stack = vec![value];
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::String(value) => indices.extend(self.file_index(value)),
+                Value::Array(items) => stack.extend(items),
+                Value::Object(fields) => stack.extend(fields.values()),
+                Value::Null | Value::Bool(_) | Value::Number(_) => {}
+            }
+        }
+        indices
+    }
+
+    /// Builds a multipart subgraph request if it references any file. The placeholders are
+    /// replaced by null and listed in the map as the spec requires.
+    pub fn encode_subgraph_request(&self, mut request: Value) -> Option<(http::HeaderValue, Bytes)> {
+        let mut paths_by_file = BTreeMap::<usize, Vec<String>>::new();
+        let mut path = Vec::new();
+        self.replace_placeholders(&mut request, &mut path, &mut paths_by_file);
+        if paths_by_file.is_empty() {
+            return None;
+        }
+
+        let boundary = format!("grafbase-{:032x}", rand::random::<u128>());
+        let mut body = BytesMut::new();
+        let mut map = serde_json::Map::new();
+
+        write_part_headers(&mut body, &boundary, "operations", None, "application/json");
+        body.put_slice(&serde_json::to_vec(&request).ok()?);
+
+        for (part, (_, paths)) in paths_by_file.iter().enumerate() {
+            map.insert(part.to_string(), paths.clone().into());
+        }
+        write_part_headers(&mut body, &boundary, "map", None, "application/json");
+        body.put_slice(&serde_json::to_vec(&map).ok()?);
+
+        for (part, index) in paths_by_file.keys().enumerate() {
+            let file = &self.files[*index];
+            let name = part.to_string();
+            write_part_headers(
+                &mut body,
+                &boundary,
+                &name,
+                Some(file.filename.as_deref().unwrap_or(&name)),
+                file.content_type.as_deref().unwrap_or("application/octet-stream"),
+            );
+            body.put_slice(&file.bytes);
+        }
+        write!(body, "\r\n--{boundary}--\r\n").ok()?;
+
+        let content_type = http::HeaderValue::from_str(&format!("multipart/form-data; boundary={boundary}")).ok()?;
+        Some((content_type, body.freeze()))
+    }
+
+    fn replace_placeholders(
+        &self,
+        value: &mut Value,
+        path: &mut Vec<String>,
+        paths_by_file: &mut BTreeMap<usize, Vec<String>>,
+    ) {
+        match value {
+            Value::String(string) => {
+                if let Some(index) = self.file_index(string) {
+                    paths_by_file.entry(index).or_default().push(path.join("."));
+                    *value = Value::Null;
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter_mut().enumerate() {
+                    path.push(i.to_string());
+                    self.replace_placeholders(item, path, paths_by_file);
+                    path.pop();
+                }
+            }
+            Value::Object(fields) => {
+                for (key, field) in fields.iter_mut() {
+                    path.push(key.clone());
+                    self.replace_placeholders(field, path, paths_by_file);
+                    path.pop();
+                }
+            }
+            Value::Null | Value::Bool(_) | Value::Number(_) => {}
+        }
+    }
+}
+
+fn write_part_headers(body: &mut BytesMut, boundary: &str, name: &str, filename: Option<&str>, content_type: &str) {
+    if !body.is_empty() {
+        body.put_slice(b"\r\n");
+    }
+    write!(body, "--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"").ok();
+    if let Some(filename) = filename {
+        let filename = filename.replace(['"', '\r', '\n'], "_");
+        write!(body, "; filename=\"{filename}\"").ok();
+    }
+    write!(body, "\r\nContent-Type: {content_type}\r\n\r\n").ok();
+}
+
+/// Parses a `multipart/form-data` request with the `operations` and `map` fields followed by the
+/// files.
+pub(crate) async fn parse_multipart_request(
+    boundary: &str,
+    body: Bytes,
+) -> Result<(BatchRequest, FileUploads), String> {
+    let stream = futures_util::stream::once(async move { Ok::<_, Infallible>(body) });
+    let mut multipart = multer::Multipart::new(stream, boundary);
+
+    let mut operations = None;
+    let mut map = None;
+    let mut files = FxHashMap::default();
+
+    while let Some(field) = multipart.next_field().await.map_err(|err| err.to_string())? {
+        let name = field.name().unwrap_or_default().to_string();
+        match name.as_str() {
+            "operations" => {
+                let bytes = field.bytes().await.map_err(|err| err.to_string())?;
+                let value: Value =
+                    serde_json::from_slice(&bytes).map_err(|err| format!("Invalid 'operations' field: {err}"))?;
+                operations = Some(value);
+            }
+            "map" => {
+                let bytes = field.bytes().await.map_err(|err| err.to_string())?;
+                let value: BTreeMap<String, Vec<String>> =
+                    serde_json::from_slice(&bytes).map_err(|err| format!("Invalid 'map' field: {err}"))?;
+                map = Some(value);
+            }
+            _ => {
+                let filename = field.file_name().map(str::to_string);
+                let content_type = field.content_type().map(ToString::to_string);
+                let bytes = field.bytes().await.map_err(|err| err.to_string())?;
+                files.insert(
+                    name,
+                    FileUpload {
+                        filename,
+                        content_type,
+                        bytes,
+                    },
+                );
+            }
+        }
+    }
+
+    let mut operations = operations.ok_or("Missing 'operations' field")?;
+    let map = map.ok_or("Missing 'map' field")?;
+
+    let mut uploads = FileUploads {
+        placeholder_prefix: format!("{PLACEHOLDER_PREFIX}:{:032x}:", rand::random::<u128>()),
+        files: Vec::new(),
+    };
+    for (name, paths) in map {
+        let file = files
+            .remove(&name)
+            .ok_or_else(|| format!("Missing file '{name}' referenced in the 'map' field"))?;
+        let placeholder = format!("{}{}", uploads.placeholder_prefix, uploads.files.len());
+        for path in paths {
+            insert_placeholder(&mut operations, &path, &placeholder)?;
+        }
+        uploads.files.push(file);
+    }
+
+    let request = serde_json::from_value(operations).map_err(|err| format!("Invalid 'operations' field: {err}"))?;
+
+    Ok((request, uploads))
+}
+
+fn insert_placeholder(operations: &mut Value, path: &str, placeholder: &str) -> Result<(), String> {
+    let invalid = || format!("Invalid file path '{path}' in the 'map' field");
+    let mut segments = path.split('.');
+    let mut value = operations;
+
+    // Batch requests are prefixed with the index of the operation.
+    if let Value::Array(requests) = value {
+        value = segments
+            .next()
+            .and_then(|index| index.parse::<usize>().ok())
+            .and_then(|index| requests.get_mut(index))
+            .ok_or_else(invalid)?;
+    }
+
+    if segments.next() != Some("variables") {
+        return Err(format!(
+            "File path '{path}' in the 'map' field must point to a variable"
+        ));
+    }
+    value = value.get_mut("variables").ok_or_else(invalid)?;
+
+    for segment in segments {
+        value = match value {
+            Value::Object(fields) => fields.get_mut(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|index| items.get_mut(index)),
+            _ => None,
+        }
+        .ok_or_else(invalid)?;
+    }
+
+    if !value.is_null() {
+        return Err(format!(
+            "File path '{path}' in the 'map' field must point to a null value"
+        ));
+    }
+    *value = Value::String(placeholder.to_string());
+
+    Ok(())
+}
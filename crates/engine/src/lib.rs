@@ -15,9 +15,11 @@ pub mod websocket;
 
 pub use engine::{ContractAwareEngine, Engine, RequestExtensions, Runtime, WebsocketSession, mcp};
 pub use error::{ErrorCode, ErrorResponse, GraphqlError};
+pub use execution::subgraph_probe_headers;
 pub use extension::*;
 pub use graphql_over_http::{Body, ResponseFormat, TelemetryExtension};
 pub use prepare::cached::CachedOperation;
+pub use query_solver::{UnsatisfiableField, check_satisfiability};
 pub use schema::Schema;
 
 pub fn http_error_response(
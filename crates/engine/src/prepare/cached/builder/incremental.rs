@@ -0,0 +1,120 @@
+use fxhash::FxHashMap;
+use operation::DeferDirectiveRecord;
+use query_solver::{
+    Edge, Node,
+    petgraph::{graph::NodeIndex, visit::EdgeRef as _},
+};
+
+use crate::prepare::{
+    DeferredFragmentId, DeferredFragmentRecord, DeferredResponseFieldRecord, QueryPlan, ResponseFieldPosition,
+    StreamedResponseFieldRecord,
+    cached::builder::{Solver, query_partition::NodeMap},
+};
+
+impl Solver<'_> {
+    /// Keeps track of the `@defer` fragments each query partition belongs to and of the response
+    /// fields starting a deferred fragment or being streamed. The query solver already split the
+    /// deferred fields into their own query partitions.
+    pub(super) fn populate_incremental_delivery_after_partition_generation(&mut self, map: &NodeMap) {
+        let query_plan = &mut self.output.query_plan;
+        query_plan.partition_deferred_fragment_id = vec![None; query_plan.partitions.len()];
+        if !self
+            .solution
+            .fields
+            .iter()
+            .any(|field| field.defer.is_some() || field.stream.is_some())
+        {
+            return;
+        }
+
+        let node_to_partition_id = map
+            .query_partition_to_node
+            .iter()
+            .map(|(id, node_ix)| (*node_ix, *id))
+            .collect::<FxHashMap<_, _>>();
+        let mut fragments = FxHashMap::<DeferredFragmentRecord, DeferredFragmentId>::default();
+        let mut get_or_insert_fragment = |query_plan: &mut QueryPlan, directive: DeferDirectiveRecord, parent_id| {
+            let record = DeferredFragmentRecord { directive, parent_id };
+            *fragments.entry(record).or_insert_with(|| {
+                query_plan.deferred_fragments.push(record);
+                DeferredFragmentId::from(query_plan.deferred_fragments.len() - 1)
+            })
+        };
+
+        // (node, depth of its fields, deferred fragment)
+        let mut stack: Vec<(NodeIndex, u16, Option<DeferredFragmentId>)> = vec![(self.solution.root_node_id, 0, None)];
+        while let Some((node_ix, depth, fragment_id)) = stack.pop() {
+            for edge in self.solution.graph.edges(node_ix) {
+                match edge.weight() {
+                    Edge::QueryPartition => {
+                        // All fields of a deferred partition share the same @defer, other fields
+                        // would have been moved into a different partition.
+                        let mut defers =
+                            self.solution.graph.neighbors(edge.target()).filter_map(|field_ix| {
+                                match self.solution.graph[field_ix] {
+                                    Node::Field(node) if self.solution[node.id].query_position.is_some() => {
+                                        Some(self.solution[node.id].defer)
+                                    }
+                                    _ => None,
+                                }
+                            });
+                        let partition_defer = defers
+                            .next()
+                            .flatten()
+                            .filter(|defer| defers.all(|other| other == Some(*defer)));
+                        let partition_fragment_id = match partition_defer {
+                            Some(defer) => Some(get_or_insert_fragment(query_plan, defer, fragment_id)),
+                            None => fragment_id,
+                        };
+                        if let Some(id) = node_to_partition_id.get(&edge.target()) {
+                            query_plan.partition_deferred_fragment_id[usize::from(*id)] = partition_fragment_id;
+                        }
+                        stack.push((edge.target(), depth, fragment_id));
+                    }
+                    Edge::Field => {
+                        let Node::Field(node) = self.solution.graph[edge.target()] else {
+                            continue;
+                        };
+                        let field = &self.solution[node.id];
+                        let Some(query_position) = field.query_position else {
+                            stack.push((edge.target(), depth + 1, fragment_id));
+                            continue;
+                        };
+                        let position = ResponseFieldPosition { depth, query_position };
+                        let field_fragment_id = match field.defer {
+                            Some(defer) => {
+                                let id = get_or_insert_fragment(query_plan, defer, fragment_id);
+                                query_plan.deferred_response_fields.push(DeferredResponseFieldRecord {
+                                    position,
+                                    fragment_id: id,
+                                });
+                                Some(id)
+                            }
+                            None => fragment_id,
+                        };
+                        if let Some(directive) = field.stream {
+                            query_plan
+                                .streamed_response_fields
+                                .push(StreamedResponseFieldRecord { position, directive });
+                        }
+                        stack.push((edge.target(), depth + 1, field_fragment_id));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        query_plan
+            .deferred_response_fields
+            .sort_unstable_by(|a, b| a.position.cmp(&b.position));
+        query_plan
+            .deferred_response_fields
+            .dedup_by(|a, b| a.position == b.position);
+        query_plan
+            .streamed_response_fields
+            .sort_unstable_by(|a, b| a.position.cmp(&b.position));
+        query_plan
+            .streamed_response_fields
+            .dedup_by(|a, b| a.position == b.position);
+    }
+}
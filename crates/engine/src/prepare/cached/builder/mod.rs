@@ -1,3 +1,4 @@
+mod incremental;
 mod modifiers;
 mod mutation_order;
 mod query_partition;
@@ -7,7 +8,7 @@ mod shapes;
 
 use operation::Operation;
 use query_solver::QuerySolution;
-use schema::Schema;
+use schema::{EnabledOverrideLabels, Schema};
 
 use super::*;
 
@@ -22,8 +23,9 @@ impl<'a> Solver<'a> {
         schema: &'a Schema,
         document: OperationDocument<'_>,
         mut operation: Operation,
+        override_labels: &EnabledOverrideLabels,
     ) -> SolveResult<Self> {
-        let mut solution = query_solver::solve(schema, &mut operation)?;
+        let mut solution = query_solver::solve(schema, &mut operation, override_labels)?;
         Ok(Self {
             schema,
             output: CachedOperation {
@@ -49,6 +51,11 @@ impl<'a> Solver<'a> {
                     response_modifier_definitions: Vec::new(),
                     lookup_fields: Vec::new(),
                     lookup_field_output_id: Vec::new(),
+                    deferred_fragments: Vec::new(),
+                    partition_deferred_fragment_id: Vec::new(),
+                    deferred_response_fields: Vec::new(),
+                    streamed_response_fields: Vec::new(),
+                    solver_cost: solution.cost(),
                 },
                 operation,
                 shapes: Shapes::default(),
@@ -62,6 +69,8 @@ impl<'a> Solver<'a> {
 
         self.generate_mutation_partition_order_after_partition_generation(&node_map)?;
 
+        self.populate_incremental_delivery_after_partition_generation(&node_map);
+
         self.populate_requirements_after_partition_generation(&node_map)?;
 
         self.populate_modifiers_after_partition_generation(&node_map, &mut response_object_set_map)?;
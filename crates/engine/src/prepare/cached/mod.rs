@@ -8,7 +8,7 @@ mod shape;
 use grafbase_telemetry::graphql::OperationType;
 use id_newtypes::IdRange;
 use operation::{Operation, OperationContext};
-use schema::Schema;
+use schema::{EnabledOverrideLabels, Schema};
 use walker::{Iter, Walk};
 
 pub(crate) use document::*;
@@ -34,8 +34,9 @@ pub(crate) fn solve(
     schema: &Schema,
     document: OperationDocument<'_>,
     operation: Operation,
+    override_labels: &EnabledOverrideLabels,
 ) -> SolveResult<CachedOperation> {
-    builder::Solver::solve(schema, document, operation)?.into_cached_operation()
+    builder::Solver::solve(schema, document, operation, override_labels)?.into_cached_operation()
 }
 
 #[derive(Clone, Copy)]
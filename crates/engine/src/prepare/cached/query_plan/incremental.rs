@@ -0,0 +1,32 @@
+use operation::{DeferDirectiveRecord, QueryPosition, StreamDirectiveRecord};
+
+/// A `@defer` fragment of the query. Nested fragments keep track of their parent as they can
+/// only be delivered after it.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct DeferredFragmentRecord {
+    pub directive: DeferDirectiveRecord,
+    pub parent_id: Option<DeferredFragmentId>,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, serde::Serialize, serde::Deserialize, id_derives::Id)]
+pub(crate) struct DeferredFragmentId(u16);
+
+/// Identifies a field within the response. Query positions are only unique within a given depth,
+/// the depth of a response object being the number of fields between it and the root object.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ResponseFieldPosition {
+    pub depth: u16,
+    pub query_position: QueryPosition,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct StreamedResponseFieldRecord {
+    pub position: ResponseFieldPosition,
+    pub directive: StreamDirectiveRecord,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct DeferredResponseFieldRecord {
+    pub position: ResponseFieldPosition,
+    pub fragment_id: DeferredFragmentId,
+}
@@ -1,5 +1,6 @@
 mod field;
 mod generated;
+mod incremental;
 mod modifier;
 mod prelude;
 mod query_partition;
@@ -11,6 +12,7 @@ use extension_catalog::ExtensionId;
 pub(crate) use field::*;
 pub(crate) use generated::*;
 use id_newtypes::{BitSet, IdRange};
+pub(crate) use incremental::*;
 pub(crate) use modifier::*;
 use query_solver::TypeConditionSharedVecId;
 pub(crate) use required_field_set::*;
@@ -72,6 +74,19 @@ pub(crate) struct QueryPlan {
     pub query_modifiers: QueryModifiers,
     pub response_modifier_definitions: Vec<ResponseModifierDefinitionRecord>,
 
+    // Incremental delivery, @defer & @stream
+    #[indexed_by(DeferredFragmentId)]
+    pub deferred_fragments: Vec<DeferredFragmentRecord>,
+    /// Deferred fragment of each query partition, if any.
+    pub partition_deferred_fragment_id: Vec<Option<DeferredFragmentId>>,
+    /// Fields starting a deferred fragment within their selection set, sorted by position.
+    pub deferred_response_fields: Vec<DeferredResponseFieldRecord>,
+    /// Sorted by position.
+    pub streamed_response_fields: Vec<StreamedResponseFieldRecord>,
+
+    /// Cost of the query solver solution, only used to explain the plan.
+    pub solver_cost: u32,
+
     // Refs are used to replace a Vec<XId> with a IdRange<XRefId>. IdRange<XRefId> will at most have a size
     // of 2 * u32 while Vec<XId> is 3 words long. And we store everything in a single Vec.
     #[indexed_by(FieldShapeRefId)]
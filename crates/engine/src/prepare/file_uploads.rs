@@ -0,0 +1,74 @@
+use fxhash::FxHashMap;
+use operation::InputValueContext;
+use schema::GraphqlSubgraphId;
+use walker::Walk;
+
+use crate::{
+    ErrorCode, Runtime,
+    resolver::Resolver,
+    response::{GraphqlError, Response},
+};
+
+use super::{PrepareContext, PreparedOperation};
+
+impl<R: Runtime> PrepareContext<'_, R> {
+    /// Uploaded files are forwarded as is to a single subgraph request, so they can only be used
+    /// in the root field arguments of a single subgraph.
+    pub(super) fn check_file_uploads(&self, operation: &PreparedOperation) -> Result<(), Response> {
+        let file_uploads = &self.request_context.file_uploads;
+        if file_uploads.is_empty() {
+            return Ok(());
+        }
+
+        let ctx = InputValueContext {
+            schema: self.schema(),
+            query_input_values: &operation.cached.operation.query_input_values,
+            variables: &operation.variables,
+        };
+        let mut subgraph_by_file = FxHashMap::<usize, GraphqlSubgraphId>::default();
+
+        for plan in &operation.plan.plans {
+            let (subgraph_id, variables, is_entity) = match &plan.resolver {
+                Resolver::Graphql(resolver) => (resolver.subgraph_id, &resolver.subgraph_operation.variables, false),
+                Resolver::FederationEntity(resolver) => {
+                    (resolver.subgraph_id, &resolver.subgraph_operation.variables, true)
+                }
+                _ => continue,
+            };
+
+            for variable in variables {
+                let Some(value) = variable.value.as_schema_or_query_input_value() else {
+                    continue;
+                };
+                let Ok(value) = serde_json::to_value(value.walk(ctx)) else {
+                    continue;
+                };
+
+                for file in file_uploads.referenced_files(&value) {
+                    if is_entity {
+                        return Err(self.file_upload_error(
+                            "File uploads are only supported in the arguments of root fields".to_string(),
+                        ));
+                    }
+                    let first_subgraph_id = *subgraph_by_file.entry(file).or_insert(subgraph_id);
+                    if first_subgraph_id != subgraph_id {
+                        return Err(self.file_upload_error(format!(
+                            "An uploaded file cannot be sent to multiple subgraphs, it is used by both '{}' and '{}'",
+                            first_subgraph_id.walk(self.schema()).name(),
+                            subgraph_id.walk(self.schema()).name(),
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn file_upload_error(&self, message: String) -> Response {
+        Response::request_error(
+            self.schema().config.error_code_mapping.clone(),
+            [GraphqlError::new(message, ErrorCode::BadRequest)],
+        )
+    }
+}
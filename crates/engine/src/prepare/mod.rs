@@ -1,6 +1,8 @@
 pub(crate) mod cached;
 mod context;
+mod file_uploads;
 mod operation_plan;
+mod rate_limit;
 mod trusted_documents;
 mod with_cache;
 mod without_cache;
@@ -16,7 +18,7 @@ pub(crate) use operation_plan::*;
 use ::operation::{ComplexityCost, Request, Variables};
 use futures::FutureExt;
 use grafbase_telemetry::graphql::GraphqlOperationAttributes;
-use runtime::operation_cache::OperationCache;
+use runtime::{operation_cache::OperationCache, rate_limiting::RateLimitBudget};
 use tracing::{Instrument, info_span};
 
 use crate::{
@@ -29,7 +31,7 @@ impl<R: Runtime> Engine<R> {
     pub(crate) fn warm_operation(&self, document: OperationDocument<'_>) -> Result<CachedOperation, String> {
         let operation = Operation::parse(&self.schema, document.operation_name(), &document.content)
             .map_err(|errors| errors.items.into_iter().next().unwrap().message)?;
-        crate::prepare::solve(&self.schema, document, operation).map_err(|err| err.to_string())
+        crate::prepare::solve(&self.schema, document, operation, &Default::default()).map_err(|err| err.to_string())
     }
 }
 
@@ -40,11 +42,21 @@ impl<R: Runtime> PrepareContext<'_, R> {
         let duration = self.executed_operation_builder.track_prepare();
 
         match result {
-            Ok(operation) => {
+            Ok(mut operation) => {
                 self.metrics()
                     .record_successful_preparation_duration(operation.attributes(), duration);
 
-                Ok(operation)
+                if let Err(response) = self.check_file_uploads(&operation) {
+                    return Err(response.with_operation_attributes(operation.attributes()));
+                }
+
+                match self.apply_rate_limits(&operation).await {
+                    Ok(budget) => {
+                        operation.rate_limit_budget = budget;
+                        Ok(operation)
+                    }
+                    Err(response) => Err(response.with_operation_attributes(operation.attributes())),
+                }
             }
             Err(response) => {
                 self.metrics()
@@ -75,7 +87,7 @@ impl<R: Runtime> PrepareContext<'_, R> {
                 }
             };
 
-            let cache_key = CacheKey::document(self.schema(), &extracted.key);
+            let cache_key = CacheKey::document(self.schema(), &extracted.key, &self.request_context.override_labels);
             if let Some(operation) = self.operation_cache().get(&cache_key).await {
                 self.executed_operation_builder.cached_plan(true);
                 self.metrics().record_operation_cache_hit();
@@ -122,6 +134,8 @@ pub(crate) struct PreparedOperation {
     pub plan: OperationPlan,
     pub variables: Variables,
     pub complexity_cost: Option<ComplexityCost>,
+    /// Complexity budget left to the client after this operation.
+    pub rate_limit_budget: Option<RateLimitBudget>,
 }
 
 impl PreparedOperation {
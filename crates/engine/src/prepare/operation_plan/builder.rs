@@ -6,16 +6,16 @@ use walker::Walk;
 use crate::{
     Runtime,
     prepare::{
-        CachedOperation, CachedOperationContext, DataOrLookupFieldId, PlanError, PrepareContext, QueryPartition,
-        QueryPartitionId, RequiredFieldSet, RequiredFieldSetRecord, ResponseModifierDefinition, ResponseModifierRule,
-        ResponseModifierRuleTarget,
+        CachedOperation, CachedOperationContext, DataOrLookupFieldId, DeferredFragmentId, PlanError, PrepareContext,
+        QueryPartition, QueryPartitionId, RequiredFieldSet, RequiredFieldSetRecord, ResponseModifierDefinition,
+        ResponseModifierRule, ResponseModifierRuleTarget,
     },
     resolver::Resolver,
 };
 
 use super::{
-    ExecutableId, OperationPlan, OperationPlanContext, PlanId, PlanQueryPartition, PlanRecord, PlanResult,
-    QueryModifications, ResponseModifierId, ResponseModifierRecord, ResponseModifierTargetRecord,
+    ExecutableId, IncrementalDeliveryPlan, OperationPlan, OperationPlanContext, PlanId, PlanQueryPartition, PlanRecord,
+    PlanResult, QueryModifications, ResponseModifierId, ResponseModifierRecord, ResponseModifierTargetRecord,
 };
 
 impl OperationPlan {
@@ -35,6 +35,7 @@ impl OperationPlan {
                 query_modifications,
                 plans: Vec::with_capacity(cached.query_plan.partitions.len()),
                 response_modifiers: Vec::with_capacity(cached.query_plan.response_modifier_definitions.len()),
+                incremental_delivery: Default::default(),
             },
             dependencies: Vec::new(),
             partition_to_plan: vec![None; cached.query_plan.partitions.len()],
@@ -121,9 +122,115 @@ impl<'op, R: Runtime> Builder<'op, '_, R> {
             self.operation_plan[next_id].parent_count += 1;
         }
 
+        self.plan_incremental_delivery();
+
         Ok(self.operation_plan)
     }
 
+    fn plan_incremental_delivery(&mut self) {
+        let fragments = &self.operation.query_plan.deferred_fragments;
+        if fragments.is_empty() {
+            return;
+        }
+        let modifications = &self.operation_plan.query_modifications;
+
+        // Closest enclosing fragment, itself included, with a `if` argument set to true.
+        let mut fragment_to_included: Vec<Option<DeferredFragmentId>> = Vec::with_capacity(fragments.len());
+        for (i, fragment) in fragments.iter().enumerate() {
+            let id = DeferredFragmentId::from(i);
+            let included = if modifications.included_deferred_fragments[id] {
+                Some(id)
+            } else {
+                fragment
+                    .parent_id
+                    .and_then(|parent_id| fragment_to_included[usize::from(parent_id)])
+            };
+            fragment_to_included.push(included);
+        }
+        let included_parent_id = |id: DeferredFragmentId| {
+            fragments[usize::from(id)]
+                .parent_id
+                .and_then(|parent_id| fragment_to_included[usize::from(parent_id)])
+        };
+        let ancestors_or_self = |mut current: Option<DeferredFragmentId>| {
+            let mut ancestors = vec![current];
+            while let Some(id) = current {
+                current = included_parent_id(id);
+                ancestors.push(current);
+            }
+            ancestors
+        };
+
+        let mut plan_fragment_ids = self
+            .operation_plan
+            .plans
+            .iter()
+            .map(|plan| {
+                self.operation.query_plan.partition_deferred_fragment_id[usize::from(plan.query_partition_id)]
+                    .and_then(|id| fragment_to_included[usize::from(id)])
+            })
+            .collect::<Vec<_>>();
+
+        // A plan can only start once all of its parents are finished. So a fragment can't be
+        // delivered before those parents either. We move the parent plans up into a common
+        // ancestor fragment until it's the case.
+        let mut descendant_plan_ids = Vec::new();
+        let mut stack = Vec::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (i, plan) in self.operation_plan.plans.iter().enumerate() {
+                descendant_plan_ids.clear();
+                stack.extend(plan.children_ids.iter().copied());
+                while let Some(id) = stack.pop() {
+                    match id {
+                        ExecutableId::Plan(id) => descendant_plan_ids.push(id),
+                        ExecutableId::ResponseModifier(id) => {
+                            stack.extend(self.operation_plan[id].children_ids.iter().copied())
+                        }
+                    }
+                }
+                for child_id in descendant_plan_ids.iter().copied() {
+                    let child_fragment_ancestors = ancestors_or_self(plan_fragment_ids[usize::from(child_id)]);
+                    let current = plan_fragment_ids[i];
+                    if !child_fragment_ancestors.contains(&current) {
+                        let current_ancestors = ancestors_or_self(current);
+                        plan_fragment_ids[i] = child_fragment_ancestors
+                            .into_iter()
+                            .find(|id| current_ancestors.contains(id))
+                            .flatten();
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        let mut is_delivered = vec![false; fragments.len()];
+        for id in plan_fragment_ids.iter().copied().flatten() {
+            is_delivered[usize::from(id)] = true;
+        }
+        let mut deferred_fragment_to_delivered: Vec<Option<DeferredFragmentId>> = Vec::with_capacity(fragments.len());
+        let mut delivered_fragments = Vec::new();
+        for (i, fragment) in fragments.iter().enumerate() {
+            let id = DeferredFragmentId::from(i);
+            let parent_id = fragment
+                .parent_id
+                .and_then(|parent_id| deferred_fragment_to_delivered[usize::from(parent_id)]);
+            if is_delivered[i] {
+                delivered_fragments.push((id, parent_id));
+                deferred_fragment_to_delivered.push(Some(id));
+            } else {
+                deferred_fragment_to_delivered.push(parent_id);
+            }
+        }
+
+        self.operation_plan.incremental_delivery = IncrementalDeliveryPlan {
+            delivered_fragments,
+            deferred_fragment_to_delivered,
+            plan_deferred_fragment_ids: plan_fragment_ids,
+        };
+    }
+
     fn generate_response_modifier(&mut self, definition: ResponseModifierDefinition<'op>) -> PlanResult<()> {
         let mut impacted_fields = Vec::new();
         for field in definition.impacted_fields() {
@@ -0,0 +1,25 @@
+use crate::prepare::{DeferredFragmentId, PlanId};
+
+/// How the response is split into multiple payloads with `@defer`. Deferred fragments without
+/// any plan of their own are delivered with their parent, as there would be nothing to wait for.
+#[derive(Default)]
+pub(crate) struct IncrementalDeliveryPlan {
+    /// Deferred fragments delivered in subsequent payloads with their closest delivered parent.
+    /// Parents always come first.
+    pub delivered_fragments: Vec<(DeferredFragmentId, Option<DeferredFragmentId>)>,
+    /// Closest enclosing fragment, itself included, which is delivered in a subsequent payload.
+    pub deferred_fragment_to_delivered: Vec<Option<DeferredFragmentId>>,
+    /// Deferred fragment each plan belongs to, `None` being the initial payload. A plan always
+    /// belongs to the same fragment, or a descendant of it, as all of its parents.
+    pub plan_deferred_fragment_ids: Vec<Option<DeferredFragmentId>>,
+}
+
+impl IncrementalDeliveryPlan {
+    pub(crate) fn plan_deferred_fragment_id(&self, id: PlanId) -> Option<DeferredFragmentId> {
+        self.plan_deferred_fragment_ids.get(usize::from(id)).copied().flatten()
+    }
+
+    pub(crate) fn delivered_fragment_id(&self, id: DeferredFragmentId) -> Option<DeferredFragmentId> {
+        self.deferred_fragment_to_delivered[usize::from(id)]
+    }
+}
@@ -1,6 +1,7 @@
 mod executable;
 mod field;
 mod generated;
+mod incremental;
 mod plan;
 mod prelude;
 mod query_partition;
@@ -16,6 +17,7 @@ use super::QueryModifications;
 
 pub(crate) use field::*;
 pub(crate) use generated::*;
+pub(crate) use incremental::*;
 pub(crate) use query_partition::*;
 pub(crate) use selection_set::*;
 
@@ -70,4 +72,14 @@ pub(crate) struct OperationPlan {
     pub plans: Vec<PlanRecord>,
     #[indexed_by(ResponseModifierId)]
     pub response_modifiers: Vec<ResponseModifierRecord>,
+    pub incremental_delivery: IncrementalDeliveryPlan,
+}
+
+impl OperationPlan {
+    /// Whether the response should be split into multiple payloads with `@defer` or `@stream`
+    /// when the client accepts a streaming response.
+    pub(crate) fn has_incremental_delivery(&self) -> bool {
+        !self.incremental_delivery.delivered_fragments.is_empty()
+            || !self.query_modifications.included_streamed_response_fields.is_empty()
+    }
 }
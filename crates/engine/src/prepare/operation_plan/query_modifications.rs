@@ -3,7 +3,7 @@ use std::{num::NonZero, ops::Deref, sync::Arc};
 use extension_catalog::ExtensionId;
 use futures::future::FutureExt;
 use id_newtypes::{BitSet, IdRange, IdToMany};
-use operation::{InputValueContext, Variables};
+use operation::{DeferDirectiveRecord, InputValueContext, StreamDirectiveRecord, Variables};
 use runtime::extension::{
     AuthorizationDecisions, AuthorizationExtension as _, AuthorizeQuery, QueryAuthorizationDecisions, QueryElement,
 };
@@ -12,13 +12,13 @@ use serde::Deserialize;
 use walker::Walk;
 
 use crate::{
-    EngineRequestContext, Runtime,
+    EngineRequestContext, ErrorCode, Runtime,
     execution::find_matching_denied_header,
     prepare::{
-        CachedOperation, CachedOperationContext, ConcreteShapeId, DataFieldId, Derive, FieldShapeId, GraphqlError,
-        PartitionField, PrepareContext, QueryModifierId, QueryModifierRecord, QueryModifierRule, QueryModifierTarget,
-        QueryOrStaticExtensionDirectiveArugmentsView, RequiredFieldSetRecord, TypenameFieldId,
-        create_extension_directive_query_view,
+        CachedOperation, CachedOperationContext, ConcreteShapeId, DataFieldId, DeferredFragmentId, Derive,
+        FieldShapeId, GraphqlError, PartitionField, PrepareContext, QueryModifierId, QueryModifierRecord,
+        QueryModifierRule, QueryModifierTarget, QueryOrStaticExtensionDirectiveArugmentsView, RequiredFieldSetRecord,
+        ResponseFieldPosition, TypenameFieldId, create_extension_directive_query_view,
     },
 };
 
@@ -35,6 +35,17 @@ pub(crate) struct QueryModifications {
     pub field_shape_id_to_error_ids: IdToMany<FieldShapeId, QueryErrorId>,
     pub root_error_ids: Vec<QueryErrorId>,
     pub extension: ExtensionPreparedOperation,
+    /// `@defer` fragments whose `if` argument is true.
+    pub included_deferred_fragments: BitSet<DeferredFragmentId>,
+    pub deferred_fragment_labels: Vec<Option<String>>,
+    /// Response fields with an enabled `@stream`, sorted by position.
+    pub included_streamed_response_fields: Vec<IncludedStreamedResponseField>,
+}
+
+pub(crate) struct IncludedStreamedResponseField {
+    pub position: ResponseFieldPosition,
+    pub initial_count: usize,
+    pub label: Option<String>,
 }
 
 #[derive(Default)]
@@ -77,6 +88,9 @@ impl QueryModifications {
                 field_shape_id_to_error_ids: Default::default(),
                 root_error_ids: Vec::new(),
                 extension: Default::default(),
+                included_deferred_fragments: BitSet::with_capacity(cached.query_plan.deferred_fragments.len()),
+                deferred_fragment_labels: Vec::with_capacity(cached.query_plan.deferred_fragments.len()),
+                included_streamed_response_fields: Vec::new(),
             },
         }
         .build()
@@ -102,6 +116,7 @@ where
         // which don't need I/O. So no need to parallelize that today.
         let modifiers = &self.operation_ctx.cached.query_plan.query_modifiers;
         self.handle_native_modifiers(&modifiers[modifiers.native_ids]).await?;
+        self.handle_incremental_delivery_directives()?;
 
         if !modifiers.by_extension.is_empty() {
             self.handle_extensions().await?;
@@ -251,6 +266,8 @@ where
                         bool::deserialize(directive.condition.walk(self.input_value_ctx))
                             .expect("at this point we've already checked the argument type")
                     }
+                    // Handled separately, they never skip anything.
+                    operation::ExecutableDirectiveId::Defer(_) | operation::ExecutableDirectiveId::Stream(_) => false,
                 });
 
                 if is_skipped {
@@ -264,6 +281,60 @@ where
         Ok(())
     }
 
+    fn handle_incremental_delivery_directives(&mut self) -> PlanResult<()> {
+        let query_plan = &self.operation_ctx.cached.query_plan;
+        for (i, fragment) in query_plan.deferred_fragments.iter().enumerate() {
+            let DeferDirectiveRecord { label, condition } = fragment.directive;
+            let id = DeferredFragmentId::from(i);
+            let is_included = bool::deserialize(condition.walk(self.input_value_ctx))
+                .expect("at this point we've already checked the argument type");
+            self.modifications.included_deferred_fragments.set(id, is_included);
+            self.modifications
+                .deferred_fragment_labels
+                .push(label.and_then(|label| {
+                    Option::<String>::deserialize(label.walk(self.input_value_ctx))
+                        .expect("at this point we've already checked the argument type")
+                }));
+        }
+
+        for field in &query_plan.streamed_response_fields {
+            let StreamDirectiveRecord {
+                label,
+                initial_count,
+                condition,
+            } = field.directive;
+            if !bool::deserialize(condition.walk(self.input_value_ctx))
+                .expect("at this point we've already checked the argument type")
+            {
+                continue;
+            }
+            let initial_count = i32::deserialize(initial_count.walk(self.input_value_ctx))
+                .expect("at this point we've already checked the argument type");
+            // Literals are already rejected when binding the operation, only variables are left.
+            let Ok(initial_count) = usize::try_from(initial_count) else {
+                return Err(GraphqlError::new(
+                    format!(
+                        "Argument 'initialCount' of directive '@stream' must be non-negative, found {initial_count}."
+                    ),
+                    ErrorCode::OperationValidationError,
+                )
+                .into());
+            };
+            self.modifications
+                .included_streamed_response_fields
+                .push(IncludedStreamedResponseField {
+                    position: field.position,
+                    initial_count,
+                    label: label.and_then(|label| {
+                        Option::<String>::deserialize(label.walk(self.input_value_ctx))
+                            .expect("at this point we've already checked the argument type")
+                    }),
+                });
+        }
+
+        Ok(())
+    }
+
     fn finalize(self) -> QueryModifications {
         let Self {
             mut modifications,
@@ -0,0 +1,55 @@
+use runtime::rate_limiting::{self, RateLimitBudget, RateLimitKey};
+
+use crate::{
+    Runtime,
+    execution::{RequestRateLimitContext, errors},
+    response::Response,
+};
+
+use super::{PrepareContext, PreparedOperation};
+
+impl<R: Runtime> PrepareContext<'_, R> {
+    /// Applies the rate limit rules keyed on the client and consumes the complexity cost of the
+    /// operation from its budget.
+    pub(super) async fn apply_rate_limits(
+        &self,
+        operation: &PreparedOperation,
+    ) -> Result<Option<RateLimitBudget>, Response> {
+        let context = RequestRateLimitContext::new(
+            RateLimitKey::Operation,
+            self.request_context,
+            operation.cached.operation.attributes.name.original(),
+        );
+        let rate_limiter = self.runtime().rate_limiter();
+
+        match rate_limiter.limit(&context).await {
+            Ok(()) => (),
+            Err(rate_limiting::Error::ExceededCapacity { retry_after }) => {
+                return Err(errors::response::gateway_rate_limited(
+                    self.schema().config.error_code_mapping.clone(),
+                    retry_after,
+                ));
+            }
+            Err(err) => {
+                tracing::error!("Failed to apply the rate limit: {err}");
+            }
+        }
+
+        let Some(cost) = operation.complexity_cost else {
+            return Ok(None);
+        };
+
+        match rate_limiter.limit_complexity(&context, cost.0).await {
+            Ok(budget) => Ok(budget),
+            Err(rate_limiting::Error::ExceededBudget(budget)) => Err(errors::response::gateway_rate_limited(
+                self.schema().config.error_code_mapping.clone(),
+                Some(budget.reset),
+            )
+            .with_rate_limit_budget(budget)),
+            Err(err) => {
+                tracing::error!("Failed to apply the complexity rate limit: {err}");
+                Ok(None)
+            }
+        }
+    }
+}
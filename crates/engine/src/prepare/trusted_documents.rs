@@ -232,6 +232,7 @@ impl<'ctx, R: Runtime> PrepareContext<'ctx, R> {
                     client_name: Cow::Borrowed(client_name),
                     doc_id: doc_id.clone(),
                 },
+                &self.request_context.override_labels,
             );
 
             let trusted_doc_matches_inline_doc = match self.operation_cache().get(&cache_key).await {
@@ -92,6 +92,7 @@ impl<R: Runtime> PrepareContext<'_, R> {
             plan,
             variables,
             complexity_cost,
+            rate_limit_budget: None,
         })
     }
 }
@@ -104,7 +104,12 @@ impl<R: Runtime> PrepareContext<'_, R> {
         };
 
         let attributes = operation.attributes.clone();
-        let cached = match crate::prepare::solve(self.schema(), document, operation) {
+        let cached = match crate::prepare::solve(
+            self.schema(),
+            document,
+            operation,
+            &self.request_context.override_labels,
+        ) {
             Ok(plan) => plan,
             Err(err) => {
                 return Err(
@@ -142,6 +147,7 @@ impl<R: Runtime> PrepareContext<'_, R> {
             plan,
             variables,
             complexity_cost,
+            rate_limit_budget: None,
         })
     }
 }
@@ -4,7 +4,7 @@ use grafbase_telemetry::graphql::GraphqlResponseStatus;
 use headers::HeaderMapExt;
 use http::HeaderMap;
 use itertools::Itertools;
-use runtime::entity_cache::EntityCache;
+use runtime::entity_cache::{CacheTag, EntityCache};
 use serde_json::value::RawValue;
 use std::time::Duration;
 
@@ -45,6 +45,42 @@ pub(super) fn calculate_cache_ttl(
     Some(cache_ttl)
 }
 
+/// Subgraph response header with additional comma-separated tags for the entries written from
+/// that response.
+const CACHE_TAG_HEADER: &str = "cache-tag";
+
+/// Tags shared by all cache entries written from a subgraph response.
+pub(super) fn cache_tags(subgraph_name: &str, headers: &HeaderMap) -> Vec<CacheTag> {
+    let custom_tags = headers
+        .get_all(CACHE_TAG_HEADER)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| CacheTag::Custom(tag.to_string()));
+
+    std::iter::once(CacheTag::Subgraph(subgraph_name.to_string()))
+        .chain(custom_tags)
+        .collect()
+}
+
+/// Tags of a single entity, identified by its key fields within the representation.
+pub(super) fn entity_cache_tags(type_name: &str, key_fields: &[&str], representation: &RawValue) -> [CacheTag; 2] {
+    let key = match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(representation.get()) {
+        Ok(mut fields) => key_fields
+            .iter()
+            .filter_map(|name| fields.remove(*name).map(|value| (name.to_string(), value)))
+            .collect(),
+        Err(_) => serde_json::Map::new(),
+    };
+
+    [
+        CacheTag::EntityType(type_name.to_string()),
+        CacheTag::entity(type_name, &serde_json::Value::Object(key)),
+    ]
+}
+
 pub(super) async fn fetch_response<R: Runtime>(
     ctx: &mut SubgraphContext<'_, R>,
     subgraph_headers: &http::HeaderMap,
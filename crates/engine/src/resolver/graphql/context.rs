@@ -4,8 +4,12 @@ use grafbase_telemetry::{
     graphql::GraphqlResponseStatus,
     span::subgraph::{SubgraphGraphqlRequestSpan, SubgraphHttpRequestSpan, SubgraphRequestSpanBuilder},
 };
-use schema::GraphqlSubgraph;
-use std::{ops::Deref, time::Instant};
+use headers::HeaderMapExt;
+use schema::{GraphqlSubgraph, HeaderMergeStrategy, ResponseHeaderRule};
+use std::{
+    ops::Deref,
+    time::{Duration, Instant},
+};
 use tower::retry::budget::TpsBudget;
 use tracing::Span;
 use url::Url;
@@ -13,13 +17,19 @@ use url::Url;
 use grafbase_telemetry::{
     graphql::SubgraphResponseStatus,
     metrics::{
-        SubgraphCacheHitAttributes, SubgraphCacheMissAttributes, SubgraphInFlightRequestAttributes,
-        SubgraphRequestBodySizeAttributes, SubgraphRequestDurationAttributes, SubgraphRequestRetryAttributes,
-        SubgraphResponseBodySizeAttributes,
+        SubgraphCacheHitAttributes, SubgraphCacheMissAttributes, SubgraphCircuitBreakerAttributes,
+        SubgraphInFlightRequestAttributes, SubgraphRequestBodySizeAttributes, SubgraphRequestDurationAttributes,
+        SubgraphRequestRetryAttributes, SubgraphResponseBodySizeAttributes,
     },
 };
 
-use crate::{Engine, Runtime, execution::ExecutionContext, resolver::ResolverResult, response::ResponsePartBuilder};
+use crate::{
+    Engine, Runtime,
+    engine::circuit_breaker::StateChange,
+    execution::{ExecutionContext, find_matching_denied_header},
+    resolver::ResolverResult,
+    response::{PropagatedHeaders, ResponsePartBuilder},
+};
 
 #[derive(Clone)]
 pub(crate) struct SubgraphContext<'ctx, R: Runtime> {
@@ -32,6 +42,8 @@ pub(crate) struct SubgraphContext<'ctx, R: Runtime> {
     status: Option<SubgraphResponseStatus>,
     http_status_code: Option<http::StatusCode>,
     send_count: usize,
+    cache_max_age: Option<Duration>,
+    propagated_headers: PropagatedHeaders,
 }
 
 impl<'ctx, R: Runtime> Deref for SubgraphContext<'ctx, R> {
@@ -68,6 +80,8 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
             status: None,
             http_status_code: None,
             send_count: 0,
+            cache_max_age: None,
+            propagated_headers: PropagatedHeaders::default(),
         }
     }
 
@@ -91,9 +105,14 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
         self.retry_budget
     }
 
-    pub async fn finalize(self, response_part: ResponsePartBuilder<'ctx>) -> ResolverResult<'ctx> {
+    pub async fn finalize(self, mut response_part: ResponsePartBuilder<'ctx>) -> ResolverResult<'ctx> {
         let duration = self.start.elapsed();
 
+        if let Some(max_age) = self.cache_max_age {
+            response_part.restrict_cache_max_age(max_age);
+        }
+        response_part.propagate_headers(self.propagated_headers);
+
         if let Some(status) = self.status {
             self.span.record_graphql_response_status(status);
 
@@ -176,6 +195,25 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
         });
     }
 
+    pub(super) fn record_circuit_breaker_state_change(&self, StateChange { from, to }: StateChange) {
+        tracing::warn!(
+            "Circuit breaker of subgraph {} changed from {} to {}",
+            self.subgraph.name(),
+            from.as_str(),
+            to.as_str()
+        );
+
+        self.metrics()
+            .record_subgraph_circuit_breaker_transition(SubgraphCircuitBreakerAttributes {
+                name: self.subgraph.name().to_string(),
+                from: from.as_str(),
+                to: to.as_str(),
+            });
+
+        self.event_queue()
+            .push_circuit_breaker_state_change(self.subgraph.name(), from, to);
+    }
+
     pub(super) fn push_request_execution(&mut self, kind: RequestExecution) {
         self.executed_request_builder.push_execution(kind);
     }
@@ -188,6 +226,66 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
             },
             response.body().len(),
         );
+
+        if self.schema().config.response_caching.enabled
+            && let Some(max_age) = subgraph_cache_max_age(response.headers())
+        {
+            self.cache_max_age = Some(self.cache_max_age.map_or(max_age, |current| current.min(max_age)));
+        }
+    }
+
+    pub(super) fn has_response_header_rules(&self) -> bool {
+        !self.schema().config.response_header_rules.is_empty() || !self.subgraph.config.response_header_rules.is_empty()
+    }
+
+    /// Keeps the subgraph response headers matching the global and subgraph response header
+    /// rules. The last matching rule wins, so a subgraph rule overrides a global one.
+    pub(super) fn propagate_response_headers(&mut self, headers: &http::HeaderMap) {
+        let rules = self
+            .schema()
+            .config
+            .response_header_rules
+            .iter()
+            .chain(self.subgraph.config.response_header_rules.iter());
+
+        let mut decisions: Vec<Option<(Option<&str>, HeaderMergeStrategy)>> = vec![None; headers.keys_len()];
+        for rule in rules {
+            for (decision, name) in decisions.iter_mut().zip(headers.keys()) {
+                match rule {
+                    ResponseHeaderRule::Propagate {
+                        name: matcher,
+                        rename,
+                        merge,
+                    } if matcher.matches(name.as_str()) => *decision = Some((rename.as_deref(), *merge)),
+                    ResponseHeaderRule::Remove { name: matcher } if matcher.matches(name.as_str()) => *decision = None,
+                    _ => {}
+                }
+            }
+        }
+
+        for (decision, name) in decisions.into_iter().zip(headers.keys()) {
+            let Some((rename, merge)) = decision else {
+                continue;
+            };
+
+            let target = match rename {
+                Some(rename) => match http::HeaderName::try_from(rename) {
+                    Ok(target) => target,
+                    Err(_) => continue,
+                },
+                None => name.clone(),
+            };
+
+            // Hop-by-hop and framing headers only make sense for the subgraph connection.
+            if find_matching_denied_header(name).is_some() || find_matching_denied_header(&target).is_some() {
+                continue;
+            }
+
+            for (i, value) in headers.get_all(name).iter().enumerate() {
+                self.propagated_headers
+                    .push(target.clone(), value.clone(), merge, i == 0);
+            }
+        }
     }
 
     pub(super) fn set_as_http_error(&mut self, status_code: Option<http::StatusCode>) {
@@ -206,3 +304,22 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
         self.executed_request_builder.graphql_response_status(status);
     }
 }
+
+/// Max-age allowed by the subgraph `Cache-Control` header for the whole response cache. Private or
+/// non-storable responses are never cached.
+fn subgraph_cache_max_age(headers: &http::HeaderMap) -> Option<Duration> {
+    let cache_control = headers.typed_get::<headers::CacheControl>()?;
+
+    if cache_control.private() || cache_control.no_store() || cache_control.no_cache() {
+        return Some(Duration::ZERO);
+    }
+
+    let age = headers
+        .typed_get::<headers::Age>()
+        .map(|age| age.as_secs())
+        .unwrap_or_default();
+
+    cache_control
+        .max_age()
+        .map(|max_age| max_age.saturating_sub(Duration::from_secs(age)))
+}
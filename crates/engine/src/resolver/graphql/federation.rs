@@ -1,6 +1,9 @@
+mod batch;
 mod with_cache;
 mod without_cache;
 
+pub(crate) use batch::EntityBatches;
+
 use error::GraphqlError;
 use grafbase_telemetry::{graphql::OperationType, span::subgraph::SubgraphRequestSpanBuilder};
 use itertools::Itertools as _;
@@ -20,7 +23,9 @@ use crate::{
 
 use super::{
     SubgraphContext,
-    request::{PreparedFederationEntityOperation, execute_subgraph_request},
+    request::{
+        PreparedFederationEntityOperation, ResponseIngester, execute_subgraph_request, ingest_subgraph_response,
+    },
 };
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -97,12 +102,24 @@ impl FederationEntityResolver {
                 }
             }
 
+            // Only needed to tag entity cache entries.
+            let key_fields = if ctx.endpoint().config.cache_ttl.is_some() {
+                plan.resolver_definition()
+                    .required_field_set()
+                    .map(|key| key.items().map(|item| item.field().definition().name()).collect())
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+
             FederationEntityExecutor {
                 resolver: self,
                 parent_objects: parent_objects.into_object_set(),
                 response_part,
                 entities_to_fetch,
                 entities_without_expected_requirements,
+                entity_type_name: plan.entity_definition().name(),
+                key_fields,
             }
         })
     }
@@ -141,6 +158,8 @@ pub(crate) struct FederationEntityExecutor<'ctx> {
     response_part: ResponsePartBuilder<'ctx>,
     entities_to_fetch: Vec<EntityToFetch>,
     entities_without_expected_requirements: Vec<ParentObjectId>,
+    entity_type_name: &'ctx str,
+    key_fields: Vec<&'ctx str>,
 }
 
 impl<'ctx> FederationEntityExecutor<'ctx> {
@@ -156,6 +175,8 @@ impl<'ctx> FederationEntityExecutor<'ctx> {
             mut response_part,
             entities_to_fetch,
             entities_without_expected_requirements,
+            entity_type_name,
+            key_fields,
         } = self;
         let span = ctx.span();
 
@@ -182,6 +203,8 @@ impl<'ctx> FederationEntityExecutor<'ctx> {
                     entities_to_fetch,
                     *shape_id,
                     response_part,
+                    entity_type_name,
+                    key_fields,
                 )
                 .await
             } else {
@@ -261,9 +284,10 @@ pub(super) async fn fetch_entities_without_cache<'ctx, R: Runtime>(
         fetched_entities: entities_to_fetch,
     };
 
-    execute_subgraph_request(ctx, subgraph_headers, false, body, response_part, ingester).await
+    execute_entities_request(ctx, subgraph_headers, body, response_part, ingester).await
 }
 
+#[expect(clippy::too_many_arguments)]
 pub(super) async fn fetch_entities_with_cache<'ctx, R: Runtime>(
     ctx: &mut SubgraphContext<'ctx, R>,
     parent_objects: ParentObjectSet,
@@ -272,6 +296,8 @@ pub(super) async fn fetch_entities_with_cache<'ctx, R: Runtime>(
     entities_to_fetch: Vec<EntityToFetch>,
     shape_id: RootFieldsShapeId,
     mut response_part: ResponsePartBuilder<'ctx>,
+    entity_type_name: &'ctx str,
+    key_fields: Vec<&'ctx str>,
 ) -> ResponsePartBuilder<'ctx> {
     let cache_fetch_outcome = super::cache::fetch_entities(ctx, &subgraph_headers, entities_to_fetch).await;
     if cache_fetch_outcome.misses.is_empty() {
@@ -326,7 +352,26 @@ pub(super) async fn fetch_entities_with_cache<'ctx, R: Runtime>(
         cache_fetch_outcome,
         shape_id,
         subgraph_default_cache_ttl: ctx.endpoint().config.cache_ttl,
+        subgraph_name: ctx.endpoint().name(),
+        entity_type_name,
+        key_fields,
     };
 
-    execute_subgraph_request(ctx, subgraph_headers, false, body, response_part, ingester).await
+    execute_entities_request(ctx, subgraph_headers, body, response_part, ingester).await
+}
+
+async fn execute_entities_request<'ctx, R: Runtime>(
+    ctx: &mut SubgraphContext<'ctx, R>,
+    subgraph_headers: http::HeaderMap,
+    body: Vec<u8>,
+    response_part: ResponsePartBuilder<'ctx>,
+    ingester: impl ResponseIngester,
+) -> ResponsePartBuilder<'ctx> {
+    match ctx.endpoint().config.entity_batching {
+        Some(config) => {
+            let result = batch::fetch_batched_entities(ctx, config, subgraph_headers, body).await;
+            ingest_subgraph_response(ctx, result, response_part, ingester).await
+        }
+        None => execute_subgraph_request(ctx, subgraph_headers, false, body, response_part, ingester).await,
+    }
 }
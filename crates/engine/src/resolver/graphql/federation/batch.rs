@@ -0,0 +1,219 @@
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use futures::channel::oneshot;
+use fxhash::FxHashMap;
+use schema::{EntityBatchingConfig, GraphqlSubgraphId};
+use serde_json::value::RawValue;
+
+use crate::{
+    Runtime,
+    resolver::graphql::{SubgraphContext, request::fetch_subgraph_response},
+    response::{ErrorCode, GraphqlError},
+};
+
+type BatchedResponse = Result<http::Response<Bytes>, GraphqlError>;
+
+/// Entity requests of an operation waiting to be sent to their subgraph. Each batch is sent by
+/// the first request that joined it, the leader, which then dispatches the responses to the
+/// others.
+#[derive(Default)]
+pub(crate) struct EntityBatches {
+    open: Mutex<FxHashMap<GraphqlSubgraphId, Arc<Batch>>>,
+}
+
+#[derive(Default)]
+struct Batch {
+    requests: Mutex<Vec<BatchedRequest>>,
+}
+
+struct BatchedRequest {
+    body: Vec<u8>,
+    // None for the leader which sends the batch itself.
+    sender: Option<oneshot::Sender<BatchedResponse>>,
+}
+
+enum Membership<'a> {
+    Leader(BatchLeader<'a>),
+    Follower(oneshot::Receiver<BatchedResponse>),
+}
+
+impl EntityBatches {
+    fn join(&self, subgraph_id: GraphqlSubgraphId, config: EntityBatchingConfig, body: Vec<u8>) -> Membership<'_> {
+        let mut open = self.open.lock().unwrap();
+
+        let (batch, membership) = match open.get(&subgraph_id) {
+            Some(batch) => {
+                let (sender, receiver) = oneshot::channel();
+                batch.requests.lock().unwrap().push(BatchedRequest {
+                    body,
+                    sender: Some(sender),
+                });
+                (batch.clone(), Membership::Follower(receiver))
+            }
+            None => {
+                let batch = Arc::new(Batch::default());
+                batch
+                    .requests
+                    .lock()
+                    .unwrap()
+                    .push(BatchedRequest { body, sender: None });
+                open.insert(subgraph_id, batch.clone());
+                let leader = BatchLeader {
+                    batches: self,
+                    subgraph_id,
+                    batch: Some(batch.clone()),
+                };
+                (batch, Membership::Leader(leader))
+            }
+        };
+
+        // Full batches don't accept any new requests.
+        if batch.requests.lock().unwrap().len() >= config.max_size {
+            open.remove(&subgraph_id);
+        }
+
+        membership
+    }
+
+    fn close(&self, subgraph_id: GraphqlSubgraphId, batch: &Arc<Batch>) -> Vec<BatchedRequest> {
+        let mut open = self.open.lock().unwrap();
+        if open
+            .get(&subgraph_id)
+            .is_some_and(|current| Arc::ptr_eq(current, batch))
+        {
+            open.remove(&subgraph_id);
+        }
+        drop(open);
+
+        std::mem::take(&mut *batch.requests.lock().unwrap())
+    }
+}
+
+/// Closes the batch if the leader is dropped before sending it, the followers will receive an
+/// error rather than waiting indefinitely.
+struct BatchLeader<'a> {
+    batches: &'a EntityBatches,
+    subgraph_id: GraphqlSubgraphId,
+    batch: Option<Arc<Batch>>,
+}
+
+impl BatchLeader<'_> {
+    fn close(mut self) -> Vec<BatchedRequest> {
+        let batch = self.batch.take().expect("Batch is only closed once");
+        self.batches.close(self.subgraph_id, &batch)
+    }
+}
+
+impl Drop for BatchLeader<'_> {
+    fn drop(&mut self) {
+        if let Some(batch) = self.batch.take() {
+            self.batches.close(self.subgraph_id, &batch);
+        }
+    }
+}
+
+/// Sends the entity request in a batch shared with the other entity requests of the operation
+/// made concurrently to the same subgraph. The subgraph is expected to respond with an array of
+/// GraphQL responses in the same order as the requests.
+pub(super) async fn fetch_batched_entities<R: Runtime>(
+    ctx: &mut SubgraphContext<'_, R>,
+    config: EntityBatchingConfig,
+    headers: http::HeaderMap,
+    body: Vec<u8>,
+) -> BatchedResponse {
+    let batches = ctx.entity_batches;
+    let leader = match batches.join(ctx.endpoint().id, config, body) {
+        Membership::Leader(leader) => leader,
+        Membership::Follower(receiver) => {
+            return receiver
+                .await
+                .unwrap_or_else(|_| Err(GraphqlError::internal_server_error()));
+        }
+    };
+
+    // Gives the other plans of the current execution wave the chance to join the batch.
+    futures_lite::future::yield_now().await;
+    if !config.max_wait.is_zero() {
+        ctx.engine().runtime.sleep(config.max_wait).await;
+    }
+
+    let mut requests = leader.close();
+    if requests.len() == 1 {
+        let body = requests.pop().map(|request| request.body).unwrap_or_default();
+        return fetch_subgraph_response(ctx, headers, false, body.into()).await;
+    }
+
+    tracing::debug!(
+        "Sending a batch of {} entity requests to subgraph named '{}'",
+        requests.len(),
+        ctx.endpoint().name()
+    );
+
+    let mut body = Vec::with_capacity(requests.iter().map(|request| request.body.len() + 1).sum::<usize>() + 1);
+    body.push(b'[');
+    for (i, request) in requests.iter().enumerate() {
+        if i > 0 {
+            body.push(b',');
+        }
+        body.extend_from_slice(&request.body);
+    }
+    body.push(b']');
+
+    let result = fetch_subgraph_response(ctx, headers, false, body.into()).await;
+    let mut responses = split_batched_response(result, requests.len()).into_iter();
+
+    // The leader is always the first request of the batch.
+    let leader_response = responses
+        .next()
+        .unwrap_or_else(|| Err(GraphqlError::internal_server_error()));
+    for (request, response) in requests.into_iter().skip(1).zip(responses) {
+        if let Some(sender) = request.sender {
+            sender.send(response).ok();
+        }
+    }
+
+    leader_response
+}
+
+fn split_batched_response(result: BatchedResponse, count: usize) -> Vec<BatchedResponse> {
+    let response = match result {
+        Ok(response) => response,
+        Err(err) => return (0..count).map(|_| Err(err.clone())).collect(),
+    };
+
+    // Error responses, or a single GraphQL response if the subgraph doesn't support batching,
+    // are shared as is.
+    let bodies = match serde_json::from_slice::<Vec<&RawValue>>(response.body()) {
+        Ok(bodies) if response.status().is_success() => bodies,
+        _ => {
+            return (0..count)
+                .map(|_| Ok(with_body(&response, response.body().clone())))
+                .collect();
+        }
+    };
+
+    if bodies.len() != count {
+        tracing::error!(
+            "Subgraph returned {} responses for a batch of {count} entity requests",
+            bodies.len()
+        );
+        let error = GraphqlError::new(
+            "Subgraph returned an invalid batch response",
+            ErrorCode::SubgraphInvalidResponseError,
+        );
+        return (0..count).map(|_| Err(error.clone())).collect();
+    }
+
+    bodies
+        .into_iter()
+        .map(|body| Ok(with_body(&response, response.body().slice_ref(body.get().as_bytes()))))
+        .collect()
+}
+
+fn with_body(response: &http::Response<Bytes>, body: Bytes) -> http::Response<Bytes> {
+    let mut copy = http::Response::new(body);
+    *copy.status_mut() = response.status();
+    *copy.headers_mut() = response.headers().clone();
+    copy
+}
@@ -14,7 +14,10 @@ use crate::{
     execution::ExecutionContext,
     prepare::RootFieldsShapeId,
     resolver::graphql::{
-        cache::{CacheFetchEntitiesOutcome, EntityCacheHit, EntityCacheMiss, calculate_cache_ttl},
+        cache::{
+            CacheFetchEntitiesOutcome, EntityCacheHit, EntityCacheMiss, cache_tags, calculate_cache_ttl,
+            entity_cache_tags,
+        },
         deserialize::{EntitiesDataSeed, EntityErrorPathConverter, GraphqlErrorsSeed, GraphqlResponseSeed},
         request::ResponseIngester,
     },
@@ -43,6 +46,9 @@ pub(super) struct PartiallyCachedEntitiesIngester<'ctx, R: Runtime> {
     pub cache_fetch_outcome: CacheFetchEntitiesOutcome,
     pub shape_id: RootFieldsShapeId,
     pub subgraph_default_cache_ttl: Option<Duration>,
+    pub subgraph_name: &'ctx str,
+    pub entity_type_name: &'ctx str,
+    pub key_fields: Vec<&'ctx str>,
 }
 
 impl<R> ResponseIngester for PartiallyCachedEntitiesIngester<'_, R>
@@ -60,6 +66,9 @@ where
             cache_fetch_outcome: CacheFetchEntitiesOutcome { hits, misses },
             shape_id,
             subgraph_default_cache_ttl,
+            subgraph_name,
+            entity_type_name,
+            key_fields,
         } = self;
 
         let http_response = match result {
@@ -121,13 +130,21 @@ where
             && let Some(cache_ttl) = calculate_cache_ttl(status, http_response.headers(), subgraph_default_cache_ttl)
         {
             let cache = ctx.runtime().entity_cache();
-            join_all(cache_updates.into_iter().map(|(key, value)| async move {
-                cache
-                    .put(&key, Cow::Borrowed(value.get().as_bytes()), cache_ttl)
-                    .await
-                    .inspect_err(|err| tracing::warn!("Failed to write the cache key {key}: {err}"))
-                    .ok();
-            }))
+            let response_tags = cache_tags(subgraph_name, http_response.headers());
+            let (response_tags, key_fields) = (&response_tags, &key_fields);
+            join_all(
+                cache_updates
+                    .into_iter()
+                    .map(|(key, representation, value)| async move {
+                        let mut tags = response_tags.clone();
+                        tags.extend(entity_cache_tags(entity_type_name, key_fields, &representation));
+                        cache
+                            .put(&key, Cow::Borrowed(value.get().as_bytes()), cache_ttl, &tags)
+                            .await
+                            .inspect_err(|err| tracing::warn!("Failed to write the cache key {key}: {err}"))
+                            .ok();
+                    }),
+            )
             .await;
         }
         (status, response_part)
@@ -138,7 +155,7 @@ struct PartiallyCachedEntitiesSeed<'ctx, 'parent, 'state, 'de> {
     state: &'state SeedState<'ctx, 'parent>,
     parent_objects: &'parent ParentObjectSet,
     cache_misses: &'state mut std::vec::IntoIter<EntityCacheMiss>,
-    cache_updates: &'state mut Vec<(String, &'de RawValue)>,
+    cache_updates: &'state mut Vec<(String, Box<RawValue>, &'de RawValue)>,
 }
 
 impl<'de> DeserializeSeed<'de> for PartiallyCachedEntitiesSeed<'_, '_, '_, 'de> {
@@ -172,7 +189,12 @@ impl<'de> Visitor<'de> for PartiallyCachedEntitiesSeed<'_, '_, '_, 'de> {
 
         let mut result = Ok(());
 
-        for EntityCacheMiss { id, key, .. } in cache_misses.by_ref() {
+        for EntityCacheMiss {
+            id,
+            key,
+            representation,
+        } in cache_misses.by_ref()
+        {
             let parent_object = &parent_objects[id];
             let raw_value = match seq.next_element::<&RawValue>() {
                 Ok(Some(value)) => value,
@@ -216,7 +238,7 @@ impl<'de> Visitor<'de> for PartiallyCachedEntitiesSeed<'_, '_, '_, 'de> {
                 break;
             }
 
-            cache_updates.push((key, raw_value));
+            cache_updates.push((key, representation, raw_value));
         }
 
         if cache_misses.len() > 0 {
@@ -18,7 +18,8 @@ use tracing::{Instrument, Span};
 
 use crate::{
     EngineOperationContext, Runtime,
-    execution::{ExecutionError, ExecutionResult},
+    engine::circuit_breaker::CircuitOpen,
+    execution::{ExecutionError, ExecutionResult, RequestRateLimitContext},
     resolver::graphql::SubgraphContext,
     response::{GraphqlError, ResponsePartBuilder},
 };
@@ -34,87 +35,132 @@ pub trait ResponseIngester: Send {
     ) -> impl Future<Output = (Option<GraphqlResponseStatus>, ResponsePartBuilder<'_>)> + Send;
 }
 
+/// Body of a subgraph request, JSON unless files are uploaded with a multipart request.
+pub(crate) struct SubgraphRequestBody {
+    pub bytes: Bytes,
+    pub content_type: http::HeaderValue,
+}
+
+impl SubgraphRequestBody {
+    pub fn multipart(content_type: http::HeaderValue, bytes: Bytes) -> Self {
+        Self { bytes, content_type }
+    }
+}
+
+impl From<Bytes> for SubgraphRequestBody {
+    fn from(bytes: Bytes) -> Self {
+        Self {
+            bytes,
+            content_type: http::HeaderValue::from_static("application/json"),
+        }
+    }
+}
+
+impl From<Vec<u8>> for SubgraphRequestBody {
+    fn from(bytes: Vec<u8>) -> Self {
+        Bytes::from(bytes).into()
+    }
+}
+
 pub(crate) async fn execute_subgraph_request<'ctx, R: Runtime>(
     ctx: &mut SubgraphContext<'ctx, R>,
     headers: http::HeaderMap,
     is_mutation: bool,
-    body: impl Into<Bytes> + Send,
+    body: impl Into<SubgraphRequestBody> + Send,
     response_part: ResponsePartBuilder<'ctx>,
     ingester: impl ResponseIngester,
 ) -> ResponsePartBuilder<'ctx> {
+    let result = fetch_subgraph_response(ctx, headers, is_mutation, body.into()).await;
+    ingest_subgraph_response(ctx, result, response_part, ingester).await
+}
+
+/// Sends the request to the subgraph, going through the hooks, rate limits and retries.
+pub(crate) async fn fetch_subgraph_response<R: Runtime>(
+    ctx: &mut SubgraphContext<'_, R>,
+    headers: http::HeaderMap,
+    is_mutation: bool,
+    SubgraphRequestBody {
+        bytes: body,
+        content_type,
+    }: SubgraphRequestBody,
+) -> Result<http::Response<Bytes>, GraphqlError> {
     let subgraph = ctx.endpoint();
 
-    let result = async {
-        let ReqwestParts {
-            url,
-            method,
-            mut headers,
-        } = ctx
-            .extensions()
-            .on_graphql_subgraph_request(
-                EngineOperationContext::from(&ctx.ctx),
-                ctx.subgraph,
-                ReqwestParts {
-                    url: Cow::Borrowed(subgraph.url()),
-                    method: http::Method::POST,
-                    headers,
-                },
-            )
-            .await?;
-
-        let body: Bytes = body.into();
-
-        headers.typed_insert(headers::ContentType::json());
-        headers.typed_insert(headers::ContentLength(body.len() as u64));
-
-        headers.insert(
-            http::header::ACCEPT,
-            http::HeaderValue::from_static(
-                "application/graphql-response+json; charset=utf-8, application/json; charset=utf-8",
-            ),
-        );
-        headers.insert(http::header::CONNECTION, http::HeaderValue::from_static("keep-alive"));
-
-        let request = FetchRequest {
-            subgraph_id: subgraph.id,
-            url,
-            is_mutation,
-            headers,
-            method,
-            body,
-            timeout: subgraph.config.timeout,
-        };
-
-        ctx.record_request_size(request.body.len());
-
-        let fetcher = ctx.runtime().fetcher();
-
-        let fetch_result = retrying_fetch(ctx, || {
-            let mut request = request.clone();
-            let subgraph_name = subgraph.name().to_string();
-
-            async move {
-                let http_span = SubgraphHttpRequestSpan::new(request.url.as_ref(), &http::Method::POST);
-
-                grafbase_telemetry::otel::opentelemetry::global::get_text_map_propagator(|propagator| {
-                    let context = http_span.context();
-                    propagator.inject_context(
-                        &context,
-                        &mut grafbase_telemetry::http::HeaderInjector(&mut request.headers),
-                    );
-                });
+    let ReqwestParts {
+        url,
+        method,
+        mut headers,
+    } = ctx
+        .extensions()
+        .on_graphql_subgraph_request(
+            EngineOperationContext::from(&ctx.ctx),
+            ctx.subgraph,
+            ReqwestParts {
+                url: Cow::Borrowed(subgraph.url()),
+                method: http::Method::POST,
+                headers,
+            },
+        )
+        .await?;
+
+    headers.insert(http::header::CONTENT_TYPE, content_type);
+    headers.typed_insert(headers::ContentLength(body.len() as u64));
+
+    headers.insert(
+        http::header::ACCEPT,
+        http::HeaderValue::from_static(
+            "application/graphql-response+json; charset=utf-8, application/json; charset=utf-8",
+        ),
+    );
+    headers.insert(http::header::CONNECTION, http::HeaderValue::from_static("keep-alive"));
+
+    let request = FetchRequest {
+        subgraph_id: subgraph.id,
+        url,
+        is_mutation,
+        headers,
+        method,
+        body,
+        timeout: subgraph.config.timeout,
+    };
+
+    ctx.record_request_size(request.body.len());
+
+    let fetcher = ctx.runtime().fetcher();
+    // The response headers are needed after the fetch by the on_graphql_subgraph_response hook and
+    // the response header rules.
+    let keep_response_headers =
+        ctx.extensions().has_graphql_subgraph_response_hook() || ctx.has_response_header_rules();
+
+    let fetch_result = retrying_fetch(ctx, || {
+        let mut request = request.clone();
+        let subgraph_name = subgraph.name().to_string();
 
-                let (fetch_result, mut info) = fetcher.fetch(request).instrument(http_span.span()).await;
+        async move {
+            let http_span = SubgraphHttpRequestSpan::new(request.url.as_ref(), &http::Method::POST);
 
-                let result = fetch_result.and_then(|mut response| {
-                    tracing::debug!("Received response:\n{}", String::from_utf8_lossy(response.body()));
-                    // For those status codes we want to retry the request, so marking the request as
-                    // failed.
-                    let status = response.status();
+            grafbase_telemetry::otel::opentelemetry::global::get_text_map_propagator(|propagator| {
+                let context = http_span.context();
+                propagator.inject_context(
+                    &context,
+                    &mut grafbase_telemetry::http::HeaderInjector(&mut request.headers),
+                );
+            });
 
-                    if let Some(ref mut info) = info {
-                        info.status(status);
+            let (fetch_result, mut info) = fetcher.fetch(request).instrument(http_span.span()).await;
 
+            let result = fetch_result.and_then(|mut response| {
+                tracing::debug!("Received response:\n{}", String::from_utf8_lossy(response.body()));
+                // For those status codes we want to retry the request, so marking the request as
+                // failed.
+                let status = response.status();
+
+                if let Some(ref mut info) = info {
+                    info.status(status);
+
+                    if keep_response_headers {
+                        info.headers(response.headers().clone());
+                    } else {
                         // Performance optimization: Instead of cloning the entire HeaderMap,
                         // we extract only the cache-related headers (Cache-Control and Age)
                         // that are needed by the caching logic. This avoids an expensive clone
@@ -135,38 +181,57 @@ pub(crate) async fn execute_subgraph_request<'ctx, R: Runtime>(
                             response.headers_mut().typed_insert(age);
                         }
                     }
+                }
 
-                    if status.is_server_error() {
-                        Err(FetchError::InvalidStatusCode(status, Some(response)))
-                    } else if status == http::StatusCode::TOO_MANY_REQUESTS {
-                        Err(FetchError::InvalidStatusCode(status, None))
-                    } else {
-                        Ok(response)
-                    }
-                });
+                if status.is_server_error() {
+                    Err(FetchError::InvalidStatusCode(status, Some(response)))
+                } else if status == http::StatusCode::TOO_MANY_REQUESTS {
+                    Err(FetchError::InvalidStatusCode(status, None))
+                } else {
+                    Ok(response)
+                }
+            });
 
-                match result {
-                    Ok(ref response) => {
-                        http_span.record_http_status_code(response.status());
-                    }
-                    Err(ref err) => {
-                        tracing::error!("Request to subgraph {} failed with: {err}", subgraph_name);
-                        http_span.set_as_http_error(err.as_invalid_status_code());
-                        // Only clear info for non-status-code errors (e.g., network errors)
-                        // For status code errors, we want to preserve the response info
-                        if !matches!(err, FetchError::InvalidStatusCode(_, _)) {
-                            info = None;
-                        }
+            match result {
+                Ok(ref response) => {
+                    http_span.record_http_status_code(response.status());
+                }
+                Err(ref err) => {
+                    tracing::error!("Request to subgraph {} failed with: {err}", subgraph_name);
+                    http_span.set_as_http_error(err.as_invalid_status_code());
+                    // Only clear info for non-status-code errors (e.g., network errors)
+                    // For status code errors, we want to preserve the response info
+                    if !matches!(err, FetchError::InvalidStatusCode(_, _)) {
+                        info = None;
                     }
-                };
+                }
+            };
 
-                (result, info)
+            (result, info)
+        }
+    })
+    .await;
+
+    let http_response = match fetch_result {
+        Ok(http_response) => {
+            ctx.record_http_response(&http_response);
+            // If the status code isn't a success as this point it means it's either a client error or
+            // we've exhausted our retry budget for server errors.
+            if !http_response.status().is_success() {
+                tracing::debug!(
+                    "Subgraph request failed with status code: {}\n{}",
+                    http_response.status().as_u16(),
+                    String::from_utf8_lossy(http_response.body())
+                );
             }
-        })
-        .await;
-
-        match fetch_result {
-            Ok(http_response) => {
+            http_response
+        }
+        Err(err) => match err {
+            ExecutionError::Fetch {
+                error: FetchError::InvalidStatusCode(code, Some(http_response)),
+                ..
+            } => {
+                ctx.set_as_http_error(Some(code));
                 ctx.record_http_response(&http_response);
                 // If the status code isn't a success as this point it means it's either a client error or
                 // we've exhausted our retry budget for server errors.
@@ -177,36 +242,34 @@ pub(crate) async fn execute_subgraph_request<'ctx, R: Runtime>(
                         String::from_utf8_lossy(http_response.body())
                     );
                 }
-                Ok((http_response, ctx))
+                http_response
             }
-            Err(err) => match err {
-                ExecutionError::Fetch {
-                    error: FetchError::InvalidStatusCode(code, Some(http_response)),
-                    ..
-                } => {
-                    ctx.set_as_http_error(Some(code));
-                    ctx.record_http_response(&http_response);
-                    // If the status code isn't a success as this point it means it's either a client error or
-                    // we've exhausted our retry budget for server errors.
-                    if !http_response.status().is_success() {
-                        tracing::debug!(
-                            "Subgraph request failed with status code: {}\n{}",
-                            http_response.status().as_u16(),
-                            String::from_utf8_lossy(http_response.body())
-                        );
-                    }
-                    Ok((http_response, ctx))
-                }
-                _ => {
-                    ctx.set_as_http_error(err.as_fetch_invalid_status_code());
-                    Err(err.into())
-                }
-            },
-        }
+            _ => {
+                ctx.set_as_http_error(err.as_fetch_invalid_status_code());
+                return Err(err.into());
+            }
+        },
     };
 
-    match result.await {
-        Ok((response, ctx)) => {
+    let (parts, body) = http_response.into_parts();
+    let parts = ctx
+        .extensions()
+        .on_graphql_subgraph_response(EngineOperationContext::from(&ctx.ctx), ctx.subgraph, parts)
+        .await?;
+    ctx.propagate_response_headers(&parts.headers);
+
+    Ok(http::Response::from_parts(parts, body))
+}
+
+/// Hands the subgraph response over to the ingester and records the resulting GraphQL status.
+pub(crate) async fn ingest_subgraph_response<'ctx, R: Runtime>(
+    ctx: &mut SubgraphContext<'ctx, R>,
+    result: Result<http::Response<Bytes>, GraphqlError>,
+    response_part: ResponsePartBuilder<'ctx>,
+    ingester: impl ResponseIngester,
+) -> ResponsePartBuilder<'ctx> {
+    match result {
+        Ok(response) => {
             let (status, response_part) = ingester.ingest(Ok(response), response_part).await;
 
             if let Some(status) = status {
@@ -248,6 +311,8 @@ where
                 }
                 return Ok(response);
             }
+            // Retrying is pointless until the breaker lets a probe through.
+            Err(err @ ExecutionError::CircuitOpen { .. }) => return Err(err),
             Err(err) => {
                 let withdraw = ctx.retry_budget().map(|b| b.withdraw()).unwrap_or_default();
 
@@ -280,19 +345,42 @@ where
     F: Future<Output = (FetchResult<T>, Option<SubgraphResponseBuilder>)> + Send,
     T: Send,
 {
+    let rate_limit_context = RequestRateLimitContext::new(
+        RateLimitKey::Subgraph(ctx.endpoint().name().into()),
+        ctx.request_context,
+        ctx.operation.cached.operation.attributes.name.original(),
+    );
+
     ctx.engine()
         .runtime
         .rate_limiter()
-        .limit(&RateLimitKey::Subgraph(ctx.endpoint().name().into()))
+        .limit(&rate_limit_context)
         .await
         .inspect_err(|_| {
             ctx.push_request_execution(RequestExecution::RateLimited);
         })?;
 
+    let circuit_breaker = ctx.execution_context().engine.get_circuit_breaker(ctx.endpoint().id);
+    if let Some(breaker) = circuit_breaker {
+        match breaker.try_acquire() {
+            Ok(Some(change)) => ctx.record_circuit_breaker_state_change(change),
+            Ok(None) => (),
+            Err(CircuitOpen) => {
+                return Err(ExecutionError::CircuitOpen {
+                    subgraph_name: ctx.endpoint().name().to_string(),
+                });
+            }
+        }
+    }
+
     ctx.increment_inflight_requests();
     let (result, info) = fetch().await;
     ctx.decrement_inflight_requests();
 
+    if let Some(change) = circuit_breaker.and_then(|breaker| breaker.record(result.is_ok())) {
+        ctx.record_circuit_breaker_state_change(change);
+    }
+
     match info {
         Some(info) => ctx.push_request_execution(RequestExecution::Response(info.build())),
         None if result.is_err() => ctx.push_request_execution(RequestExecution::RequestError),
@@ -12,7 +12,9 @@ use super::{
     SubgraphContext,
     cache::{ResponseCacheHit, ResponseCacheMiss},
     deserialize::{GraphqlErrorsSeed, GraphqlResponseSeed},
-    request::{PreparedGraphqlOperation, ResponseIngester, SubgraphVariables, execute_subgraph_request},
+    request::{
+        PreparedGraphqlOperation, ResponseIngester, SubgraphRequestBody, SubgraphVariables, execute_subgraph_request,
+    },
 };
 use crate::{
     Runtime,
@@ -122,7 +124,19 @@ impl GraphqlResolver {
         async {
             let subgraph_headers = ctx.subgraph_headers_with_rules(ctx.endpoint().header_rules());
 
-            if ctx.endpoint().config.cache_ttl.is_some() {
+            // Requests with uploaded files are never cached, their content isn't part of the key.
+            if let Some((content_type, multipart_body)) = self.encode_file_uploads(ctx, &body) {
+                fetch_response_without_cache(
+                    ctx,
+                    parent_objects,
+                    subgraph_headers,
+                    self.ty.is_mutation(),
+                    SubgraphRequestBody::multipart(content_type, multipart_body),
+                    plan.shape().id,
+                    response_part,
+                )
+                .await
+            } else if ctx.endpoint().config.cache_ttl.is_some() {
                 fetch_response_with_cache(
                     ctx,
                     parent_objects,
@@ -139,7 +153,7 @@ impl GraphqlResolver {
                     parent_objects,
                     subgraph_headers,
                     self.ty.is_mutation(),
-                    body,
+                    body.into(),
                     plan.shape().id,
                     response_part,
                 )
@@ -149,6 +163,21 @@ impl GraphqlResolver {
         .instrument(span)
         .await
     }
+
+    /// Re-encodes the JSON request as a multipart one if any of its variables reference an
+    /// uploaded file.
+    fn encode_file_uploads<R: Runtime>(
+        &self,
+        ctx: &SubgraphContext<'_, R>,
+        body: &[u8],
+    ) -> Option<(http::HeaderValue, Bytes)> {
+        let file_uploads = &ctx.request_context.file_uploads;
+        if file_uploads.is_empty() {
+            return None;
+        }
+        let request = serde_json::from_slice(body).ok()?;
+        file_uploads.encode_subgraph_request(request)
+    }
 }
 
 async fn fetch_response_without_cache<'ctx, R: Runtime>(
@@ -156,7 +185,7 @@ async fn fetch_response_without_cache<'ctx, R: Runtime>(
     parent_objects: ParentObjectSet,
     subgraph_headers: http::HeaderMap,
     is_mutation: bool,
-    body: Vec<u8>,
+    body: SubgraphRequestBody,
     shape_id: RootFieldsShapeId,
     response_part: ResponsePartBuilder<'ctx>,
 ) -> ResponsePartBuilder<'ctx> {
@@ -228,6 +257,7 @@ async fn fetch_response_with_cache<'ctx, R: Runtime>(
                 parent_objects,
                 subgraph_default_cache_ttl: ctx.endpoint().config.cache_ttl,
                 cache_key: key,
+                subgraph_name: ctx.endpoint().name(),
                 shape_id,
             };
 
@@ -242,6 +272,7 @@ struct GraphqlWithCachePutIngester<'ctx, R: Runtime> {
     shape_id: RootFieldsShapeId,
     subgraph_default_cache_ttl: Option<Duration>,
     cache_key: String,
+    subgraph_name: &'ctx str,
 }
 
 impl<R> ResponseIngester for GraphqlWithCachePutIngester<'_, R>
@@ -259,6 +290,7 @@ where
             parent_objects,
             subgraph_default_cache_ttl,
             cache_key,
+            subgraph_name,
         } = self;
 
         let http_response = match result {
@@ -280,11 +312,17 @@ where
             let cache_ttl =
                 super::cache::calculate_cache_ttl(status, http_response.headers(), subgraph_default_cache_ttl);
             if let Some(cache_ttl) = cache_ttl {
+                let tags = super::cache::cache_tags(subgraph_name, http_response.headers());
                 // We could probably put this call into the background at some point, but for
                 // simplicities sake I am not going to do that just now.
                 ctx.runtime()
                     .entity_cache()
-                    .put(&cache_key, Cow::Borrowed(http_response.body().as_ref()), cache_ttl)
+                    .put(
+                        &cache_key,
+                        Cow::Borrowed(http_response.body().as_ref()),
+                        cache_ttl,
+                        &tags,
+                    )
                     .await
                     .inspect_err(|err| tracing::warn!("Failed to write the cache key {cache_key}: {err}"))
                     .ok();
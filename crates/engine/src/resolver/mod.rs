@@ -6,7 +6,7 @@ mod lookup;
 pub(crate) use extension::{ExtensionResolver, FieldResolverExtension, SelectionSetExtensionResolver};
 use futures::{FutureExt, future::BoxFuture};
 use futures_util::stream::BoxStream;
-pub(crate) use graphql::{FederationEntityResolver, GraphqlResolver};
+pub(crate) use graphql::{EntityBatches, FederationEntityResolver, GraphqlResolver};
 use introspection::IntrospectionResolver;
 pub(crate) use lookup::{LookupProxiedResolver, LookupResolver};
 use operation::{Operation, OperationContext};
@@ -17,12 +17,6 @@ impl std::ops::Deref for ResponseData {
     }
 }
 
-impl ResponseData {
-    pub(super) fn root_object(&self) -> &ResponseObject {
-        &self.parts[self.root]
-    }
-}
-
 /// The response data is composed of multiple parts, each with its own objects and lists.
 /// This allows subgraph request to be processed independently. Each object/list is uniquely
 /// identifier by its DataPartId and PartObjectId/PartListId.
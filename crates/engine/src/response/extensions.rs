@@ -1,3 +1,5 @@
+use std::fmt::Write as _;
+
 use grafbase_telemetry::otel::opentelemetry::trace::TraceId;
 use schema::Schema;
 use serde::Serialize;
@@ -5,7 +7,7 @@ use walker::Walk;
 
 use crate::{
     mcp::McpResponseExtension,
-    prepare::{Executable, OperationPlanContext, PlanId, PreparedOperation},
+    prepare::{Executable, OperationPlanContext, PlanId, PreparedOperation, RequiredFieldSet, SubgraphSelectionSet},
     resolver::{
         ExtensionResolver, FederationEntityResolver, FieldResolverExtension, GraphqlResolver, LookupProxiedResolver,
         Resolver, SelectionSetExtensionResolver,
@@ -46,6 +48,8 @@ pub(crate) struct GrafbaseResponseExtension {
     trace_id: Option<TraceId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     query_plan: Option<QueryPlan>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explain: Option<Explain>,
 }
 
 impl GrafbaseResponseExtension {
@@ -53,6 +57,7 @@ impl GrafbaseResponseExtension {
         Self {
             trace_id: self.trace_id.or(other.trace_id),
             query_plan: self.query_plan.or(other.query_plan),
+            explain: self.explain.or(other.explain),
         }
     }
 }
@@ -86,6 +91,98 @@ impl GrafbaseResponseExtension {
         self.query_plan = Some(QueryPlan { nodes, edges });
         self
     }
+
+    pub fn with_explain(mut self, schema: &Schema, prepared_operation: &PreparedOperation) -> Self {
+        let ctx = OperationPlanContext {
+            schema,
+            cached: &prepared_operation.cached,
+            plan: &prepared_operation.plan,
+        };
+
+        // Without complexity control there is no configured list size, so lists count as a
+        // single item.
+        let list_size = schema.config.complexity_control.list_size().unwrap_or(1);
+
+        let mut fetches = ctx
+            .plans()
+            .map(|plan| ExplainedFetch {
+                id: usize::from(plan.id),
+                resolver: (ctx, &plan.resolver).into(),
+                entity: plan.entity_definition().name().to_string(),
+                requires: (!plan.required_fields().is_empty()).then(|| render_required_fields(plan.required_fields())),
+                estimated_complexity: estimated_complexity(plan.selection_set(), list_size),
+                depends_on: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut dot = String::from("digraph {\n");
+        for plan in ctx.plans() {
+            let id = usize::from(plan.id);
+            writeln!(
+                dot,
+                "    {id} [label=\"{}\\n{}\"];",
+                plan.resolver_definition().name(),
+                plan.entity_definition().name()
+            )
+            .unwrap();
+
+            for child in plan.children() {
+                if let Executable::Plan(child) = child {
+                    let child_id = usize::from(child.id);
+                    writeln!(dot, "    {id} -> {child_id};").unwrap();
+                    fetches[child_id].depends_on.push(id);
+                }
+            }
+        }
+        dot.push('}');
+
+        self.explain = Some(Explain {
+            complexity: prepared_operation.complexity_cost.map(|cost| cost.0),
+            solver_cost: prepared_operation.cached.query_plan.solver_cost,
+            fetches,
+            dot,
+        });
+        self
+    }
+}
+
+/// Rough estimate of the complexity of a subgraph request with the same cost model as complexity
+/// control, but ignoring arguments.
+fn estimated_complexity(selection_set: SubgraphSelectionSet<'_>, list_size: usize) -> usize {
+    selection_set
+        .fields()
+        .map(|field| {
+            let definition = field.definition();
+            let count = if definition.ty().wrapping.is_list() {
+                definition
+                    .list_size()
+                    .and_then(|directive| directive.assumed_size)
+                    .map(|size| size as usize)
+                    .unwrap_or(list_size)
+            } else {
+                1
+            };
+
+            count
+                * (operation::field_definition_cost(definition)
+                    + estimated_complexity(field.selection_set(), list_size))
+        })
+        .sum()
+}
+
+fn render_required_fields(fields: RequiredFieldSet<'_>) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            let name = field.data_field().definition().name();
+            if field.subselection().is_empty() {
+                name.to_string()
+            } else {
+                format!("{name} {{ {} }}", render_required_fields(field.subselection()))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 fn serialize_trace_id<S>(trace_id: &Option<TraceId>, serializer: S) -> Result<S::Ok, S::Error>
@@ -107,6 +204,31 @@ struct QueryPlan {
     edges: Vec<(usize, usize)>,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Explain {
+    /// Complexity of the whole operation, only computed with complexity control.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    complexity: Option<usize>,
+    solver_cost: u32,
+    fetches: Vec<ExplainedFetch>,
+    /// Graphviz rendering of the fetches and their dependencies.
+    dot: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExplainedFetch {
+    id: usize,
+    resolver: QueryPlanNode,
+    entity: String,
+    /// Fields retrieved by previous fetches needed to send this one, entity keys for example.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requires: Option<String>,
+    estimated_complexity: usize,
+    depends_on: Vec<usize>,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(tag = "__typename", rename_all = "PascalCase")]
 enum QueryPlanNode {
@@ -3,11 +3,12 @@ mod error;
 mod extensions;
 mod object_set;
 mod path;
+mod propagated_headers;
 mod read;
 mod value;
 mod write;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 pub(crate) use data::*;
 pub(crate) use error::*;
@@ -16,7 +17,9 @@ use gateway_config::ErrorCodeMapping;
 use grafbase_telemetry::graphql::{GraphqlExecutionTelemetry, GraphqlOperationAttributes, GraphqlResponseStatus};
 pub(crate) use object_set::*;
 pub(crate) use path::*;
+pub(crate) use propagated_headers::*;
 pub(crate) use read::*;
+use runtime::rate_limiting::RateLimitBudget;
 use schema::Schema;
 pub(crate) use value::*;
 pub(crate) use write::*;
@@ -37,6 +40,11 @@ pub(crate) enum Response {
     /// So `data` is present, even if null. That's considered to be a "partial response" and
     /// HTTP status code SHOULD be 2xx according to the GraphQL-over-HTTP spec for application/graphql-response+json
     Executed(ExecutedResponse),
+    /// One of the payloads of an incremental delivery response, with `@defer` or `@stream`. Only
+    /// used with streaming response formats.
+    Incremental(IncrementalResponse),
+    /// Response of a previous execution, served from the response cache.
+    Cached(CachedResponse),
 }
 
 pub(crate) struct ExecutedResponse {
@@ -46,6 +54,11 @@ pub(crate) struct ExecutedResponse {
     data: Option<ResponseData>,
     errors: ErrorParts,
     extensions: ResponseExtensions,
+    /// Smallest max-age sent by the subgraphs in their Cache-Control header.
+    subgraph_cache_max_age: Option<Duration>,
+    cache_control: Option<headers::CacheControl>,
+    /// Subgraph response headers to send to the client.
+    propagated_headers: http::HeaderMap,
 }
 
 impl ExecutedResponse {
@@ -65,6 +78,43 @@ impl ExecutedResponse {
     }
 }
 
+pub(crate) struct IncrementalResponse {
+    operation_attributes: GraphqlOperationAttributes,
+    /// Only present in the initial payload.
+    data: Option<serde_json::Value>,
+    data_is_null: bool,
+    errors: Option<serde_json::Value>,
+    incremental: Vec<serde_json::Value>,
+    has_next: bool,
+    /// Errors sent within this payload.
+    error_code_counter: ErrorCodeCounter,
+    extensions: ResponseExtensions,
+}
+
+impl IncrementalResponse {
+    pub(crate) fn graphql_status(&self) -> GraphqlResponseStatus {
+        let count = self.error_code_counter.count();
+        if count == 0 {
+            GraphqlResponseStatus::Success
+        } else {
+            GraphqlResponseStatus::FieldError {
+                count: count as u64,
+                data_is_null: self.data_is_null,
+            }
+        }
+    }
+}
+
+pub(crate) struct CachedResponse {
+    operation_attributes: GraphqlOperationAttributes,
+    data: serde_json::Value,
+    error_code_counter: ErrorCodeCounter,
+    extensions: ResponseExtensions,
+    cache_control: headers::CacheControl,
+    rate_limit_budget: Option<RateLimitBudget>,
+    propagated_headers: http::HeaderMap,
+}
+
 pub(crate) struct RequestErrorResponse {
     error_code_mapping: ErrorCodeMapping,
     operation_attributes: Option<GraphqlOperationAttributes>,
@@ -81,6 +131,7 @@ pub(crate) struct RefusedRequestResponse {
     error_code_counter: ErrorCodeCounter,
     extensions: ResponseExtensions,
     pub(crate) headers: http::HeaderMap,
+    rate_limit_budget: Option<RateLimitBudget>,
 }
 
 impl RefusedRequestResponse {
@@ -97,6 +148,11 @@ impl Response {
             Self::Executed(resp) => {
                 resp.errors.len() * 80 + resp.data.as_ref().map(|data| data.size_hint()).unwrap_or(10)
             }
+            // Payloads are usually small, only the initial one might not be.
+            Self::Incremental(resp) => {
+                resp.error_code_counter.count() * 80 + if resp.data.is_some() { 4096 } else { 512 }
+            }
+            Self::Cached(_) => 4096,
         }
     }
 
@@ -125,6 +181,7 @@ impl Response {
             error_code_counter,
             extensions: Default::default(),
             headers,
+            rate_limit_budget: None,
         })
     }
 
@@ -158,9 +215,98 @@ impl Response {
             data: None,
             errors,
             extensions: Default::default(),
+            subgraph_cache_max_age: None,
+            cache_control: None,
+            propagated_headers: Default::default(),
+        })
+    }
+
+    /// The operation was prepared but not executed, `data` is null.
+    pub(crate) fn not_executed(schema: &Arc<Schema>, operation: PreparedOperation) -> Self {
+        Self::Executed(ExecutedResponse {
+            schema: schema.clone(),
+            operation_attributes: operation.attributes(),
+            operation: Arc::new(operation),
+            data: None,
+            errors: Default::default(),
+            extensions: Default::default(),
+            subgraph_cache_max_age: None,
+            cache_control: None,
+            propagated_headers: Default::default(),
         })
     }
 
+    pub(crate) fn cached(
+        operation_attributes: GraphqlOperationAttributes,
+        data: serde_json::Value,
+        cache_control: headers::CacheControl,
+        propagated_headers: http::HeaderMap,
+    ) -> Self {
+        Self::Cached(CachedResponse {
+            operation_attributes,
+            data,
+            error_code_counter: Default::default(),
+            extensions: Default::default(),
+            cache_control,
+            rate_limit_budget: None,
+            propagated_headers,
+        })
+    }
+
+    /// Cache-Control header to send with the response, if any.
+    pub(crate) fn cache_control(&self) -> Option<&headers::CacheControl> {
+        match self {
+            Self::Executed(resp) => resp.cache_control.as_ref(),
+            Self::Cached(resp) => Some(&resp.cache_control),
+            Self::RefusedRequest(_) | Self::RequestError(_) | Self::Incremental(_) => None,
+        }
+    }
+
+    pub(crate) fn with_cache_control(mut self, cache_control: headers::CacheControl) -> Self {
+        match &mut self {
+            Self::Executed(resp) => resp.cache_control = Some(cache_control),
+            Self::Cached(resp) => resp.cache_control = cache_control,
+            Self::RefusedRequest(_) | Self::RequestError(_) | Self::Incremental(_) => {}
+        }
+        self
+    }
+
+    /// Complexity budget left to the client, sent back in the rate limit headers.
+    pub(crate) fn rate_limit_budget(&self) -> Option<RateLimitBudget> {
+        match self {
+            Self::Executed(resp) => resp.operation.rate_limit_budget,
+            Self::Cached(resp) => resp.rate_limit_budget,
+            Self::RefusedRequest(resp) => resp.rate_limit_budget,
+            Self::RequestError(_) | Self::Incremental(_) => None,
+        }
+    }
+
+    pub(crate) fn with_rate_limit_budget(mut self, budget: impl Into<Option<RateLimitBudget>>) -> Self {
+        let budget = budget.into();
+        match &mut self {
+            Self::Cached(resp) => resp.rate_limit_budget = budget,
+            Self::RefusedRequest(resp) => resp.rate_limit_budget = budget,
+            Self::Executed(_) | Self::RequestError(_) | Self::Incremental(_) => {}
+        }
+        self
+    }
+
+    /// Subgraph response headers to send with the response.
+    pub(crate) fn propagated_headers(&self) -> Option<&http::HeaderMap> {
+        match self {
+            Self::Executed(resp) => Some(&resp.propagated_headers),
+            Self::Cached(resp) => Some(&resp.propagated_headers),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn subgraph_cache_max_age(&self) -> Option<Duration> {
+        match self {
+            Self::Executed(resp) => resp.subgraph_cache_max_age,
+            _ => None,
+        }
+    }
+
     pub(crate) fn with_extensions(mut self, extensions: ResponseExtensions) -> Self {
         let ext = self.extensions_mut();
         *ext = std::mem::take(ext).merge(extensions);
@@ -173,6 +319,8 @@ impl Response {
             Self::RefusedRequest(resp) => &mut resp.extensions,
             Self::RequestError(resp) => &mut resp.extensions,
             Self::Executed(resp) => &mut resp.extensions,
+            Self::Incremental(resp) => &mut resp.extensions,
+            Self::Cached(resp) => &mut resp.extensions,
         }
     }
 
@@ -192,6 +340,8 @@ impl Response {
             Self::RefusedRequest(resp) => resp.operation_attributes.as_ref(),
             Self::RequestError(resp) => resp.operation_attributes.as_ref(),
             Self::Executed(resp) => Some(&resp.operation_attributes),
+            Self::Incremental(resp) => Some(&resp.operation_attributes),
+            Self::Cached(resp) => Some(&resp.operation_attributes),
         }
     }
 
@@ -206,6 +356,12 @@ impl Response {
             Self::Executed(resp) => {
                 resp.operation_attributes = operation_attributes;
             }
+            Self::Incremental(resp) => {
+                resp.operation_attributes = operation_attributes;
+            }
+            Self::Cached(resp) => {
+                resp.operation_attributes = operation_attributes;
+            }
         }
         self
     }
@@ -213,6 +369,8 @@ impl Response {
     pub(crate) fn graphql_status(&self) -> GraphqlResponseStatus {
         match self {
             Self::Executed(resp) => resp.graphql_status(),
+            Self::Incremental(resp) => resp.graphql_status(),
+            Self::Cached(_) => GraphqlResponseStatus::Success,
             Self::RequestError(resp) => GraphqlResponseStatus::RequestError {
                 count: resp.errors.len() as u64,
             },
@@ -224,7 +382,7 @@ impl Response {
         match self {
             Response::RefusedRequest(resp) => &resp.errors,
             Response::RequestError(resp) => &resp.errors,
-            Response::Executed(_) => unreachable!(),
+            Response::Executed(_) | Response::Incremental(_) | Response::Cached(_) => unreachable!(),
         }
     }
 
@@ -233,6 +391,8 @@ impl Response {
             Response::RefusedRequest(resp) => &resp.error_code_counter,
             Response::RequestError(resp) => &resp.error_code_counter,
             Response::Executed(resp) => resp.errors.code_counter(),
+            Response::Incremental(resp) => &resp.error_code_counter,
+            Response::Cached(resp) => &resp.error_code_counter,
         }
     }
 }
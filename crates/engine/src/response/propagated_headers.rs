@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use schema::HeaderMergeStrategy;
+
+/// Subgraph response headers propagated to the client, merged once all the subgraphs responded.
+#[derive(Default, Clone)]
+pub(crate) struct PropagatedHeaders {
+    entries: Vec<PropagatedHeader>,
+}
+
+#[derive(Clone)]
+struct PropagatedHeader {
+    name: http::HeaderName,
+    value: http::HeaderValue,
+    merge: HeaderMergeStrategy,
+    /// Whether it's the first value of the header in its subgraph response. The values of a
+    /// subgraph response are merged together, so multi-valued headers like Set-Cookie are kept
+    /// whole.
+    is_first_value: bool,
+}
+
+impl PropagatedHeaders {
+    pub fn push(
+        &mut self,
+        name: http::HeaderName,
+        value: http::HeaderValue,
+        merge: HeaderMergeStrategy,
+        is_first_value: bool,
+    ) {
+        self.entries.push(PropagatedHeader {
+            name,
+            value,
+            merge,
+            is_first_value,
+        });
+    }
+
+    pub fn append(&mut self, other: PropagatedHeaders) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Subgraph responses are merged in the order they were received, each with all its values
+    /// of the header.
+    pub fn into_header_map(self) -> http::HeaderMap {
+        let mut headers = http::HeaderMap::new();
+        // Whether the values of the current subgraph response are kept, per header.
+        let mut kept = HashMap::new();
+
+        for PropagatedHeader {
+            name,
+            value,
+            merge,
+            is_first_value,
+        } in self.entries
+        {
+            if !is_first_value {
+                if kept.get(&name).copied().unwrap_or_default() {
+                    headers.append(name, value);
+                }
+                continue;
+            }
+
+            let keep = match merge {
+                HeaderMergeStrategy::First => !headers.contains_key(&name),
+                HeaderMergeStrategy::Last => true,
+                HeaderMergeStrategy::Append => {
+                    kept.insert(name.clone(), true);
+                    headers.append(name, value);
+                    continue;
+                }
+                HeaderMergeStrategy::MinTtl => headers.get(&name).is_none_or(|current| ttl(&value) < ttl(current)),
+            };
+
+            kept.insert(name.clone(), keep);
+            if keep {
+                headers.insert(name, value);
+            }
+        }
+
+        headers
+    }
+}
+
+/// Time to live in seconds defined by a header value. Values without any are treated as living
+/// forever.
+fn ttl(value: &http::HeaderValue) -> u64 {
+    let Ok(value) = value.to_str() else {
+        return u64::MAX;
+    };
+
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return seconds;
+    }
+
+    let mut ttl = u64::MAX;
+    for directive in value.split(',') {
+        let (key, argument) = match directive.split_once('=') {
+            Some((key, argument)) => (key.trim(), Some(argument.trim().trim_matches('"'))),
+            None => (directive.trim(), None),
+        };
+
+        if ["no-store", "no-cache", "private"]
+            .iter()
+            .any(|directive| key.eq_ignore_ascii_case(directive))
+        {
+            return 0;
+        }
+
+        if (key.eq_ignore_ascii_case("max-age") || key.eq_ignore_ascii_case("s-maxage"))
+            && let Some(seconds) = argument.and_then(|argument| argument.parse::<u64>().ok())
+        {
+            ttl = ttl.min(seconds);
+        }
+    }
+
+    ttl
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merge(strategy: HeaderMergeStrategy, values: &[&'static str]) -> Vec<String> {
+        merge_responses(strategy, &values.iter().map(std::slice::from_ref).collect::<Vec<_>>())
+    }
+
+    /// Merges the values of several subgraph responses.
+    fn merge_responses(strategy: HeaderMergeStrategy, responses: &[&[&'static str]]) -> Vec<String> {
+        let mut headers = PropagatedHeaders::default();
+        for values in responses {
+            for (i, value) in values.iter().enumerate() {
+                headers.push(
+                    http::HeaderName::from_static("x-test"),
+                    http::HeaderValue::from_static(value),
+                    strategy,
+                    i == 0,
+                );
+            }
+        }
+
+        headers
+            .into_header_map()
+            .get_all("x-test")
+            .iter()
+            .map(|value| value.to_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn merge_strategies() {
+        assert_eq!(merge(HeaderMergeStrategy::First, &["a", "b"]), ["a"]);
+        assert_eq!(merge(HeaderMergeStrategy::Last, &["a", "b"]), ["b"]);
+        assert_eq!(merge(HeaderMergeStrategy::Append, &["a", "b"]), ["a", "b"]);
+        assert_eq!(
+            merge(
+                HeaderMergeStrategy::MinTtl,
+                &["max-age=60", "public, s-maxage=10", "max-age=30"]
+            ),
+            ["public, s-maxage=10"]
+        );
+        assert_eq!(
+            merge(HeaderMergeStrategy::MinTtl, &["120", "no-store", "5"]),
+            ["no-store"]
+        );
+        assert_eq!(
+            merge(HeaderMergeStrategy::MinTtl, &["public", "max-age=5"]),
+            ["max-age=5"]
+        );
+    }
+
+    #[test]
+    fn multi_valued_headers_are_merged_per_subgraph_response() {
+        let responses: &[&[&str]] = &[&["a=1", "b=1"], &["c=2", "d=2"]];
+
+        assert_eq!(merge_responses(HeaderMergeStrategy::First, responses), ["a=1", "b=1"]);
+        assert_eq!(merge_responses(HeaderMergeStrategy::Last, responses), ["c=2", "d=2"]);
+        assert_eq!(
+            merge_responses(HeaderMergeStrategy::Append, responses),
+            ["a=1", "b=1", "c=2", "d=2"]
+        );
+    }
+}
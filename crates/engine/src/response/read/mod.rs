@@ -4,6 +4,7 @@ use super::{ParentObjectSet, ResponseBuilder};
 mod ser;
 mod view;
 
+pub(crate) use ser::IncrementalResponseWriter;
 pub(crate) use view::*;
 
 impl ResponseBuilder<'_> {
@@ -2,17 +2,20 @@ use operation::ResponseKeys;
 use schema::Schema;
 use serde::ser::{SerializeMap, SerializeSeq};
 
-use crate::response::{PartString, ResponseData, ResponseObject, ResponseValue, value::ResponseObjectField};
+use crate::response::{
+    DataParts, PartString, ResponseObject, ResponseObjectId, ResponseValue, value::ResponseObjectField,
+};
 
 #[derive(Clone, Copy)]
 pub(super) struct Context<'a> {
     pub keys: &'a ResponseKeys,
-    pub data: &'a ResponseData,
+    pub data: &'a DataParts,
     pub schema: &'a Schema,
 }
 
 pub(super) struct SerializableResponseData<'a> {
     pub ctx: Context<'a>,
+    pub root: ResponseObjectId,
 }
 
 impl serde::Serialize for SerializableResponseData<'_> {
@@ -22,7 +25,7 @@ impl serde::Serialize for SerializableResponseData<'_> {
     {
         SerializableResponseObject {
             ctx: self.ctx,
-            object: self.ctx.data.root_object(),
+            object: &self.ctx.data[self.root],
         }
         .serialize(serializer)
     }
@@ -74,9 +77,9 @@ impl serde::Serialize for SerializableResponseList<'_> {
     }
 }
 
-struct SerializableResponseValue<'a> {
-    ctx: Context<'a>,
-    value: &'a ResponseValue,
+pub(super) struct SerializableResponseValue<'a> {
+    pub ctx: Context<'a>,
+    pub value: &'a ResponseValue,
 }
 
 impl serde::Serialize for SerializableResponseValue<'_> {
@@ -9,14 +9,15 @@ use serde::ser::{SerializeMap, SerializeSeq};
 use crate::{
     ErrorCode,
     prepare::QueryModifications,
-    response::{ErrorParts, ErrorPathSegment, GraphqlError, QueryErrorWithLocationAndPath},
+    response::{ErrorPart, ErrorPathSegment, GraphqlError, QueryErrorWithLocationAndPath},
 };
 
 pub(super) struct SerializableErrorParts<'a> {
     pub error_code_mapping: &'a ErrorCodeMapping,
     pub keys: &'a ResponseKeys,
     pub query_modifications: &'a QueryModifications,
-    pub errors: &'a ErrorParts,
+    pub parts: &'a [ErrorPart],
+    pub len: usize,
 }
 
 impl serde::Serialize for SerializableErrorParts<'_> {
@@ -24,8 +25,8 @@ impl serde::Serialize for SerializableErrorParts<'_> {
     where
         S: serde::Serializer,
     {
-        let mut seq = serializer.serialize_seq(Some(self.errors.len()))?;
-        for part in self.errors.parts() {
+        let mut seq = serializer.serialize_seq(Some(self.len))?;
+        for part in self.parts {
             for error in part.errors() {
                 seq.serialize_element(&SerializableError {
                     error_code_mapping: self.error_code_mapping,
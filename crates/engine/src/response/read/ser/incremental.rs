@@ -0,0 +1,370 @@
+use grafbase_telemetry::graphql::GraphqlOperationAttributes;
+use serde_json::Value;
+
+use crate::{
+    prepare::{DeferredFragmentId, PreparedOperation, ResponseFieldPosition},
+    response::{
+        ErrorCodeCounter, IncrementalResponse, Response, ResponseBuilder, ResponseFloatListId, ResponseIntListId,
+        ResponseListId, ResponseObjectId, ResponseValue, value::ResponseObjectField,
+    },
+};
+
+use super::{
+    data::{Context, SerializableResponseValue},
+    errors::SerializableErrorParts,
+};
+
+/// Writes the payloads of an incremental delivery response, for `@defer` and `@stream`. Data
+/// belonging to a deferred fragment is skipped and the location of the skipped objects is kept
+/// until the fragment is delivered. Streamed lists are truncated to their `initialCount` and
+/// the remaining items are sent in the next payload.
+#[derive(Default)]
+pub(crate) struct IncrementalResponseWriter {
+    deferred_objects: Vec<DeferredObject>,
+    streamed_lists: Vec<StreamedList>,
+    sent_error_parts_count: usize,
+}
+
+struct DeferredObject {
+    fragment_id: DeferredFragmentId,
+    object_id: ResponseObjectId,
+    depth: u16,
+    path: Vec<Value>,
+}
+
+struct StreamedList {
+    fragment_id: Option<DeferredFragmentId>,
+    list: StreamedListId,
+    label: Option<String>,
+    initial_count: usize,
+    depth: u16,
+    path: Vec<Value>,
+}
+
+#[derive(Clone, Copy)]
+enum StreamedListId {
+    Values(ResponseListId),
+    Ints(ResponseIntListId),
+    Floats(ResponseFloatListId),
+}
+
+impl IncrementalResponseWriter {
+    pub(crate) fn has_streamed_lists(&self) -> bool {
+        !self.streamed_lists.is_empty()
+    }
+
+    pub(crate) fn initial_payload(
+        &mut self,
+        response: &mut ResponseBuilder<'_>,
+        operation_attributes: GraphqlOperationAttributes,
+    ) -> Response {
+        let data = match response.root {
+            Some((root, _)) => {
+                let mut writer = self.writer(response, None);
+                writer.write_object(root, 0, &mut Vec::new(), None)
+            }
+            None => Value::Null,
+        };
+        let data_is_null = data.is_null();
+        let (errors, error_code_counter) = self.take_new_errors(response);
+        Response::Incremental(IncrementalResponse {
+            operation_attributes,
+            data: Some(data),
+            data_is_null,
+            errors,
+            incremental: Vec::new(),
+            has_next: true,
+            error_code_counter,
+            extensions: Default::default(),
+        })
+    }
+
+    /// Adds the data of a deferred fragment to the incremental payload. Its parent must have been
+    /// delivered before.
+    pub(crate) fn write_deferred_fragment(
+        &mut self,
+        response: &ResponseBuilder<'_>,
+        fragment_id: DeferredFragmentId,
+        incremental: &mut Vec<Value>,
+    ) {
+        let operation = response.operation.as_ref();
+        let label = operation.plan.query_modifications.deferred_fragment_labels[usize::from(fragment_id)].clone();
+
+        let (objects, remaining) = std::mem::take(&mut self.deferred_objects)
+            .into_iter()
+            .partition::<Vec<_>, _>(|object| object.fragment_id == fragment_id);
+        self.deferred_objects = remaining;
+
+        for DeferredObject {
+            object_id,
+            depth,
+            mut path,
+            ..
+        } in objects
+        {
+            let mut writer = self.writer(response, Some(fragment_id));
+            let data = writer.write_object(object_id, depth, &mut path, Some(fragment_id));
+            let mut entry = serde_json::Map::new();
+            entry.insert("data".into(), data);
+            entry.insert("path".into(), Value::Array(path));
+            if let Some(label) = &label {
+                entry.insert("label".into(), Value::String(label.clone()));
+            }
+            incremental.push(Value::Object(entry));
+        }
+    }
+
+    /// Adds the remaining items of all streamed lists found so far to the incremental payload.
+    pub(crate) fn write_streamed_lists(&mut self, response: &ResponseBuilder<'_>, incremental: &mut Vec<Value>) {
+        for StreamedList {
+            fragment_id,
+            list,
+            label,
+            initial_count,
+            depth,
+            mut path,
+        } in std::mem::take(&mut self.streamed_lists)
+        {
+            let mut writer = self.writer(response, fragment_id);
+            let items = match list {
+                StreamedListId::Values(id) => {
+                    let mut items = Vec::new();
+                    for (index, value) in response.data_parts[id].iter().enumerate().skip(initial_count) {
+                        path.push(index.into());
+                        items.push(writer.write_value(value, depth, &mut path, None));
+                        path.pop();
+                    }
+                    items
+                }
+                StreamedListId::Ints(id) => response.data_parts[id][initial_count..]
+                    .iter()
+                    .map(|value| (*value).into())
+                    .collect(),
+                StreamedListId::Floats(id) => response.data_parts[id][initial_count..]
+                    .iter()
+                    .map(|value| (*value).into())
+                    .collect(),
+            };
+            if items.is_empty() {
+                continue;
+            }
+            path.push(initial_count.into());
+            let mut entry = serde_json::Map::new();
+            entry.insert("items".into(), Value::Array(items));
+            entry.insert("path".into(), Value::Array(path));
+            if let Some(label) = label {
+                entry.insert("label".into(), Value::String(label));
+            }
+            incremental.push(Value::Object(entry));
+        }
+    }
+
+    pub(crate) fn subsequent_payload(
+        &mut self,
+        response: &mut ResponseBuilder<'_>,
+        operation_attributes: GraphqlOperationAttributes,
+        mut incremental: Vec<Value>,
+        has_next: bool,
+    ) -> Response {
+        let (mut errors, error_code_counter) = self.take_new_errors(response);
+        // Errors are attached to the first incremental result, the spec doesn't allow top-level
+        // errors in subsequent payloads unless there is nothing else to attach them to.
+        if let Some(Value::Object(entry)) = incremental.first_mut()
+            && let Some(errors) = errors.take()
+        {
+            entry.insert("errors".into(), errors);
+        }
+        Response::Incremental(IncrementalResponse {
+            operation_attributes,
+            data: None,
+            data_is_null: false,
+            errors,
+            incremental,
+            has_next,
+            error_code_counter,
+            extensions: Default::default(),
+        })
+    }
+
+    fn take_new_errors(&mut self, response: &mut ResponseBuilder<'_>) -> (Option<Value>, ErrorCodeCounter) {
+        response.flush_errors();
+        let operation = response.operation.as_ref();
+        let parts = &response.error_parts.parts()[self.sent_error_parts_count..];
+        self.sent_error_parts_count = response.error_parts.parts().len();
+
+        let mut error_code_counter = ErrorCodeCounter::default();
+        for part in parts {
+            for error in part.errors() {
+                error_code_counter.increment(error.code);
+            }
+            for error in part.shared_query_errors() {
+                error_code_counter.increment(operation.plan.query_modifications[error.error_id].code);
+            }
+        }
+        if error_code_counter.count() == 0 {
+            return (None, error_code_counter);
+        }
+
+        let errors = serde_json::to_value(SerializableErrorParts {
+            error_code_mapping: &response.schema.config.error_code_mapping,
+            keys: &operation.cached.operation.response_keys,
+            query_modifications: &operation.plan.query_modifications,
+            parts,
+            len: error_code_counter.count(),
+        })
+        .unwrap_or_default();
+        (Some(errors), error_code_counter)
+    }
+
+    fn writer<'a>(
+        &'a mut self,
+        response: &'a ResponseBuilder<'_>,
+        fragment_id: Option<DeferredFragmentId>,
+    ) -> Writer<'a> {
+        let operation = response.operation.as_ref();
+        Writer {
+            ctx: Context {
+                keys: &operation.cached.operation.response_keys,
+                data: &response.data_parts,
+                schema: response.schema,
+            },
+            operation,
+            fragment_id,
+            deferred_objects: &mut self.deferred_objects,
+            streamed_lists: &mut self.streamed_lists,
+        }
+    }
+}
+
+struct Writer<'a> {
+    ctx: Context<'a>,
+    operation: &'a PreparedOperation,
+    /// Fragment being written, `None` for the initial payload.
+    fragment_id: Option<DeferredFragmentId>,
+    deferred_objects: &'a mut Vec<DeferredObject>,
+    streamed_lists: &'a mut Vec<StreamedList>,
+}
+
+impl Writer<'_> {
+    /// If `only_fragment_id` is provided, only the fields starting this deferred fragment are
+    /// written. Otherwise fields are written unless they belong to a different deferred fragment
+    /// which will be delivered later.
+    fn write_object(
+        &mut self,
+        object_id: ResponseObjectId,
+        depth: u16,
+        path: &mut Vec<Value>,
+        only_fragment_id: Option<DeferredFragmentId>,
+    ) -> Value {
+        let mut map = serde_json::Map::new();
+        let mut deferred_fragment_ids = Vec::new();
+        for ResponseObjectField { key, value } in self.ctx.data[object_id].fields() {
+            // Fields without a query position are extra fields, not part of the response.
+            let Some(query_position) = key.query_position else {
+                continue;
+            };
+            let position = ResponseFieldPosition { depth, query_position };
+            let fragment_id = self.delivered_fragment_id(position);
+            match (only_fragment_id, fragment_id) {
+                (Some(only_fragment_id), _) if fragment_id != Some(only_fragment_id) => continue,
+                (None, Some(id)) if fragment_id != self.fragment_id => {
+                    if !deferred_fragment_ids.contains(&id) {
+                        deferred_fragment_ids.push(id);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            let key = &self.ctx.keys[key.response_key];
+            path.push(Value::String(key.to_string()));
+            let value = self.write_value(value, depth + 1, path, Some(position));
+            path.pop();
+            map.insert(key.to_string(), value);
+        }
+
+        for fragment_id in deferred_fragment_ids {
+            self.deferred_objects.push(DeferredObject {
+                fragment_id,
+                object_id,
+                depth,
+                path: path.clone(),
+            });
+        }
+
+        Value::Object(map)
+    }
+
+    /// `depth` is the depth of the objects within this value.
+    fn write_value(
+        &mut self,
+        value: &ResponseValue,
+        depth: u16,
+        path: &mut Vec<Value>,
+        position: Option<ResponseFieldPosition>,
+    ) -> Value {
+        let list = match value {
+            ResponseValue::Object { id } => return self.write_object(*id, depth, path, None),
+            ResponseValue::List { id } => StreamedListId::Values(*id),
+            ResponseValue::IntList { id } => StreamedListId::Ints(*id),
+            ResponseValue::FloatList { id } => StreamedListId::Floats(*id),
+            value => {
+                return serde_json::to_value(SerializableResponseValue { ctx: self.ctx, value }).unwrap_or_default();
+            }
+        };
+
+        let stream = position.and_then(|position| {
+            let streams = &self
+                .operation
+                .plan
+                .query_modifications
+                .included_streamed_response_fields;
+            streams
+                .binary_search_by(|stream| stream.position.cmp(&position))
+                .ok()
+                .map(|ix| &streams[ix])
+        });
+        let len = match list {
+            StreamedListId::Values(id) => self.ctx.data[id].len(),
+            StreamedListId::Ints(id) => self.ctx.data[id].len(),
+            StreamedListId::Floats(id) => self.ctx.data[id].len(),
+        };
+        let count = match stream {
+            Some(stream) if stream.initial_count < len => {
+                self.streamed_lists.push(StreamedList {
+                    fragment_id: self.fragment_id,
+                    list,
+                    label: stream.label.clone(),
+                    initial_count: stream.initial_count,
+                    depth,
+                    path: path.clone(),
+                });
+                stream.initial_count
+            }
+            _ => len,
+        };
+
+        match list {
+            StreamedListId::Values(id) => {
+                let mut items = Vec::with_capacity(count);
+                for (index, value) in self.ctx.data[id][..count].iter().enumerate() {
+                    path.push(index.into());
+                    items.push(self.write_value(value, depth, path, None));
+                    path.pop();
+                }
+                Value::Array(items)
+            }
+            StreamedListId::Ints(id) => self.ctx.data[id][..count].iter().map(|value| (*value).into()).collect(),
+            StreamedListId::Floats(id) => self.ctx.data[id][..count].iter().map(|value| (*value).into()).collect(),
+        }
+    }
+
+    fn delivered_fragment_id(&self, position: ResponseFieldPosition) -> Option<DeferredFragmentId> {
+        let fields = &self.operation.cached.query_plan.deferred_response_fields;
+        let ix = fields.binary_search_by(|field| field.position.cmp(&position)).ok()?;
+        self.operation
+            .plan
+            .incremental_delivery
+            .delivered_fragment_id(fields[ix].fragment_id)
+    }
+}
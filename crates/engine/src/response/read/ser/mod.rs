@@ -1,10 +1,15 @@
 mod data;
 mod errors;
+mod incremental;
 
 use operation::ResponseKeys;
 use serde::ser::SerializeMap;
 
-use crate::response::{ExecutedResponse, RefusedRequestResponse, RequestErrorResponse, Response};
+pub(crate) use incremental::IncrementalResponseWriter;
+
+use crate::response::{
+    CachedResponse, ExecutedResponse, IncrementalResponse, RefusedRequestResponse, RequestErrorResponse, Response,
+};
 
 impl serde::Serialize for Response {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -27,7 +32,12 @@ impl serde::Serialize for Response {
                     map.serialize_entry(
                         "data",
                         &data::SerializableResponseData {
-                            ctx: data::Context { keys, data, schema },
+                            ctx: data::Context {
+                                keys,
+                                data: &data.parts,
+                                schema,
+                            },
+                            root: data.root,
                         },
                     )?;
                 } else {
@@ -41,7 +51,8 @@ impl serde::Serialize for Response {
                             error_code_mapping: &schema.config.error_code_mapping,
                             query_modifications: &operation.plan.query_modifications,
                             keys,
-                            errors,
+                            parts: errors.parts(),
+                            len: errors.len(),
                         },
                     )?;
                 }
@@ -52,6 +63,38 @@ impl serde::Serialize for Response {
 
                 map.end()
             }
+            Response::Incremental(IncrementalResponse {
+                data,
+                errors,
+                incremental,
+                has_next,
+                extensions,
+                ..
+            }) => {
+                let mut map = serializer.serialize_map(None)?;
+                if let Some(data) = data {
+                    map.serialize_entry("data", data)?;
+                }
+                if let Some(errors) = errors {
+                    map.serialize_entry("errors", errors)?;
+                }
+                if !incremental.is_empty() {
+                    map.serialize_entry("incremental", incremental)?;
+                }
+                map.serialize_entry("hasNext", has_next)?;
+                if !extensions.is_empty() {
+                    map.serialize_entry("extensions", extensions)?;
+                }
+                map.end()
+            }
+            Response::Cached(CachedResponse { data, extensions, .. }) => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("data", data)?;
+                if !extensions.is_empty() {
+                    map.serialize_entry("extensions", extensions)?;
+                }
+                map.end()
+            }
             Response::RequestError(RequestErrorResponse {
                 errors,
                 extensions,
@@ -2,15 +2,15 @@ mod deserialize;
 mod merge;
 mod part;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use grafbase_telemetry::graphql::{GraphqlOperationAttributes, GraphqlResponseStatus};
 use schema::{ObjectDefinitionId, Schema};
 use walker::Walk;
 
 use super::{
-    DataParts, ErrorPartBuilder, ErrorParts, ExecutedResponse, GraphqlError, Response, ResponseData, ResponseObject,
-    ResponseObjectId, ResponseObjectRef, ResponseObjectSet, ResponseValueId,
+    DataParts, ErrorPartBuilder, ErrorParts, ExecutedResponse, GraphqlError, PropagatedHeaders, Response, ResponseData,
+    ResponseObject, ResponseObjectId, ResponseObjectRef, ResponseObjectSet, ResponseValueId,
 };
 use crate::prepare::{OperationPlanContext, PreparedOperation, ResponseObjectSetId};
 pub(crate) use deserialize::*;
@@ -24,6 +24,8 @@ pub(crate) struct ResponseBuilder<'ctx> {
     pub(super) data_parts: DataParts,
     pub(super) error_parts: ErrorParts,
     errors: ErrorPartBuilder<'ctx>,
+    cache_max_age: Option<Duration>,
+    propagated_headers: PropagatedHeaders,
 }
 
 impl<'ctx> ResponseBuilder<'ctx> {
@@ -41,6 +43,8 @@ impl<'ctx> ResponseBuilder<'ctx> {
             data_parts,
             error_parts: ErrorParts::default(),
             errors: ErrorPartBuilder::new(operation),
+            cache_max_age: None,
+            propagated_headers: Default::default(),
         }
     }
 
@@ -83,6 +87,10 @@ impl<'ctx> ResponseBuilder<'ctx> {
     pub fn ingest(&mut self, part: ResponsePartBuilder<'ctx>) -> PartIngestionResult {
         self.data_parts.insert(part.data);
         self.error_parts.push(part.errors);
+        if let Some(max_age) = part.cache_max_age {
+            self.cache_max_age = Some(self.cache_max_age.map_or(max_age, |current| current.min(max_age)));
+        }
+        self.propagated_headers.append(part.propagated_headers);
 
         if part.propagated_null_up_to_root {
             self.root = None;
@@ -121,6 +129,15 @@ impl<'ctx> ResponseBuilder<'ctx> {
         }
     }
 
+    /// Moves the errors pushed so far into their own part, so that they can be sent in an
+    /// incremental payload.
+    pub fn flush_errors(&mut self) {
+        if !self.errors.is_empty() {
+            let errors = std::mem::replace(&mut self.errors, ErrorPartBuilder::new(self.operation));
+            self.error_parts.push(errors);
+        }
+    }
+
     pub fn graphql_status(&self) -> GraphqlResponseStatus {
         if self.errors.is_empty() && self.error_parts.is_empty() {
             GraphqlResponseStatus::Success
@@ -145,6 +162,9 @@ impl<'ctx> ResponseBuilder<'ctx> {
             }),
             errors: self.error_parts,
             extensions: Default::default(),
+            subgraph_cache_max_age: self.cache_max_age,
+            cache_control: None,
+            propagated_headers: self.propagated_headers.into_header_map(),
         })
     }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use id_newtypes::IdRange;
 use schema::Schema;
 use walker::Walk as _;
@@ -5,8 +7,8 @@ use walker::Walk as _;
 use crate::{
     prepare::{DefaultFieldShapeId, OnRootFieldsError, PreparedOperation, ResponseObjectSetId, RootFieldsShapeId},
     response::{
-        DataPart, ErrorPartBuilder, GraphqlError, ResponseObjectField, ResponseObjectId, ResponseObjectRef,
-        ResponseObjectSet, ResponsePath, ResponseValueId,
+        DataPart, ErrorPartBuilder, GraphqlError, PropagatedHeaders, ResponseObjectField, ResponseObjectId,
+        ResponseObjectRef, ResponseObjectSet, ResponsePath, ResponseValueId,
     },
 };
 
@@ -22,6 +24,10 @@ pub(crate) struct ResponsePartBuilder<'ctx> {
     pub(super) propagated_null_at: Vec<ResponseValueId>,
     pub(super) object_updates: Vec<ObjectUpdate>,
     pub(super) object_sets: Vec<(ResponseObjectSetId, ResponseObjectSet)>,
+    /// Smallest max-age of the subgraph responses used to build this part, for the response cache.
+    pub(super) cache_max_age: Option<Duration>,
+    /// Subgraph response headers to propagate to the client.
+    pub(super) propagated_headers: PropagatedHeaders,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, id_derives::Id)]
@@ -39,9 +45,19 @@ impl<'ctx> ResponsePartBuilder<'ctx> {
             propagated_null_up_to_root: false,
             propagated_null_at: Vec::new(),
             object_sets: Vec::new(),
+            cache_max_age: None,
+            propagated_headers: Default::default(),
         }
     }
 
+    pub fn restrict_cache_max_age(&mut self, max_age: Duration) {
+        self.cache_max_age = Some(self.cache_max_age.map_or(max_age, |current| current.min(max_age)));
+    }
+
+    pub fn propagate_headers(&mut self, headers: PropagatedHeaders) {
+        self.propagated_headers.append(headers);
+    }
+
     pub fn into_seed_state<'parent>(self, shape_id: RootFieldsShapeId) -> SeedState<'ctx, 'parent> {
         SeedState::new(self, shape_id)
     }
@@ -20,6 +20,8 @@ pub enum Event {
     Http(ExecutedHttpRequest),
     /// A custom extension-generated event
     Extension(ExtensionEvent),
+    /// A subgraph circuit breaker changed state
+    CircuitBreaker(CircuitBreakerStateChange),
 }
 
 /// Represents a completed GraphQL operation execution.
@@ -175,6 +177,35 @@ impl ExecutedHttpRequest {
     }
 }
 
+/// Represents a state change of the circuit breaker of a subgraph.
+#[derive(Debug)]
+pub struct CircuitBreakerStateChange {
+    pub subgraph_name: String,
+    pub from: CircuitBreakerState,
+    pub to: CircuitBreakerState,
+}
+
+/// The state of a subgraph circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitBreakerState {
+    /// Requests are sent to the subgraph
+    Closed,
+    /// Requests are rejected without reaching the subgraph
+    Open,
+    /// A single probe request is sent to decide whether the subgraph recovered
+    HalfOpen,
+}
+
+impl CircuitBreakerState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
 /// Represents a custom event emitted by an extension.
 #[derive(Debug)]
 pub struct ExtensionEvent {
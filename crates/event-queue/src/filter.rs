@@ -14,4 +14,5 @@ pub enum EventFilterType {
     SubgraphRequest = 1 << 1,
     HttpRequest = 1 << 2,
     Extension = 1 << 3,
+    CircuitBreaker = 1 << 4,
 }
@@ -130,6 +130,32 @@ impl EventQueue {
         Ok(())
     }
 
+    /// Pushes a circuit breaker state change event to the queue.
+    ///
+    /// The event is only queued if circuit breaker events are allowed by the current filter configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `subgraph_name` - The name of the subgraph protected by the circuit breaker
+    /// * `from` - The previous state of the circuit breaker
+    /// * `to` - The new state of the circuit breaker
+    pub fn push_circuit_breaker_state_change(
+        &self,
+        subgraph_name: &str,
+        from: CircuitBreakerState,
+        to: CircuitBreakerState,
+    ) {
+        if !self.must_keep_event(EventFilterType::CircuitBreaker) {
+            return;
+        }
+
+        self.queue.push(Event::CircuitBreaker(CircuitBreakerStateChange {
+            subgraph_name: subgraph_name.to_string(),
+            from,
+            to,
+        }));
+    }
+
     /// Pops an event from the queue.
     ///
     /// This method removes and returns the next event from the queue if one is available.
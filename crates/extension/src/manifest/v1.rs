@@ -133,6 +133,10 @@ pub struct ContractType {
 pub struct HooksType {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub event_filter: Option<EventFilter>,
+    /// Whether the extension implements the `on_graphql_response` hook. The response body is only
+    /// copied into the extension if it does.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub on_graphql_response: bool,
 }
 
 // Allows us to add fields later, as adding a value to an enum that doesn't have one would be
@@ -638,7 +642,7 @@ mod tests {
                 name: "hooks-test".to_string(),
                 version: semver::Version::new(1, 0, 0),
             },
-            r#type: Type::Hooks(HooksType { event_filter: None }),
+            r#type: Type::Hooks(HooksType::default()),
             sdk_version: semver::Version::new(0, 1, 0),
             minimum_gateway_version: semver::Version::new(0, 1, 0),
             sdl: None,
@@ -676,7 +680,7 @@ mod tests {
                 name: "hooks-test".to_string(),
                 version: semver::Version::new(1, 0, 0),
             },
-            r#type: Type::Hooks(HooksType { event_filter: None }),
+            r#type: Type::Hooks(HooksType::default()),
             sdk_version: semver::Version::new(0, 1, 0),
             minimum_gateway_version: semver::Version::new(0, 1, 0),
             sdl: None,
@@ -724,6 +728,7 @@ mod tests {
             },
             r#type: Type::Hooks(HooksType {
                 event_filter: Some(EventFilter::All),
+                on_graphql_response: false,
             }),
             sdk_version: semver::Version::new(0, 1, 0),
             minimum_gateway_version: semver::Version::new(0, 1, 0),
@@ -765,6 +770,7 @@ mod tests {
             },
             r#type: Type::Hooks(HooksType {
                 event_filter: Some(EventFilter::Types(vec![EventType::Operation, EventType::Extension])),
+                on_graphql_response: false,
             }),
             sdk_version: semver::Version::new(0, 1, 0),
             minimum_gateway_version: semver::Version::new(0, 1, 0),
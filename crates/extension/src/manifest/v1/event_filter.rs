@@ -12,4 +12,5 @@ pub enum EventType {
     SubgraphRequest,
     HttpRequest,
     Extension,
+    CircuitBreaker,
 }
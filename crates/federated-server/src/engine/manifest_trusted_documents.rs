@@ -0,0 +1,253 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock},
+    time::Duration,
+};
+
+use notify::{EventHandler, EventKind, PollWatcher, Watcher};
+use runtime::trusted_documents_client::{
+    TrustedDocumentsClient, TrustedDocumentsEnforcementMode, TrustedDocumentsError, TrustedDocumentsResult,
+};
+
+/// Document bodies by id, for each client name.
+type Documents = HashMap<String, HashMap<String, String>>;
+
+/// Trusted documents read from persisted query manifests on disk, one per client name. The
+/// manifests are reloaded whenever they change.
+pub(crate) struct ManifestTrustedDocuments {
+    documents: Arc<RwLock<Documents>>,
+    enforcement_mode: TrustedDocumentsEnforcementMode,
+    bypass_header: Option<(String, String)>,
+    // Stops polling the manifests once the engine using them is dropped.
+    _watcher: PollWatcher,
+}
+
+impl ManifestTrustedDocuments {
+    /// Loads all the manifests, failing if any of them can't be read. Later reload errors are
+    /// only logged and the previous documents of the client are kept.
+    pub(crate) fn new(
+        manifests: &BTreeMap<String, PathBuf>,
+        enforcement_mode: TrustedDocumentsEnforcementMode,
+        bypass_header: Option<(String, String)>,
+    ) -> crate::Result<Self> {
+        let mut documents = Documents::with_capacity(manifests.len());
+
+        for (client_name, path) in manifests {
+            let manifest = load_manifest(path).map_err(|err| {
+                crate::Error::InternalError(format!(
+                    "Failed to load the trusted documents manifest of client '{client_name}' at {}: {err}",
+                    path.display()
+                ))
+            })?;
+
+            documents.insert(client_name.clone(), manifest);
+        }
+
+        let documents = Arc::new(RwLock::new(documents));
+
+        let reloader = ManifestReloader {
+            manifests: manifests.clone(),
+            documents: documents.clone(),
+        };
+
+        let config = notify::Config::default().with_poll_interval(Duration::from_secs(1));
+        let mut watcher = PollWatcher::new(reloader, config).map_err(|err| {
+            crate::Error::InternalError(format!("Failed to watch trusted documents manifests: {err}"))
+        })?;
+
+        for path in manifests.values() {
+            watcher
+                .watch(path, notify::RecursiveMode::NonRecursive)
+                .map_err(|err| crate::Error::InternalError(format!("Failed to watch {}: {err}", path.display())))?;
+        }
+
+        Ok(Self {
+            documents,
+            enforcement_mode,
+            bypass_header,
+            _watcher: watcher,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl TrustedDocumentsClient for ManifestTrustedDocuments {
+    fn enforcement_mode(&self) -> TrustedDocumentsEnforcementMode {
+        self.enforcement_mode
+    }
+
+    fn bypass_header(&self) -> Option<(&str, &str)> {
+        self.bypass_header
+            .as_ref()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    async fn fetch(&self, client_name: &str, document_id: &str) -> TrustedDocumentsResult<String> {
+        self.documents
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(client_name)
+            .and_then(|documents| documents.get(document_id))
+            .cloned()
+            .ok_or(TrustedDocumentsError::DocumentNotFound)
+    }
+}
+
+struct ManifestReloader {
+    manifests: BTreeMap<String, PathBuf>,
+    documents: Arc<RwLock<Documents>>,
+}
+
+impl EventHandler for ManifestReloader {
+    fn handle_event(&mut self, event: notify::Result<notify::Event>) {
+        let event = match event {
+            Ok(event) => event,
+            Err(err) => {
+                tracing::error!("error watching trusted documents manifests: {err}");
+                return;
+            }
+        };
+
+        if !matches!(
+            event.kind,
+            EventKind::Any | EventKind::Create(_) | EventKind::Modify(_) | EventKind::Other
+        ) {
+            return;
+        }
+
+        // Few manifests are expected, reloading all of them is simpler than matching the event
+        // paths with relative manifest paths.
+        for (client_name, path) in &self.manifests {
+            tracing::debug!("reloading trusted documents manifest of client {client_name}");
+
+            match load_manifest(path) {
+                Ok(manifest) => {
+                    self.documents
+                        .write()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .insert(client_name.clone(), manifest);
+                }
+                Err(err) => {
+                    tracing::error!(
+                        "error reloading trusted documents manifest of client {client_name} at {}: {err}",
+                        path.display()
+                    );
+                }
+            }
+        }
+    }
+}
+
+const APOLLO_MANIFEST_FORMAT: &str = "apollo-persisted-query-manifest";
+
+/// https://www.apollographql.com/docs/graphos/platform/security/persisted-queries#manifest-format
+#[derive(serde::Deserialize)]
+struct ApolloManifest {
+    operations: Vec<ApolloOperation>,
+}
+
+#[derive(serde::Deserialize)]
+struct ApolloOperation {
+    id: String,
+    body: String,
+}
+
+fn load_manifest(path: &Path) -> Result<HashMap<String, String>, String> {
+    let content = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    parse_manifest(&content)
+}
+
+/// Apollo manifests are identified by their `format` key, anything else is read as a Relay
+/// manifest: a map of document ids to document bodies.
+fn parse_manifest(content: &str) -> Result<HashMap<String, String>, String> {
+    let object: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(content).map_err(|err| err.to_string())?;
+
+    match object.get("format") {
+        Some(serde_json::Value::String(format)) if format == APOLLO_MANIFEST_FORMAT => {
+            let manifest: ApolloManifest =
+                serde_json::from_value(serde_json::Value::Object(object)).map_err(|err| err.to_string())?;
+
+            Ok(manifest
+                .operations
+                .into_iter()
+                .map(|operation| (operation.id, operation.body))
+                .collect())
+        }
+        Some(serde_json::Value::String(format)) => Err(format!("unsupported manifest format '{format}'")),
+        _ => serde_json::from_value(serde_json::Value::Object(object)).map_err(|err| err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apollo_manifest() {
+        let manifest = parse_manifest(
+            r#"{
+                "format": "apollo-persisted-query-manifest",
+                "version": 1,
+                "operations": [
+                    { "id": "abc", "body": "query A { a }", "name": "A", "type": "query" },
+                    { "id": "def", "body": "mutation B { b }", "name": "B", "type": "mutation" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.len(), 2);
+        assert_eq!(manifest["abc"], "query A { a }");
+        assert_eq!(manifest["def"], "mutation B { b }");
+    }
+
+    #[test]
+    fn relay_manifest() {
+        let manifest = parse_manifest(r#"{ "abc": "query A { a }", "operations": "query B { b }" }"#).unwrap();
+
+        assert_eq!(manifest.len(), 2);
+        assert_eq!(manifest["abc"], "query A { a }");
+        assert_eq!(manifest["operations"], "query B { b }");
+    }
+
+    #[test]
+    fn invalid_manifest() {
+        assert!(parse_manifest(r#"{ "abc": 1 }"#).is_err());
+        assert!(parse_manifest(r#"{ "format": "apollo-persisted-query-manifest", "abc": "query A { a }" }"#).is_err());
+    }
+
+    #[test]
+    fn unsupported_manifest_format() {
+        let err = parse_manifest(r#"{ "format": "other-manifest", "operations": [] }"#).unwrap_err();
+
+        assert_eq!(err, "unsupported manifest format 'other-manifest'");
+    }
+
+    #[test]
+    fn reloads_changed_manifest() {
+        use futures_lite::future::block_on;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("web.json");
+        std::fs::write(&path, r#"{ "abc": "query A { a }" }"#).unwrap();
+
+        let manifests = BTreeMap::from([("web".to_string(), path.clone())]);
+        let client = ManifestTrustedDocuments::new(&manifests, TrustedDocumentsEnforcementMode::Enforce, None).unwrap();
+
+        assert_eq!(block_on(client.fetch("web", "abc")).unwrap(), "query A { a }");
+        assert!(block_on(client.fetch("ios", "abc")).is_err());
+        assert!(block_on(client.fetch("web", "def")).is_err());
+
+        std::fs::write(&path, r#"{ "def": "query B { b }", "ghi": "query C { c }" }"#).unwrap();
+
+        let reloaded = (0..50).any(|_| {
+            std::thread::sleep(Duration::from_millis(100));
+            block_on(client.fetch("web", "def")).is_ok()
+        });
+
+        assert!(reloaded);
+        assert!(block_on(client.fetch("web", "abc")).is_err());
+    }
+}
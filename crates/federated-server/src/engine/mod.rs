@@ -1,4 +1,5 @@
 mod hive_persisted_documents;
+mod manifest_trusted_documents;
 mod reloader;
 mod runtime;
 mod trusted_documents_client;
@@ -5,12 +5,14 @@ use extension_catalog::ExtensionCatalog;
 use gateway_config::{EntityCachingRedisConfig, operation_caching::OperationCacheConfig};
 use grafbase_telemetry::metrics::EngineMetrics;
 use hive_console_sdk::persisted_documents::PersistedDocumentsManager;
-use runtime::{entity_cache::EntityCache, trusted_documents_client::TrustedDocumentsEnforcementMode};
+use runtime::{
+    entity_cache::EntityCache, health::DependencyHealth, trusted_documents_client::TrustedDocumentsEnforcementMode,
+};
 use runtime_local::{
     InMemoryEntityCache, InMemoryOperationCache, NativeFetcher, RedisEntityCache,
     operation_cache::{RedisOperationCache, TieredOperationCache},
     rate_limiting::{in_memory::key_based::InMemoryRateLimiter, redis::RedisRateLimiter},
-    redis::{RedisPoolFactory, RedisTlsConfig},
+    redis::{Pool, RedisPoolFactory, RedisTlsConfig},
 };
 use url::Url;
 use wasi_component_loader::extension::EngineWasmExtensions;
@@ -19,6 +21,7 @@ use crate::{
     engine::{
         EngineBuildContext,
         hive_persisted_documents::HivePersistedDocuments,
+        manifest_trusted_documents::ManifestTrustedDocuments,
         trusted_documents_client::{TrustedDocumentsClient, TrustedDocumentsClientConfig},
     },
     graph::{Graph, object_storage_host},
@@ -38,6 +41,9 @@ pub struct EngineRuntime {
     pub(crate) operation_cache: TieredOperationCache<Arc<CachedOperation>>,
     operation_cache_config: OperationCacheConfig,
     redis_factory: Arc<tokio::sync::Mutex<RedisPoolFactory>>,
+    /// Redis pools checked by the readiness endpoint, with the feature using them.
+    redis_backends: Vec<(&'static str, Pool)>,
+    graph_version: Option<String>,
 }
 
 impl EngineRuntime {
@@ -50,6 +56,7 @@ impl EngineRuntime {
         tracing::debug!("Build engine runtime.");
 
         let mut redis_factory = RedisPoolFactory::default();
+        let mut redis_backends = Vec::new();
         let config_watcher = ConfigWatcher::init(ctx.gateway_config.clone(), ctx.hot_reload_config_path.cloned())?;
         let meter = grafbase_telemetry::metrics::meter_from_global_provider();
 
@@ -66,6 +73,7 @@ impl EngineRuntime {
                 let pool = redis_factory
                     .pool(config.redis.url.as_str(), tls)
                     .map_err(|e| crate::Error::InternalError(e.to_string()))?;
+                redis_backends.push(("rate_limiting", pool.clone()));
 
                 let global_config = runtime_local::rate_limiting::redis::RateLimitRedisConfig {
                     key_prefix: &config.redis.key_prefix,
@@ -79,8 +87,16 @@ impl EngineRuntime {
         };
 
         tracing::debug!("Building cache");
-        let entity_cache = build_entity_cache(&ctx.gateway_config.entity_caching, &mut redis_factory)?;
-        let operation_cache = build_operation_cache(&ctx.gateway_config.operation_caching, &mut redis_factory)?;
+        let entity_cache = build_entity_cache(
+            &ctx.gateway_config.entity_caching,
+            &mut redis_factory,
+            &mut redis_backends,
+        )?;
+        let operation_cache = build_operation_cache(
+            &ctx.gateway_config.operation_caching,
+            &mut redis_factory,
+            &mut redis_backends,
+        )?;
 
         tracing::debug!("Building extensions");
 
@@ -103,14 +119,20 @@ impl EngineRuntime {
             TrustedDocumentsEnforcementMode::Allow
         };
 
-        let trusted_documents = if let Some((access_token, branch_id)) = ctx.access_token.zip(graph.branch_id()) {
-            let bypass_header = cfg
-                .bypass_header
-                .bypass_header_name
-                .as_ref()
-                .zip(cfg.bypass_header.bypass_header_value.as_ref())
-                .map(|(name, value)| (name.clone().into(), String::from(value.as_str())));
+        let bypass_header = cfg
+            .bypass_header
+            .bypass_header_name
+            .as_ref()
+            .zip(cfg.bypass_header.bypass_header_value.as_ref())
+            .map(|(name, value)| (name.clone().into(), String::from(value.as_str())));
 
+        let trusted_documents = if !cfg.manifests.is_empty() {
+            runtime::trusted_documents_client::Client::new(ManifestTrustedDocuments::new(
+                &cfg.manifests,
+                enforcement_mode,
+                bypass_header,
+            )?)
+        } else if let Some((access_token, branch_id)) = ctx.access_token.zip(graph.branch_id()) {
             runtime::trusted_documents_client::Client::new(TrustedDocumentsClient::new(TrustedDocumentsClientConfig {
                 branch_id,
                 bypass_header,
@@ -147,6 +169,8 @@ impl EngineRuntime {
             operation_cache,
             operation_cache_config: ctx.gateway_config.operation_caching.clone(),
             redis_factory: Arc::new(tokio::sync::Mutex::new(redis_factory)),
+            redis_backends,
+            graph_version: graph.version_id().map(|id| id.to_string()),
         };
 
         Ok(runtime)
@@ -192,9 +216,10 @@ impl engine::Runtime for EngineRuntime {
 
     async fn clone_and_adjust_for_contract(&self, schema: &Arc<Schema>) -> Result<Self, String> {
         let mut redis_facttory = self.redis_factory.lock().await;
-        let entity_cache = build_entity_cache(&self.entity_cache_config, &mut redis_facttory)
+        // The pools are deduplicated by the factory, the backends are the same as ours.
+        let entity_cache = build_entity_cache(&self.entity_cache_config, &mut redis_facttory, &mut Vec::new())
             .map_err(|err| format!("Failed to build entity cache: {err}"))?;
-        let operation_cache = build_operation_cache(&self.operation_cache_config, &mut redis_facttory)
+        let operation_cache = build_operation_cache(&self.operation_cache_config, &mut redis_facttory, &mut Vec::new())
             .map_err(|err| format!("Failed to build operation cache: {err}"))?;
         Ok(EngineRuntime {
             fetcher: self.fetcher.clone(),
@@ -211,13 +236,34 @@ impl engine::Runtime for EngineRuntime {
             operation_cache,
             operation_cache_config: self.operation_cache_config.clone(),
             redis_factory: self.redis_factory.clone(),
+            redis_backends: self.redis_backends.clone(),
+            graph_version: self.graph_version.clone(),
         })
     }
+
+    fn graph_version(&self) -> Option<String> {
+        self.graph_version.clone()
+    }
+
+    async fn dependencies_health(&self) -> Vec<DependencyHealth> {
+        let checks = self.redis_backends.iter().map(|&(name, ref pool)| async move {
+            let result = runtime_local::redis::ping(pool).await;
+
+            DependencyHealth {
+                name,
+                reachable: result.is_ok(),
+                error: result.err().map(|err| err.to_string()),
+            }
+        });
+
+        futures_util::future::join_all(checks).await
+    }
 }
 
 fn build_entity_cache(
     config: &gateway_config::EntityCachingConfig,
     redis_factory: &mut RedisPoolFactory,
+    redis_backends: &mut Vec<(&'static str, Pool)>,
 ) -> Result<Box<dyn EntityCache>, crate::Error> {
     Ok(match config.storage {
         gateway_config::EntityCachingStorage::Memory => Box::new(InMemoryEntityCache::default()),
@@ -231,6 +277,7 @@ fn build_entity_cache(
             let pool = redis_factory
                 .pool(url.as_str(), tls)
                 .map_err(|e| crate::Error::InternalError(e.to_string()))?;
+            redis_backends.push(("entity_cache", pool.clone()));
             Box::new(RedisEntityCache::new(pool, key_prefix))
         }
     })
@@ -239,6 +286,7 @@ fn build_entity_cache(
 fn build_operation_cache(
     config: &OperationCacheConfig,
     redis_factory: &mut RedisPoolFactory,
+    redis_backends: &mut Vec<(&'static str, Pool)>,
 ) -> Result<TieredOperationCache<Arc<CachedOperation>>, crate::Error> {
     Ok(match (config.enabled, config.redis.as_ref()) {
         (false, _) => TieredOperationCache::new(InMemoryOperationCache::inactive(), None),
@@ -253,6 +301,7 @@ fn build_operation_cache(
             let pool = redis_factory
                 .pool(redis_config.url.as_ref(), tls)
                 .map_err(|e| crate::Error::InternalError(e.to_string()))?;
+            redis_backends.push(("operation_cache", pool.clone()));
 
             TieredOperationCache::new(
                 InMemoryOperationCache::new(config.limit),
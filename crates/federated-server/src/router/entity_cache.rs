@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use http::{HeaderMap, StatusCode};
+use runtime::entity_cache::CacheTag;
+
+use crate::router::EngineWatcher;
+
+pub(super) struct InvalidationState<R: engine::Runtime> {
+    pub engine: EngineWatcher<R>,
+    pub secret: String,
+}
+
+/// Entries matching any of the selectors are purged.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct InvalidationRequest {
+    /// Every entry fetched from these subgraphs.
+    #[serde(default)]
+    subgraphs: Vec<String>,
+    /// Every entity of these types.
+    #[serde(default)]
+    types: Vec<String>,
+    /// Individual entities.
+    #[serde(default)]
+    entities: Vec<EntitySelector>,
+    /// Tags sent by the subgraphs in the `Cache-Tag` response header.
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntitySelector {
+    #[serde(rename = "type")]
+    type_name: String,
+    key: serde_json::Value,
+}
+
+impl InvalidationRequest {
+    fn into_tags(self) -> Vec<CacheTag> {
+        let Self {
+            subgraphs,
+            types,
+            entities,
+            tags,
+        } = self;
+
+        subgraphs
+            .into_iter()
+            .map(CacheTag::Subgraph)
+            .chain(types.into_iter().map(CacheTag::EntityType))
+            .chain(
+                entities
+                    .into_iter()
+                    .map(|entity| CacheTag::entity(entity.type_name, &entity.key)),
+            )
+            .chain(tags.into_iter().map(CacheTag::Custom))
+            .collect()
+    }
+}
+
+pub(super) async fn invalidate<R: engine::Runtime>(
+    State(state): State<Arc<InvalidationState<R>>>,
+    headers: HeaderMap,
+    Json(request): Json<InvalidationRequest>,
+) -> Response {
+    let authorized = headers
+        .get(http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        // blake3 hashes are compared in constant time, so the comparison doesn't leak the secret.
+        .is_some_and(|token| blake3::hash(token.as_bytes()) == blake3::hash(state.secret.as_bytes()));
+
+    if !authorized {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let tags = request.into_tags();
+    if tags.is_empty() {
+        return (StatusCode::BAD_REQUEST, "Nothing to invalidate").into_response();
+    }
+
+    let engine = state.engine.borrow().clone();
+    match engine.purge_entity_cache(&tags).await {
+        Ok(()) => {
+            tracing::info!("Purged entity cache entries for {} tags", tags.len());
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(err) => {
+            tracing::error!("Failed to purge the entity cache: {err}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to purge the entity cache").into_response()
+        }
+    }
+}
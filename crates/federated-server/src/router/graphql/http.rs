@@ -11,13 +11,20 @@ pub(crate) async fn execute<R: engine::Runtime, SR: ServerRuntime>(
     let engine = state.engine.borrow().clone();
 
     let (parts, body) = request.into_parts();
-    let body = axum::body::to_bytes(body, state.request_body_limit_bytes).map_err(|error| {
+
+    // Multipart requests carrying file uploads have their own limit.
+    let body_limit_bytes = state
+        .file_uploads_body_limit_bytes
+        .filter(|_| is_multipart_form_data(&parts.headers))
+        .unwrap_or(state.request_body_limit_bytes);
+
+    let body = axum::body::to_bytes(body, body_limit_bytes).map_err(move |error| {
         if let Some(source) = std::error::Error::source(&error)
             && source.is::<http_body_util::LengthLimitError>()
         {
             return (
                 http::StatusCode::PAYLOAD_TOO_LARGE,
-                format!("Request body exceeded: {}", state.request_body_limit_bytes),
+                format!("Request body exceeded: {body_limit_bytes}"),
             );
         }
         (http::StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
@@ -35,3 +42,11 @@ pub(crate) async fn execute<R: engine::Runtime, SR: ServerRuntime>(
 
     response
 }
+
+fn is_multipart_form_data(headers: &http::HeaderMap) -> bool {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|essence| essence.trim().eq_ignore_ascii_case("multipart/form-data"))
+}
@@ -1,15 +1,28 @@
-use std::net::SocketAddr;
+use std::{borrow::Cow, net::SocketAddr, sync::Arc, time::Instant};
 
-use gateway_config::{HealthConfig, TlsConfig};
+use engine::Engine;
+use engine_schema::GraphqlSubgraph;
+use extension_catalog::ExtensionCatalog;
+use gateway_config::{HealthConfig, SubgraphProbeConfig, TlsConfig};
 
-use axum::{Json, Router, routing::get};
-use http::StatusCode;
+use axum::{Json, Router, extract::State, routing::get};
+use http::{HeaderValue, StatusCode};
+use runtime::{
+    fetch::{FetchError, FetchRequest, Fetcher as _, SubgraphEndpointsHealth},
+    health::DependencyHealth,
+};
+
+use super::EngineWatcher;
 
 #[derive(Debug, serde::Serialize)]
 #[serde(tag = "status", rename_all = "lowercase")]
 pub(crate) enum HealthState {
     /// Indicates that the server is healthy and operational.
-    Healthy,
+    Healthy {
+        /// Endpoints of the subgraphs balanced across several replicas.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        subgraphs: Vec<SubgraphEndpointsHealth>,
+    },
 
     /// Indicates that the server is unhealthy and not operational.
     #[expect(dead_code)] // I assume we'll use this sometime
@@ -20,13 +33,221 @@ pub(crate) enum HealthState {
 ///
 /// # Arguments
 ///
-/// - `State(state)`: The server state containing the gateway information.
+/// - `State(engine)`: The current engine, whose fetcher reports the subgraph endpoints health.
 ///
 /// # Returns
 ///
 /// A tuple containing the HTTP status code and a JSON representation of the health status.
-pub(crate) async fn health() -> (StatusCode, Json<HealthState>) {
-    (StatusCode::OK, Json(HealthState::Healthy))
+pub(crate) async fn health<R: engine::Runtime>(
+    State(engine): State<EngineWatcher<R>>,
+) -> (StatusCode, Json<HealthState>) {
+    let subgraphs = engine.borrow().no_contract.runtime.fetcher().endpoints_health();
+
+    (StatusCode::OK, Json(HealthState::Healthy { subgraphs }))
+}
+
+pub(crate) struct ReadinessState<R: engine::Runtime> {
+    pub engine: EngineWatcher<R>,
+    pub extensions: Vec<ExtensionReadiness>,
+    pub subgraph_probe: SubgraphProbeConfig,
+    /// Results of the last subgraph probe, reused until the probe interval elapses.
+    last_subgraph_probe: tokio::sync::Mutex<Option<SubgraphProbe>>,
+}
+
+struct SubgraphProbe {
+    probed_at: Instant,
+    schema_hash: [u8; 32],
+    subgraphs: Vec<SubgraphReadiness>,
+}
+
+impl<R: engine::Runtime> ReadinessState<R> {
+    pub fn new(engine: EngineWatcher<R>, extension_catalog: &ExtensionCatalog, health_config: &HealthConfig) -> Self {
+        // Extensions are initialized before the engine is built, a gateway answering requests
+        // has them all.
+        let extensions = extension_catalog
+            .iter()
+            .map(|extension| ExtensionReadiness {
+                name: extension.manifest.id.name.clone(),
+                version: extension.manifest.id.version.to_string(),
+            })
+            .collect();
+
+        Self {
+            engine,
+            extensions,
+            subgraph_probe: health_config.subgraph_probe.clone(),
+            last_subgraph_probe: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    async fn probe_subgraphs(&self, engine: &Engine<R>) -> Vec<SubgraphReadiness> {
+        // Concurrent readiness checks wait for the probe in progress rather than starting their own.
+        let mut last_probe = self.last_subgraph_probe.lock().await;
+
+        if let Some(probe) = last_probe.as_ref().filter(|probe| {
+            probe.schema_hash == engine.schema.hash && probe.probed_at.elapsed() < self.subgraph_probe.interval
+        }) {
+            return probe.subgraphs.clone();
+        }
+
+        let probes = engine
+            .schema
+            .graphql_subgraphs()
+            .map(|subgraph| probe_subgraph(engine, &self.subgraph_probe, subgraph));
+        let subgraphs = futures_util::future::join_all(probes).await;
+
+        *last_probe = Some(SubgraphProbe {
+            probed_at: Instant::now(),
+            schema_hash: engine.schema.hash,
+            subgraphs: subgraphs.clone(),
+        });
+
+        subgraphs
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ReadinessStatus {
+    Ready,
+    NotReady,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub(crate) struct Readiness {
+    status: ReadinessStatus,
+    /// Only present if the graph was loaded from a graph reference.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    graph_version: Option<String>,
+    dependencies: Vec<DependencyHealth>,
+    extensions: Vec<ExtensionReadiness>,
+    /// Only present if the subgraph probe is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subgraphs: Option<Vec<SubgraphReadiness>>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct ExtensionReadiness {
+    name: String,
+    version: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct SubgraphReadiness {
+    name: String,
+    url: String,
+    healthy: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Handles liveness requests, only telling whether the gateway process is up.
+pub(crate) async fn liveness() -> (StatusCode, Json<HealthState>) {
+    (StatusCode::OK, Json(HealthState::Healthy { subgraphs: Vec::new() }))
+}
+
+/// Handles readiness requests. The routes are only served once the graph is loaded, so the
+/// gateway is ready if all the Redis backends are reachable. The subgraphs are only reported.
+pub(crate) async fn readiness<R: engine::Runtime>(
+    State(state): State<Arc<ReadinessState<R>>>,
+) -> (StatusCode, Json<Readiness>) {
+    // Not holding the watch lock across the await points.
+    let engine = state.engine.borrow().no_contract.clone();
+
+    let dependencies = engine.runtime.dependencies_health().await;
+
+    let subgraphs = if state.subgraph_probe.enabled {
+        Some(state.probe_subgraphs(&engine).await)
+    } else {
+        None
+    };
+
+    let ready = dependencies.iter().all(|dependency| dependency.reachable);
+
+    let readiness = Readiness {
+        status: if ready {
+            ReadinessStatus::Ready
+        } else {
+            ReadinessStatus::NotReady
+        },
+        graph_version: engine.runtime.graph_version(),
+        dependencies,
+        extensions: state.extensions.clone(),
+        subgraphs,
+    };
+
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(readiness))
+}
+
+/// Sends the probe query through the engine fetcher, so with the subgraph TLS settings and
+/// message signing, and with the headers from the header rules which don't depend on the client
+/// request.
+async fn probe_subgraph<R: engine::Runtime>(
+    engine: &Engine<R>,
+    config: &SubgraphProbeConfig,
+    subgraph: GraphqlSubgraph<'_>,
+) -> SubgraphReadiness {
+    let mut headers = engine::subgraph_probe_headers(&engine.schema, subgraph.id);
+    headers.insert(http::header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(http::header::ACCEPT, HeaderValue::from_static("application/json"));
+
+    let body = serde_json::to_vec(&serde_json::json!({ "query": config.query })).unwrap_or_default();
+
+    let (result, _) = engine
+        .runtime
+        .fetcher()
+        .fetch(FetchRequest {
+            subgraph_id: subgraph.id,
+            url: Cow::Borrowed(subgraph.url()),
+            is_mutation: false,
+            method: http::Method::POST,
+            headers,
+            body: body.into(),
+            timeout: config.timeout,
+        })
+        .await;
+
+    let result = result.and_then(|response| match response.status() {
+        status if status.is_success() => Ok(()),
+        status => Err(FetchError::InvalidStatusCode(status, None)),
+    });
+
+    SubgraphReadiness {
+        name: subgraph.name().to_string(),
+        url: subgraph.url().to_string(),
+        healthy: result.is_ok(),
+        error: result.err().map(|err| err.to_string()),
+    }
+}
+
+/// All the health routes: the legacy health path, liveness and readiness.
+pub(super) fn routes<R: engine::Runtime>(
+    health_config: &HealthConfig,
+    engine: EngineWatcher<R>,
+    extension_catalog: &ExtensionCatalog,
+) -> Router {
+    let readiness_state = Arc::new(ReadinessState::new(engine.clone(), extension_catalog, health_config));
+
+    // Without the subgraph endpoints, the health response is the same as the liveness one.
+    let router = if health_config.include_subgraph_endpoints {
+        Router::new()
+            .route(&health_config.path, get(health::<R>))
+            .with_state(engine)
+    } else {
+        Router::new().route(&health_config.path, get(liveness))
+    };
+
+    router.route(&health_config.liveness_path, get(liveness)).merge(
+        Router::new()
+            .route(&health_config.readiness_path, get(readiness::<R>))
+            .with_state(readiness_state),
+    )
 }
 
 /// Binds the health check endpoint to the specified address and configuration.
@@ -36,7 +257,7 @@ pub(crate) async fn health() -> (StatusCode, Json<HealthState>) {
 /// - `addr`: The socket address to bind the server to.
 /// - `tls_config`: Optional TLS configuration for secure connections.
 /// - `health_config`: Configuration for health check settings.
-/// - `state`: The current state of the server.
+/// - `routes`: The health routes, see [`routes`].
 ///
 /// # Returns
 ///
@@ -45,26 +266,17 @@ pub(super) async fn bind_health_endpoint(
     addr: SocketAddr,
     tls_config: Option<TlsConfig>,
     health_config: HealthConfig,
+    routes: Router,
 ) -> crate::Result<()> {
     let scheme = if tls_config.is_some() { "https" } else { "http" };
     let path = &health_config.path;
-    let app = Router::new().route(path, get(health)).into_make_service();
 
     tracing::info!("Health check endpoint exposed at {scheme}://{addr}{path}");
+    tracing::info!(
+        "Liveness and readiness endpoints exposed at {scheme}://{addr}{} and {scheme}://{addr}{}",
+        health_config.liveness_path,
+        health_config.readiness_path
+    );
 
-    match tls_config {
-        Some(tls) => {
-            let rustls_config = axum_server::tls_rustls::RustlsConfig::from_pem_file(&tls.certificate, &tls.key)
-                .await
-                .map_err(crate::Error::CertificateError)?;
-
-            axum_server::bind_rustls(addr, rustls_config)
-                .serve(app)
-                .await
-                .map_err(crate::Error::Server)?;
-        }
-        None => axum_server::bind(addr).serve(app).await.map_err(crate::Error::Server)?,
-    }
-
-    Ok(())
+    super::serve_on_listener(addr, tls_config, routes).await
 }
@@ -1,6 +1,6 @@
-use std::{fmt::Display, future::Future, pin::Pin, sync::Arc};
+use std::{fmt::Display, future::Future, net::SocketAddr, pin::Pin, sync::Arc};
 
-use axum::body::Body;
+use axum::{body::Body, extract::ConnectInfo};
 use engine::{ErrorResponse, GraphqlError, RequestExtensions};
 use event_queue::ExecutedHttpRequest;
 use extension_catalog::ExtensionId;
@@ -96,6 +96,7 @@ where
                 hooks_context,
                 mut parts,
                 contract_key,
+                override_labels,
             } = match layer.extensions.on_request(parts).await {
                 Ok(on_request) => on_request,
                 Err(err) => {
@@ -134,6 +135,11 @@ where
                 }
             };
 
+            let ip = parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip());
+
             let response = match result {
                 Ok(token) => {
                     parts.extensions.insert(RequestExtensions {
@@ -141,6 +147,8 @@ where
                         event_queue: event_queue.clone(),
                         token,
                         contract_key: contract_key.or_else(|| layer.default_contract_key.clone()),
+                        override_labels,
+                        ip,
                     });
 
                     next.call(Request::from_parts(parts, body)).await?
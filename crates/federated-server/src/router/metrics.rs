@@ -0,0 +1,32 @@
+use std::net::SocketAddr;
+
+use axum::{Router, routing::get};
+use gateway_config::{PrometheusExporterConfig, TlsConfig};
+use grafbase_telemetry::otel::prometheus;
+use http::{StatusCode, header};
+
+/// Serves the metrics in the Prometheus text exposition format.
+pub(crate) async fn metrics() -> (StatusCode, [(header::HeaderName, &'static str); 1], String) {
+    match prometheus::snapshot() {
+        Some(text) => (StatusCode::OK, [(header::CONTENT_TYPE, prometheus::CONTENT_TYPE)], text),
+        None => (
+            StatusCode::NOT_FOUND,
+            [(header::CONTENT_TYPE, "text/plain")],
+            "Prometheus exporter is not enabled".to_string(),
+        ),
+    }
+}
+
+/// Binds the metrics endpoint to its own address, like the health endpoint.
+pub(super) async fn bind_metrics_endpoint(
+    addr: SocketAddr,
+    tls_config: Option<TlsConfig>,
+    config: PrometheusExporterConfig,
+) -> crate::Result<()> {
+    let scheme = if tls_config.is_some() { "https" } else { "http" };
+    let path = &config.path;
+
+    tracing::info!("Prometheus metrics endpoint exposed at {scheme}://{addr}{path}");
+
+    super::serve_on_listener(addr, tls_config, Router::new().route(path, get(metrics))).await
+}
@@ -1,15 +1,17 @@
+mod entity_cache;
 mod graphql;
 mod health;
 pub(crate) mod layers;
+mod metrics;
 mod public_metadata;
 mod state;
 
 use std::{net::SocketAddr, sync::Arc};
 
-use axum::routing::get;
+use axum::routing::{get, post};
 use engine::ContractAwareEngine;
 use extension_catalog::ExtensionCatalog;
-use gateway_config::{AuthenticationResourcesConfig, Config};
+use gateway_config::{AuthenticationResourcesConfig, Config, TlsConfig};
 use runtime::extension::GatewayExtensions;
 use tokio::sync::{mpsc, watch};
 use tokio_util::sync::CancellationToken;
@@ -110,6 +112,10 @@ where
         .with_state(ServerState::new(
             engine.clone(),
             config.request_body_limit.bytes().max(0) as usize,
+            config
+                .file_uploads
+                .enabled
+                .then(|| config.file_uploads.max_body_size.bytes().max(0) as usize),
             server_runtime.clone(),
         ))
         //
@@ -171,14 +177,58 @@ where
         _ => None,
     };
 
+    //
+    // == /entity-cache/invalidate ==
+    //
+    let invalidation = &config.entity_caching.invalidation;
+    if invalidation.enabled {
+        let Some(secret) = invalidation.secret.clone().filter(|secret| !secret.is_empty()) else {
+            return Err("entity_caching.invalidation.secret must be set to enable the invalidation endpoint".into());
+        };
+
+        let state = Arc::new(entity_cache::InvalidationState {
+            engine: engine.clone(),
+            secret,
+        });
+
+        router = router.merge(
+            axum::Router::new()
+                .route(&invalidation.path, post(entity_cache::invalidate))
+                .with_state(state)
+                .layer(telemetry.clone().with_route(invalidation.path.as_ref())),
+        );
+    }
+
     //
     // == /health ==
     //
     if config.health.enabled {
+        let routes = health::routes(&config.health, engine.clone(), &extension_catalog);
+
         if let Some(listen) = config.health.listen {
-            tokio::spawn(health::bind_health_endpoint(listen, config.tls.clone(), config.health));
+            tokio::spawn(health::bind_health_endpoint(
+                listen,
+                config.tls.clone(),
+                config.health,
+                routes,
+            ));
         } else {
-            router = router.route(&config.health.path, get(health::health));
+            router = router.merge(routes);
+        }
+    }
+
+    //
+    // == /metrics ==
+    //
+    if let Some(prometheus) = config.telemetry.metrics_prometheus_config() {
+        if let Some(listen) = prometheus.listen {
+            tokio::spawn(metrics::bind_metrics_endpoint(
+                listen,
+                config.tls.clone(),
+                prometheus.clone(),
+            ));
+        } else {
+            router = router.route(&prometheus.path, get(metrics::metrics));
         }
     }
 
@@ -230,6 +280,28 @@ fn build_extension_layer<E: GatewayExtensions>(
     ))
 }
 
+/// Serves the router on its own listener, with the gateway TLS certificate if configured. Used by
+/// the endpoints which can be exposed apart from the GraphQL one.
+async fn serve_on_listener(addr: SocketAddr, tls_config: Option<TlsConfig>, router: axum::Router) -> crate::Result<()> {
+    let app = router.into_make_service();
+
+    match tls_config {
+        Some(tls) => {
+            let rustls_config = axum_server::tls_rustls::RustlsConfig::from_pem_file(&tls.certificate, &tls.key)
+                .await
+                .map_err(crate::Error::CertificateError)?;
+
+            axum_server::bind_rustls(addr, rustls_config)
+                .serve(app)
+                .await
+                .map_err(crate::Error::Server)?;
+        }
+        None => axum_server::bind(addr).serve(app).await.map_err(crate::Error::Server)?,
+    }
+
+    Ok(())
+}
+
 async fn fallback() -> (http::StatusCode, &'static str) {
     (http::StatusCode::NOT_FOUND, "Not Found")
 }
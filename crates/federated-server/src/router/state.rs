@@ -9,6 +9,9 @@ pub struct ServerStateInner<R: engine::Runtime, SR> {
     /// The maximum size in bytes for the request body.
     pub request_body_limit_bytes: usize,
 
+    /// The maximum size in bytes for multipart request bodies, if file uploads are enabled.
+    pub file_uploads_body_limit_bytes: Option<usize>,
+
     /// The server runtime, defining how to trigger IO depending on the platform.
     #[cfg_attr(not(feature = "lambda"), allow(unused))]
     pub server_runtime: SR,
@@ -27,12 +30,18 @@ impl<R: engine::Runtime, SR> Clone for ServerState<R, SR> {
 }
 
 impl<R: engine::Runtime, SR> ServerState<R, SR> {
-    pub(super) fn new(engine: EngineWatcher<R>, request_body_limit_bytes: usize, server_runtime: SR) -> Self {
+    pub(super) fn new(
+        engine: EngineWatcher<R>,
+        request_body_limit_bytes: usize,
+        file_uploads_body_limit_bytes: Option<usize>,
+        server_runtime: SR,
+    ) -> Self {
         Self {
             inner: Arc::new(ServerStateInner {
                 engine,
                 server_runtime,
                 request_body_limit_bytes,
+                file_uploads_body_limit_bytes,
             }),
         }
     }
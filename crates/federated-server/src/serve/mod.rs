@@ -162,7 +162,7 @@ async fn bind(
     server_runtime: impl ServerRuntime,
     mcp_url: Option<String>,
 ) -> crate::Result<()> {
-    let app = router.into_make_service();
+    let app = router.into_make_service_with_connect_info::<SocketAddr>();
 
     let handle = axum_server::Handle::new();
 
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer, de::Error};
+
+/// Stops sending requests to a failing subgraph. Once the failure rate over a time window reaches
+/// the threshold the breaker opens and the subgraph fields resolve to null with an error. After
+/// `half_open_after` a single probe request is let through, closing the breaker again if it
+/// succeeds.
+#[derive(Debug, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CircuitBreakerConfig {
+    /// Disabled by default.
+    pub enabled: bool,
+    /// Ratio of failed requests, between 0 and 1, opening the breaker. Default: 0.5.
+    #[serde(deserialize_with = "deserialize_failure_rate_threshold")]
+    pub failure_rate_threshold: f32,
+    /// Minimum number of requests in the window before the failure rate is considered. Default: 20.
+    pub minimum_requests: u32,
+    /// Time window over which the failure rate is computed. Default: 10 seconds.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub window: Duration,
+    /// How long the breaker stays open before letting a probe request through. Default: 30 seconds.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub half_open_after: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            failure_rate_threshold: 0.5,
+            minimum_requests: 20,
+            window: Duration::from_secs(10),
+            half_open_after: Duration::from_secs(30),
+        }
+    }
+}
+
+fn deserialize_failure_rate_threshold<'de, D>(deserializer: D) -> Result<f32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let threshold = f32::deserialize(deserializer)?;
+
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(D::Error::custom(format!(
+            "failure_rate_threshold must be between 0 and 1, got {threshold}"
+        )));
+    }
+
+    Ok(threshold)
+}
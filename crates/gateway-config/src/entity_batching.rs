@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+const DEFAULT_ENTITY_BATCH_MAX_SIZE: usize = 32;
+
+/// Coalesces the concurrent `_entities` requests of an operation to a subgraph into a single
+/// HTTP request with an array of GraphQL requests as body. The subgraph must support batching.
+#[derive(Debug, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct EntityBatchingConfig {
+    /// Disabled by default.
+    pub enabled: bool,
+    /// Maximum number of entity requests in a single batch. Default: 32.
+    pub max_size: usize,
+    /// How long to wait for other entity requests before sending a batch. By default only the
+    /// requests started concurrently are batched together.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub max_wait: Duration,
+}
+
+impl Default for EntityBatchingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_size: DEFAULT_ENTITY_BATCH_MAX_SIZE,
+            max_wait: Duration::ZERO,
+        }
+    }
+}
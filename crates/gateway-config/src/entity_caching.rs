@@ -1,4 +1,4 @@
-use std::{path::PathBuf, time::Duration};
+use std::{borrow::Cow, path::PathBuf, time::Duration};
 
 const DEFAULT_ENTITY_CACHE_TTL: Duration = Duration::from_secs(60);
 
@@ -22,6 +22,9 @@ pub struct EntityCachingConfig {
     /// The ttl to store cache entries with.  Defaults to 60s
     #[serde(deserialize_with = "duration_str::deserialize_duration")]
     pub ttl: Duration,
+
+    /// HTTP endpoint to purge cache entries before they expire.
+    pub invalidation: EntityCachingInvalidationConfig,
 }
 
 impl Default for EntityCachingConfig {
@@ -31,6 +34,7 @@ impl Default for EntityCachingConfig {
             storage: Default::default(),
             redis: Default::default(),
             ttl: DEFAULT_ENTITY_CACHE_TTL,
+            invalidation: Default::default(),
         }
     }
 }
@@ -43,6 +47,7 @@ pub enum EntityCachingStorage {
     Redis,
 }
 
+/// Tagging entries for invalidation requires Redis 7 or later.
 #[derive(Debug, Clone, PartialEq, serde::Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct EntityCachingRedisConfig {
@@ -68,3 +73,22 @@ pub struct EntityCachingRedisTlsConfig {
     pub key: Option<PathBuf>,
     pub ca: Option<PathBuf>,
 }
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EntityCachingInvalidationConfig {
+    pub enabled: bool,
+    pub path: Cow<'static, str>,
+    /// Required when enabled. Invalidation requests must provide it with `Authorization: Bearer <secret>`.
+    pub secret: Option<String>,
+}
+
+impl Default for EntityCachingInvalidationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: Cow::Borrowed("/entity-cache/invalidate"),
+            secret: None,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use size::Size;
+
+use crate::size_ext;
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileUploadsConfig {
+    /// Whether `multipart/form-data` requests following the GraphQL multipart request spec are
+    /// accepted. They must include the CSRF header (`csrf.header_name`) even if the CSRF
+    /// protection is disabled, as browsers send them cross-site without a preflight request.
+    pub enabled: bool,
+    /// Maximum size of multipart request bodies. It replaces the `request_body_limit` for them.
+    #[serde(deserialize_with = "size_ext::deserialize_positive_size")]
+    pub max_body_size: Size,
+}
+
+impl Default for FileUploadsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_body_size: Size::from_mebibytes(10),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, sync::OnceLock};
 
 use ascii::AsciiString;
 use regex::{Regex, RegexBuilder};
@@ -81,6 +81,8 @@ pub struct HeaderForward {
     pub default: Option<AsciiString>,
     /// Use this name instead of the original when forwarding.
     pub rename: Option<AsciiString>,
+    /// Only forward the header when the condition holds.
+    pub when: Option<HeaderRuleCondition>,
 }
 
 /// Header insertion rules.
@@ -89,8 +91,230 @@ pub struct HeaderForward {
 pub struct HeaderInsert {
     /// The name of the header.
     pub name: AsciiString,
-    /// The value of the header.
-    pub value: AsciiString,
+    /// The value of the header, a template which may reference request data with placeholders
+    /// such as `{{ jwt.sub }}`, see [HeaderTemplateVariable].
+    pub value: HeaderTemplate,
+    /// Only insert the header when the condition holds.
+    pub when: Option<HeaderRuleCondition>,
+}
+
+/// Condition restricting a header rule to some requests, evaluated at request time.
+#[derive(Deserialize, serde::Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum HeaderRuleCondition {
+    /// The client request has this header.
+    HeaderPresent(AsciiString),
+    /// The authenticated JWT has this claim.
+    JwtClaimPresent(String),
+    /// The GraphQL operation is of this type. Only known in subgraph header rules, the global ones
+    /// being applied before the operation is parsed.
+    OperationType(HeaderRuleOperationType),
+    /// All the conditions hold.
+    All(Vec<HeaderRuleCondition>),
+    /// At least one of the conditions holds.
+    Any(Vec<HeaderRuleCondition>),
+    /// The condition doesn't hold.
+    Not(Box<HeaderRuleCondition>),
+}
+
+impl HeaderRuleCondition {
+    fn depends_on_operation(&self) -> bool {
+        match self {
+            HeaderRuleCondition::HeaderPresent(_) | HeaderRuleCondition::JwtClaimPresent(_) => false,
+            HeaderRuleCondition::OperationType(_) => true,
+            HeaderRuleCondition::All(conditions) | HeaderRuleCondition::Any(conditions) => {
+                conditions.iter().any(HeaderRuleCondition::depends_on_operation)
+            }
+            HeaderRuleCondition::Not(condition) => condition.depends_on_operation(),
+        }
+    }
+}
+
+#[derive(Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HeaderRuleOperationType {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+/// Placeholder of a header value template, resolved at request time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeaderTemplateVariable {
+    /// `{{ jwt.<claim> }}`: claim of the authenticated JWT.
+    JwtClaim(String),
+    /// `{{ header.<name> }}`: header of the client request.
+    Header(String),
+    /// `{{ client.ip }}`: IP address of the client connection.
+    ClientIp,
+    /// `{{ operation.name }}`: name of the GraphQL operation. Only known in subgraph header rules.
+    OperationName,
+    /// `{{ operation.type }}`: type of the GraphQL operation. Only known in subgraph header rules.
+    OperationType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeaderTemplatePart {
+    Static(String),
+    Variable(HeaderTemplateVariable),
+}
+
+/// A header value template, parsed when loading the configuration. Environment variables were
+/// already substituted at that point.
+#[derive(Clone, PartialEq, Eq)]
+pub struct HeaderTemplate {
+    template: AsciiString,
+    parts: Vec<HeaderTemplatePart>,
+}
+
+impl HeaderTemplate {
+    /// The template as written in the configuration.
+    pub fn as_str(&self) -> &str {
+        self.template.as_str()
+    }
+
+    /// The value itself if the template has no variables.
+    pub fn as_static(&self) -> Option<&str> {
+        match self.parts.as_slice() {
+            [] => Some(""),
+            [HeaderTemplatePart::Static(value)] => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn parts(&self) -> &[HeaderTemplatePart] {
+        &self.parts
+    }
+
+    fn depends_on_operation(&self) -> bool {
+        self.parts.iter().any(|part| {
+            matches!(
+                part,
+                HeaderTemplatePart::Variable(
+                    HeaderTemplateVariable::OperationName | HeaderTemplateVariable::OperationType
+                )
+            )
+        })
+    }
+}
+
+impl std::fmt::Debug for HeaderTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl TryFrom<AsciiString> for HeaderTemplate {
+    type Error = String;
+
+    fn try_from(template: AsciiString) -> Result<Self, Self::Error> {
+        let parts = parse_header_template(template.as_str())?;
+        Ok(HeaderTemplate { template, parts })
+    }
+}
+
+impl std::str::FromStr for HeaderTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AsciiString::from_ascii(s).map_err(|err| err.to_string())?.try_into()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for HeaderTemplate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        AsciiString::deserialize(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl serde::Serialize for HeaderTemplate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Splits a header value template into its static and variable parts.
+fn parse_header_template(template: &str) -> Result<Vec<HeaderTemplatePart>, String> {
+    fn re() -> &'static Regex {
+        static RE: OnceLock<Regex> = OnceLock::new();
+        RE.get_or_init(|| Regex::new(r"\{\{\s*([[:alnum:]_.\-]+)\s*\}\}").expect("must be valid"))
+    }
+
+    let mut parts = Vec::new();
+    let mut last_end = 0;
+
+    for captures in re().captures_iter(template) {
+        let overall_match = captures.get(0).unwrap();
+        let key = captures.get(1).unwrap().as_str();
+
+        let variable = match key.split_once('.') {
+            Some(("jwt", claim)) if !claim.is_empty() => HeaderTemplateVariable::JwtClaim(claim.to_owned()),
+            Some(("header", name)) if !name.is_empty() => HeaderTemplateVariable::Header(name.to_owned()),
+            Some(("client", "ip")) => HeaderTemplateVariable::ClientIp,
+            Some(("operation", "name")) => HeaderTemplateVariable::OperationName,
+            Some(("operation", "type")) => HeaderTemplateVariable::OperationType,
+            // Substituted when loading the configuration file.
+            Some(("env", _)) => continue,
+            _ => {
+                return Err(format!(
+                    "unknown header template variable `{key}`, expected one of `jwt.<claim>`, `header.<name>`, `client.ip`, `operation.name` or `operation.type`"
+                ));
+            }
+        };
+
+        if overall_match.start() > last_end {
+            parts.push(HeaderTemplatePart::Static(
+                template[last_end..overall_match.start()].to_owned(),
+            ));
+        }
+        parts.push(HeaderTemplatePart::Variable(variable));
+        last_end = overall_match.end();
+    }
+
+    if last_end < template.len() {
+        parts.push(HeaderTemplatePart::Static(template[last_end..].to_owned()));
+    }
+
+    Ok(parts)
+}
+
+/// Deserializes the global header rules, applied before the operation is parsed. They can't
+/// depend on it.
+pub(crate) fn deserialize_global_header_rules<'de, D>(deserializer: D) -> Result<Vec<HeaderRule>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let rules = Vec::<HeaderRule>::deserialize(deserializer)?;
+
+    for rule in &rules {
+        let (condition, template) = match rule {
+            HeaderRule::Forward(rule) => (rule.when.as_ref(), None),
+            HeaderRule::Insert(rule) => (rule.when.as_ref(), Some(&rule.value)),
+            HeaderRule::Remove(_) | HeaderRule::RenameDuplicate(_) => continue,
+        };
+
+        if condition.is_some_and(HeaderRuleCondition::depends_on_operation) {
+            return Err(serde::de::Error::custom(
+                "the `operation_type` condition is only available in subgraph header rules",
+            ));
+        }
+
+        if template.is_some_and(HeaderTemplate::depends_on_operation) {
+            return Err(serde::de::Error::custom(
+                "the `operation.name` and `operation.type` template variables are only available in subgraph header rules",
+            ));
+        }
+    }
+
+    Ok(rules)
 }
 
 /// Header removal rules
@@ -101,3 +325,48 @@ pub struct HeaderRemove {
     #[serde(flatten)]
     pub name: NameOrPattern,
 }
+
+/// Defines a rule applied to the headers of subgraph responses, executed in order, global rules
+/// first. Subgraph response headers only reach the client when propagated.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "rule")]
+pub enum ResponseHeaderRule {
+    /// Propagate the subgraph response header to the client.
+    #[serde(rename = "propagate")]
+    Propagate(ResponseHeaderPropagate),
+    /// Stop propagating a header, typically one propagated by a global rule.
+    #[serde(rename = "remove")]
+    Remove(HeaderRemove),
+}
+
+/// Response header propagation rules.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseHeaderPropagate {
+    /// Name or pattern of the header to be propagated.
+    #[serde(flatten)]
+    pub name: NameOrPattern,
+    /// Use this name instead of the original when propagating.
+    pub rename: Option<AsciiString>,
+    /// How to combine the values when several subgraphs send the header.
+    #[serde(default)]
+    pub merge: HeaderMergeStrategy,
+}
+
+/// How the values of a header sent by several subgraph responses are combined into the client
+/// response.
+#[derive(Deserialize, serde::Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HeaderMergeStrategy {
+    /// Keep the value of the first subgraph response.
+    First,
+    /// Keep the value of the last subgraph response.
+    #[default]
+    Last,
+    /// Keep all the values, as separate header entries.
+    Append,
+    /// Keep the value with the shortest time to live: the smallest `max-age` or `s-maxage` for
+    /// `Cache-Control` like values, `no-store`, `no-cache` and `private` counting as zero, or the
+    /// value itself if it's a number of seconds.
+    MinTtl,
+}
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, net::SocketAddr};
+use std::{borrow::Cow, net::SocketAddr, time::Duration};
 
 /// Health endpoint configuration.
 #[derive(Clone, Debug, serde::Deserialize)]
@@ -7,6 +7,17 @@ pub struct HealthConfig {
     pub enabled: bool,
     pub listen: Option<SocketAddr>,
     pub path: Cow<'static, str>,
+    /// Reports the endpoints of the load balanced subgraphs, with their URLs, in the health
+    /// response. Disabled by default not to expose internal URLs, prefer a dedicated `listen`
+    /// address when enabling it.
+    pub include_subgraph_endpoints: bool,
+    /// Only tells whether the gateway process is up.
+    pub liveness_path: Cow<'static, str>,
+    /// Checks the Redis backends, answering with a 503 if the gateway can't serve requests. Also
+    /// reports the graph version, the extensions and the subgraphs if probed.
+    pub readiness_path: Cow<'static, str>,
+    /// Probing of the subgraphs by the readiness endpoint.
+    pub subgraph_probe: SubgraphProbeConfig,
 }
 
 impl Default for HealthConfig {
@@ -15,6 +26,40 @@ impl Default for HealthConfig {
             enabled: true,
             listen: None,
             path: Cow::Borrowed("/health"),
+            include_subgraph_endpoints: false,
+            liveness_path: Cow::Borrowed("/health/live"),
+            readiness_path: Cow::Borrowed("/health/ready"),
+            subgraph_probe: SubgraphProbeConfig::default(),
+        }
+    }
+}
+
+/// Sends a query to every subgraph, with the headers, authentication and TLS settings of regular
+/// subgraph requests, reporting the results in the readiness checks. An unavailable subgraph
+/// doesn't make the gateway unready.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubgraphProbeConfig {
+    /// Disabled by default.
+    pub enabled: bool,
+    /// Query sent to the subgraphs. Default: `{ __typename }`.
+    pub query: String,
+    /// Time after which a subgraph is considered unavailable. Default: 1 second.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub timeout: Duration,
+    /// Minimum time between two probes, the readiness checks in between report the last results.
+    /// Default: 10 seconds.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub interval: Duration,
+}
+
+impl Default for SubgraphProbeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            query: "{ __typename }".to_string(),
+            timeout: Duration::from_secs(1),
+            interval: Duration::from_secs(10),
         }
     }
 }
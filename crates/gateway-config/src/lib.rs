@@ -5,18 +5,23 @@ use serde::Deserialize;
 
 pub mod apq;
 pub mod authentication;
+mod circuit_breaker;
 mod complexity_control;
 pub mod cors;
+pub mod entity_batching;
 pub mod entity_caching;
 pub mod extensions;
+mod file_uploads;
 pub mod header;
 pub mod health;
 pub mod hooks;
+mod load_balancing;
 mod log_level;
 mod mcp;
 pub mod message_signatures;
 pub mod operation_caching;
 pub mod rate_limit;
+pub mod response_caching;
 mod size_ext;
 mod subscription_protocol;
 pub mod telemetry;
@@ -36,21 +41,29 @@ use std::{
 
 pub use self::{
     log_level::*,
-    mcp::{McpTransport, ModelControlProtocolConfig},
+    mcp::{
+        McpOperationsConfig, McpPromptArgumentConfig, McpPromptConfig, McpTools, McpTransport,
+        ModelControlProtocolConfig,
+    },
     subscription_protocol::SubscriptionProtocol,
     trusted_documents::*,
     websockets_config::WebsocketsConfig,
 };
 pub use authentication::*;
+pub use circuit_breaker::*;
 pub use complexity_control::*;
 pub use cors::*;
+pub use entity_batching::*;
 pub use entity_caching::*;
 pub use extensions::*;
+pub use file_uploads::*;
 pub use header::*;
 pub use health::*;
 pub use hooks::*;
+pub use load_balancing::*;
 pub use message_signatures::MessageSignaturesConfig;
 pub use rate_limit::*;
+pub use response_caching::*;
 use size::Size;
 pub use telemetry::*;
 pub use traffic_shaping::*;
@@ -102,26 +115,42 @@ impl Loader {
             value: &'a mut toml::Value,
         ) -> Result<(), String> {
             match value {
-                toml::Value::String(s) => match serde_dynamic_string::DynamicString::<String>::from_str(s) {
-                    Ok(out) => *s = out.into_inner(),
-                    Err(err) => {
-                        use std::fmt::Write;
-                        let mut p = String::new();
-                        for segment in path {
-                            match segment {
-                                Ok(s) => {
-                                    p.push_str(s);
-                                    p.push('.');
+                toml::Value::String(s) => {
+                    // Header rule values are rendered for each request, so they may also reference
+                    // request data such as `{{ jwt.sub }}`.
+                    let is_header_rule_value = matches!(
+                        path.as_slice(),
+                        [Ok("headers"), Err(_), Ok("value")]
+                            | [Ok("subgraphs"), Ok(_), Ok("headers"), Err(_), Ok("value")]
+                    );
+
+                    let expanded = if is_header_rule_value {
+                        serde_dynamic_string::DynamicString::<String>::with_request_scopes(s)
+                    } else {
+                        serde_dynamic_string::DynamicString::<String>::from_str(s)
+                    };
+
+                    match expanded {
+                        Ok(out) => *s = out.into_inner(),
+                        Err(err) => {
+                            use std::fmt::Write;
+                            let mut p = String::new();
+                            for segment in path {
+                                match segment {
+                                    Ok(s) => {
+                                        p.push_str(s);
+                                        p.push('.');
+                                    }
+                                    Err(i) => write!(p, "[{i}]").unwrap(),
                                 }
-                                Err(i) => write!(p, "[{i}]").unwrap(),
                             }
+                            if p.ends_with('.') {
+                                p.pop();
+                            }
+                            return Err(format!("At {p}, failed substituing environment variable: {err}",));
                         }
-                        if p.ends_with('.') {
-                            p.pop();
-                        }
-                        return Err(format!("At {p}, failed substituing environment variable: {err}",));
                     }
-                },
+                }
                 toml::Value::Array(values) => {
                     for (i, value) in values.iter_mut().enumerate() {
                         path.push(Err(i));
@@ -188,6 +217,8 @@ pub struct Config {
     /// Maximum size of the executable document in bytes
     #[serde(deserialize_with = "size_ext::deserialize_positive_size")]
     pub executable_document_limit: Size,
+    /// Multipart file uploads settings
+    pub file_uploads: FileUploadsConfig,
     /// Cross-site request forgery settings
     pub csrf: CsrfConfig,
     /// Cross-origin resource sharing settings
@@ -204,7 +235,10 @@ pub struct Config {
     /// Authentication configuration
     pub authentication: AuthenticationConfig,
     /// Header bypass configuration
+    #[serde(deserialize_with = "header::deserialize_global_header_rules")]
     pub headers: Vec<HeaderRule>,
+    /// Subgraph response headers propagated to the client
+    pub response_headers: Vec<ResponseHeaderRule>,
     /// Subgraph configuration
     pub subgraphs: BTreeMap<String, SubgraphConfig>,
     /// Hooks configuration
@@ -221,6 +255,8 @@ pub struct Config {
     pub apq: AutomaticPersistedQueries,
     /// Operation caching configuration
     pub operation_caching: OperationCacheConfig,
+    /// Whole-response caching configuration
+    pub response_caching: ResponseCachingConfig,
     /// Websockets configuration
     pub websockets: WebsocketsConfig,
     /// Model Control Protocol configuration
@@ -266,6 +302,14 @@ impl Config {
             }
         }
 
+        if let Some(mcp) = &mut self.mcp {
+            for operations in &mut mcp.operations {
+                if operations.path.is_relative() {
+                    operations.path = parent.join(&operations.path);
+                }
+            }
+        }
+
         if let Some(wasm) = &mut self.wasm
             && let Some(dir) = &mut wasm.cache_path
             && dir.is_relative()
@@ -273,6 +317,12 @@ impl Config {
             *dir = parent.join(&dir);
         }
 
+        for path in self.trusted_documents.manifests.values_mut() {
+            if path.is_relative() {
+                *path = parent.join(&path);
+            }
+        }
+
         Some(self)
     }
 }
@@ -286,6 +336,7 @@ impl Default for Config {
             gateway: Default::default(),
             request_body_limit: Size::from_mebibytes(2),
             executable_document_limit: Size::from_kibibytes(32),
+            file_uploads: Default::default(),
             csrf: Default::default(),
             cors: Default::default(),
             tls: Default::default(),
@@ -295,6 +346,7 @@ impl Default for Config {
             trusted_documents: Default::default(),
             authentication: Default::default(),
             headers: Default::default(),
+            response_headers: Default::default(),
             subgraphs: Default::default(),
             hooks: Default::default(),
             health: Default::default(),
@@ -302,6 +354,7 @@ impl Default for Config {
             complexity_control: Default::default(),
             apq: Default::default(),
             operation_caching: Default::default(),
+            response_caching: Default::default(),
             websockets: Default::default(),
             extensions: Default::default(),
             mcp: Default::default(),
@@ -407,6 +460,8 @@ pub struct SubgraphConfig {
     pub url: Option<Url>,
     /// Header bypass configuration
     pub headers: Vec<HeaderRule>,
+    /// Response headers of this subgraph propagated to the client, applied after the global ones
+    pub response_headers: Vec<ResponseHeaderRule>,
     /// The URL to use for GraphQL websocket calls.
     pub websocket_url: Option<Url>,
     /// Rate limiting configuration specifically for this Subgraph
@@ -418,6 +473,12 @@ pub struct SubgraphConfig {
     /// Subgraph specific entity caching config  this overrides the global config if there
     /// is any
     pub entity_caching: Option<SubgraphEntityCachingConfig>,
+    /// Batching of the entity requests sent to this subgraph
+    pub entity_batching: Option<EntityBatchingConfig>,
+    /// Circuit breaker protecting this subgraph
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Balancing of the requests across several replicas of the subgraph
+    pub load_balancing: Option<LoadBalancingConfig>,
     /// Subgraph specific message signatures config
     pub message_signatures: Option<MessageSignaturesConfig>,
     /// The path of an SDL schema file for the subgraph (dev only).
@@ -426,6 +487,9 @@ pub struct SubgraphConfig {
     pub introspection_url: Option<Url>,
     /// Header configuration for subgraph introspection (dev only).
     pub introspection_headers: Option<BTreeMap<String, String>>,
+    /// Respond with data generated from the subgraph schema instead of sending requests to
+    /// its URL (dev only).
+    pub mock: bool,
     /// The protocol used for subscriptions
     pub subscription_protocol: Option<SubscriptionProtocol>,
     /// Mutual TLS (mTLS) configuration for the subgraph
@@ -443,15 +507,20 @@ impl Default for SubgraphConfig {
         Self {
             url: Default::default(),
             headers: Default::default(),
+            response_headers: Default::default(),
             websocket_url: Default::default(),
             rate_limit: Default::default(),
             timeout: DEFAULT_SUBGRAPH_TIMEOUT,
             retry: Default::default(),
             entity_caching: Default::default(),
+            entity_batching: Default::default(),
+            circuit_breaker: Default::default(),
+            load_balancing: Default::default(),
             message_signatures: Default::default(),
             schema_path: Default::default(),
             introspection_url: Default::default(),
             introspection_headers: Default::default(),
+            mock: false,
             subscription_protocol: Default::default(),
             mtls: Default::default(),
         }
@@ -620,6 +689,24 @@ mod tests {
     use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
     use std::time::Duration;
 
+    #[test]
+    fn file_uploads() {
+        let config: Config = toml::from_str(indoc! {r#"
+            [file_uploads]
+            enabled = true
+            max_body_size = "50MiB"
+        "#})
+        .unwrap();
+
+        assert!(config.file_uploads.enabled);
+        assert_eq!(Size::from_mebibytes(50), config.file_uploads.max_body_size);
+
+        let config: Config = toml::from_str("").unwrap();
+
+        assert!(!config.file_uploads.enabled);
+        assert_eq!(Size::from_mebibytes(10), config.file_uploads.max_body_size);
+    }
+
     #[test]
     fn request_body_limit() {
         let config: Config = toml::from_str(indoc! {r#"
@@ -1105,6 +1192,7 @@ mod tests {
             document_id_unknown_log_level: Info,
             document_id_and_query_mismatch_log_level: Info,
             inline_document_unknown_log_level: Info,
+            manifests: {},
         }
         "#)
     }
@@ -1129,6 +1217,7 @@ mod tests {
             document_id_unknown_log_level: Info,
             document_id_and_query_mismatch_log_level: Info,
             inline_document_unknown_log_level: Info,
+            manifests: {},
         }
         "#)
     }
@@ -1180,6 +1269,50 @@ mod tests {
             document_id_unknown_log_level: Error,
             document_id_and_query_mismatch_log_level: Off,
             inline_document_unknown_log_level: Warn,
+            manifests: {},
+        }
+        "#);
+    }
+
+    #[test]
+    fn trusted_documents_manifests_are_relative_to_the_config() {
+        let input = indoc! {r#"
+            [trusted_documents]
+            enabled = true
+
+            [trusted_documents.manifests]
+            web = "./manifests/web.json"
+            ios = "/etc/grafbase/ios.json"
+        "#};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(&path, input).unwrap();
+        let config = Config::load(&path).unwrap().unwrap();
+
+        let manifests = &config.trusted_documents.manifests;
+        assert_eq!(manifests["web"], tmp.path().join("./manifests/web.json"));
+        assert_eq!(manifests["ios"], std::path::Path::new("/etc/grafbase/ios.json"));
+    }
+
+    #[test]
+    fn trusted_documents_manifests() {
+        let input = indoc! {r#"
+            [trusted_documents]
+            enabled = true
+            enforced = true
+
+            [trusted_documents.manifests]
+            web = "./manifests/web.json"
+            ios = "/etc/grafbase/ios.json"
+        "#};
+
+        let config = toml::from_str::<Config>(input).unwrap();
+
+        insta::assert_debug_snapshot!(config.trusted_documents.manifests, @r#"
+        {
+            "ios": "/etc/grafbase/ios.json",
+            "web": "./manifests/web.json",
         }
         "#);
     }
@@ -1272,6 +1405,7 @@ mod tests {
                     ),
                     default: None,
                     rename: None,
+                    when: None,
                 },
             ),
         ]
@@ -1320,6 +1454,7 @@ mod tests {
                     ),
                     default: None,
                     rename: None,
+                    when: None,
                 },
             ),
             Forward(
@@ -1329,6 +1464,7 @@ mod tests {
                     ),
                     default: None,
                     rename: None,
+                    when: None,
                 },
             ),
         ]
@@ -1358,6 +1494,7 @@ mod tests {
                     ),
                     default: None,
                     rename: None,
+                    when: None,
                 },
             ),
         ]
@@ -1408,6 +1545,7 @@ mod tests {
                         "application/json",
                     ),
                     rename: None,
+                    when: None,
                 },
             ),
         ]
@@ -1456,6 +1594,7 @@ mod tests {
                     rename: Some(
                         "kekw-type",
                     ),
+                    when: None,
                 },
             ),
         ]
@@ -1499,6 +1638,7 @@ mod tests {
                 HeaderInsert {
                     name: "content-type",
                     value: "application/json",
+                    when: None,
                 },
             ),
         ]
@@ -1523,6 +1663,7 @@ mod tests {
                     HeaderInsert {
                         name: "content-type",
                         value: "{{ env.CONTENT_TYPE }}",
+                        when: None,
                     },
                 ),
             ]
@@ -1570,6 +1711,181 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn header_insert_template_with_condition() {
+        let input = indoc! {r#"
+            [[headers]]
+            rule = "insert"
+            name = "x-user-id"
+            value = "user:{{ jwt.sub }}"
+            when = { all = [{ jwt_claim_present = "sub" }, { not = { header_present = "x-user-id" } }] }
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.headers, @r#"
+        [
+            Insert(
+                HeaderInsert {
+                    name: "x-user-id",
+                    value: "user:{{ jwt.sub }}",
+                    when: Some(
+                        All(
+                            [
+                                JwtClaimPresent(
+                                    "sub",
+                                ),
+                                Not(
+                                    HeaderPresent(
+                                        "x-user-id",
+                                    ),
+                                ),
+                            ],
+                        ),
+                    ),
+                },
+            ),
+        ]
+        "#);
+    }
+
+    #[test]
+    fn request_scoped_placeholders_are_only_kept_in_header_rule_values() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+
+        std::fs::write(
+            &path,
+            indoc! {r#"
+                [[headers]]
+                rule = "insert"
+                name = "x-user-id"
+                value = "user:{{ jwt.sub }}"
+
+                [[subgraphs.accounts.headers]]
+                rule = "insert"
+                name = "x-client-ip"
+                value = "{{ client.ip }}"
+            "#},
+        )
+        .unwrap();
+
+        let config = Config::load(&path).unwrap().unwrap();
+
+        assert!(matches!(
+            &config.headers[0],
+            HeaderRule::Insert(rule) if rule.value.as_str() == "user:{{ jwt.sub }}"
+        ));
+        assert!(matches!(
+            &config.subgraphs["accounts"].headers[0],
+            HeaderRule::Insert(rule) if rule.value.as_str() == "{{ client.ip }}"
+        ));
+
+        std::fs::write(
+            &path,
+            indoc! {r#"
+                [subgraphs.accounts]
+                url = "http://{{ header.host }}/graphql"
+            "#},
+        )
+        .unwrap();
+
+        let error = Config::load(&path).unwrap_err();
+
+        insta::assert_snapshot!(&error, @"At subgraphs.accounts.url, failed substituing environment variable: right now only variables scoped with 'env.' are supported: `header.host`");
+    }
+
+    #[test]
+    fn header_insert_unknown_template_variable() {
+        let input = indoc! {r#"
+            [[headers]]
+            rule = "insert"
+            name = "x-user-id"
+            value = "{{ jwt }}"
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 1, column 1
+          |
+        1 | [[headers]]
+          | ^^^^^^^^^^^
+        unknown header template variable `jwt`, expected one of `jwt.<claim>`, `header.<name>`, `client.ip`, `operation.name` or `operation.type`
+        "#);
+    }
+
+    #[test]
+    fn global_header_insert_with_operation_template_variable() {
+        let input = indoc! {r#"
+            [[headers]]
+            rule = "insert"
+            name = "x-operation"
+            value = "{{ operation.name }}"
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 1, column 1
+          |
+        1 | [[headers]]
+          | ^^^^^^^^^^^
+        the `operation.name` and `operation.type` template variables are only available in subgraph header rules
+        "#);
+    }
+
+    #[test]
+    fn global_header_forward_with_nested_operation_type_condition() {
+        let input = indoc! {r#"
+            [[headers]]
+            rule = "forward"
+            name = "authorization"
+            when = { any = [{ header_present = "x-debug" }, { not = { operation_type = "mutation" } }] }
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 1, column 1
+          |
+        1 | [[headers]]
+          | ^^^^^^^^^^^
+        the `operation_type` condition is only available in subgraph header rules
+        "#);
+    }
+
+    #[test]
+    fn subgraph_header_insert_with_operation_data() {
+        let input = indoc! {r#"
+            [[subgraphs.products.headers]]
+            rule = "insert"
+            name = "x-operation"
+            value = "{{ operation.type }}:{{ operation.name }}"
+            when = { not = { operation_type = "subscription" } }
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.subgraphs["products"].headers, @r#"
+        [
+            Insert(
+                HeaderInsert {
+                    name: "x-operation",
+                    value: "{{ operation.type }}:{{ operation.name }}",
+                    when: Some(
+                        Not(
+                            OperationType(
+                                Subscription,
+                            ),
+                        ),
+                    ),
+                },
+            ),
+        ]
+        "#);
+    }
+
     #[test]
     fn header_remove() {
         let input = indoc! {r#"
@@ -1634,18 +1950,24 @@ mod tests {
                             ),
                             default: None,
                             rename: None,
+                            when: None,
                         },
                     ),
                 ],
+                response_headers: [],
                 websocket_url: None,
                 rate_limit: None,
                 timeout: 30s,
                 retry: None,
                 entity_caching: None,
+                entity_batching: None,
+                circuit_breaker: None,
+                load_balancing: None,
                 message_signatures: None,
                 schema_path: None,
                 introspection_url: None,
                 introspection_headers: None,
+                mock: false,
                 subscription_protocol: None,
                 mtls: None,
             },
@@ -1653,6 +1975,102 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn response_header_rules() {
+        let input = indoc! {r#"
+            [[response_headers]]
+            rule = "propagate"
+            name = "set-cookie"
+            merge = "append"
+
+            [[response_headers]]
+            rule = "propagate"
+            pattern = "^x-ratelimit-"
+
+            [[subgraphs.products.response_headers]]
+            rule = "propagate"
+            name = "cache-control"
+            rename = "x-products-cache-control"
+            merge = "min_ttl"
+
+            [[subgraphs.products.response_headers]]
+            rule = "remove"
+            name = "set-cookie"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.response_headers, @r#"
+        [
+            Propagate(
+                ResponseHeaderPropagate {
+                    name: Name(
+                        "set-cookie",
+                    ),
+                    rename: None,
+                    merge: Append,
+                },
+            ),
+            Propagate(
+                ResponseHeaderPropagate {
+                    name: Pattern(
+                        NamePattern(
+                            Regex(
+                                "^x-ratelimit-",
+                            ),
+                        ),
+                    ),
+                    rename: None,
+                    merge: Last,
+                },
+            ),
+        ]
+        "#);
+
+        insta::assert_debug_snapshot!(&result.subgraphs["products"].response_headers, @r#"
+        [
+            Propagate(
+                ResponseHeaderPropagate {
+                    name: Name(
+                        "cache-control",
+                    ),
+                    rename: Some(
+                        "x-products-cache-control",
+                    ),
+                    merge: MinTtl,
+                },
+            ),
+            Remove(
+                HeaderRemove {
+                    name: Name(
+                        "set-cookie",
+                    ),
+                },
+            ),
+        ]
+        "#);
+    }
+
+    #[test]
+    fn response_header_rule_invalid_merge() {
+        let input = indoc! {r#"
+            [[response_headers]]
+            rule = "propagate"
+            name = "set-cookie"
+            merge = "concat"
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 1, column 1
+          |
+        1 | [[response_headers]]
+          | ^^^^^^^^^^^^^^^^^^^^
+        unknown variant `concat`, expected one of `first`, `last`, `append`, `min_ttl`
+        "#);
+    }
+
     #[test]
     fn subgraph_ws_valid_url() {
         let input = indoc! {r#"
@@ -1757,6 +2175,8 @@ mod tests {
                         duration: 10s,
                     },
                 ),
+                complexity: None,
+                rules: [],
                 storage: Memory,
                 redis: RateLimitRedisConfig {
                     url: Url {
@@ -1784,6 +2204,111 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn complexity_rate_limiting() {
+        let input = indoc! {r#"
+            [gateway.rate_limit.complexity]
+            limit = 5000
+            duration = "1m"
+            key = { header = "x-client-id" }
+        "#};
+
+        let config = toml::from_str::<Config>(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.gateway.rate_limit.unwrap().complexity, @r#"
+        Some(
+            ComplexityRateLimit {
+                limit: 5000,
+                duration: 60s,
+                key: Header(
+                    "x-client-id",
+                ),
+            },
+        )
+        "#);
+
+        let input = indoc! {r#"
+            [gateway.rate_limit.complexity]
+            limit = 5000
+            duration = "1m"
+        "#};
+
+        let config = toml::from_str::<Config>(input).unwrap();
+        let complexity = config.gateway.rate_limit.unwrap().complexity.unwrap();
+
+        assert_eq!(complexity.key, RateLimitClientKey::Ip);
+
+        let input = indoc! {r#"
+            [gateway.rate_limit.complexity]
+            limit = 5000
+            duration = "1m"
+            key = { jwt_claim = "sub" }
+        "#};
+
+        let config = toml::from_str::<Config>(input).unwrap();
+        let complexity = config.gateway.rate_limit.unwrap().complexity.unwrap();
+
+        assert_eq!(complexity.key, RateLimitClientKey::JwtClaim("sub".to_string()));
+    }
+
+    #[test]
+    fn keyed_rate_limiting_rules() {
+        let input = indoc! {r#"
+            [[gateway.rate_limit.rules]]
+            name = "per-tenant"
+            limit = 100
+            duration = "10s"
+            key = { header = "x-tenant-id" }
+
+            [[gateway.rate_limit.rules]]
+            name = "per-user-products"
+            limit = 10
+            duration = "1s"
+            key = { jwt_claim = "sub" }
+            subgraph = "products"
+
+            [[gateway.rate_limit.rules]]
+            name = "per-operation"
+            limit = 1000
+            duration = "1m"
+            key = "operation_name"
+        "#};
+
+        let config = toml::from_str::<Config>(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.gateway.rate_limit.unwrap().rules, @r#"
+        [
+            RateLimitRule {
+                name: "per-tenant",
+                limit: 100,
+                duration: 10s,
+                key: Header(
+                    "x-tenant-id",
+                ),
+                subgraph: None,
+            },
+            RateLimitRule {
+                name: "per-user-products",
+                limit: 10,
+                duration: 1s,
+                key: JwtClaim(
+                    "sub",
+                ),
+                subgraph: Some(
+                    "products",
+                ),
+            },
+            RateLimitRule {
+                name: "per-operation",
+                limit: 1000,
+                duration: 60s,
+                key: OperationName,
+                subgraph: None,
+            },
+        ]
+        "#);
+    }
+
     #[test]
     fn global_rate_limiting_redis_defaults() {
         let input = indoc! {r#"
@@ -1797,6 +2322,8 @@ mod tests {
         Some(
             RateLimitConfig {
                 global: None,
+                complexity: None,
+                rules: [],
                 storage: Redis,
                 redis: RateLimitRedisConfig {
                     url: Url {
@@ -1840,6 +2367,8 @@ mod tests {
         Some(
             RateLimitConfig {
                 global: None,
+                complexity: None,
+                rules: [],
                 storage: Redis,
                 redis: RateLimitRedisConfig {
                     url: Url {
@@ -1885,6 +2414,8 @@ mod tests {
         Some(
             RateLimitConfig {
                 global: None,
+                complexity: None,
+                rules: [],
                 storage: Redis,
                 redis: RateLimitRedisConfig {
                     url: Url {
@@ -1929,6 +2460,8 @@ mod tests {
         Some(
             RateLimitConfig {
                 global: None,
+                complexity: None,
+                rules: [],
                 storage: Redis,
                 redis: RateLimitRedisConfig {
                     url: Url {
@@ -1984,6 +2517,8 @@ mod tests {
         Some(
             RateLimitConfig {
                 global: None,
+                complexity: None,
+                rules: [],
                 storage: Redis,
                 redis: RateLimitRedisConfig {
                     url: Url {
@@ -2091,6 +2626,7 @@ mod tests {
             "products": SubgraphConfig {
                 url: None,
                 headers: [],
+                response_headers: [],
                 websocket_url: None,
                 rate_limit: None,
                 timeout: 30s,
@@ -2104,10 +2640,14 @@ mod tests {
                     },
                 ),
                 entity_caching: None,
+                entity_batching: None,
+                circuit_breaker: None,
+                load_balancing: None,
                 message_signatures: None,
                 schema_path: None,
                 introspection_url: None,
                 introspection_headers: None,
+                mock: false,
                 subscription_protocol: None,
                 mtls: None,
             },
@@ -2115,6 +2655,186 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn subgraph_entity_batching() {
+        let input = indoc! {r#"
+            [subgraphs.products.entity_batching]
+            enabled = true
+            max_size = 10
+            max_wait = "5ms"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.subgraphs["products"].entity_batching, @r#"
+        Some(
+            EntityBatchingConfig {
+                enabled: true,
+                max_size: 10,
+                max_wait: 5ms,
+            },
+        )
+        "#);
+    }
+
+    #[test]
+    fn subgraph_circuit_breaker() {
+        let input = indoc! {r#"
+            [subgraphs.products.circuit_breaker]
+            enabled = true
+            failure_rate_threshold = 0.25
+            half_open_after = "5s"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.subgraphs["products"].circuit_breaker, @r#"
+        Some(
+            CircuitBreakerConfig {
+                enabled: true,
+                failure_rate_threshold: 0.25,
+                minimum_requests: 20,
+                window: 10s,
+                half_open_after: 5s,
+            },
+        )
+        "#);
+    }
+
+    #[test]
+    fn subgraph_circuit_breaker_invalid_failure_rate_threshold() {
+        let input = indoc! {r#"
+            [subgraphs.products.circuit_breaker]
+            enabled = true
+            failure_rate_threshold = 1.5
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 3, column 26
+          |
+        3 | failure_rate_threshold = 1.5
+          |                          ^^^
+        failure_rate_threshold must be between 0 and 1, got 1.5
+        "#);
+    }
+
+    #[test]
+    fn subgraph_load_balancing() {
+        let input = indoc! {r#"
+            [subgraphs.products.load_balancing]
+            strategy = "weighted"
+            endpoints = [
+                { url = "http://products-a:4000/graphql", weight = 3 },
+                { url = "http://products-b:4000/graphql" },
+            ]
+
+            [subgraphs.products.load_balancing.health_check]
+            enabled = true
+            interval = "5s"
+
+            [subgraphs.products.load_balancing.passive_health_check]
+            enabled = true
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.subgraphs["products"].load_balancing, @r#"
+        Some(
+            LoadBalancingConfig {
+                endpoints: [
+                    SubgraphEndpointConfig {
+                        url: Url {
+                            scheme: "http",
+                            cannot_be_a_base: false,
+                            username: "",
+                            password: None,
+                            host: Some(
+                                Domain(
+                                    "products-a",
+                                ),
+                            ),
+                            port: Some(
+                                4000,
+                            ),
+                            path: "/graphql",
+                            query: None,
+                            fragment: None,
+                        },
+                        weight: 3,
+                    },
+                    SubgraphEndpointConfig {
+                        url: Url {
+                            scheme: "http",
+                            cannot_be_a_base: false,
+                            username: "",
+                            password: None,
+                            host: Some(
+                                Domain(
+                                    "products-b",
+                                ),
+                            ),
+                            port: Some(
+                                4000,
+                            ),
+                            path: "/graphql",
+                            query: None,
+                            fragment: None,
+                        },
+                        weight: 1,
+                    },
+                ],
+                strategy: Weighted,
+                health_check: ActiveHealthCheckConfig {
+                    enabled: true,
+                    interval: 5s,
+                    timeout: 1s,
+                    unhealthy_threshold: 2,
+                    healthy_threshold: 1,
+                },
+                passive_health_check: PassiveHealthCheckConfig {
+                    enabled: true,
+                    max_failures: 5,
+                    ejection_duration: 30s,
+                },
+            },
+        )
+        "#);
+    }
+
+    #[test]
+    fn health_readiness() {
+        let input = indoc! {r#"
+            [health]
+            readiness_path = "/ready"
+
+            [health.subgraph_probe]
+            enabled = true
+            timeout = "500ms"
+            interval = "30s"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.health, @r#"
+        HealthConfig {
+            enabled: true,
+            listen: None,
+            path: "/health",
+            include_subgraph_endpoints: false,
+            liveness_path: "/health/live",
+            readiness_path: "/ready",
+            subgraph_probe: SubgraphProbeConfig {
+                enabled: true,
+                query: "{ __typename }",
+                timeout: 500ms,
+                interval: 30s,
+            },
+        }
+        "#);
+    }
+
     #[test]
     fn access_logs_default() {
         let input = indoc! {r#"
@@ -2287,6 +3007,61 @@ mod tests {
         assert_eq!(500, config.operation_caching.limit);
     }
 
+    #[test]
+    fn response_cache_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+
+        assert!(!config.response_caching.enabled);
+        assert_eq!(Duration::from_secs(60), config.response_caching.ttl);
+        assert!(config.response_caching.key.headers.is_empty());
+        assert!(config.response_caching.key.jwt_claims.is_empty());
+    }
+
+    #[test]
+    fn response_cache_settings() {
+        let input = indoc! {r#"
+            [response_caching]
+            enabled = true
+            ttl = "5m"
+
+            [response_caching.key]
+            headers = ["accept-language"]
+            jwt_claims = ["sub", "tenant"]
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        assert!(config.response_caching.enabled);
+        assert_eq!(Duration::from_secs(300), config.response_caching.ttl);
+        assert_eq!(
+            vec![AsciiString::from_ascii("accept-language").unwrap()],
+            config.response_caching.key.headers
+        );
+        assert_eq!(
+            vec!["sub".to_string(), "tenant".to_string()],
+            config.response_caching.key.jwt_claims
+        );
+    }
+
+    #[test]
+    fn entity_cache_invalidation_settings() {
+        let input = indoc! {r#"
+            [entity_caching]
+            enabled = true
+
+            [entity_caching.invalidation]
+            enabled = true
+            path = "/admin/purge"
+            secret = "s3cr3t"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        assert!(config.entity_caching.invalidation.enabled);
+        assert_eq!("/admin/purge", config.entity_caching.invalidation.path);
+        assert_eq!(Some("s3cr3t"), config.entity_caching.invalidation.secret.as_deref());
+    }
+
     #[test]
     fn extension_only_version() {
         let input = indoc! {r#"
@@ -2389,6 +3164,11 @@ mod tests {
                 path: "/mcp",
                 execute_mutations: false,
                 transport: StreamingHttp,
+                tools: All,
+                client_name: None,
+                operations: [],
+                prompts: [],
+                resource_subscriptions: false,
             },
         )
         "#);
@@ -2410,11 +3190,95 @@ mod tests {
                 path: "/mcp",
                 execute_mutations: false,
                 transport: Sse,
+                tools: All,
+                client_name: None,
+                operations: [],
+                prompts: [],
+                resource_subscriptions: false,
+            },
+        )
+        "#);
+    }
+
+    #[test]
+    fn mcp_operations() {
+        let input = indoc! {r#"
+            [mcp]
+            enabled = true
+            tools = "operations"
+            client_name = "assistant"
+
+            [[mcp.operations]]
+            path = "operations/customers.graphql"
+            document_id = "customers-v1"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.mcp, @r#"
+        Some(
+            ModelControlProtocolConfig {
+                enabled: true,
+                path: "/mcp",
+                execute_mutations: false,
+                transport: StreamingHttp,
+                tools: Operations,
+                client_name: Some(
+                    "assistant",
+                ),
+                operations: [
+                    McpOperationsConfig {
+                        path: "operations/customers.graphql",
+                        document_id: Some(
+                            "customers-v1",
+                        ),
+                    },
+                ],
+                prompts: [],
+                resource_subscriptions: false,
             },
         )
         "#);
     }
 
+    #[test]
+    fn mcp_prompts() {
+        let input = indoc! {r#"
+            [[mcp.prompts]]
+            name = "find_customer"
+            description = "Find a customer by email"
+            template = "Find the customer with the email {{email}} and summarize their orders."
+
+            [[mcp.prompts.arguments]]
+            name = "email"
+            description = "Email of the customer"
+            required = true
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.mcp.unwrap().prompts, @r#"
+        [
+            McpPromptConfig {
+                name: "find_customer",
+                description: Some(
+                    "Find a customer by email",
+                ),
+                template: "Find the customer with the email {{email}} and summarize their orders.",
+                arguments: [
+                    McpPromptArgumentConfig {
+                        name: "email",
+                        description: Some(
+                            "Email of the customer",
+                        ),
+                        required: true,
+                    },
+                ],
+            },
+        ]
+        "#);
+    }
+
     #[test]
     fn extension_structured_config() {
         let input = indoc! {r#"
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use url::Url;
+
+/// Spreads the requests sent to a subgraph across several replicas. Only queries and mutations
+/// are balanced, subscriptions keep using the subgraph URL.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoadBalancingConfig {
+    /// Endpoints of the subgraph replicas, replacing the subgraph URL.
+    pub endpoints: Vec<SubgraphEndpointConfig>,
+    /// How the endpoint of each request is chosen. Default: round robin.
+    pub strategy: LoadBalancingStrategy,
+    /// Periodically probes every endpoint, ejecting the ones failing to answer.
+    pub health_check: ActiveHealthCheckConfig,
+    /// Ejects the endpoints for which requests keep failing.
+    pub passive_health_check: PassiveHealthCheckConfig,
+}
+
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SubgraphEndpointConfig {
+    pub url: Url,
+    /// Relative share of the requests with the weighted strategy. Default: 1.
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+#[derive(Debug, serde::Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadBalancingStrategy {
+    /// Each endpoint in turn.
+    #[default]
+    RoundRobin,
+    /// The endpoint with the fewest requests in flight.
+    LeastInflight,
+    /// Each endpoint in turn, proportionally to its weight.
+    Weighted,
+}
+
+#[derive(Debug, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ActiveHealthCheckConfig {
+    /// Disabled by default.
+    pub enabled: bool,
+    /// Time between two probes of an endpoint. Default: 10 seconds.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub interval: Duration,
+    /// Time after which a probe is considered failed. Default: 1 second.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub timeout: Duration,
+    /// Number of consecutive failed probes ejecting an endpoint. Default: 2.
+    pub unhealthy_threshold: u32,
+    /// Number of consecutive successful probes bringing an ejected endpoint back. Default: 1.
+    pub healthy_threshold: u32,
+}
+
+impl Default for ActiveHealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(10),
+            timeout: Duration::from_secs(1),
+            unhealthy_threshold: 2,
+            healthy_threshold: 1,
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PassiveHealthCheckConfig {
+    /// Disabled by default.
+    pub enabled: bool,
+    /// Number of consecutive failed requests, network errors or 5xx responses, ejecting an
+    /// endpoint. Default: 5.
+    pub max_failures: u32,
+    /// How long an endpoint stays ejected. Default: 30 seconds.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub ejection_duration: Duration,
+}
+
+impl Default for PassiveHealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_failures: 5,
+            ejection_duration: Duration::from_secs(30),
+        }
+    }
+}
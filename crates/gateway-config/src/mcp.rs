@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 #[derive(Clone, Debug, serde::Deserialize)]
 #[serde(deny_unknown_fields, default)]
 pub struct ModelControlProtocolConfig {
@@ -9,6 +11,17 @@ pub struct ModelControlProtocolConfig {
     pub execute_mutations: bool,
     /// The transport to use (defaults to streaming-http).
     pub transport: McpTransport,
+    /// Which tools are exposed (defaults to all).
+    pub tools: McpTools,
+    /// The client name sent with the configured operations, required to execute trusted documents.
+    pub client_name: Option<String>,
+    /// GraphQL documents whose operations are each exposed as a tool.
+    pub operations: Vec<McpOperationsConfig>,
+    /// Prompt templates offered to the MCP clients.
+    pub prompts: Vec<McpPromptConfig>,
+    /// Whether clients can subscribe to the schema resources. With the streaming-http transport,
+    /// this keeps a session in memory for each client to send the notifications on.
+    pub resource_subscriptions: bool,
 }
 
 #[derive(serde::Deserialize, Debug, Clone, Copy)]
@@ -19,6 +32,53 @@ pub enum McpTransport {
     Sse,
 }
 
+#[derive(serde::Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum McpTools {
+    /// The generic `introspect`, `search` and `execute` tools as well as the configured operations.
+    #[default]
+    All,
+    /// Only the configured operations, arbitrary GraphQL cannot be executed.
+    Operations,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct McpOperationsConfig {
+    /// Path of the GraphQL document. Each named query and mutation in it becomes a tool, described
+    /// by the comments preceding it.
+    pub path: PathBuf,
+    /// The trusted document id of the document. If present, operations are executed by id rather
+    /// than by sending their text.
+    pub document_id: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct McpPromptConfig {
+    /// The unique name of the prompt.
+    pub name: String,
+    /// A human readable description of the prompt.
+    pub description: Option<String>,
+    /// The text of the prompt. Arguments are inserted with `{{argument_name}}`.
+    pub template: String,
+    /// The arguments accepted by the prompt.
+    #[serde(default)]
+    pub arguments: Vec<McpPromptArgumentConfig>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct McpPromptArgumentConfig {
+    /// The name of the argument, as used in the template.
+    pub name: String,
+    /// A human readable description of the argument.
+    pub description: Option<String>,
+    /// Whether the argument must be provided.
+    #[serde(default)]
+    pub required: bool,
+}
+
 impl Default for ModelControlProtocolConfig {
     fn default() -> Self {
         Self {
@@ -26,6 +86,11 @@ impl Default for ModelControlProtocolConfig {
             path: "/mcp".to_string(),
             execute_mutations: false,
             transport: McpTransport::StreamingHttp,
+            tools: McpTools::All,
+            client_name: None,
+            operations: Vec::new(),
+            prompts: Vec::new(),
+            resource_subscriptions: false,
         }
     }
 }
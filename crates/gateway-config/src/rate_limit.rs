@@ -16,12 +16,61 @@ pub struct GraphRateLimit {
 #[serde(deny_unknown_fields)]
 pub struct RateLimitConfig {
     pub global: Option<GraphRateLimit>,
+    pub complexity: Option<ComplexityRateLimit>,
+    /// Limits applied to each client separately.
+    #[serde(default)]
+    pub rules: Vec<RateLimitRule>,
     #[serde(default)]
     pub storage: RateLimitStorage,
     #[serde(default)]
     pub redis: RateLimitRedisConfig,
 }
 
+/// Rate limit counting the complexity points of the executed operations per client rather than
+/// the number of requests.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ComplexityRateLimit {
+    /// Complexity points a client can spend within the duration.
+    pub limit: usize,
+    #[serde(deserialize_with = "deserialize_duration_internal")]
+    pub duration: Duration,
+    /// How clients are identified, by IP address by default.
+    #[serde(default)]
+    pub key: RateLimitClientKey,
+}
+
+/// Request limit applied to each client separately, either to every operation or to the requests
+/// sent to a single subgraph.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitRule {
+    /// Unique name of the rule, used to store its counters.
+    pub name: String,
+    pub limit: usize,
+    #[serde(deserialize_with = "deserialize_duration_internal")]
+    pub duration: Duration,
+    /// How clients are identified.
+    pub key: RateLimitClientKey,
+    /// Restricts the rule to the requests sent to this subgraph.
+    pub subgraph: Option<String>,
+}
+
+/// Identifies the clients of a rate limit. Requests without a value for it aren't limited.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitClientKey {
+    /// IP address of the client connection.
+    #[default]
+    Ip,
+    /// Value of a request header.
+    Header(String),
+    /// Value of a claim of the authenticated JWT.
+    JwtClaim(String),
+    /// Name of the GraphQL operation.
+    OperationName,
+}
+
 #[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum RateLimitStorage {
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use ascii::AsciiString;
+
+const DEFAULT_RESPONSE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Whole-response caching. Responses are stored in the same storage as the entity cache and
+/// keyed on the normalized operation, its variables and the configured key parts.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResponseCachingConfig {
+    pub enabled: bool,
+    /// The maximum ttl of a cached response. Shorter `@cacheControl` hints and subgraph
+    /// `Cache-Control` headers take precedence. Defaults to 60s
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub ttl: Duration,
+    /// Additional parts of the request partitioning the cache.
+    pub key: ResponseCacheKeyConfig,
+}
+
+impl Default for ResponseCachingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl: DEFAULT_RESPONSE_CACHE_TTL,
+            key: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, serde::Deserialize, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResponseCacheKeyConfig {
+    /// Request headers whose values are part of the cache key.
+    pub headers: Vec<AsciiString>,
+    /// Claims of the authentication token whose values are part of the cache key. Authenticated
+    /// requests bypass the cache unless at least one claim is configured.
+    pub jwt_claims: Vec<String>,
+}
@@ -1,6 +1,7 @@
 pub mod logs;
 pub mod metrics;
 pub mod otlp;
+pub mod prometheus;
 pub mod response_extension;
 pub mod stdout;
 pub mod tracing;
@@ -8,10 +9,13 @@ pub mod tracing;
 use std::time::Duration;
 
 pub use logs::LogsConfig;
-pub use metrics::MetricsConfig;
+pub use metrics::{MetricsConfig, MetricsExportersConfig};
 pub use otlp::*;
+pub use prometheus::PrometheusExporterConfig;
 pub use response_extension::*;
-pub use tracing::{DEFAULT_SAMPLING, PropagationConfig, TracingCollectConfig, TracingConfig};
+pub use tracing::{
+    DEFAULT_SAMPLING, PropagationConfig, SamplingRuleConfig, TailSamplingConfig, TracingCollectConfig, TracingConfig,
+};
 
 use serde::Deserialize;
 pub use stdout::StdoutExporterConfig;
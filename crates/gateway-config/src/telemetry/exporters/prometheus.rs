@@ -0,0 +1,29 @@
+use std::{borrow::Cow, net::SocketAddr, time::Duration};
+
+/// Prometheus exporter configuration, serving the metrics in the text exposition format.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrometheusExporterConfig {
+    /// Enable or disable the exporter
+    pub enabled: bool,
+    /// Address of a dedicated server for the metrics endpoint. If not set, the endpoint is served
+    /// by the gateway itself.
+    pub listen: Option<SocketAddr>,
+    /// Path of the metrics endpoint.
+    pub path: Cow<'static, str>,
+    /// Interval at which the served metrics are refreshed.
+    /// The default value is 5 seconds.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub refresh_interval: Duration,
+}
+
+impl Default for PrometheusExporterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            listen: None,
+            path: Cow::Borrowed("/metrics"),
+            refresh_interval: Duration::from_secs(5),
+        }
+    }
+}
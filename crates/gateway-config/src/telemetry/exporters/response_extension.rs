@@ -10,6 +10,10 @@ pub struct ResponseExtensionExporterConfig {
     pub trace_id: bool,
     /// Whether queryPlan is exposed in the grafbase response extension. Defaults to true.
     pub query_plan: bool,
+    /// Whether requests with the `x-grafbase-explain` header only return the explained query plan
+    /// in the grafbase response extension, without executing the operation. The same access
+    /// control applies. Defaults to false.
+    pub explain: bool,
     /// Defines under which conditions the grafbase response extension will be added.
     /// Defaults to a simple header rule, the presence of `x-grafbase-telemetry` is enough.
     pub access_control: Vec<AccessControl>,
@@ -20,6 +24,7 @@ impl Default for ResponseExtensionExporterConfig {
         Self {
             trace_id: true,
             query_plan: true,
+            explain: false,
             access_control: vec![AccessControl::Header(HeaderAccessControl {
                 name: AsciiString::from_str("x-grafbase-telemetry").unwrap(),
                 value: None,
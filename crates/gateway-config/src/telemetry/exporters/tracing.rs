@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use super::OpenTelemetryExportersConfig;
 
 use serde::de::Error as DeserializeError;
@@ -22,6 +24,9 @@ pub struct TracingConfig {
     pub exporters: OpenTelemetryExportersConfig,
     /// Trace parent and context propagation configuration
     pub propagation: PropagationConfig,
+    /// Sampling decided once the request has finished. If set, `sampling` is only applied to the
+    /// traces that aren't kept by any of its rules.
+    pub tail_sampling: Option<TailSamplingConfig>,
 }
 
 impl Default for TracingConfig {
@@ -32,6 +37,7 @@ impl Default for TracingConfig {
             exporters: Default::default(),
             propagation: Default::default(),
             parent_based_sampler: false,
+            tail_sampling: None,
         }
     }
 }
@@ -69,6 +75,52 @@ impl Default for TracingCollectConfig {
     }
 }
 
+/// Tail sampling configuration
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TailSamplingConfig {
+    /// Always keep traces with GraphQL errors or a failed HTTP request.
+    /// The default is true.
+    pub keep_errors: bool,
+    /// Always keep traces with a failed subgraph request.
+    /// The default is true.
+    pub keep_subgraph_failures: bool,
+    /// Always keep traces of requests slower than this threshold.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub latency_threshold: Option<Duration>,
+    /// Sampling ratios for specific operations or clients, the first matching rule is applied.
+    pub rules: Vec<SamplingRuleConfig>,
+    /// The maximum number of traces kept in memory while waiting for their request to finish. The
+    /// oldest traces are dropped beyond it.
+    /// The default is 10000.
+    pub max_buffered_traces: usize,
+}
+
+impl Default for TailSamplingConfig {
+    fn default() -> Self {
+        Self {
+            keep_errors: true,
+            keep_subgraph_failures: true,
+            latency_threshold: None,
+            rules: Vec::new(),
+            max_buffered_traces: 10_000,
+        }
+    }
+}
+
+/// Sampling ratio applied to the traces matching all the conditions of the rule.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SamplingRuleConfig {
+    /// Name of the GraphQL operation
+    pub operation_name: Option<String>,
+    /// Name of the client, from the `x-grafbase-client-name` header
+    pub client_name: Option<String>,
+    /// The sampler between 0.0 and 1.0.
+    #[serde(deserialize_with = "deserialize_sampling")]
+    pub sampling: f64,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct PropagationConfig {
@@ -82,6 +82,13 @@ impl TelemetryConfig {
         if cfg.is_enabled() { Some(cfg) } else { None }
     }
 
+    pub fn metrics_prometheus_config(&self) -> Option<&PrometheusExporterConfig> {
+        self.metrics
+            .as_ref()
+            .and_then(|c| c.exporters.prometheus.as_ref())
+            .filter(|c| c.enabled)
+    }
+
     pub fn logs_stdout_config(&self) -> Option<&StdoutExporterConfig> {
         match self.logs.as_ref().and_then(|c| c.exporters.stdout.as_ref()) {
             Some(config) if config.enabled => Some(config),
@@ -936,6 +943,61 @@ mod tests {
         assert!(expected.is_some());
     }
 
+    #[test]
+    fn metrics_prometheus_defaults() {
+        let input = indoc! {r#"
+            [metrics.exporters.prometheus]
+        "#};
+
+        let config: TelemetryConfig = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            Some(&PrometheusExporterConfig {
+                enabled: true,
+                listen: None,
+                path: "/metrics".into(),
+                refresh_interval: Duration::from_secs(5),
+            }),
+            config.metrics_prometheus_config()
+        );
+    }
+
+    #[test]
+    fn metrics_prometheus_custom() {
+        let input = indoc! {r#"
+            [metrics.exporters.prometheus]
+            listen = "0.0.0.0:9090"
+            path = "/prometheus"
+            refresh_interval = "1s"
+        "#};
+
+        let config: TelemetryConfig = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            Some(&PrometheusExporterConfig {
+                enabled: true,
+                listen: Some("0.0.0.0:9090".parse().unwrap()),
+                path: "/prometheus".into(),
+                refresh_interval: Duration::from_secs(1),
+            }),
+            config.metrics_prometheus_config()
+        );
+    }
+
+    #[test]
+    fn metrics_prometheus_not_enabled() {
+        let input = indoc! {r#"
+            [metrics.exporters.prometheus]
+            enabled = false
+        "#};
+
+        let config: TelemetryConfig = toml::from_str(input).unwrap();
+        assert_eq!(None, config.metrics_prometheus_config());
+
+        let config: TelemetryConfig = toml::from_str("").unwrap();
+        assert_eq!(None, config.metrics_prometheus_config());
+    }
+
     #[test]
     fn metrics_otlp_default_config() {
         let input = indoc! {r#"
@@ -1959,9 +2021,62 @@ mod tests {
                         baggage: true,
                         aws_xray: false
                     },
+                    tail_sampling: None,
                 },
             },
             config
         );
     }
+
+    #[test]
+    fn tail_sampling() {
+        let input = indoc! {r#"
+            [tail_sampling]
+            latency_threshold = "2s"
+
+            [[tail_sampling.rules]]
+            operation_name = "GetUser"
+            sampling = 1.0
+
+            [[tail_sampling.rules]]
+            client_name = "ios"
+            sampling = 0.5
+        "#};
+
+        let config: TracingConfig = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            Some(TailSamplingConfig {
+                keep_errors: true,
+                keep_subgraph_failures: true,
+                latency_threshold: Some(Duration::from_secs(2)),
+                rules: vec![
+                    SamplingRuleConfig {
+                        operation_name: Some("GetUser".into()),
+                        client_name: None,
+                        sampling: 1.0,
+                    },
+                    SamplingRuleConfig {
+                        operation_name: None,
+                        client_name: Some("ios".into()),
+                        sampling: 0.5,
+                    },
+                ],
+                max_buffered_traces: 10_000,
+            }),
+            config.tail_sampling
+        );
+    }
+
+    #[test]
+    fn tail_sampling_rule_invalid_sampling() {
+        let input = indoc! {r#"
+            [[tail_sampling.rules]]
+            operation_name = "GetUser"
+            sampling = 2.0
+        "#};
+
+        let error = toml::from_str::<TracingConfig>(input).unwrap_err();
+        assert!(error.to_string().contains("input value should be 0..1"), "{error}");
+    }
 }
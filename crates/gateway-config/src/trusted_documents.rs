@@ -1,3 +1,5 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
 use ascii::AsciiString;
 
 use crate::LogLevel;
@@ -18,6 +20,8 @@ pub struct TrustedDocumentsConfig {
     pub document_id_and_query_mismatch_log_level: LogLevel,
     /// The log level to emit logs when a request contains only an inline document but it does not correspond to any trusted document. Default: INFO.
     pub inline_document_unknown_log_level: LogLevel,
+    /// Persisted query manifests loaded from disk, by client name. Both the Apollo persisted query manifest and the Relay `{ id: query }` formats are supported. Relative paths are resolved from the directory of the configuration file. The files are reloaded when they change. If any is defined, trusted documents are only read from these files. Default: none.
+    pub manifests: BTreeMap<String, PathBuf>,
 }
 
 impl Default for TrustedDocumentsConfig {
@@ -29,6 +33,7 @@ impl Default for TrustedDocumentsConfig {
             document_id_unknown_log_level: LogLevel::Info,
             document_id_and_query_mismatch_log_level: LogLevel::Info,
             inline_document_unknown_log_level: LogLevel::Info,
+            manifests: BTreeMap::new(),
         }
     }
 }
@@ -1,6 +1,6 @@
 use clap::Parser;
 use colored::Colorize;
-use graphql_lint::{LinterError, Severity, lint};
+use graphql_lint::{Diagnostic, LintConfig, LinterError, Severity, lint_with_config};
 use std::{fs, path::PathBuf, process};
 
 #[derive(Debug, Parser)]
@@ -8,12 +8,19 @@ use std::{fs, path::PathBuf, process};
 struct Interface {
     /// The GraphQL SDL file to lint
     schema: PathBuf,
+    /// The path of a TOML file enabling, disabling or setting the severity of lint rules
+    #[arg(long)]
+    config: Option<PathBuf>,
 }
 
 #[derive(Debug, thiserror::Error)]
 enum Error {
     #[error("Could not read the provided schema file\nCaused by: {0}")]
     ReadSchemaFile(#[from] std::io::Error),
+    #[error("Could not read the lint configuration at {}\nCaused by: {1}", .0.display())]
+    ReadConfigFile(PathBuf, std::io::Error),
+    #[error("Could not parse the lint configuration at {}\nCaused by: {1}", .0.display())]
+    ParseConfigFile(PathBuf, toml::de::Error),
     #[error(transparent)]
     Lint(#[from] LinterError),
 }
@@ -22,11 +29,14 @@ fn report_error(error: Error) {
     eprintln!("{}", format!("Error: {error}").bright_red());
 }
 
-fn report_diagnostic(message: String, severity: Severity) {
-    let label = match severity {
-        Severity::Warning => "Warning",
-    };
-    println!("{}", format!("⚠️ [{label}]: {message}").bright_yellow());
+fn report_diagnostic(diagnostic: &Diagnostic) {
+    let message = diagnostic.message();
+    let rule = diagnostic.rule();
+
+    match diagnostic.severity() {
+        Severity::Warning => println!("{}", format!("⚠️ [Warning]: {message} ({rule})").bright_yellow()),
+        Severity::Error => println!("{}", format!("❌ [Error]: {message} ({rule})").bright_red()),
+    }
 }
 
 fn report_success() {
@@ -37,7 +47,9 @@ fn main() {
     let arguments = Interface::parse();
 
     let exit_code = match try_main(arguments) {
-        Ok(()) => 0,
+        Ok(Outcome::Clean) => 0,
+        // Warnings alone don't fail the run.
+        Ok(Outcome::HasErrors) => 1,
         Err(error) => {
             report_error(error);
             1
@@ -47,18 +59,40 @@ fn main() {
     process::exit(exit_code);
 }
 
-fn try_main(arguments: Interface) -> Result<(), Error> {
+enum Outcome {
+    Clean,
+    HasErrors,
+}
+
+fn try_main(arguments: Interface) -> Result<Outcome, Error> {
+    let config = match arguments.config {
+        Some(config_path) => {
+            let config =
+                fs::read_to_string(&config_path).map_err(|error| Error::ReadConfigFile(config_path.clone(), error))?;
+
+            toml::from_str::<LintConfig>(&config).map_err(|error| Error::ParseConfigFile(config_path, error))?
+        }
+        None => LintConfig::default(),
+    };
+
     let schema = fs::read_to_string(arguments.schema)?;
-    let diagnostics = lint(&schema)?;
+    let diagnostics = lint_with_config(&schema, &config)?;
 
     if diagnostics.is_empty() {
         report_success();
-        return Ok(());
+        return Ok(Outcome::Clean);
+    }
+
+    for diagnostic in &diagnostics {
+        report_diagnostic(diagnostic);
     }
 
-    for (message, severity) in diagnostics {
-        report_diagnostic(message, severity);
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity() == Severity::Error)
+    {
+        return Ok(Outcome::HasErrors);
     }
 
-    Ok(())
+    Ok(Outcome::Clean)
 }
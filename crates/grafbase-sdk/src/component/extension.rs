@@ -120,4 +120,22 @@ pub(crate) trait AnyExtension {
     ) -> Result<(), Error> {
         Ok(())
     }
+
+    fn on_graphql_subgraph_response(
+        &mut self,
+        ctx: &AuthorizedOperationContext,
+        subgraph_name: &str,
+        status: http::StatusCode,
+        headers: &mut Headers,
+    ) -> Result<(), Error> {
+        Err(Error::new(
+            "Hooks extension not initialized correctly. Is it defined with the appropriate type?",
+        ))
+    }
+
+    fn on_graphql_response(&mut self, ctx: &RequestContext, body: &mut Vec<u8>) -> Result<(), Error> {
+        Err(Error::new(
+            "Hooks extension not initialized correctly. Is it defined with the appropriate type?",
+        ))
+    }
 }
@@ -18,6 +18,7 @@ impl wit::HooksGuest for Component {
                     contract_key: output.contract_key,
                     context: output.context,
                     headers: parts.headers.into(),
+                    override_labels: output.override_labels,
                 })
                 .map_err(Into::into)
         })
@@ -75,4 +76,36 @@ impl wit::HooksGuest for Component {
                 .map_err(Into::into)
         })
     }
+
+    fn on_graphql_subgraph_response(
+        event_queue: wit::EventQueue,
+        ctx: wit::AuthorizedOperationContext,
+        subgraph_name: String,
+        status: u16,
+        headers: wit::Headers,
+    ) -> Result<wit::Headers, wit::Error> {
+        state::with_event_queue(event_queue, || {
+            let status = http::StatusCode::from_u16(status)
+                .expect("we converted this from http::StatusCode in the host, this cannot be invalid");
+            let mut headers: Headers = headers.into();
+
+            state::extension()?
+                .on_graphql_subgraph_response(&(ctx.into()), &subgraph_name, status, &mut headers)
+                .map(|_| headers.into())
+                .map_err(Into::into)
+        })
+    }
+
+    fn on_graphql_response(
+        event_queue: wit::EventQueue,
+        ctx: wit::RequestContext,
+        mut body: Vec<u8>,
+    ) -> Result<Vec<u8>, wit::Error> {
+        state::with_event_queue(event_queue, || {
+            state::extension()?
+                .on_graphql_response(&(ctx.into()), &mut body)
+                .map(|_| body)
+                .map_err(Into::into)
+        })
+    }
 }
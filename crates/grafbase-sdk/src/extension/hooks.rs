@@ -146,6 +146,28 @@ pub trait HooksExtension: Sized + 'static {
     ) -> Result<(), Error> {
         Ok(())
     }
+
+    /// Called once a GraphQL subgraph responded, after all retries, allowing you to inspect the status and modify the response headers before the engine processes them.
+    ///
+    /// Returning an error fails the subgraph request.
+    fn on_graphql_subgraph_response(
+        &mut self,
+        ctx: &AuthorizedOperationContext,
+        subgraph_name: &str,
+        status: http::StatusCode,
+        headers: &mut Headers,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called with the serialized JSON body of the GraphQL response before it is sent back to the client, allowing you to rewrite or redact it.
+    ///
+    /// This hook is not called for streaming responses, such as subscriptions and incremental delivery.
+    /// It must be enabled in the `extension.toml` with `on_graphql_response = true` in the `[hooks]`
+    /// section, otherwise the response body isn't copied into the extension.
+    fn on_graphql_response(&mut self, ctx: &RequestContext, body: &mut Vec<u8>) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 pub trait IntoOnRequestOutput {
@@ -207,6 +229,20 @@ pub fn register<T: HooksExtension>() {
         ) -> Result<(), Error> {
             self.0.on_virtual_subgraph_request(ctx, subgraph_name, headers)
         }
+
+        fn on_graphql_subgraph_response(
+            &mut self,
+            ctx: &AuthorizedOperationContext,
+            subgraph_name: &str,
+            status: http::StatusCode,
+            headers: &mut Headers,
+        ) -> Result<(), Error> {
+            self.0.on_graphql_subgraph_response(ctx, subgraph_name, status, headers)
+        }
+
+        fn on_graphql_response(&mut self, ctx: &RequestContext, body: &mut Vec<u8>) -> Result<(), Error> {
+            self.0.on_graphql_response(ctx, body)
+        }
     }
 
     crate::component::register_extension(Box::new(|_, config| {
@@ -10,6 +10,7 @@
 //! - GraphQL operation execution (including timing, caching, and status)
 //! - Subgraph request details (including retries, caching, and response times)
 //! - HTTP request execution
+//! - Subgraph circuit breaker state changes
 //! - Custom extension logs with serializable data
 //!
 //! # Example
@@ -126,6 +127,8 @@ pub enum Event {
     Http(ExecutedHttpRequest),
     /// A custom extension log entry with serialized data.
     Extension(ExtensionEvent),
+    /// A subgraph circuit breaker changed state.
+    CircuitBreaker(CircuitBreakerStateChange),
 }
 
 impl From<wit::Event> for Event {
@@ -135,6 +138,7 @@ impl From<wit::Event> for Event {
             wit::Event::Subgraph(executed_subgraph_request) => Self::Subgraph(executed_subgraph_request.into()),
             wit::Event::Http(executed_http_request) => Self::Http(executed_http_request.into()),
             wit::Event::Extension(event) => Self::Extension(event.into()),
+            wit::Event::CircuitBreaker(change) => Self::CircuitBreaker(change.into()),
         }
     }
 }
@@ -481,3 +485,62 @@ impl From<wit::ExtensionEvent> for ExtensionEvent {
         }
     }
 }
+
+/// Represents a state change of the circuit breaker of a subgraph.
+#[non_exhaustive]
+pub struct CircuitBreakerStateChange {
+    /// The name of the subgraph protected by the circuit breaker.
+    pub subgraph_name: String,
+    /// The state before the change.
+    pub previous_state: CircuitBreakerState,
+    /// The state after the change.
+    pub state: CircuitBreakerState,
+}
+
+impl From<wit::CircuitBreakerStateChange> for CircuitBreakerStateChange {
+    fn from(value: wit::CircuitBreakerStateChange) -> Self {
+        Self {
+            subgraph_name: value.subgraph_name,
+            previous_state: value.previous_state.into(),
+            state: value.state.into(),
+        }
+    }
+}
+
+/// The state of a subgraph circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitBreakerState {
+    /// Requests are sent to the subgraph.
+    Closed,
+    /// Requests are rejected without reaching the subgraph.
+    Open,
+    /// A single probe request is sent to decide whether the subgraph recovered.
+    HalfOpen,
+}
+
+impl From<wit::CircuitBreakerState> for CircuitBreakerState {
+    fn from(value: wit::CircuitBreakerState) -> Self {
+        match value {
+            wit::CircuitBreakerState::Closed => Self::Closed,
+            wit::CircuitBreakerState::Open => Self::Open,
+            wit::CircuitBreakerState::HalfOpen => Self::HalfOpen,
+        }
+    }
+}
+
+impl CircuitBreakerState {
+    /// Returns the circuit breaker state as a string slice.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+impl AsRef<str> for CircuitBreakerState {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
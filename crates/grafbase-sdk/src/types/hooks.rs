@@ -36,6 +36,7 @@ impl From<HttpRequestParts> for wit::HttpRequestParts {
 pub struct OnRequestOutput {
     pub(crate) context: Vec<u8>,
     pub(crate) contract_key: Option<String>,
+    pub(crate) override_labels: Vec<String>,
 }
 
 impl OnRequestOutput {
@@ -50,6 +51,13 @@ impl OnRequestOutput {
         self
     }
 
+    /// Enables a custom progressive override label, `@override(label: "...")`, for the request.
+    /// Fields overridden with that label are resolved from the overriding subgraph.
+    pub fn override_label(mut self, label: impl Into<String>) -> Self {
+        self.override_labels.push(label.into());
+        self
+    }
+
     /// Set the Hooks context for the request.
     /// Accessible by other extensions.
     pub fn context(mut self, context: impl Into<Vec<u8>>) -> Self {
@@ -8,7 +8,7 @@ mod resolver_types;
 
 wit_bindgen::generate!({
     skip: ["register-extension"],
-    path: "./wit/since_0_23_0/",
+    path: "./wit/since_0_24_0/",
     world: "sdk",
     with: {
         "grafbase:sdk/resolver-types": resolver_types,
@@ -31,8 +31,9 @@ pub(crate) use grafbase::sdk::contracts_types::{Contract, GraphqlSubgraph};
 pub(crate) use grafbase::sdk::error::{Error, ErrorResponse};
 pub(crate) use grafbase::sdk::event_queue::EventQueue;
 pub(crate) use grafbase::sdk::event_types::{
-    CacheStatus, Event, ExecutedHttpRequest, ExecutedOperation, ExecutedSubgraphRequest, ExtensionEvent, FieldError,
-    GraphqlResponseStatus, OperationType, RequestError, SubgraphRequestExecutionKind, SubgraphResponse,
+    CacheStatus, CircuitBreakerState, CircuitBreakerStateChange, Event, ExecutedHttpRequest, ExecutedOperation,
+    ExecutedSubgraphRequest, ExtensionEvent, FieldError, GraphqlResponseStatus, OperationType, RequestError,
+    SubgraphRequestExecutionKind, SubgraphResponse,
 };
 pub(crate) use grafbase::sdk::grpc::*;
 pub(crate) use grafbase::sdk::headers::HeaderError;
@@ -17,7 +17,7 @@ pub(crate) use self::context::Context as ComposeContext;
 use self::{context::Context, directives::collect_composed_directives, input_object::*};
 use crate::{
     composition_ir as ir,
-    diagnostics::CompositeSchemasPreMergeValidationErrorCode,
+    diagnostics::{CompositeSchemasPreMergeValidationErrorCode, DiagnosticLocation},
     federated_graph as federated,
     subgraphs::{self, DefinitionKind, DefinitionView, StringId},
 };
@@ -67,9 +67,13 @@ fn merge_object_definitions<'a>(ctx: &mut Context<'a>, first: &DefinitionView<'a
         let name = ctx.subgraphs[first.name].as_ref();
         let first_subgraph = ctx.subgraphs[ctx.subgraphs.at(first.subgraph_id).name].as_ref();
         let second_subgraph = ctx.subgraphs[ctx.subgraphs.at(incompatible.subgraph_id).name].as_ref();
-        ctx.diagnostics.push_composite_schemas_pre_merge_validation_error(format!(
-            "Cannot merge {first_kind:?} with {second_kind:?} (`{name}` in `{first_subgraph}` and `{second_subgraph}`)",
-        ), CompositeSchemasPreMergeValidationErrorCode::TypeKindMismatch);
+        ctx.diagnostics.push_composite_schemas_pre_merge_validation_error(
+            DiagnosticLocation::definition(ctx.subgraphs, *incompatible),
+            format!(
+                "Cannot merge {first_kind:?} with {second_kind:?} (`{name}` in `{first_subgraph}` and `{second_subgraph}`)",
+            ),
+            CompositeSchemasPreMergeValidationErrorCode::TypeKindMismatch,
+        );
         return;
     }
 
@@ -124,7 +128,9 @@ fn validate_consistent_entityness(ctx: &mut Context<'_>, definitions: &[Definiti
         return true;
     }
 
-    let Some(definition) = definitions.first() else {
+    let Some(definition) = definitions.iter().find(|def| {
+        !def.id.is_entity(ctx.subgraphs) && ctx.subgraphs.at(def.subgraph_id).federation_spec.is_apollo_v2()
+    }) else {
         return false;
     };
     let mut non_entity_fed_v2 = Vec::new();
@@ -143,16 +149,15 @@ fn validate_consistent_entityness(ctx: &mut Context<'_>, definitions: &[Definiti
         }
     }
 
-    ctx.diagnostics.push_fatal(format!(
-        "The `{name}` object is an entity in subgraphs {entity_definitions} but not in subgraphs {non_entity_subgraphs}.",
-        name = ctx.subgraphs[definition.name],
-        entity_definitions = entity_definitions
-            .into_iter()
-            .join(", "),
-        non_entity_subgraphs = non_entity_fed_v2
-            .into_iter()
-            .join(", "),
-    ));
+    ctx.diagnostics.push_fatal_at(
+        DiagnosticLocation::definition(ctx.subgraphs, *definition),
+        format!(
+            "The `{name}` object is an entity in subgraphs {entity_definitions} but not in subgraphs {non_entity_subgraphs}.",
+            name = ctx.subgraphs[definition.name],
+            entity_definitions = entity_definitions.into_iter().join(", "),
+            non_entity_subgraphs = non_entity_fed_v2.into_iter().join(", "),
+        ),
+    );
 
     false
 }
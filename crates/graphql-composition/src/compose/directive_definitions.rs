@@ -1,4 +1,4 @@
-use crate::federated_graph::DirectiveLocations;
+use crate::{diagnostics::DiagnosticLocation, federated_graph::DirectiveLocations};
 
 use super::*;
 use std::fmt::Write as _;
@@ -46,7 +46,10 @@ pub(super) fn compose_directive_definitions(ctx: &mut Context<'_>) {
                     .unwrap();
                 }
 
-                ctx.diagnostics.push_warning(diagnostic);
+                ctx.diagnostics.push_warning_at(
+                    DiagnosticLocation::directive_definition(ctx.subgraphs, definition),
+                    diagnostic,
+                );
             }
         }
 
@@ -84,7 +87,10 @@ pub(super) fn compose_directive_definitions(ctx: &mut Context<'_>) {
                         .unwrap();
                     }
 
-                    ctx.diagnostics.push_fatal(diagnostic);
+                    ctx.diagnostics.push_fatal_at(
+                        DiagnosticLocation::directive_definition(ctx.subgraphs, definition),
+                        diagnostic,
+                    );
                     continue 'directives;
                 } else {
                     let input_value_definition = ir::InputValueDefinitionIr {
@@ -131,15 +137,21 @@ pub(super) fn compose_directive_definitions(ctx: &mut Context<'_>) {
                     .unwrap();
                 }
 
-                ctx.diagnostics.push_warning(diagnostic);
+                ctx.diagnostics.push_warning_at(
+                    DiagnosticLocation::directive_definition(ctx.subgraphs, definition),
+                    diagnostic,
+                );
             }
             if definition.repeatable != first_definition.repeatable {
-                ctx.diagnostics.push_fatal(format!(
-                    "Directive `{}` is defined as repeatable in {} but not in {}.",
-                    ctx.subgraphs[first_definition.name].as_ref(),
-                    ctx.subgraphs[ctx.subgraphs.at(definition.subgraph_id).name].as_ref(),
-                    ctx.subgraphs[ctx.subgraphs.at(first_definition.subgraph_id).name].as_ref(),
-                ));
+                ctx.diagnostics.push_fatal_at(
+                    DiagnosticLocation::directive_definition(ctx.subgraphs, definition),
+                    format!(
+                        "Directive `{}` is defined as repeatable in {} but not in {}.",
+                        ctx.subgraphs[first_definition.name].as_ref(),
+                        ctx.subgraphs[ctx.subgraphs.at(definition.subgraph_id).name].as_ref(),
+                        ctx.subgraphs[ctx.subgraphs.at(first_definition.subgraph_id).name].as_ref(),
+                    ),
+                );
                 continue 'directives;
             }
         }
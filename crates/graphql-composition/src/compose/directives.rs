@@ -1,4 +1,4 @@
-use crate::subgraphs::DirectiveSiteId;
+use crate::{diagnostics::DiagnosticLocation, subgraphs::DirectiveSiteId};
 
 use super::*;
 
@@ -79,9 +79,15 @@ pub(super) fn collect_composed_directives(
                     let extension_id = ctx.get_extension_for_linked_schema(linked_schema_id);
                     match (extension_id, is_composed_directive) {
                         (Some(_), true) => {
-                            ctx.diagnostics.push_fatal(String::from(
-                                "Directives from extensions must not be composed with `@composeDirective`",
-                            ));
+                            ctx.diagnostics.push_fatal_at(
+                                DiagnosticLocation::subgraph(
+                                    ctx.subgraphs,
+                                    ctx.subgraphs.at(linked_schema_id).subgraph_id,
+                                ),
+                                String::from(
+                                    "Directives from extensions must not be composed with `@composeDirective`",
+                                ),
+                            );
                             None
                         }
                         (Some(extension_id), false) => {
@@ -1,7 +1,10 @@
 use std::collections::HashSet;
 
 use super::*;
-use crate::subgraphs::{StringId, Subgraphs};
+use crate::{
+    diagnostics::DiagnosticLocation,
+    subgraphs::{StringId, Subgraphs},
+};
 
 pub(super) fn merge_enum_definitions<'a>(
     first: &DefinitionView<'_>,
@@ -90,10 +93,10 @@ fn merge_intersection<'a>(
     }
 
     if intersection.is_empty() {
-        ctx.diagnostics.push_fatal(format!(
-            "Values for enum {} are empty (intersection)",
-            ctx.subgraphs[first.name],
-        ));
+        ctx.diagnostics.push_fatal_at(
+            DiagnosticLocation::definition(ctx.subgraphs, *first),
+            format!("Values for enum {} are empty (intersection)", ctx.subgraphs[first.name]),
+        );
     }
 
     for value in intersection {
@@ -158,12 +161,15 @@ fn merge_exactly_matching<'a>(
             .copied()
             .eq(definition.id.enum_values(ctx.subgraphs).map(|v| v.name))
         {
-            ctx.diagnostics.push_fatal(format!(
-                "The values of enum \"{}\" should match exactly in all subgraphs because the enum is used both in input and output positions, but they do not match in subgraphs \"{}\" and \"{}\".",
-                ctx.subgraphs[first.name],
-                ctx.subgraphs[ctx.subgraphs.at(first.subgraph_id).name],
-                ctx.subgraphs[ctx.subgraphs.at(definition.subgraph_id).name],
-            ));
+            ctx.diagnostics.push_fatal_at(
+                DiagnosticLocation::definition(ctx.subgraphs, *definition),
+                format!(
+                    "The values of enum \"{}\" should match exactly in all subgraphs because the enum is used both in input and output positions, but they do not match in subgraphs \"{}\" and \"{}\".",
+                    ctx.subgraphs[first.name],
+                    ctx.subgraphs[ctx.subgraphs.at(first.subgraph_id).name],
+                    ctx.subgraphs[ctx.subgraphs.at(definition.subgraph_id).name],
+                ),
+            );
             return;
         }
     }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::diagnostics::DiagnosticLocation;
 
 /// Group fields of the definitions that share the same name. For each name group, `compose_fn` is
 /// called once with the relevant fields.
@@ -63,9 +64,12 @@ fn compose_field_types<'a>(
         if is_guest_batched {
             ty.wrapping = ty.wrapping.without_list().ok_or_else(|| {
                 let parent_definition = ctx.subgraphs.at(field.parent_definition_id);
-                format!(
-                    "The field {}.{} has an argument with a batched @require, it must return a list",
-                    ctx.subgraphs[parent_definition.name], ctx.subgraphs[field.name]
+                (
+                    DiagnosticLocation::field(ctx.subgraphs, field),
+                    format!(
+                        "The field {}.{} has an argument with a batched @require, it must return a list",
+                        ctx.subgraphs[parent_definition.name], ctx.subgraphs[field.name]
+                    ),
                 )
             })?
         }
@@ -74,8 +78,8 @@ fn compose_field_types<'a>(
 
     let first = match fields.next()? {
         Ok(first) => first,
-        Err(err) => {
-            ctx.diagnostics.push_fatal(err);
+        Err((location, message)) => {
+            ctx.diagnostics.push_fatal_at(location, message);
             return None;
         }
     };
@@ -89,7 +93,7 @@ fn compose_field_types<'a>(
                     .at(ctx.subgraphs.at(field.parent_definition_id).subgraph_id)
             });
 
-            format!(
+            let message = format!(
                 "The {}.{} field has conflicting types in different subgraphs: {} in {} but {} in {}",
                 ctx.subgraphs[parent_definition.name],
                 ctx.subgraphs[first.0.name],
@@ -103,12 +107,14 @@ fn compose_field_types<'a>(
                     .wrapping
                     .type_display(&ctx.subgraphs[b_field.r#type.definition_name_id]),
                 ctx.subgraphs[b_field_subgraph.name],
-            )
+            );
+
+            (DiagnosticLocation::field(ctx.subgraphs, b_field), message)
         })
     }) {
         Ok((_, ty)) => Some(ty),
-        Err(msg) => {
-            ctx.diagnostics.push_fatal(msg);
+        Err((location, message)) => {
+            ctx.diagnostics.push_fatal_at(location, message);
             None
         }
     }
@@ -135,16 +141,19 @@ pub(super) fn compose_argument_types<'a>(
                 [a_arg.parent_definition_id, b_arg.parent_definition_id].map(|id| ctx.subgraphs.at(id));
             let [a_subgraph, b_subgraph] = [a_definition, b_definition].map(|def| ctx.subgraphs.at(def.subgraph_id));
 
-            ctx.diagnostics.push_fatal(format!(
-                "The {}.{}({}:) argument has conflicting types in different subgraphs: {} in {} but {} in {}",
-                ctx.subgraphs[parent_definition_name],
-                ctx.subgraphs[a_arg.parent_field_name],
-                ctx.subgraphs[a_arg.name],
-                a_arg.r#type.display(ctx.subgraphs),
-                ctx.subgraphs[a_subgraph.name],
-                b_arg.r#type.display(ctx.subgraphs),
-                ctx.subgraphs[b_subgraph.name],
-            ));
+            ctx.diagnostics.push_fatal_at(
+                DiagnosticLocation::argument(ctx.subgraphs, b_arg.record),
+                format!(
+                    "The {}.{}({}:) argument has conflicting types in different subgraphs: {} in {} but {} in {}",
+                    ctx.subgraphs[parent_definition_name],
+                    ctx.subgraphs[a_arg.parent_field_name],
+                    ctx.subgraphs[a_arg.name],
+                    a_arg.r#type.display(ctx.subgraphs),
+                    ctx.subgraphs[a_subgraph.name],
+                    b_arg.r#type.display(ctx.subgraphs),
+                    ctx.subgraphs[b_subgraph.name],
+                ),
+            );
             None
         }
     }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::diagnostics::DiagnosticLocation;
 
 pub(super) fn merge_input_object_definitions(
     ctx: &mut Context<'_>,
@@ -44,12 +45,15 @@ pub(super) fn merge_input_object_definitions(
         // Check that no required field was excluded.
         if !intersection.contains(&field_name) {
             if let Some(required_field) = fields.iter().find(|field| field.r#type.is_required()) {
-                ctx.diagnostics.push_fatal(format!(
-                    "The {input_type_name}.{field_name} field is not defined in all subgraphs, but it is required in {bad_subgraph}",
-                    input_type_name = ctx.subgraphs[first.name],
-                    field_name = ctx.subgraphs[required_field.name],
-                    bad_subgraph = ctx.subgraphs[ctx.subgraphs.at(ctx.subgraphs.at(required_field.parent_definition_id).subgraph_id).name],
-                ));
+                ctx.diagnostics.push_fatal_at(
+                    DiagnosticLocation::field(ctx.subgraphs, *required_field),
+                    format!(
+                        "The {input_type_name}.{field_name} field is not defined in all subgraphs, but it is required in {bad_subgraph}",
+                        input_type_name = ctx.subgraphs[first.name],
+                        field_name = ctx.subgraphs[required_field.name],
+                        bad_subgraph = ctx.subgraphs[ctx.subgraphs.at(ctx.subgraphs.at(required_field.parent_definition_id).subgraph_id).name],
+                    ),
+                );
             }
             continue;
         }
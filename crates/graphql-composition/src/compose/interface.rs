@@ -1,4 +1,5 @@
 use super::*;
+use crate::diagnostics::DiagnosticLocation;
 
 pub(super) fn merge_interface_definitions<'a>(
     ctx: &mut Context<'a>,
@@ -15,12 +16,15 @@ pub(super) fn merge_interface_definitions<'a>(
     ctx.insert_interface(interface_name, interface_description, directives);
 
     fields::for_each_field_group(ctx.subgraphs, definitions, |fields| {
-        if fields.iter().any(|field| field.directives.shareable(ctx.subgraphs)) {
-            ctx.diagnostics.push_fatal(format!(
-                "The field {}.{} is marked as shareable but this is not allowed on interfaces.",
-                ctx.subgraphs[first.name].as_ref(),
-                ctx.subgraphs[fields.first().unwrap().name].as_ref()
-            ));
+        if let Some(shareable_field) = fields.iter().find(|field| field.directives.shareable(ctx.subgraphs)) {
+            ctx.diagnostics.push_fatal_at(
+                DiagnosticLocation::field(ctx.subgraphs, *shareable_field),
+                format!(
+                    "The field {}.{} is marked as shareable but this is not allowed on interfaces.",
+                    ctx.subgraphs[first.name].as_ref(),
+                    ctx.subgraphs[shareable_field.name].as_ref()
+                ),
+            );
         }
     });
 
@@ -35,16 +39,23 @@ pub(super) fn merge_interface_definitions<'a>(
 
 fn check_implementers(interface_name: StringId, field_names: &[subgraphs::StringId], ctx: &mut Context<'_>) {
     for implementer_name in ctx.subgraphs.iter_implementers_for_interface(interface_name) {
+        let Some((_, implementer_id)) = ctx.subgraphs.iter_definitions_with_name(implementer_name).next() else {
+            continue;
+        };
+
         for field_name in field_names {
             if !ctx
                 .subgraphs
                 .iter_definitions_with_name(implementer_name)
                 .any(|(_, def)| def.field_by_name(ctx.subgraphs, *field_name).is_some())
             {
-                ctx.diagnostics.push_fatal(format!(
-                    "The `{}.{}` field is not implemented by `{}`, but it should be.",
-                    ctx.subgraphs[interface_name], ctx.subgraphs[*field_name], ctx.subgraphs[implementer_name],
-                ));
+                ctx.diagnostics.push_fatal_at(
+                    DiagnosticLocation::definition(ctx.subgraphs, ctx.subgraphs.at(implementer_id)),
+                    format!(
+                        "The `{}.{}` field is not implemented by `{}`, but it should be.",
+                        ctx.subgraphs[interface_name], ctx.subgraphs[*field_name], ctx.subgraphs[implementer_name],
+                    ),
+                );
             }
         }
     }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::diagnostics::DiagnosticLocation;
 
 /// The arguments of a federated graph's fields are the intersection of the subgraph's arguments for
 /// that field.
@@ -57,7 +58,7 @@ pub(super) fn merge_field_arguments<'a>(
         };
 
         let argument_is_inaccessible = || arguments.iter().any(|arg| arg.directives.inaccessible(ctx.subgraphs));
-        let argument_type_is_inaccessible = arguments.iter().any(|arg| {
+        let argument_of_inaccessible_type = arguments.iter().find(|arg| {
             let parent_definition = arg.parent_definition_id;
             let subgraph_id = ctx.subgraphs.at(parent_definition).subgraph_id;
             let arg_type = ctx
@@ -69,11 +70,14 @@ pub(super) fn merge_field_arguments<'a>(
                 .unwrap_or(false)
         });
 
-        if argument_type_is_inaccessible && !argument_is_inaccessible() {
-            ctx.diagnostics.push_fatal(format!(
-                "The argument `{}.{}({}:)` is of an @inaccessible type, but is itself not marked as @inaccessible.",
-                ctx.subgraphs[parent_definition.name], ctx.subgraphs[field_name], ctx.subgraphs[argument_name],
-            ));
+        if let Some(argument) = argument_of_inaccessible_type.filter(|_| !argument_is_inaccessible()) {
+            ctx.diagnostics.push_fatal_at(
+                DiagnosticLocation::argument(ctx.subgraphs, argument.record),
+                format!(
+                    "The argument `{}.{}({}:)` is of an @inaccessible type, but is itself not marked as @inaccessible.",
+                    ctx.subgraphs[parent_definition.name], ctx.subgraphs[field_name], ctx.subgraphs[argument_name],
+                ),
+            );
         }
 
         let description = arguments
@@ -131,14 +135,17 @@ fn compose_field_argument_defaults<'a>(
                 let first_subgraph = ctx.subgraphs.at(first_subgraph_id);
                 let second_subgraph = ctx.subgraphs.at(definition.subgraph_id);
 
-                ctx.diagnostics.push_fatal(format!(
-                r#"The argument {type_name}.{field_name}.{argument_name} has incompatible defaults in subgraphs "{first_subgraph}" and "{second_subgraph}""#,
-                type_name = ctx.subgraphs[definition.name],
-                field_name = ctx.subgraphs[argument.parent_field_name],
-                argument_name = ctx.subgraphs[argument.name],
-                first_subgraph = ctx.subgraphs[first_subgraph.name],
-                second_subgraph = ctx.subgraphs[second_subgraph.name],
-            ))
+                ctx.diagnostics.push_fatal_at(
+                    DiagnosticLocation::argument(ctx.subgraphs, argument.record),
+                    format!(
+                        r#"The argument {type_name}.{field_name}.{argument_name} has incompatible defaults in subgraphs "{first_subgraph}" and "{second_subgraph}""#,
+                        type_name = ctx.subgraphs[definition.name],
+                        field_name = ctx.subgraphs[argument.parent_field_name],
+                        argument_name = ctx.subgraphs[argument.name],
+                        first_subgraph = ctx.subgraphs[first_subgraph.name],
+                        second_subgraph = ctx.subgraphs[second_subgraph.name],
+                    ),
+                )
             }
         }
     }
@@ -168,14 +175,17 @@ fn required_argument_not_in_intersection_error(
         })
         .collect::<Vec<_>>();
 
-    ctx.diagnostics.push_fatal(format!(
-        "The argument `{}.{}({}:)` is required in {} but missing in {}.",
-        ctx.subgraphs[ctx.subgraphs[definition_id_where_required].name],
-        ctx.subgraphs[field_name],
-        ctx.subgraphs[argument_name],
-        ctx.subgraphs[subgraph_where_required.name],
-        subgraphs_where_missing.join(", "),
-    ));
+    ctx.diagnostics.push_fatal_at(
+        DiagnosticLocation::argument(ctx.subgraphs, required_arg),
+        format!(
+            "The argument `{}.{}({}:)` is required in {} but missing in {}.",
+            ctx.subgraphs[ctx.subgraphs[definition_id_where_required].name],
+            ctx.subgraphs[field_name],
+            ctx.subgraphs[argument_name],
+            ctx.subgraphs[subgraph_where_required.name],
+            subgraphs_where_missing.join(", "),
+        ),
+    );
 }
 
 pub(super) fn compose_fields<'a>(
@@ -204,7 +214,7 @@ pub(super) fn compose_field<'a>(
         crate::validate::composite_schemas::post_merge::invalid_field_sharing(ctx, fields);
     }
 
-    if fields.iter().any(|field| {
+    if let Some(field_of_inaccessible_type) = fields.iter().find(|field| {
         !field.directives.inaccessible(ctx.subgraphs)
             && ctx
                 .subgraphs
@@ -224,16 +234,19 @@ pub(super) fn compose_field<'a>(
             .iter()
             .filter(|field| !field.directives.inaccessible(ctx.subgraphs));
 
-        ctx.diagnostics.push_fatal(format!(
-            "The field `{name}` is of an @inaccessible type, but is itself not marked as @inaccessible in subgraphs {}",
-            non_marked_subgraphs
-                .into_iter()
-                .map(|f| {
-                    let def = ctx.subgraphs.at(f.parent_definition_id);
-                    ctx.subgraphs[ctx.subgraphs.at(def.subgraph_id).name].as_ref()
-                })
-                .join(", "),
-        ));
+        ctx.diagnostics.push_fatal_at(
+            DiagnosticLocation::field(ctx.subgraphs, *field_of_inaccessible_type),
+            format!(
+                "The field `{name}` is of an @inaccessible type, but is itself not marked as @inaccessible in subgraphs {}",
+                non_marked_subgraphs
+                    .into_iter()
+                    .map(|f| {
+                        let def = ctx.subgraphs.at(f.parent_definition_id);
+                        ctx.subgraphs[ctx.subgraphs.at(def.subgraph_id).name].as_ref()
+                    })
+                    .join(", "),
+            ),
+        );
     }
 
     let arguments = object::merge_field_arguments(first, fields, ctx);
@@ -295,22 +308,28 @@ fn ingest_join_field_directives(
 
         if directives.requires(ctx.subgraphs).is_some() && is_external {
             let parent_definition = ctx.subgraphs.at(field.parent_definition_id);
-            ctx.diagnostics.push_fatal(format!(
-                "field `{}` on `{}` declared as `@external` in subgraph `{}` cannot have a `@requires`.",
-                ctx.subgraphs[field_name],
-                ctx.subgraphs[parent_definition.name],
-                ctx.subgraphs[ctx.subgraphs.at(parent_definition.subgraph_id).name],
-            ));
+            ctx.diagnostics.push_fatal_at(
+                DiagnosticLocation::field(ctx.subgraphs, *field),
+                format!(
+                    "field `{}` on `{}` declared as `@external` in subgraph `{}` cannot have a `@requires`.",
+                    ctx.subgraphs[field_name],
+                    ctx.subgraphs[parent_definition.name],
+                    ctx.subgraphs[ctx.subgraphs.at(parent_definition.subgraph_id).name],
+                ),
+            );
         }
 
         if directives.provides(ctx.subgraphs).is_some() && is_external {
             let parent_definition = ctx.subgraphs.at(field.parent_definition_id);
-            ctx.diagnostics.push_fatal(format!(
-                "field `{}` on `{}` declared as `@external` in subgraph `{}` cannot have a `@provides`.",
-                ctx.subgraphs[field_name],
-                ctx.subgraphs[parent_definition.name],
-                ctx.subgraphs[ctx.subgraphs.at(parent_definition.subgraph_id).name],
-            ));
+            ctx.diagnostics.push_fatal_at(
+                DiagnosticLocation::field(ctx.subgraphs, *field),
+                format!(
+                    "field `{}` on `{}` declared as `@external` in subgraph `{}` cannot have a `@provides`.",
+                    ctx.subgraphs[field_name],
+                    ctx.subgraphs[parent_definition.name],
+                    ctx.subgraphs[ctx.subgraphs.at(parent_definition.subgraph_id).name],
+                ),
+            );
         }
 
         out.push(ir::Directive::JoinField(directive));
@@ -333,12 +352,15 @@ pub(crate) fn validate_shareable_object_fields_match(definitions: &[DefinitionVi
     for definition in definitions {
         for field in all_fields.difference(&inaccessible_fields) {
             if definition.id.field_by_name(ctx.subgraphs, *field).is_none() {
-                ctx.diagnostics.push_fatal(format!(
-                    "[{}] The shareable object `{}` is missing the `{}` field defined in other subgraphs.",
-                    ctx.subgraphs[ctx.subgraphs.at(ctx.subgraphs.at(definition.id).subgraph_id).name],
-                    ctx.subgraphs[definition.name],
-                    ctx.subgraphs[*field],
-                ));
+                ctx.diagnostics.push_fatal_at(
+                    DiagnosticLocation::definition(ctx.subgraphs, *definition),
+                    format!(
+                        "[{}] The shareable object `{}` is missing the `{}` field defined in other subgraphs.",
+                        ctx.subgraphs[ctx.subgraphs.at(ctx.subgraphs.at(definition.id).subgraph_id).name],
+                        ctx.subgraphs[definition.name],
+                        ctx.subgraphs[*field],
+                    ),
+                );
             }
         }
     }
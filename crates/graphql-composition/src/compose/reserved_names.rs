@@ -1,4 +1,5 @@
 use super::*;
+use crate::diagnostics::DiagnosticLocation;
 
 /// This is a reserved name.
 const JOIN_GRAPH_ENUM_NAME: &str = "join__Graph";
@@ -17,11 +18,14 @@ pub(super) fn validate_definition_names(
     }
 
     for definition in definitions {
-        ctx.diagnostics.push_fatal(format!(
-            "[{}] Definition name `{}` is a reserved federation definition name, it cannot be defined in subgraphs.",
-            ctx.subgraphs[ctx.subgraphs.at(definition.subgraph_id).name],
-            JOIN_GRAPH_ENUM_NAME
-        ));
+        ctx.diagnostics.push_fatal_at(
+            DiagnosticLocation::definition(ctx.subgraphs, *definition),
+            format!(
+                "[{}] Definition name `{}` is a reserved federation definition name, it cannot be defined in subgraphs.",
+                ctx.subgraphs[ctx.subgraphs.at(definition.subgraph_id).name],
+                JOIN_GRAPH_ENUM_NAME
+            ),
+        );
     }
 
     true
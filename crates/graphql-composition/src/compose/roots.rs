@@ -1,4 +1,4 @@
-use crate::diagnostics::CompositeSchemasPostMergeValidationErrorCode;
+use crate::diagnostics::{CompositeSchemasPostMergeValidationErrorCode, DiagnosticLocation};
 
 use super::*;
 
@@ -52,6 +52,7 @@ fn merge_fields<'a>(
         for definition in definitions {
             if definition.directives.shareable(ctx.subgraphs) {
                 ctx.diagnostics.push_composite_schemas_post_merge_validation_error(
+                    DiagnosticLocation::definition(ctx.subgraphs, *definition),
                     format!(
                         "[{}] The Subscription type cannot be marked as @shareable.",
                         ctx.subgraphs[ctx.subgraphs.at(definition.subgraph_id).name],
@@ -64,6 +65,7 @@ fn merge_fields<'a>(
         fields::for_each_field_group(ctx.subgraphs, definitions, |fields| {
             for shareable_field in fields.iter().filter(|field| field.directives.shareable(ctx.subgraphs)) {
                 ctx.diagnostics.push_composite_schemas_post_merge_validation_error(
+                    DiagnosticLocation::field(ctx.subgraphs, *shareable_field),
                     format!(
                         "[{}] Subscription root fields cannot be marked as @shareable: {}.{}.",
                         {
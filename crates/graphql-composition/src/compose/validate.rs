@@ -1,5 +1,8 @@
 use super::*;
-use crate::{diagnostics::CompositeSchemasPreMergeValidationErrorCode, subgraphs::StringId};
+use crate::{
+    diagnostics::{CompositeSchemasPreMergeValidationErrorCode, DiagnosticLocation},
+    subgraphs::StringId,
+};
 
 pub(super) fn override_source_has_override(fields: &[subgraphs::FieldView<'_>], ctx: &mut Context<'_>) {
     use std::collections::BTreeMap;
@@ -80,6 +83,7 @@ pub(super) fn override_source_has_override(fields: &[subgraphs::FieldView<'_>],
             };
 
             ctx.diagnostics.push_composite_schemas_pre_merge_validation_error(
+                DiagnosticLocation::field(ctx.subgraphs, overriding_fields[0]),
                 message,
                 CompositeSchemasPreMergeValidationErrorCode::OverrideSourceHasOverride,
             );
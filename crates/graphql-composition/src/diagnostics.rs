@@ -1,9 +1,10 @@
 //! Composition warnings and errors.
 
+use crate::subgraphs::{ArgumentRecord, DefinitionView, DirectiveDefinition, FieldView, SubgraphId, Subgraphs};
 use std::fmt;
 
 /// Warnings and errors produced by composition.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct Diagnostics(Vec<Diagnostic>);
 
 impl Diagnostics {
@@ -47,21 +48,33 @@ impl Diagnostics {
         self.0.iter().map(|diagnostic| diagnostic.message.as_str())
     }
 
+    /// Report a field of the composed graph that can't be resolved from any root, as found by a
+    /// satisfiability check on the federated graph. It's only a warning, as the check may be wrong
+    /// about fields that can be resolved.
+    pub fn push_unsatisfiable_field(&mut self, field: &str, reason: impl fmt::Display, example_query: &str) {
+        self.push_warning(format!(
+            "The field `{field}` cannot be resolved: {reason}. Example failing query:\n{example_query}"
+        ));
+    }
+
     pub(crate) fn push_composite_schemas_source_schema_validation_error(
         &mut self,
         source_schema_name: &str,
         message: impl fmt::Display,
         error_code: CompositeSchemasSourceSchemaValidationErrorCode,
+        location: DiagnosticLocation,
     ) {
         self.0.push(Diagnostic {
             message: format!("[{source_schema_name}] {message}"),
             severity: error_code.severity(),
             error_code: Some(error_code.into()),
+            location: Some(location),
         });
     }
 
     pub(crate) fn push_composite_schemas_pre_merge_validation_error(
         &mut self,
+        location: DiagnosticLocation,
         message: String,
         error_code: CompositeSchemasPreMergeValidationErrorCode,
     ) {
@@ -69,11 +82,13 @@ impl Diagnostics {
             message,
             severity: error_code.severity(),
             error_code: Some(error_code.into()),
+            location: Some(location),
         });
     }
 
     pub(crate) fn push_composite_schemas_post_merge_validation_error(
         &mut self,
+        location: DiagnosticLocation,
         message: String,
         error_code: CompositeSchemasPostMergeValidationErrorCode,
     ) {
@@ -81,14 +96,17 @@ impl Diagnostics {
             message,
             severity: error_code.severity(),
             error_code: Some(error_code.into()),
+            location: Some(location),
         });
     }
 
+    /// For errors that can't be attributed to a subgraph, use [Self::push_fatal_at] otherwise.
     pub(crate) fn push_fatal(&mut self, message: String) {
         self.0.push(Diagnostic {
             message,
             severity: Severity::Error,
             error_code: None,
+            location: None,
         });
     }
 
@@ -97,6 +115,25 @@ impl Diagnostics {
             message,
             severity: Severity::Warning,
             error_code: None,
+            location: None,
+        });
+    }
+
+    pub(crate) fn push_fatal_at(&mut self, location: DiagnosticLocation, message: String) {
+        self.0.push(Diagnostic {
+            message,
+            severity: Severity::Error,
+            error_code: None,
+            location: Some(location),
+        });
+    }
+
+    pub(crate) fn push_warning_at(&mut self, location: DiagnosticLocation, message: String) {
+        self.0.push(Diagnostic {
+            message,
+            severity: Severity::Warning,
+            error_code: None,
+            location: Some(location),
         });
     }
 }
@@ -107,6 +144,7 @@ pub struct Diagnostic {
     message: String,
     severity: Severity,
     error_code: Option<CompositeSchemasErrorCode>,
+    location: Option<DiagnosticLocation>,
 }
 
 impl Diagnostic {
@@ -124,6 +162,89 @@ impl Diagnostic {
     pub fn composite_schemas_error_code(&self) -> Option<CompositeSchemasErrorCode> {
         self.error_code
     }
+
+    /// Where the diagnostic originates in the subgraphs, when it can be attributed to one.
+    pub fn location(&self) -> Option<&DiagnosticLocation> {
+        self.location.as_ref()
+    }
+}
+
+/// The subgraph, and the place in its schema, a [Diagnostic] originates from.
+#[derive(Debug, Clone)]
+pub struct DiagnosticLocation {
+    subgraph_name: String,
+    span: Option<cynic_parser::Span>,
+    schema_coordinate: Option<String>,
+}
+
+impl DiagnosticLocation {
+    /// The name of the subgraph.
+    pub fn subgraph_name(&self) -> &str {
+        &self.subgraph_name
+    }
+
+    /// The span in the SDL the subgraph was ingested from.
+    pub fn span(&self) -> Option<cynic_parser::Span> {
+        self.span
+    }
+
+    /// The [schema coordinate](https://spec.graphql.org/draft/#sec-Schema-Coordinates), e.g. `User.email`.
+    pub fn schema_coordinate(&self) -> Option<&str> {
+        self.schema_coordinate.as_deref()
+    }
+
+    pub(crate) fn subgraph(subgraphs: &Subgraphs, subgraph_id: SubgraphId) -> Self {
+        DiagnosticLocation {
+            subgraph_name: subgraphs[subgraphs.at(subgraph_id).name].to_string(),
+            span: None,
+            schema_coordinate: None,
+        }
+    }
+
+    pub(crate) fn definition(subgraphs: &Subgraphs, definition: DefinitionView<'_>) -> Self {
+        DiagnosticLocation {
+            span: Some(definition.span),
+            schema_coordinate: Some(subgraphs[definition.name].to_string()),
+            ..Self::subgraph(subgraphs, definition.subgraph_id)
+        }
+    }
+
+    pub(crate) fn directive_definition(subgraphs: &Subgraphs, definition: &DirectiveDefinition) -> Self {
+        DiagnosticLocation {
+            schema_coordinate: Some(format!("@{}", subgraphs[definition.name])),
+            ..Self::subgraph(subgraphs, definition.subgraph_id)
+        }
+    }
+
+    pub(crate) fn field(subgraphs: &Subgraphs, field: FieldView<'_>) -> Self {
+        let parent_definition = subgraphs.at(field.parent_definition_id);
+
+        DiagnosticLocation {
+            span: Some(field.span),
+            schema_coordinate: Some(format!(
+                "{}.{}",
+                subgraphs[parent_definition.name], subgraphs[field.name]
+            )),
+            ..Self::subgraph(subgraphs, parent_definition.subgraph_id)
+        }
+    }
+
+    /// Arguments have no span of their own, the span is the one of their field.
+    pub(crate) fn argument(subgraphs: &Subgraphs, argument: &ArgumentRecord) -> Self {
+        let parent_definition = subgraphs.at(argument.parent_definition_id);
+
+        DiagnosticLocation {
+            span: argument
+                .parent_definition_id
+                .field_by_name(subgraphs, argument.parent_field_name)
+                .map(|field| field.span),
+            schema_coordinate: Some(format!(
+                "{}.{}({}:)",
+                subgraphs[parent_definition.name], subgraphs[argument.parent_field_name], subgraphs[argument.name]
+            )),
+            ..Self::subgraph(subgraphs, parent_definition.subgraph_id)
+        }
+    }
 }
 
 /// The severity of a [Diagnostic].
@@ -76,6 +76,7 @@ fn ingest_top_level_definitions(ctx: &mut Context<'_>) {
                             type_name,
                             DefinitionKind::Object,
                             description,
+                            type_definition.span(),
                         );
 
                         match ctx.root_type_matcher.match_name(type_name) {
@@ -101,16 +102,21 @@ fn ingest_top_level_definitions(ctx: &mut Context<'_>) {
                         type_name,
                         DefinitionKind::Interface,
                         description,
+                        type_definition.span(),
+                    ),
+                    ast::TypeDefinition::Union(_) => ctx.subgraphs.get_or_push_definition(
+                        subgraph_id,
+                        type_name,
+                        DefinitionKind::Union,
+                        description,
+                        type_definition.span(),
                     ),
-                    ast::TypeDefinition::Union(_) => {
-                        ctx.subgraphs
-                            .get_or_push_definition(subgraph_id, type_name, DefinitionKind::Union, description)
-                    }
                     ast::TypeDefinition::InputObject(_) => ctx.subgraphs.get_or_push_definition(
                         subgraph_id,
                         type_name,
                         DefinitionKind::InputObject,
                         description,
+                        type_definition.span(),
                     ),
 
                     ast::TypeDefinition::Scalar(_) => ctx.subgraphs.get_or_push_definition(
@@ -118,12 +124,16 @@ fn ingest_top_level_definitions(ctx: &mut Context<'_>) {
                         type_name,
                         DefinitionKind::Scalar,
                         description,
+                        type_definition.span(),
                     ),
 
-                    ast::TypeDefinition::Enum(_enum_type) => {
-                        ctx.subgraphs
-                            .get_or_push_definition(subgraph_id, type_name, DefinitionKind::Enum, description)
-                    }
+                    ast::TypeDefinition::Enum(_enum_type) => ctx.subgraphs.get_or_push_definition(
+                        subgraph_id,
+                        type_name,
+                        DefinitionKind::Enum,
+                        description,
+                        type_definition.span(),
+                    ),
                 };
 
                 let directive_site_id = ctx.subgraphs.at(definition_id).directives;
@@ -34,6 +34,7 @@ pub(super) fn ingest_input_fields(
             directives,
             description,
             input_field_default_value: default,
+            span: field.name_span(),
         });
     }
 }
@@ -106,6 +107,7 @@ pub(super) fn ingest_fields(
             description,
             directives,
             input_field_default_value: None,
+            span: field.name_span(),
         });
 
         directives::ingest_directives(ctx, directives, field.directives(), |subgraphs| {
@@ -9,6 +9,7 @@ mod emit_federated_graph;
 mod federated_graph;
 mod grafbase_extensions;
 mod ingest_subgraph;
+mod parsed_subgraphs;
 mod result;
 mod subgraphs;
 mod validate;
@@ -17,6 +18,7 @@ pub use self::{
     diagnostics::Diagnostics,
     federated_graph::{DomainError, FederatedGraph, render_api_sdl, render_federated_sdl},
     grafbase_extensions::LoadedExtension,
+    parsed_subgraphs::ParsedSubgraphs,
     result::CompositionResult,
     subgraphs::{IngestError, Subgraphs},
 };
@@ -0,0 +1,74 @@
+use crate::{CompositionResult, IngestError, Subgraphs};
+
+/// A set of subgraphs that stays parsed between compositions. Replacing a subgraph only parses
+/// that subgraph again, but every composition still ingests and merges all the subgraphs.
+///
+/// Subgraphs are composed in the order they were first added, so the result is the same as
+/// ingesting the same subgraphs in that order into [Subgraphs] and calling [compose()](crate::compose()).
+#[derive(Default)]
+pub struct ParsedSubgraphs {
+    subgraphs: Vec<ParsedSubgraph>,
+}
+
+struct ParsedSubgraph {
+    name: String,
+    url: Option<String>,
+    sdl: String,
+    document: cynic_parser::TypeSystemDocument,
+}
+
+impl ParsedSubgraphs {
+    /// Add a subgraph, or replace the subgraph with the same name. Returns whether anything
+    /// changed: the subgraph isn't parsed again if its SDL and url are the same.
+    pub fn replace_subgraph(&mut self, name: &str, sdl: &str, url: Option<&str>) -> Result<bool, IngestError> {
+        let existing = self.subgraphs.iter().position(|subgraph| subgraph.name == name);
+
+        if let Some(idx) = existing {
+            let subgraph = &self.subgraphs[idx];
+
+            if subgraph.sdl == sdl && subgraph.url.as_deref() == url {
+                return Ok(false);
+            }
+        }
+
+        let document = cynic_parser::parse_type_system_document(sdl).map_err(|error| IngestError::new(error, sdl))?;
+
+        let subgraph = ParsedSubgraph {
+            name: name.to_owned(),
+            url: url.map(ToOwned::to_owned),
+            sdl: sdl.to_owned(),
+            document,
+        };
+
+        match existing {
+            Some(idx) => self.subgraphs[idx] = subgraph,
+            None => self.subgraphs.push(subgraph),
+        }
+
+        Ok(true)
+    }
+
+    /// Remove the subgraphs for which `keep` returns false, given the subgraph name.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.subgraphs.retain(|subgraph| keep(&subgraph.name));
+    }
+
+    /// The SDL of the subgraph with that name, if it was added.
+    pub fn sdl(&self, name: &str) -> Option<&str> {
+        self.subgraphs
+            .iter()
+            .find(|subgraph| subgraph.name == name)
+            .map(|subgraph| subgraph.sdl.as_str())
+    }
+
+    /// Compose all the subgraphs.
+    pub fn compose(&self) -> CompositionResult {
+        let mut subgraphs = Subgraphs::default();
+
+        for subgraph in &self.subgraphs {
+            subgraphs.ingest(&subgraph.document, &subgraph.name, subgraph.url.as_deref());
+        }
+
+        crate::compose(&mut subgraphs)
+    }
+}
@@ -79,6 +79,15 @@ pub struct IngestError {
     report: String,
 }
 
+impl IngestError {
+    pub(crate) fn new(error: cynic_parser::Error, source: &str) -> Self {
+        IngestError {
+            report: error.to_report(source).to_string(),
+            error,
+        }
+    }
+}
+
 impl std::error::Error for IngestError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         self.error.source()
@@ -102,11 +111,8 @@ impl Subgraphs {
 
     /// Add a subgraph to compose.
     pub fn ingest_str(&mut self, subgraph_schema: &str, name: &str, url: Option<&str>) -> Result<(), IngestError> {
-        let subgraph_schema =
-            cynic_parser::parse_type_system_document(subgraph_schema).map_err(|error| IngestError {
-                report: error.to_report(subgraph_schema).to_string(),
-                error,
-            })?;
+        let subgraph_schema = cynic_parser::parse_type_system_document(subgraph_schema)
+            .map_err(|error| IngestError::new(error, subgraph_schema))?;
         crate::ingest_subgraph::ingest_subgraph(&subgraph_schema, name, url, self);
         Ok(())
     }
@@ -157,13 +163,15 @@ impl Subgraphs {
     }
 
     pub(crate) fn push_ingestion_diagnostic(&mut self, subgraph: SubgraphId, message: String) {
+        let location = crate::diagnostics::DiagnosticLocation::subgraph(self, subgraph);
         self.ingestion_diagnostics
-            .push_fatal(format!("[{}]: {message}", self[self.at(subgraph).name]));
+            .push_fatal_at(location, format!("[{}]: {message}", self[self.at(subgraph).name]));
     }
 
     pub(crate) fn push_ingestion_warning(&mut self, subgraph: SubgraphId, message: String) {
+        let location = crate::diagnostics::DiagnosticLocation::subgraph(self, subgraph);
         self.ingestion_diagnostics
-            .push_warning(format!("[{}]: {message}", self[self.at(subgraph).name]));
+            .push_warning_at(location, format!("[{}]: {message}", self[self.at(subgraph).name]));
     }
 
     /// Iterates all builtin scalars.
@@ -29,6 +29,8 @@ pub(crate) struct Definition {
     /// ```
     pub(crate) description: Option<StringId>,
     pub(crate) directives: DirectiveSiteId,
+    /// Span of the definition in the ingested SDL.
+    pub(crate) span: cynic_parser::Span,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -92,6 +94,7 @@ impl Subgraphs {
         name: &str,
         kind: DefinitionKind,
         description: Option<StringId>,
+        span: cynic_parser::Span,
     ) -> DefinitionId {
         let name = self.strings.intern(name);
 
@@ -107,6 +110,7 @@ impl Subgraphs {
             kind,
             description,
             directives: directive_site_id,
+            span,
         };
 
         let id = DefinitionId::from(self.definitions.definitions.push_return_idx(definition));
@@ -57,6 +57,8 @@ pub(crate) struct FieldTuple {
     pub(crate) description: Option<StringId>,
     pub(crate) directives: DirectiveSiteId,
     pub(crate) input_field_default_value: Option<Value>,
+    /// Span of the field name in the ingested SDL.
+    pub(crate) span: cynic_parser::Span,
 }
 
 impl Subgraphs {
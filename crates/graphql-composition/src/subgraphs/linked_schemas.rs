@@ -101,10 +101,15 @@ impl Subgraphs {
                 .insert((linked_schema.subgraph_id, namespace), id);
 
             if previous.is_some() {
-                self.ingestion_diagnostics.push_warning(format!(
-                    "Linked schema namespace collision for subgraph {}: the \"{}\" namespace is defined at least twice",
-                    self[self[linked_schema.subgraph_id].name], self[namespace],
-                ));
+                let location = crate::diagnostics::DiagnosticLocation::subgraph(self, linked_schema.subgraph_id);
+                self.ingestion_diagnostics.push_warning_at(
+                    location,
+                    format!(
+                        "Linked schema namespace collision for subgraph {}: the \"{}\" namespace is defined at least twice",
+                        self[self[linked_schema.subgraph_id].name],
+                        self[namespace],
+                    ),
+                );
             }
         }
 
@@ -1,4 +1,4 @@
-use crate::{federated_graph::OverrideLabel, subgraphs};
+use crate::{diagnostics::DiagnosticLocation, federated_graph::OverrideLabel, subgraphs};
 
 pub(crate) mod composite_schemas;
 mod directives;
@@ -51,9 +51,12 @@ fn validate_override_labels(ctx: &mut ValidateContext<'_>, field: subgraphs::Fie
         return;
     };
 
-    ctx.diagnostics.push_fatal(format!(
-        "Invalid @override label argument on {ty}.{field}: {err}",
-        ty = ctx.subgraphs[ctx.subgraphs.at(field.parent_definition_id).name],
-        field = ctx.subgraphs[field.name],
-    ));
+    ctx.diagnostics.push_fatal_at(
+        DiagnosticLocation::field(ctx.subgraphs, field),
+        format!(
+            "Invalid @override label argument on {ty}.{field}: {err}",
+            ty = ctx.subgraphs[ctx.subgraphs.at(field.parent_definition_id).name],
+            field = ctx.subgraphs[field.name],
+        ),
+    );
 }
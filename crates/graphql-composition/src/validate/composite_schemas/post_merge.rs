@@ -1,7 +1,9 @@
 use itertools::Itertools;
 
 use crate::{
-    composition_ir as ir, diagnostics::CompositeSchemasPostMergeValidationErrorCode, subgraphs,
+    composition_ir as ir,
+    diagnostics::{CompositeSchemasPostMergeValidationErrorCode, DiagnosticLocation},
+    subgraphs,
     validate::ValidateContext,
 };
 
@@ -42,9 +44,10 @@ pub(crate) fn invalid_field_sharing(ctx: &mut ValidateContext<'_>, fields: &[sub
     };
 
     // Single source of truth for a non-shareable field. That's fine.
-    if sources_of_truth.peek().is_none() {
+    let Some(second) = sources_of_truth.peek() else {
         return;
-    }
+    };
+    let location = DiagnosticLocation::field(ctx.subgraphs, **second);
 
     let field_name = &ctx.subgraphs[first.name];
     let parent = ctx.subgraphs.at(first.parent_definition_id);
@@ -59,6 +62,7 @@ pub(crate) fn invalid_field_sharing(ctx: &mut ValidateContext<'_>, fields: &[sub
         .join(", ");
 
     ctx.diagnostics.push_composite_schemas_post_merge_validation_error(
+        location,
         format!("The field `{parent_name}.{field_name}` is resolved by multiple subgraphs, but not marked as `@shareable`. The field must be marked as `@shareable` in at least one of the subgraphs: {first_subgraph}, {others}"),
         CompositeSchemasPostMergeValidationErrorCode::InvalidFieldSharing,
     );
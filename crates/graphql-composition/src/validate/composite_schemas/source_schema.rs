@@ -1,5 +1,5 @@
 use super::*;
-use crate::diagnostics::CompositeSchemasSourceSchemaValidationErrorCode;
+use crate::diagnostics::{CompositeSchemasSourceSchemaValidationErrorCode, DiagnosticLocation};
 
 /// https://graphql.github.io/composite-schemas-spec/draft/#sec-Query-Root-Type-Inaccessible
 pub(crate) fn query_root_type_inaccessible(ctx: &mut ValidateContext<'_>) {
@@ -19,6 +19,7 @@ pub(crate) fn query_root_type_inaccessible(ctx: &mut ValidateContext<'_>) {
             subgraph_name,
             format_args!("The query root type cannot be inaccessible"),
             CompositeSchemasSourceSchemaValidationErrorCode::QueryRootTypeInaccessible,
+            DiagnosticLocation::definition(ctx.subgraphs, ctx.subgraphs.at(query_root)),
         );
     }
 }
@@ -44,6 +45,7 @@ pub(crate) fn lookup_returns_non_nullable_type(ctx: &mut ValidateContext<'_>, fi
             source_schema_name,
             message,
             CompositeSchemasSourceSchemaValidationErrorCode::LookupReturnsNonNullableType,
+            DiagnosticLocation::field(ctx.subgraphs, field),
         );
     }
 }
@@ -69,5 +71,6 @@ pub(crate) fn override_from_self(ctx: &mut ValidateContext<'_>, field: subgraphs
             field_name = ctx.subgraphs[field.name]
         ),
         CompositeSchemasSourceSchemaValidationErrorCode::OverrideFromSelf,
+        DiagnosticLocation::field(ctx.subgraphs, field),
     );
 }
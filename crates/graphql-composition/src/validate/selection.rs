@@ -1,10 +1,10 @@
-use crate::diagnostics::CompositeSchemasSourceSchemaValidationErrorCode;
+use crate::diagnostics::{CompositeSchemasSourceSchemaValidationErrorCode, DiagnosticLocation};
 
 use super::*;
 
 pub(super) fn validate_selections(ctx: &mut ValidateContext<'_>, field: subgraphs::FieldView<'_>) {
     let parent_definition = ctx.subgraphs.at(field.parent_definition_id);
-    let subgraph_name = &ctx.subgraphs[ctx.subgraphs[parent_definition.subgraph_id].name];
+    let location = DiagnosticLocation::field(ctx.subgraphs, field);
 
     for (selection, directive_name) in field
         .directives
@@ -27,7 +27,7 @@ pub(super) fn validate_selections(ctx: &mut ValidateContext<'_>, field: subgraph
             parent_definition,
             &directive_path,
             directive_name,
-            subgraph_name,
+            &location,
         );
     }
 
@@ -45,17 +45,20 @@ pub(super) fn validate_selections(ctx: &mut ValidateContext<'_>, field: subgraph
             .definition_by_name_id(field.r#type.definition_name_id, parent_definition.subgraph_id);
 
         let Some(field_type) = referenced_definition else {
-            ctx.diagnostics.push_fatal(format!(
-                "Invalid @provides at {}: no selection possible on this field type.",
-                directive_path()
-            ));
+            ctx.diagnostics.push_fatal_at(
+                location.clone(),
+                format!(
+                    "Invalid @provides at {}: no selection possible on this field type.",
+                    directive_path()
+                ),
+            );
             continue;
         };
 
-        validate_selection_in_provides(ctx, selection, &directive_path, subgraph_name);
+        validate_selection_in_provides(ctx, selection, &directive_path, &location);
 
         let field_type = ctx.subgraphs.at(field_type);
-        validate_selection(ctx, selection, field_type, &directive_path, "provides", subgraph_name);
+        validate_selection(ctx, selection, field_type, &directive_path, "provides", &location);
     }
 }
 
@@ -63,7 +66,7 @@ fn validate_selection_in_provides(
     ctx: &mut ValidateContext<'_>,
     selection: &subgraphs::Selection,
     directive_path: &dyn Fn() -> String,
-    subgraph_name: &str,
+    location: &DiagnosticLocation,
 ) {
     match selection {
         subgraphs::Selection::Field(subgraphs::FieldSelection {
@@ -78,12 +81,13 @@ fn validate_selection_in_provides(
             has_directives,
         } if *has_directives => {
             ctx.diagnostics.push_composite_schemas_source_schema_validation_error(
-                subgraph_name,
+                location.subgraph_name(),
                 format!(
                     "Error at {}: no directives are allowed in the selection sets in `@provides(fields:)`.",
                     directive_path()
                 ),
                 CompositeSchemasSourceSchemaValidationErrorCode::ProvidesDirectiveInFieldsArgument,
+                location.clone(),
             );
         }
         subgraphs::Selection::Field(subgraphs::FieldSelection {
@@ -98,7 +102,7 @@ fn validate_selection_in_provides(
             has_directives: _,
         } => {
             for selection in subselection {
-                validate_selection_in_provides(ctx, selection, directive_path, subgraph_name);
+                validate_selection_in_provides(ctx, selection, directive_path, location);
             }
         }
     }
@@ -110,8 +114,10 @@ fn validate_selection(
     on_definition: subgraphs::View<'_, subgraphs::DefinitionId, subgraphs::Definition>,
     directive_path: &dyn Fn() -> String,
     directive_name: &str,
-    subgraph_name: &str,
+    location: &DiagnosticLocation,
 ) {
+    let subgraph_name = location.subgraph_name();
+
     match selection {
         subgraphs::Selection::Field(field_selection) => validate_field_selection(
             ctx,
@@ -119,7 +125,7 @@ fn validate_selection(
             on_definition,
             directive_path,
             directive_name,
-            subgraph_name,
+            location,
         ),
         subgraphs::Selection::InlineFragment {
             on,
@@ -129,10 +135,13 @@ fn validate_selection(
             let Some(on) = ctx.subgraphs.definition_by_name_id(*on, on_definition.subgraph_id) else {
                 let directive_path = directive_path();
                 let on = &ctx.subgraphs[*on];
-                ctx.diagnostics.push_fatal(format!(
-                    "[{subgraph_name}] Error in {directive_name} at {directive_path}: type condition `... {on}` is invalid on {parent_definition}",
-                    parent_definition = ctx.subgraphs[on_definition.name]
-                ));
+                ctx.diagnostics.push_fatal_at(
+                    location.clone(),
+                    format!(
+                        "[{subgraph_name}] Error in {directive_name} at {directive_path}: type condition `... {on}` is invalid on {parent_definition}",
+                        parent_definition = ctx.subgraphs[on_definition.name]
+                    ),
+                );
                 return;
             };
 
@@ -143,7 +152,7 @@ fn validate_selection(
                     ctx.subgraphs.at(on),
                     directive_path,
                     directive_name,
-                    subgraph_name,
+                    location,
                 );
             }
         }
@@ -156,31 +165,42 @@ fn validate_field_selection(
     on_definition: subgraphs::View<'_, subgraphs::DefinitionId, subgraphs::Definition>,
     directive_path: &dyn Fn() -> String,
     directive_name: &str,
-    subgraph_name: &str,
+    location: &DiagnosticLocation,
 ) {
+    let subgraph_name = location.subgraph_name();
+
     if &ctx[selection.field] == "__typename" {
         if !selection.arguments.is_empty() {
-            return ctx.diagnostics.push_fatal(format!(
-                "[{subgraph_name}] Error in @{directive_name} on {directive_path}: the __typename field does not accept arguments.",
-                directive_path = directive_path(),
-            ));
+            return ctx.diagnostics.push_fatal_at(
+                location.clone(),
+                format!(
+                    "[{subgraph_name}] Error in @{directive_name} on {directive_path}: the __typename field does not accept arguments.",
+                    directive_path = directive_path(),
+                ),
+            );
         }
         if !selection.subselection.is_empty() {
-            return ctx.diagnostics.push_fatal(format!(
-                "Error in @{directive_name} on {directive_path}: the __typename field does not accept subselections.",
-                directive_path = directive_path(),
-            ));
+            return ctx.diagnostics.push_fatal_at(
+                location.clone(),
+                format!(
+                    "Error in @{directive_name} on {directive_path}: the __typename field does not accept subselections.",
+                    directive_path = directive_path(),
+                ),
+            );
         }
         return;
     }
     // The selected field must exist.
     let Some(field) = on_definition.id.field_by_name(ctx.subgraphs, selection.field) else {
-        return ctx.diagnostics.push_fatal(format!(
-            "[{subgraph_name}] Error in @{directive_name} at {directive_path}: the {field_in_selection} field does not exist on {definition_name}",
-            field_in_selection = ctx.subgraphs[selection.field],
-            directive_path = directive_path(),
-            definition_name = ctx.subgraphs[on_definition.name]
-        ));
+        return ctx.diagnostics.push_fatal_at(
+            location.clone(),
+            format!(
+                "[{subgraph_name}] Error in @{directive_name} at {directive_path}: the {field_in_selection} field does not exist on {definition_name}",
+                field_in_selection = ctx.subgraphs[selection.field],
+                directive_path = directive_path(),
+                definition_name = ctx.subgraphs[on_definition.name]
+            ),
+        );
     };
 
     for required_argument in field
@@ -189,35 +209,44 @@ fn validate_field_selection(
     {
         let arg_name = required_argument.name;
         if selection.arguments.iter().all(|(name, _)| *name != arg_name) {
-            ctx.diagnostics.push_fatal(format!(
-                "[{subgraph_name}] Error in @{directive_name} on {directive_path}: the {field_name}.{arg_name} argument is required but not provided.",
-                field_name = ctx.subgraphs[field.name],
-                arg_name = ctx.subgraphs[arg_name],
-                directive_path = directive_path(),
-            ));
+            ctx.diagnostics.push_fatal_at(
+                location.clone(),
+                format!(
+                    "[{subgraph_name}] Error in @{directive_name} on {directive_path}: the {field_name}.{arg_name} argument is required but not provided.",
+                    field_name = ctx.subgraphs[field.name],
+                    arg_name = ctx.subgraphs[arg_name],
+                    directive_path = directive_path(),
+                ),
+            );
         }
     }
 
     // The arguments must exist on the field.
     for (argument_name, argument_value) in &selection.arguments {
         let Some(argument) = field.argument_by_name(ctx.subgraphs, *argument_name) else {
-            return ctx.diagnostics.push_fatal(format!(
-                "[{subgraph_name}] Error in @{directive_name} on {directive_path}: the {field_in_selection}.{argument_name} argument does not exist on {definition_name}",
-                argument_name = ctx.subgraphs[*argument_name],
-                field_in_selection = ctx.subgraphs[field.name],
-                definition_name = ctx.subgraphs[on_definition.name],
-                directive_path = directive_path(),
-            ));
+            return ctx.diagnostics.push_fatal_at(
+                location.clone(),
+                format!(
+                    "[{subgraph_name}] Error in @{directive_name} on {directive_path}: the {field_in_selection}.{argument_name} argument does not exist on {definition_name}",
+                    argument_name = ctx.subgraphs[*argument_name],
+                    field_in_selection = ctx.subgraphs[field.name],
+                    definition_name = ctx.subgraphs[on_definition.name],
+                    directive_path = directive_path(),
+                ),
+            );
         };
 
         if !argument_type_matches(ctx, on_definition.subgraph_id, &argument.r#type, argument_value) {
-            return ctx.diagnostics.push_fatal(format!(
-                "[{subgraph_name}] Error in @{directive_name} on {directive_path}: the {field_in_selection}.{argument_name} argument does not not match the expected type ({expected_type})",
-                argument_name = ctx.subgraphs[*argument_name],
-                field_in_selection = ctx.subgraphs[field.name],
-                expected_type = argument.r#type.display(ctx.subgraphs),
-                directive_path = directive_path(),
-            ));
+            return ctx.diagnostics.push_fatal_at(
+                location.clone(),
+                format!(
+                    "[{subgraph_name}] Error in @{directive_name} on {directive_path}: the {field_in_selection}.{argument_name} argument does not not match the expected type ({expected_type})",
+                    argument_name = ctx.subgraphs[*argument_name],
+                    field_in_selection = ctx.subgraphs[field.name],
+                    expected_type = argument.r#type.display(ctx.subgraphs),
+                    directive_path = directive_path(),
+                ),
+            );
         }
     }
 
@@ -237,7 +266,7 @@ fn validate_field_selection(
             ctx.subgraphs.at(referenced_type),
             directive_path,
             directive_name,
-            subgraph_name,
+            location,
         );
     }
 }
@@ -296,11 +325,10 @@ pub(crate) fn validate_keys(ctx: &mut ValidateContext<'_>) {
         };
 
         let parent_definition = ctx.subgraphs.at(key.definition_id);
-        let subgraph = ctx.subgraphs.at(parent_definition.subgraph_id);
-        let subgraph_name = &ctx.subgraphs[subgraph.name];
+        let location = DiagnosticLocation::definition(ctx.subgraphs, parent_definition);
 
         for selection in &key.selection_set {
-            validate_selection(ctx, selection, parent_definition, &directive_path, "key", subgraph_name)
+            validate_selection(ctx, selection, parent_definition, &directive_path, "key", &location)
         }
     }
 }
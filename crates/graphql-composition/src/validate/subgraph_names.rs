@@ -1,4 +1,5 @@
 use super::ValidateContext;
+use crate::{diagnostics::DiagnosticLocation, subgraphs::SubgraphId};
 use std::collections::HashSet;
 
 pub(crate) fn validate_subgraph_names(ctx: &mut ValidateContext<'_>) {
@@ -8,22 +9,25 @@ pub(crate) fn validate_subgraph_names(ctx: &mut ValidateContext<'_>) {
 
     for subgraph in subgraphs {
         let name = ctx.subgraphs[subgraph.name].as_ref();
-        validate_name(name, ctx);
+        validate_name(subgraph.id, name, ctx);
 
         if !seen.insert(name.to_ascii_lowercase()) {
-            ctx.diagnostics.push_fatal(format!(
-                r#"Found two subgraphs named "{name}". Subgraph names are case insensitive."#
-            ));
+            ctx.diagnostics.push_fatal_at(
+                DiagnosticLocation::subgraph(ctx.subgraphs, subgraph.id),
+                format!(r#"Found two subgraphs named "{name}". Subgraph names are case insensitive."#),
+            );
         }
     }
 }
 
-fn validate_name(name: &str, ctx: &mut ValidateContext<'_>) {
+fn validate_name(subgraph_id: SubgraphId, name: &str, ctx: &mut ValidateContext<'_>) {
     let mut chars = name.chars();
 
     let Some(first) = chars.next() else {
-        ctx.diagnostics
-            .push_fatal("The empty string is not a valid subgraph name".to_owned());
+        ctx.diagnostics.push_fatal_at(
+            DiagnosticLocation::subgraph(ctx.subgraphs, subgraph_id),
+            "The empty string is not a valid subgraph name".to_owned(),
+        );
         return;
     };
 
@@ -34,7 +38,10 @@ fn validate_name(name: &str, ctx: &mut ValidateContext<'_>) {
         return;
     }
 
-    ctx.diagnostics.push_fatal(format!(
-        r#"Invalid subgraph name: "{name}". Only alphanumeric characters and hyphens (`-`) are allowed, and the first character must be alphabetic."#
-    ));
+    ctx.diagnostics.push_fatal_at(
+        DiagnosticLocation::subgraph(ctx.subgraphs, subgraph_id),
+        format!(
+            r#"Invalid subgraph name: "{name}". Only alphanumeric characters and hyphens (`-`) are allowed, and the first character must be alphabetic."#
+        ),
+    );
 }
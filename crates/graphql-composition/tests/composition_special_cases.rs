@@ -20,3 +20,82 @@ fn subgraph_names_that_differ_only_by_case_are_not_allowed() {
         "Found two subgraphs named \"Valid\". Subgraph names are case insensitive."
     );
 }
+
+#[test]
+fn diagnostics_point_to_the_subgraph_source() {
+    let sdl = r#"
+        extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@override", "@shareable"])
+
+        type Query {
+          getMammoth: Mammoth
+        }
+
+        type Mammoth @shareable {
+          weightGrams: Int
+          tuskLength: Int @override(from: "steppe")
+        }
+    "#;
+
+    let mut subgraphs = graphql_composition::Subgraphs::default();
+    subgraphs.ingest_str(sdl, "steppe", Some("example.com")).unwrap();
+
+    let result = graphql_composition::compose(&mut subgraphs);
+    let diagnostics: Vec<_> = result.diagnostics().iter().collect();
+    assert_eq!(diagnostics.len(), 1);
+
+    let location = diagnostics[0].location().unwrap();
+    assert_eq!(location.subgraph_name(), "steppe");
+    assert_eq!(location.schema_coordinate(), Some("Mammoth.tuskLength"));
+
+    let span = location.span().unwrap();
+    assert_eq!(&sdl[span.start..span.end], "tuskLength");
+}
+
+#[test]
+fn merge_diagnostics_point_to_the_conflicting_subgraph() {
+    let tundra = r#"
+        extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@shareable"])
+
+        type Query {
+          getMammoth: Mammoth
+        }
+
+        type Mammoth @shareable {
+          weightGrams: Int
+        }
+    "#;
+
+    let steppe = r#"
+        extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@shareable"])
+
+        type Query {
+          getSteppeMammoth: Mammoth
+        }
+
+        type Mammoth @shareable {
+          weightGrams: String
+        }
+    "#;
+
+    let mut subgraphs = graphql_composition::Subgraphs::default();
+    subgraphs.ingest_str(tundra, "tundra", Some("example.com")).unwrap();
+    subgraphs.ingest_str(steppe, "steppe", Some("example.com")).unwrap();
+
+    let result = graphql_composition::compose(&mut subgraphs);
+    let diagnostic = result
+        .diagnostics()
+        .iter()
+        .find(|diagnostic| diagnostic.message().contains("conflicting types"))
+        .unwrap();
+
+    let location = diagnostic.location().unwrap();
+    assert_eq!(location.schema_coordinate(), Some("Mammoth.weightGrams"));
+
+    let sdl = if location.subgraph_name() == "steppe" {
+        steppe
+    } else {
+        tundra
+    };
+    let span = location.span().unwrap();
+    assert_eq!(&sdl[span.start..span.end], "weightGrams");
+}
@@ -0,0 +1,141 @@
+//! Checks that composing through [ParsedSubgraphs] gives the same results as composing from
+//! scratch, on all the composition test cases.
+
+use std::{fs, path::Path};
+
+use graphql_composition::ParsedSubgraphs;
+
+const REPLACEMENT_SDL: &str = "type Query { parsedSubgraphsProbe: String }";
+
+struct Subgraph {
+    name: String,
+    url: String,
+    sdl: String,
+}
+
+fn read_subgraphs(subgraphs_dir: &Path) -> Vec<Subgraph> {
+    let mut subgraphs = fs::read_dir(subgraphs_dir)
+        .unwrap()
+        .filter_map(Result::ok)
+        .filter(|file| file.file_name() != ".gitignore")
+        .map(|file| {
+            let name = file.path().file_stem().unwrap().to_str().unwrap().replace('_', "-");
+
+            Subgraph {
+                url: format!("http://example.com/{name}"),
+                sdl: fs::read_to_string(file.path()).unwrap(),
+                name,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    subgraphs.sort_by(|a, b| a.name.cmp(&b.name));
+    subgraphs
+}
+
+fn render(result: graphql_composition::CompositionResult) -> String {
+    let mut rendered = result.diagnostics().iter_messages().collect::<Vec<_>>().join("\n");
+
+    if let Ok(federated_graph) = result.into_result() {
+        rendered.push_str("\n\n");
+        rendered.push_str(&graphql_composition::render_federated_sdl(&federated_graph).unwrap());
+    }
+
+    rendered
+}
+
+fn compose_from_scratch<'a>(subgraphs: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>) -> String {
+    let mut composed = graphql_composition::Subgraphs::default();
+
+    for (name, sdl, url) in subgraphs {
+        composed.ingest_str(sdl, name, Some(url)).unwrap();
+    }
+
+    render(graphql_composition::compose(&mut composed))
+}
+
+fn check_equivalence(subgraphs_dir: &Path) {
+    let subgraphs = read_subgraphs(subgraphs_dir);
+    let all = || {
+        subgraphs
+            .iter()
+            .map(|subgraph| (subgraph.name.as_str(), subgraph.sdl.as_str(), subgraph.url.as_str()))
+    };
+
+    let mut parsed = ParsedSubgraphs::default();
+
+    for (name, sdl, url) in all() {
+        assert!(parsed.replace_subgraph(name, sdl, Some(url)).unwrap());
+    }
+
+    let expected = compose_from_scratch(all());
+    pretty_assertions::assert_eq!(expected, render(parsed.compose()), "{}", subgraphs_dir.display());
+
+    for subgraph in &subgraphs {
+        let (name, url) = (subgraph.name.as_str(), subgraph.url.as_str());
+
+        // Same SDL, nothing to do.
+        assert!(!parsed.replace_subgraph(name, &subgraph.sdl, Some(url)).unwrap());
+
+        assert!(parsed.replace_subgraph(name, REPLACEMENT_SDL, Some(url)).unwrap());
+        let replaced = compose_from_scratch(all().map(|(other_name, sdl, url)| {
+            if other_name == name {
+                (other_name, REPLACEMENT_SDL, url)
+            } else {
+                (other_name, sdl, url)
+            }
+        }));
+        pretty_assertions::assert_eq!(
+            replaced,
+            render(parsed.compose()),
+            "{} with {name} replaced",
+            subgraphs_dir.display()
+        );
+
+        assert!(parsed.replace_subgraph(name, &subgraph.sdl, Some(url)).unwrap());
+        pretty_assertions::assert_eq!(
+            expected,
+            render(parsed.compose()),
+            "{} with {name} restored",
+            subgraphs_dir.display()
+        );
+    }
+
+    if let Some(last) = subgraphs.last() {
+        parsed.retain(|name| name != last.name);
+        assert_eq!(parsed.sdl(&last.name), None);
+
+        pretty_assertions::assert_eq!(
+            compose_from_scratch(all().filter(|(name, _, _)| *name != last.name)),
+            render(parsed.compose()),
+            "{} without {}",
+            subgraphs_dir.display(),
+            last.name
+        );
+    }
+}
+
+fn visit(dir: &Path) {
+    let subgraphs_dir = dir.join("subgraphs");
+
+    if dir.join("test.md").is_file() && subgraphs_dir.is_dir() {
+        check_equivalence(&subgraphs_dir);
+    }
+
+    for entry in fs::read_dir(dir).unwrap().filter_map(Result::ok) {
+        let path = entry.path();
+
+        if path.is_dir() && path != subgraphs_dir {
+            visit(&path);
+        }
+    }
+}
+
+#[test]
+fn parsed_subgraphs_composition_matches_composition_from_scratch() {
+    if cfg!(windows) {
+        return; // newlines
+    }
+
+    visit(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/composition"));
+}
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use crate::{Rule, RuleLevel};
+
+/// Enables, disables or changes the severity of lint rules. Rules that aren't configured use
+/// their [default level](Rule::default_level).
+///
+/// ```toml
+/// [rules]
+/// naming-convention = "error"
+/// forbidden-affix = "off"
+/// description-required = "warning"
+/// ```
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    pub rules: HashMap<Rule, RuleLevel>,
+}
+
+impl LintConfig {
+    pub fn level(&self, rule: Rule) -> RuleLevel {
+        self.rules.get(&rule).copied().unwrap_or(rule.default_level())
+    }
+
+    pub fn with_level(mut self, rule: Rule, level: RuleLevel) -> Self {
+        self.rules.insert(rule, level);
+        self
+    }
+}
@@ -1,12 +1,22 @@
+mod config;
+mod rules;
+
 use cynic_parser::TypeSystemDocument;
+use cynic_parser::common::WrappingType;
 use cynic_parser::type_system::{
     Definition, Directive, DirectiveDefinition, EnumDefinition, EnumValueDefinition, FieldDefinition,
-    InputObjectDefinition, InputValueDefinition, InterfaceDefinition, ObjectDefinition, ScalarDefinition,
+    InputObjectDefinition, InputValueDefinition, InterfaceDefinition, ObjectDefinition, ScalarDefinition, Type,
     TypeDefinition, UnionDefinition,
 };
 use heck::{ToLowerCamelCase, ToPascalCase, ToShoutySnakeCase};
 use thiserror::Error;
 
+pub use self::{
+    config::LintConfig,
+    rules::{Rule, RuleLevel},
+};
+pub use cynic_parser::Span;
+
 enum CaseMatch<'a> {
     Correct,
     Incorrect { current: &'a str, fix: String },
@@ -18,8 +28,37 @@ enum Case {
     Camel,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Severity {
     Warning,
+    Error,
+}
+
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    rule: Rule,
+    severity: Severity,
+    message: String,
+    span: Span,
+}
+
+impl Diagnostic {
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The byte range of the offending definition in the linted schema.
+    pub fn span(&self) -> Span {
+        self.span
+    }
 }
 
 #[derive(Error, Debug)]
@@ -28,30 +67,43 @@ pub enum LinterError {
     Parse(String),
 }
 
-pub fn lint(schema: &str) -> Result<Vec<(String, Severity)>, LinterError> {
+/// Lints the schema with the default rule levels.
+pub fn lint(schema: &str) -> Result<Vec<Diagnostic>, LinterError> {
+    lint_with_config(schema, &LintConfig::default())
+}
+
+pub fn lint_with_config(schema: &str, config: &LintConfig) -> Result<Vec<Diagnostic>, LinterError> {
     let parsed_schema =
         cynic_parser::parse_type_system_document(schema).map_err(|error| LinterError::Parse(error.to_string()))?;
-    Ok(SchemaLinter::new().lint(&parsed_schema))
+    Ok(SchemaLinter::new(config).lint(&parsed_schema))
 }
 
-struct SchemaLinter {
-    diagnostics: Vec<(String, Severity)>,
+struct SchemaLinter<'c> {
+    config: &'c LintConfig,
+    diagnostics: Vec<Diagnostic>,
 }
 
-impl<'a> SchemaLinter {
-    pub fn new() -> Self {
+impl<'c> SchemaLinter<'c> {
+    pub fn new(config: &'c LintConfig) -> Self {
         Self {
+            config,
             diagnostics: Vec::new(),
         }
     }
+}
 
-    pub fn lint(mut self, schema: &'a TypeSystemDocument) -> Vec<(String, Severity)> {
+impl<'a> SchemaLinter<'_> {
+    pub fn lint(mut self, schema: &'a TypeSystemDocument) -> Vec<Diagnostic> {
         schema.definitions().for_each(|definition| match definition {
             Definition::Schema(_) => {}
             Definition::SchemaExtension(_) => {}
             // TODO: we can optimize this by not rechecking spelling for extensions.
             // We'll also need to do this to avoid duplicate warnings if extending a type with an incorrect name
             Definition::TypeExtension(r#type) | Definition::Type(r#type) => {
+                if let Definition::Type(_) = definition {
+                    self.visit_type_definition(r#type);
+                }
+
                 match r#type {
                     TypeDefinition::Scalar(scalar) => {
                         self.visit_scalar(scalar);
@@ -132,13 +184,36 @@ impl<'a> SchemaLinter {
         self.diagnostics
     }
 
-    fn case_check(current: &'a str, case: Case) -> CaseMatch<'a> {
+    fn enabled(&self, rule: Rule) -> bool {
+        self.config.level(rule) != RuleLevel::Off
+    }
+
+    fn push(&mut self, rule: Rule, span: Span, message: String) {
+        let severity = match self.config.level(rule) {
+            RuleLevel::Off => return,
+            RuleLevel::Warning => Severity::Warning,
+            RuleLevel::Error => Severity::Error,
+        };
+
+        self.diagnostics.push(Diagnostic {
+            rule,
+            severity,
+            message,
+            span,
+        });
+    }
+
+    fn is_exception(name: &str) -> bool {
         use regex::RegexSet;
         use std::sync::LazyLock;
 
         static EXCEPTIONS: LazyLock<RegexSet> = LazyLock::new(|| RegexSet::new(["^_", "^[a-zA-Z]+__."]).unwrap());
 
-        if EXCEPTIONS.is_match(current) {
+        EXCEPTIONS.is_match(name)
+    }
+
+    fn case_check(current: &'a str, case: Case) -> CaseMatch<'a> {
+        if Self::is_exception(current) {
             return CaseMatch::Correct;
         }
 
@@ -155,6 +230,109 @@ impl<'a> SchemaLinter {
         }
     }
 
+    fn is_non_null(ty: Type<'_>) -> bool {
+        ty.wrappers().last() == Some(WrappingType::NonNull)
+    }
+
+    fn is_list(ty: Type<'_>) -> bool {
+        ty.wrappers().any(|wrapper| wrapper == WrappingType::List)
+    }
+
+    /// Checks that only apply once per type, on its definition rather than its extensions.
+    pub fn visit_type_definition(&mut self, definition: TypeDefinition<'_>) {
+        let name = definition.name();
+
+        if Self::is_exception(name) {
+            return;
+        }
+
+        if self.enabled(Rule::DescriptionRequired)
+            && definition.description().is_none()
+            && !definition
+                .directives()
+                .any(|directive| directive.name() == "inaccessible")
+        {
+            self.push(
+                Rule::DescriptionRequired,
+                definition.span(),
+                format!(
+                    "{} '{name}' is missing a description",
+                    Self::type_definition_display(definition)
+                ),
+            );
+        }
+
+        match definition {
+            TypeDefinition::InputObject(_) if !name.ends_with("Input") => {
+                self.push(
+                    Rule::InputTypeSuffix,
+                    definition.span(),
+                    format!("input '{name}' should be renamed to '{name}Input'"),
+                );
+            }
+            TypeDefinition::Object(object) if self.enabled(Rule::RelayConnectionSpec) => {
+                self.visit_relay_object(object, definition.span());
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_relay_object(&mut self, object: ObjectDefinition<'_>, span: Span) {
+        let name = object.name();
+        let field = |field_name: &str| object.fields().find(|field| field.name() == field_name);
+
+        let mut required_fields: Vec<(&str, fn(Type<'_>) -> bool, &str)> = Vec::new();
+
+        if name.ends_with("Connection") {
+            required_fields.push((
+                "edges",
+                |ty| Self::is_list(ty) && ty.name().ends_with("Edge"),
+                "a list of edges",
+            ));
+            required_fields.push((
+                "pageInfo",
+                |ty| Self::is_non_null(ty) && !Self::is_list(ty) && ty.name() == "PageInfo",
+                "'PageInfo!'",
+            ));
+        } else if name.ends_with("Edge") {
+            required_fields.push(("node", |ty| !Self::is_list(ty), "a single node"));
+            required_fields.push((
+                "cursor",
+                |ty| Self::is_non_null(ty) && !Self::is_list(ty),
+                "a non-null cursor",
+            ));
+        } else if name == "PageInfo" {
+            for field_name in ["hasNextPage", "hasPreviousPage"] {
+                required_fields.push((
+                    field_name,
+                    |ty| Self::is_non_null(ty) && !Self::is_list(ty) && ty.name() == "Boolean",
+                    "'Boolean!'",
+                ));
+            }
+            for field_name in ["startCursor", "endCursor"] {
+                required_fields.push((field_name, |ty| !Self::is_list(ty), "a single cursor"));
+            }
+        }
+
+        for (field_name, is_valid, expected) in required_fields {
+            match field(field_name) {
+                None => self.push(
+                    Rule::RelayConnectionSpec,
+                    span,
+                    format!(
+                        "type '{name}' is missing the '{field_name}' field required by the connection specification"
+                    ),
+                ),
+                Some(field) if !is_valid(field.ty()) => self.push(
+                    Rule::RelayConnectionSpec,
+                    field.name_span(),
+                    format!("field '{field_name}' on type '{name}' should be {expected}"),
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+
     pub fn visit_field_argument(
         &mut self,
         parent_type: TypeDefinition<'_>,
@@ -162,39 +340,42 @@ impl<'a> SchemaLinter {
         argument: InputValueDefinition<'_>,
     ) {
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(argument.name(), Case::Camel) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                argument.span(),
                 format!(
                     "argument '{current}' on field '{}' on {} '{}' should be renamed to '{fix}'",
                     field.name(),
                     Self::type_definition_display(parent_type),
                     parent_type.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
     pub fn visit_directive_argument(&mut self, directive: DirectiveDefinition<'_>, argument: InputValueDefinition<'_>) {
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(argument.name(), Case::Camel) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                argument.span(),
                 format!(
                     "argument '{current}' on directive '{}' should be renamed to '{fix}'",
                     directive.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
     pub fn visit_input_value(&mut self, parent: TypeDefinition<'_>, value: InputValueDefinition<'_>) {
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(value.name(), Case::Camel) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                value.span(),
                 format!(
                     "input value '{current}' on input '{}' should be renamed to '{fix}'",
                     parent.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -211,6 +392,7 @@ impl<'a> SchemaLinter {
 
     pub fn visit_field(&mut self, parent: TypeDefinition<'_>, field: FieldDefinition<'_>) {
         let field_name = field.name();
+        let span = field.name_span();
 
         // ignore system fields
         if field_name.starts_with("__") {
@@ -218,62 +400,98 @@ impl<'a> SchemaLinter {
         }
 
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(field_name, Case::Camel) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                span,
                 format!(
                     "field '{current}' on {} '{}' should be renamed to '{fix}'",
                     Self::type_definition_display(parent),
                     parent.name()
                 ),
-                Severity::Warning,
-            ));
+            );
+        }
+
+        if field.ty().name() == "ID" && !Self::is_non_null(field.ty()) && !Self::is_list(field.ty()) {
+            self.push(
+                Rule::NullableId,
+                span,
+                format!(
+                    "field '{field_name}' on {} '{}' should be of type 'ID!'",
+                    Self::type_definition_display(parent),
+                    parent.name()
+                ),
+            );
+        }
+
+        if field.ty().name().ends_with("Connection") && !Self::is_list(field.ty()) {
+            let has_argument = |name: &str| field.arguments().any(|argument| argument.name() == name);
+
+            if !((has_argument("first") && has_argument("after")) || (has_argument("last") && has_argument("before"))) {
+                self.push(
+                    Rule::RelayConnectionSpec,
+                    span,
+                    format!(
+                        "field '{field_name}' on {} '{}' returns a connection without 'first' and 'after' or 'last' and 'before' arguments",
+                        Self::type_definition_display(parent),
+                        parent.name()
+                    ),
+                );
+            }
         }
+
         match parent.name() {
             "Query" => {
                 for prefix in ["query", "get", "list"] {
                     if field_name.starts_with(prefix) {
-                        self.diagnostics.push((
+                        self.push(
+                            Rule::ForbiddenAffix,
+                            span,
                             format!("field '{field_name}' on type 'Query' has a forbidden prefix: '{prefix}'"),
-                            Severity::Warning,
-                        ));
+                        );
                         break;
                     }
                 }
                 if field_name.ends_with("Query") {
-                    self.diagnostics.push((
+                    self.push(
+                        Rule::ForbiddenAffix,
+                        span,
                         format!("field '{field_name}' on type 'Query' has a forbidden suffix: 'Query'"),
-                        Severity::Warning,
-                    ));
+                    );
                 }
             }
             "Mutation" => {
                 for prefix in ["mutation", "put", "post", "patch"] {
                     if field_name.starts_with(prefix) {
-                        self.diagnostics.push((
+                        self.push(
+                            Rule::ForbiddenAffix,
+                            span,
                             format!("field '{field_name}' on type 'Mutation' has a forbidden prefix: '{prefix}'"),
-                            Severity::Warning,
-                        ));
+                        );
                         break;
                     }
                 }
                 if field_name.ends_with("Mutation") {
-                    self.diagnostics.push((
+                    self.push(
+                        Rule::ForbiddenAffix,
+                        span,
                         format!("field '{field_name}' on type 'Mutation' has a forbidden suffix: 'Mutation'"),
-                        Severity::Warning,
-                    ));
+                    );
                 }
             }
             "Subscription" => {
                 if field_name.starts_with("subscription") {
-                    self.diagnostics.push((
+                    self.push(
+                        Rule::ForbiddenAffix,
+                        span,
                         format!("field '{field_name}' on type 'Subscription' has a forbidden prefix: 'subscription'"),
-                        Severity::Warning,
-                    ));
+                    );
                 }
                 if field_name.ends_with("Subscription") {
-                    self.diagnostics.push((
+                    self.push(
+                        Rule::ForbiddenAffix,
+                        span,
                         format!("field '{field_name}' on type 'Subscription' has a forbidden suffix: 'Subscription'"),
-                        Severity::Warning,
-                    ));
+                    );
                 }
             }
             _ => {}
@@ -282,23 +500,29 @@ impl<'a> SchemaLinter {
 
     pub fn visit_directive(&mut self, directive: DirectiveDefinition<'_>) {
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(directive.name(), Case::Camel) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                directive.span(),
                 format!("directive '{current}' should be renamed to '{fix}'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
+    fn is_deprecated_without_reason(directive: Directive<'_>) -> bool {
+        directive.name() == "deprecated" && !directive.arguments().any(|argument| argument.name() == "reason")
+    }
+
     pub fn visit_directive_usage(&mut self, parent: TypeDefinition<'_>, directive: Directive<'_>) {
-        if directive.name() == "deprecated" && !directive.arguments().any(|argument| argument.name() == "reason") {
-            self.diagnostics.push((
+        if Self::is_deprecated_without_reason(directive) {
+            self.push(
+                Rule::DeprecationReason,
+                directive.name_span(),
                 format!(
                     "usage of directive 'deprecated' on {} '{}' does not populate the 'reason' argument",
                     Self::type_definition_display(parent),
                     parent.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -308,16 +532,17 @@ impl<'a> SchemaLinter {
         parent_field: FieldDefinition<'_>,
         directive: Directive<'_>,
     ) {
-        if directive.name() == "deprecated" && !directive.arguments().any(|argument| argument.name() == "reason") {
-            self.diagnostics.push((
+        if Self::is_deprecated_without_reason(directive) {
+            self.push(
+                Rule::DeprecationReason,
+                directive.name_span(),
                 format!(
                     "usage of directive 'deprecated' on field '{}' on {} '{}' does not populate the 'reason' argument",
                     parent_field.name(),
                     Self::type_definition_display(parent_type),
                     parent_type.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -327,15 +552,16 @@ impl<'a> SchemaLinter {
         parent_input_value: InputValueDefinition<'_>,
         directive: Directive<'_>,
     ) {
-        if directive.name() == "deprecated" && !directive.arguments().any(|argument| argument.name() == "reason") {
-            self.diagnostics.push((
+        if Self::is_deprecated_without_reason(directive) {
+            self.push(
+                Rule::DeprecationReason,
+                directive.name_span(),
                 format!(
                     "usage of directive 'deprecated' on input value '{}' on input '{}' does not populate the 'reason' argument",
                     parent_input_value.name(),
                     parent_input.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -345,15 +571,16 @@ impl<'a> SchemaLinter {
         parent_value: EnumValueDefinition<'_>,
         directive: Directive<'_>,
     ) {
-        if directive.name() == "deprecated" && !directive.arguments().any(|argument| argument.name() == "reason") {
-            self.diagnostics.push((
+        if Self::is_deprecated_without_reason(directive) {
+            self.push(
+                Rule::DeprecationReason,
+                directive.name_span(),
                 format!(
                     "usage of directive 'deprecated' on enum value '{}' on enum '{}' does not populate the 'reason' argument",
                     parent_value.value(),
                     parent_enum.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -361,17 +588,20 @@ impl<'a> SchemaLinter {
 
     pub fn visit_union(&mut self, union: UnionDefinition<'_>) {
         let union_name = union.name();
+        let span = TypeDefinition::Union(union).span();
         if union_name.starts_with("Union") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenAffix,
+                span,
                 format!("union '{union_name}' has a forbidden prefix: 'Union'"),
-                Severity::Warning,
-            ));
+            );
         }
         if union_name.ends_with("Union") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenAffix,
+                span,
                 format!("union '{union_name}' has a forbidden suffix: 'Union'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -379,62 +609,73 @@ impl<'a> SchemaLinter {
 
     pub fn visit_interface(&mut self, object: InterfaceDefinition<'_>) {
         let interface_name = object.name();
+        let span = TypeDefinition::Interface(object).span();
         if interface_name.starts_with("Interface") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenAffix,
+                span,
                 format!("interface '{interface_name}' has a forbidden prefix: 'Interface'"),
-                Severity::Warning,
-            ));
+            );
         }
         if interface_name.ends_with("Interface") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenAffix,
+                span,
                 format!("interface '{interface_name}' has a forbidden suffix: 'Interface'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
     pub fn visit_object(&mut self, object: ObjectDefinition<'_>) {
         let object_name = object.name();
+        let span = TypeDefinition::Object(object).span();
 
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(object_name, Case::Pascal) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                span,
                 format!("type '{current}' should be renamed to '{fix}'"),
-                Severity::Warning,
-            ));
+            );
         }
         if object_name.starts_with("Type") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenAffix,
+                span,
                 format!("type '{object_name}' has a forbidden prefix: 'Type'"),
-                Severity::Warning,
-            ));
+            );
         }
         if object_name.ends_with("Type") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenAffix,
+                span,
                 format!("type '{object_name}' has a forbidden suffix: 'Type'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
     pub fn visit_enum(&mut self, r#enum: EnumDefinition<'_>) {
         let enum_name = r#enum.name();
+        let span = TypeDefinition::Enum(r#enum).span();
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(enum_name, Case::Pascal) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                span,
                 format!("enum '{current}' should be renamed to '{fix}'"),
-                Severity::Warning,
-            ));
+            );
         }
         if enum_name.starts_with("Enum") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenAffix,
+                span,
                 format!("enum '{enum_name}' has a forbidden prefix: 'Enum'"),
-                Severity::Warning,
-            ));
+            );
         }
         if enum_name.ends_with("Enum") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenAffix,
+                span,
                 format!("enum '{enum_name}' has a forbidden suffix: 'Enum'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -443,10 +684,11 @@ impl<'a> SchemaLinter {
 
         let name = enum_value.value();
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(name, Case::ShoutySnake) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                enum_value.span(),
                 format!("value '{current}' on enum '{enum_name}' should be renamed to '{fix}'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 }
@@ -542,7 +784,7 @@ fn linter() {
 
     let messages = diagnostics
         .iter()
-        .map(|diagnostic| diagnostic.0.clone())
+        .map(|diagnostic| diagnostic.message().to_owned())
         .collect::<Vec<_>>();
 
     [
@@ -657,3 +899,101 @@ fn linter() {
 
     assert!(diagnostics.is_empty());
 }
+
+#[test]
+fn configured_rules() {
+    let schema = r#"
+        type Query {
+          user(id: ID): User
+          usersConnection(first: Int): UserConnection
+        }
+
+        """A user"""
+        type User {
+          id: ID
+          friends(first: Int, after: String): UserConnection!
+          getName: String
+        }
+
+        type UserConnection {
+          edges: UserEdge
+        }
+
+        type UserEdge {
+          node: [User]
+          cursor: String
+        }
+
+        type PageInfo {
+          hasNextPage: Boolean
+          startCursor: String
+        }
+
+        input UserFilter {
+          name: String
+        }
+
+        type _Service {
+          sdl: String
+        }
+    "#;
+
+    let diagnostics = lint(schema).unwrap();
+    assert!(
+        diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.rule().default_level() != RuleLevel::Off),
+        "rules that are off by default should not report anything"
+    );
+
+    let config = Rule::ALL
+        .into_iter()
+        .fold(LintConfig::default(), |config, rule| {
+            config.with_level(rule, RuleLevel::Warning)
+        })
+        .with_level(Rule::ForbiddenAffix, RuleLevel::Off)
+        .with_level(Rule::NullableId, RuleLevel::Error);
+
+    let diagnostics = lint_with_config(schema, &config).unwrap();
+
+    let messages = diagnostics
+        .iter()
+        .map(|diagnostic| format!("[{}] {}", diagnostic.rule(), diagnostic.message()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        messages,
+        [
+            "[description-required] type 'Query' is missing a description",
+            "[relay-connection-spec] field 'usersConnection' on type 'Query' returns a connection without 'first' and 'after' or 'last' and 'before' arguments",
+            "[nullable-id] field 'id' on type 'User' should be of type 'ID!'",
+            "[description-required] type 'UserConnection' is missing a description",
+            "[relay-connection-spec] field 'edges' on type 'UserConnection' should be a list of edges",
+            "[relay-connection-spec] type 'UserConnection' is missing the 'pageInfo' field required by the connection specification",
+            "[description-required] type 'UserEdge' is missing a description",
+            "[relay-connection-spec] field 'node' on type 'UserEdge' should be a single node",
+            "[relay-connection-spec] field 'cursor' on type 'UserEdge' should be a non-null cursor",
+            "[description-required] type 'PageInfo' is missing a description",
+            "[relay-connection-spec] field 'hasNextPage' on type 'PageInfo' should be 'Boolean!'",
+            "[relay-connection-spec] type 'PageInfo' is missing the 'hasPreviousPage' field required by the connection specification",
+            "[relay-connection-spec] type 'PageInfo' is missing the 'endCursor' field required by the connection specification",
+            "[description-required] input 'UserFilter' is missing a description",
+            "[input-type-suffix] input 'UserFilter' should be renamed to 'UserFilterInput'",
+        ]
+    );
+
+    let severities = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity() == Severity::Error)
+        .map(|diagnostic| diagnostic.rule())
+        .collect::<Vec<_>>();
+
+    assert_eq!(severities, [Rule::NullableId]);
+
+    let nullable_id = diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.rule() == Rule::NullableId)
+        .unwrap();
+
+    assert_eq!(&schema[nullable_id.span().start..nullable_id.span().end], "id");
+}
@@ -0,0 +1,73 @@
+use std::fmt;
+
+/// A lint rule, identified in configuration files and diagnostics by its kebab-case id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// Types and enums in `PascalCase`, fields, arguments, input values and directives in
+    /// `camelCase`, enum values in `SCREAMING_SNAKE_CASE`.
+    NamingConvention,
+    /// Names repeating their kind, like `TypeUser`, `StatusEnum` or `getUser` on `Query`.
+    ForbiddenAffix,
+    /// `@deprecated` without a `reason` argument.
+    DeprecationReason,
+    /// Public types without a description.
+    DescriptionRequired,
+    /// Connection types, edge types, `PageInfo` and connection fields not following the
+    /// [Relay connection specification](https://relay.dev/graphql/connections.htm).
+    RelayConnectionSpec,
+    /// Nullable fields of type `ID`.
+    NullableId,
+    /// Input object names not ending with `Input`.
+    InputTypeSuffix,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 7] = [
+        Rule::NamingConvention,
+        Rule::ForbiddenAffix,
+        Rule::DeprecationReason,
+        Rule::DescriptionRequired,
+        Rule::RelayConnectionSpec,
+        Rule::NullableId,
+        Rule::InputTypeSuffix,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Rule::NamingConvention => "naming-convention",
+            Rule::ForbiddenAffix => "forbidden-affix",
+            Rule::DeprecationReason => "deprecation-reason",
+            Rule::DescriptionRequired => "description-required",
+            Rule::RelayConnectionSpec => "relay-connection-spec",
+            Rule::NullableId => "nullable-id",
+            Rule::InputTypeSuffix => "input-type-suffix",
+        }
+    }
+
+    /// The rules that predate the configuration are enabled by default, the others have to be
+    /// enabled explicitly.
+    pub fn default_level(self) -> RuleLevel {
+        match self {
+            Rule::NamingConvention | Rule::ForbiddenAffix | Rule::DeprecationReason => RuleLevel::Warning,
+            Rule::DescriptionRequired | Rule::RelayConnectionSpec | Rule::NullableId | Rule::InputTypeSuffix => {
+                RuleLevel::Off
+            }
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// How the diagnostics of a rule are reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Warning,
+    Error,
+}
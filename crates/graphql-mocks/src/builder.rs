@@ -7,11 +7,16 @@ use crate::{MockGraphQlServer, Schema};
 pub struct MockGraphQlServerBuilder {
     schema: Arc<dyn Schema>,
     port: Option<u16>,
+    record_requests: bool,
 }
 
 impl MockGraphQlServerBuilder {
     pub(super) fn new(schema: Arc<dyn Schema>) -> Self {
-        MockGraphQlServerBuilder { schema, port: None }
+        MockGraphQlServerBuilder {
+            schema,
+            port: None,
+            record_requests: true,
+        }
     }
 
     pub fn with_port(mut self, port: u16) -> Self {
@@ -19,8 +24,15 @@ impl MockGraphQlServerBuilder {
         self
     }
 
+    /// Only serve the schema, without keeping the received requests for inspection. For
+    /// long-running servers, which would otherwise keep all of them in memory.
+    pub fn without_recording(mut self) -> Self {
+        self.record_requests = false;
+        self
+    }
+
     pub async fn build(self) -> MockGraphQlServer {
-        MockGraphQlServer::new_impl(self.schema, self.port).await
+        MockGraphQlServer::new_impl(self.schema, self.port, self.record_requests).await
     }
 }
 
@@ -1,6 +1,6 @@
 #![allow(clippy::panic)]
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use async_graphql::{
     ServerError,
@@ -11,12 +11,18 @@ use serde::Deserialize;
 
 use crate::dynamic::entity_resolvers::EntityResolverContext;
 
-use super::{DynamicSchema, DynamicSubgraph, entity_resolvers::EntityResolver, resolvers::Resolver};
+use super::{
+    DynamicSchema, DynamicSubgraph,
+    entity_resolvers::EntityResolver,
+    fake::{FakeData, fake_field_resolver},
+    resolvers::Resolver,
+};
 
 pub struct DynamicSchemaBuilder {
     sdl: String,
     field_resolvers: ResolverMap,
     entity_resolvers: EntityResolverMap,
+    fake_data: bool,
 }
 
 type ResolverMap = HashMap<(String, String), Box<dyn Resolver>>;
@@ -28,6 +34,7 @@ impl DynamicSchemaBuilder {
             sdl: sdl.into(),
             field_resolvers: Default::default(),
             entity_resolvers: Default::default(),
+            fake_data: false,
         }
     }
 
@@ -42,6 +49,13 @@ impl DynamicSchemaBuilder {
         self
     }
 
+    /// Makes up deterministic data for the fields without a resolver, and resolves all entities
+    /// without a resolver from their representation.
+    pub fn with_fake_data(mut self) -> Self {
+        self.fake_data = true;
+        self
+    }
+
     pub fn into_subgraph(self, name: &str) -> DynamicSubgraph {
         DynamicSubgraph {
             name: name.into(),
@@ -50,15 +64,19 @@ impl DynamicSchemaBuilder {
     }
 
     pub fn finish(self) -> DynamicSchema {
+        self.try_finish().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn try_finish(self) -> Result<DynamicSchema, String> {
         let Self {
             sdl,
             mut field_resolvers,
-            entity_resolvers,
+            mut entity_resolvers,
+            fake_data,
         } = self;
 
-        let schema = cynic_parser::parse_type_system_document(&sdl)
-            .map_err(|e| e.to_report(&sdl))
-            .expect("a valid document");
+        let schema = cynic_parser::parse_type_system_document(&sdl).map_err(|e| e.to_report(&sdl).to_string())?;
+        let fake_data = fake_data.then(|| FakeData::new(&schema));
 
         let (query_type, ..) = root_types(&schema);
 
@@ -72,6 +90,14 @@ impl DynamicSchemaBuilder {
             builder = builder.register(entity_type(&entities));
         }
 
+        if fake_data.is_some() {
+            for entity in &entities {
+                entity_resolvers
+                    .entry(entity.to_string())
+                    .or_insert_with(|| Box::new(representation_entity_resolver));
+            }
+        }
+
         let mut entity_resolvers = if !entity_resolvers.is_empty() {
             Some(entity_resolvers)
         } else {
@@ -80,7 +106,7 @@ impl DynamicSchemaBuilder {
         for definition in schema.definitions() {
             match definition {
                 parser::Definition::Type(def) => {
-                    let mut ty = convert_type(def, &mut field_resolvers);
+                    let mut ty = convert_type(def, &mut field_resolvers, fake_data.as_ref());
                     if def.name() == query_type
                         && let Some(entity_resolvers) = entity_resolvers.take()
                     {
@@ -103,9 +129,9 @@ impl DynamicSchemaBuilder {
             ));
         }
 
-        let schema = builder.finish().unwrap();
+        let schema = builder.finish().map_err(|error| error.to_string())?;
 
-        DynamicSchema { schema, sdl }
+        Ok(DynamicSchema { schema, sdl })
     }
 }
 
@@ -122,10 +148,14 @@ fn find_entities(schema: &parser::TypeSystemDocument) -> Vec<&str> {
         .collect()
 }
 
-fn convert_type(def: parser::TypeDefinition<'_>, resolvers: &mut ResolverMap) -> async_graphql::dynamic::Type {
+fn convert_type(
+    def: parser::TypeDefinition<'_>,
+    resolvers: &mut ResolverMap,
+    fake_data: Option<&Arc<FakeData>>,
+) -> async_graphql::dynamic::Type {
     match def {
         parser::TypeDefinition::Scalar(def) => async_graphql::dynamic::Scalar::new(def.name()).into(),
-        parser::TypeDefinition::Object(def) => convert_object(def, resolvers),
+        parser::TypeDefinition::Object(def) => convert_object(def, resolvers, fake_data),
         parser::TypeDefinition::Interface(def) => convert_iface(def),
         parser::TypeDefinition::Union(def) => convert_union(def),
         parser::TypeDefinition::Enum(def) => convert_enum(def),
@@ -133,7 +163,11 @@ fn convert_type(def: parser::TypeDefinition<'_>, resolvers: &mut ResolverMap) ->
     }
 }
 
-fn convert_object(def: parser::ObjectDefinition<'_>, resolvers: &mut ResolverMap) -> async_graphql::dynamic::Type {
+fn convert_object(
+    def: parser::ObjectDefinition<'_>,
+    resolvers: &mut ResolverMap,
+    fake_data: Option<&Arc<FakeData>>,
+) -> async_graphql::dynamic::Type {
     use async_graphql::dynamic::*;
 
     let mut object = Object::new(def.name());
@@ -147,7 +181,10 @@ fn convert_object(def: parser::ObjectDefinition<'_>, resolvers: &mut ResolverMap
         let resolver = std::sync::Mutex::new(
             resolvers
                 .remove(&(def.name().into(), field_def.name().into()))
-                .unwrap_or_else(|| Box::new(default_field_resolver(field_def.name()))),
+                .unwrap_or_else(|| match fake_data {
+                    Some(fake_data) => Box::new(fake_field_resolver(fake_data.clone(), def.name(), field_def)),
+                    None => Box::new(default_field_resolver(field_def.name())),
+                }),
         );
 
         let mut field = Field::new(field_def.name(), type_ref, move |context| {
@@ -349,6 +386,10 @@ fn default_field_resolver(field_name: &str) -> impl Resolver + 'static {
     }
 }
 
+fn representation_entity_resolver(context: EntityResolverContext<'_>) -> Option<serde_json::Value> {
+    Some(serde_json::Value::Object(context.representation))
+}
+
 fn service_type(sdl: &str) -> async_graphql::dynamic::Type {
     use async_graphql::dynamic::*;
     let mut object = Object::new("_Service");
@@ -0,0 +1,256 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::{ServerError, dynamic::ResolverContext};
+use cynic_parser::{common::WrappingType, type_system as parser};
+
+use super::resolvers::Resolver;
+
+/// Carries the seed of a made up object down to its fields. It's not part of the schema, so it
+/// never ends up in a response.
+const SEED_FIELD: &str = "__fakeSeed";
+
+/// What we need to know about the output types of a schema to make up values for them.
+pub(super) struct FakeData {
+    types: HashMap<String, OutputType>,
+}
+
+enum OutputType {
+    /// An object with the top-level fields of its first `@key`, if it's an entity.
+    Object(Vec<KeyField>),
+    Enum(Vec<String>),
+    /// An interface or union, with its possible object types.
+    Abstract(Vec<String>),
+}
+
+struct KeyField {
+    name: String,
+    type_name: String,
+    wrappers: Vec<WrappingType>,
+}
+
+impl FakeData {
+    pub(super) fn new(schema: &parser::TypeSystemDocument) -> Arc<Self> {
+        let mut types = HashMap::new();
+        let mut implementations = HashMap::<&str, Vec<String>>::new();
+
+        for definition in schema.definitions() {
+            let parser::Definition::Type(def) = definition else {
+                continue;
+            };
+
+            let ty = match def {
+                parser::TypeDefinition::Object(def) => {
+                    for interface in def.implements_interfaces() {
+                        implementations.entry(interface).or_default().push(def.name().into());
+                    }
+                    OutputType::Object(key_fields(def))
+                }
+                parser::TypeDefinition::Interface(_) => OutputType::Abstract(Vec::new()),
+                parser::TypeDefinition::Union(def) => {
+                    OutputType::Abstract(def.members().map(|member| member.name().into()).collect())
+                }
+                parser::TypeDefinition::Enum(def) => {
+                    OutputType::Enum(def.values().map(|value| value.value().into()).collect())
+                }
+                parser::TypeDefinition::Scalar(_) | parser::TypeDefinition::InputObject(_) => continue,
+            };
+
+            types.insert(def.name().to_string(), ty);
+        }
+
+        for (interface, objects) in implementations {
+            if let Some(OutputType::Abstract(possible_types)) = types.get_mut(interface) {
+                possible_types.extend(objects);
+            }
+        }
+
+        Arc::new(FakeData { types })
+    }
+
+    fn value(&self, field_name: &str, type_name: &str, wrappers: &[WrappingType], seed: u64) -> serde_json::Value {
+        use serde_json::Value;
+
+        match wrappers.split_first() {
+            Some((WrappingType::NonNull, rest)) => return self.value(field_name, type_name, rest, seed),
+            Some((WrappingType::List, rest)) => {
+                let len = 1 + seed % 3;
+                return Value::Array(
+                    (0..len)
+                        .map(|index| self.value(field_name, type_name, rest, mix(seed, index)))
+                        .collect(),
+                );
+            }
+            None => {}
+        }
+
+        match (type_name, self.types.get(type_name)) {
+            ("ID", _) => Value::String(format!("{:08x}", seed as u32)),
+            ("Int", _) => Value::from(seed % 1000),
+            ("Float", _) => Value::from((seed % 100_000) as f64 / 100.0),
+            ("Boolean", _) => Value::Bool(seed % 2 == 0),
+            (_, Some(OutputType::Object(keys))) => Value::Object(self.object(type_name, keys, seed)),
+            (_, Some(OutputType::Enum(values))) if !values.is_empty() => {
+                Value::String(values[(seed % values.len() as u64) as usize].clone())
+            }
+            (_, Some(OutputType::Abstract(possible_types))) if !possible_types.is_empty() => {
+                let typename = &possible_types[(seed % possible_types.len() as u64) as usize];
+                let mut object = match self.types.get(typename) {
+                    Some(OutputType::Object(keys)) => self.object(typename, keys, seed),
+                    _ => Default::default(),
+                };
+                object.insert("__typename".into(), Value::String(typename.clone()));
+                Value::Object(object)
+            }
+            (_, Some(_)) => Value::Null,
+            // String and custom scalars
+            (_, None) => Value::String(format!("{field_name} {}", seed % 1000)),
+        }
+    }
+
+    /// Made up objects come with their key fields, so that their other fields get the same
+    /// values as when the gateway fetches the entity through `_entities`.
+    fn object(&self, type_name: &str, keys: &[KeyField], seed: u64) -> serde_json::Map<String, serde_json::Value> {
+        let mut object = serde_json::Map::new();
+        object.insert(SEED_FIELD.into(), serde_json::Value::String(seed.to_string()));
+
+        for key in keys {
+            let key_seed = hash(&[type_name.as_bytes(), key.name.as_bytes(), &seed.to_le_bytes()]);
+            let value = self.value(&key.name, &key.type_name, &key.wrappers, key_seed);
+            object.insert(key.name.clone(), value);
+        }
+
+        object
+    }
+
+    /// What identifies the parent object: its key fields for an entity, so the data only depends
+    /// on the entity and not on how it was reached, and the seed it was made up with otherwise.
+    fn parent_identity(&self, parent_type: &str, parent: Option<&async_graphql::Value>) -> String {
+        let Some(async_graphql::Value::Object(parent)) = parent else {
+            return String::new();
+        };
+
+        if let Some(OutputType::Object(keys)) = self.types.get(parent_type)
+            && !keys.is_empty()
+            && let Some(values) = keys
+                .iter()
+                .map(|key| parent.get(key.name.as_str()).map(ToString::to_string))
+                .collect::<Option<Vec<_>>>()
+        {
+            return values.join(",");
+        }
+
+        match parent.get(SEED_FIELD) {
+            Some(seed) => seed.to_string(),
+            None => String::new(),
+        }
+    }
+}
+
+/// The top-level fields of the first `@key` of the object, nested selections are left out.
+fn key_fields(def: parser::ObjectDefinition<'_>) -> Vec<KeyField> {
+    let Some(fields) = def
+        .directives()
+        .find(|directive| directive.name() == "key")
+        .and_then(|directive| directive.argument("fields"))
+        .and_then(|argument| argument.value().as_str())
+    else {
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    let mut depth = 0;
+
+    for token in fields.replace('{', " { ").replace('}', " } ").split_whitespace() {
+        match token {
+            "{" => depth += 1,
+            "}" => depth -= 1,
+            name if depth == 0 => names.push(name.to_string()),
+            _ => {}
+        }
+    }
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let field = def.fields().find(|field| field.name() == name)?;
+            let mut wrappers = field.ty().wrappers().collect::<Vec<_>>();
+            wrappers.reverse();
+
+            Some(KeyField {
+                name,
+                type_name: field.ty().name().to_string(),
+                wrappers,
+            })
+        })
+        .collect()
+}
+
+/// Resolves the field from the parent value when it's there, e.g. key fields from an entity
+/// representation, and makes up a value otherwise. The value only depends on the field, its
+/// arguments and the parent object, so the same entity always gets the same data whichever
+/// query reaches it.
+pub(super) fn fake_field_resolver(
+    fake_data: Arc<FakeData>,
+    parent_type: &str,
+    field_def: parser::FieldDefinition<'_>,
+) -> impl Resolver + 'static {
+    let field_name = async_graphql::Name::new(field_def.name());
+    let parent_type = parent_type.to_string();
+    let coordinate = format!("{parent_type}.{}", field_def.name());
+    let type_name = field_def.ty().name().to_string();
+
+    // Outermost wrapper first
+    let mut wrappers = field_def.ty().wrappers().collect::<Vec<_>>();
+    wrappers.reverse();
+
+    move |context: ResolverContext<'_>| {
+        let parent = context.parent_value.as_value();
+
+        if let Some(async_graphql::Value::Object(map)) = parent
+            && let Some(value) = map.get(&field_name)
+        {
+            return match value.clone().into_json() {
+                Ok(value) => Some(value),
+                Err(error) => {
+                    context.add_error(ServerError::new(
+                        format!("could not convert the value of {coordinate}: {error}"),
+                        None,
+                    ));
+                    None
+                }
+            };
+        }
+
+        let mut arguments = context
+            .args
+            .iter()
+            .map(|(name, value)| format!("{name}: {}", value.as_value()))
+            .collect::<Vec<_>>();
+        arguments.sort();
+
+        let identity = fake_data.parent_identity(&parent_type, parent);
+        let seed = hash(&[
+            coordinate.as_bytes(),
+            identity.as_bytes(),
+            arguments.join(", ").as_bytes(),
+        ]);
+
+        Some(fake_data.value(field_name.as_str(), &type_name, &wrappers, seed))
+    }
+}
+
+/// FNV-1a, which unlike the std hashers is guaranteed to stay the same between releases.
+fn hash(parts: &[&[u8]]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+
+    for byte in parts.iter().flat_map(|part| part.iter().chain(Some(&0xff))) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
+
+fn mix(seed: u64, index: u64) -> u64 {
+    hash(&[&seed.to_le_bytes(), &index.to_le_bytes()])
+}
@@ -1,5 +1,6 @@
 mod builder;
 mod entity_resolvers;
+mod fake;
 mod resolvers;
 
 use std::sync::Arc;
@@ -0,0 +1,69 @@
+use async_graphql::{Context, EmptySubscription, Object, SimpleObject, Upload};
+
+/// A schema receiving files with the GraphQL multipart request spec.
+pub struct FileUploadSchema {
+    schema: async_graphql::Schema<Query, Mutation, EmptySubscription>,
+}
+
+impl crate::Subgraph for FileUploadSchema {
+    fn name(&self) -> String {
+        "file-upload".to_string()
+    }
+    async fn start(self) -> crate::MockGraphQlServer {
+        crate::MockGraphQlServer::new(self.schema).await
+    }
+}
+
+impl Default for FileUploadSchema {
+    fn default() -> Self {
+        FileUploadSchema {
+            schema: async_graphql::Schema::build(Query, Mutation, EmptySubscription).finish(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Query;
+
+#[Object]
+impl Query {
+    async fn ok(&self) -> bool {
+        true
+    }
+
+    async fn read(&self, ctx: &Context<'_>, file: Upload) -> async_graphql::Result<UploadedFile> {
+        read_file(ctx, file)
+    }
+}
+
+#[derive(Default)]
+pub struct Mutation;
+
+#[derive(SimpleObject)]
+struct UploadedFile {
+    filename: String,
+    content_type: Option<String>,
+    content: String,
+}
+
+#[Object]
+impl Mutation {
+    async fn upload(&self, ctx: &Context<'_>, file: Upload) -> async_graphql::Result<UploadedFile> {
+        read_file(ctx, file)
+    }
+
+    async fn upload_many(&self, ctx: &Context<'_>, files: Vec<Upload>) -> async_graphql::Result<Vec<UploadedFile>> {
+        files.into_iter().map(|file| read_file(ctx, file)).collect()
+    }
+}
+
+fn read_file(ctx: &Context<'_>, file: Upload) -> async_graphql::Result<UploadedFile> {
+    let mut value = file.value(ctx)?;
+    let mut content = String::new();
+    std::io::Read::read_to_string(&mut value.content, &mut content)?;
+    Ok(UploadedFile {
+        filename: value.filename,
+        content_type: value.content_type,
+        content,
+    })
+}
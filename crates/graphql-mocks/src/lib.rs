@@ -11,7 +11,8 @@ use std::{
     time::Duration,
 };
 
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql::{BatchRequest, BatchResponse};
+use async_graphql_axum::{GraphQLBatchRequest, GraphQLResponse};
 use axum::{
     Router,
     extract::{FromRequestParts, State},
@@ -31,6 +32,7 @@ mod echo;
 mod error_schema;
 mod fake_github;
 mod federation;
+mod file_upload;
 mod query_plan_bench;
 mod secure;
 mod slow;
@@ -40,8 +42,8 @@ mod websockets;
 
 pub use {
     almost_empty::AlmostEmptySchema, echo::EchoSchema, error_schema::ErrorSchema, fake_github::FakeGithubSchema,
-    federation::*, query_plan_bench::QueryBenchSchema, secure::SecureSchema, slow::SlowSchema, stateful::Stateful,
-    tea_shop::TeaShop,
+    federation::*, file_upload::FileUploadSchema, query_plan_bench::QueryBenchSchema, secure::SecureSchema,
+    slow::SlowSchema, stateful::Stateful, tea_shop::TeaShop,
 };
 
 #[derive(Debug)]
@@ -98,10 +100,12 @@ impl MockGraphQlServer {
         MockGraphQlServerBuilder::new(Arc::new(schema))
     }
 
-    async fn new_impl(schema: Arc<dyn Schema>, port: Option<u16>) -> Self {
+    async fn new_impl(schema: Arc<dyn Schema>, port: Option<u16>, record_requests: bool) -> Self {
         let state = AppState {
             schema: schema.clone(),
+            record_requests,
             received_requests: Default::default(),
+            received_batch_sizes: Default::default(),
             next_responses: Default::default(),
             additional_headers: Default::default(),
             signature_key: Default::default(),
@@ -158,6 +162,11 @@ impl MockGraphQlServer {
         std::iter::from_fn(|| self.state.received_requests.pop())
     }
 
+    /// Number of GraphQL requests in each batch received, requests sent on their own aren't counted.
+    pub fn drain_received_batch_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::from_fn(|| self.state.received_batch_sizes.pop())
+    }
+
     pub fn force_next_response(&self, response: impl IntoResponse) {
         self.state.next_responses.push(response.into_response());
     }
@@ -178,16 +187,24 @@ async fn graphql_handler(
     State(state): State<AppState>,
     _sig: ValidMessageSignature,
     headers: HeaderMap,
-    req: GraphQLRequest,
+    req: GraphQLBatchRequest,
 ) -> axum::response::Response {
     let req = req.into_inner();
 
-    // Record the request incase tests want to inspect it.
-    // async_graphql::Request isn't clone so we do a deser roundtrip instead
-    state.received_requests.push(ReceivedRequest {
-        headers: headers.clone(),
-        body: serde_json::from_value(serde_json::to_value(&req).unwrap()).unwrap(),
-    });
+    if state.record_requests {
+        if let BatchRequest::Batch(requests) = &req {
+            state.received_batch_sizes.push(requests.len());
+        }
+
+        // Record the request incase tests want to inspect it.
+        // async_graphql::Request isn't clone so we do a deser roundtrip instead
+        for req in req.iter() {
+            state.received_requests.push(ReceivedRequest {
+                headers: headers.clone(),
+                body: serde_json::from_value(serde_json::to_value(req).unwrap()).unwrap(),
+            });
+        }
+    }
 
     if let Some(response) = state.next_responses.pop() {
         return response;
@@ -198,7 +215,16 @@ async fn graphql_handler(
         .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).to_string()))
         .collect();
 
-    let response: GraphQLResponse = state.schema.execute(headers, req).await.into();
+    let response: GraphQLResponse = match req {
+        BatchRequest::Single(req) => state.schema.execute(headers, req).await.into(),
+        BatchRequest::Batch(requests) => {
+            let mut responses = Vec::with_capacity(requests.len());
+            for req in requests {
+                responses.push(state.schema.execute(headers.clone(), req).await);
+            }
+            BatchResponse::Batch(responses).into()
+        }
+    };
     let mut http_response = response.into_response();
 
     http_response
@@ -211,7 +237,9 @@ async fn graphql_handler(
 #[derive(Clone)]
 struct AppState {
     schema: Arc<dyn Schema>,
+    record_requests: bool,
     received_requests: Arc<crossbeam_queue::SegQueue<ReceivedRequest>>,
+    received_batch_sizes: Arc<crossbeam_queue::SegQueue<usize>>,
     next_responses: Arc<crossbeam_queue::SegQueue<axum::response::Response>>,
     additional_headers: Arc<Mutex<http::HeaderMap>>,
     #[expect(clippy::type_complexity)]
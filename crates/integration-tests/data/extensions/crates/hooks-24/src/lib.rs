@@ -0,0 +1,137 @@
+use grafbase_sdk::{
+    HooksExtension,
+    host_io::{
+        event_queue::{Event, EventQueue},
+        http::{Method, StatusCode},
+    },
+    types::{
+        AuthorizedOperationContext, Configuration, Error, ErrorResponse, GatewayHeaders, Headers, OnRequestOutput,
+        RequestContext,
+    },
+};
+
+#[derive(HooksExtension)]
+struct Hooks {
+    config: TestConfig,
+}
+
+#[derive(Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TestConfig {
+    override_labels: Vec<String>,
+    on_subgraph_response: OnSubgraphResponseConfig,
+    on_graphql_response: OnGraphqlResponseConfig,
+    circuit_breaker_events_header: Option<String>,
+}
+
+#[derive(Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct OnSubgraphResponseConfig {
+    reject_status: Option<u16>,
+    required_header: Option<String>,
+    set_header: Option<HeaderConfig>,
+    remove_header: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HeaderConfig {
+    name: String,
+    value: String,
+}
+
+#[derive(Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct OnGraphqlResponseConfig {
+    redact: Option<String>,
+}
+
+impl HooksExtension for Hooks {
+    fn new(config: Configuration) -> Result<Self, Error> {
+        let config = config.deserialize::<TestConfig>()?;
+
+        Ok(Self { config })
+    }
+
+    #[allow(refining_impl_trait)]
+    fn on_request(&mut self, _: &str, _: Method, _: &mut GatewayHeaders) -> Result<OnRequestOutput, ErrorResponse> {
+        Ok(self
+            .config
+            .override_labels
+            .iter()
+            .fold(OnRequestOutput::new(), |output, label| output.override_label(label)))
+    }
+
+    fn on_response(
+        &mut self,
+        _: &RequestContext,
+        _: &mut StatusCode,
+        headers: &mut Headers,
+        queue: EventQueue,
+    ) -> Result<(), Error> {
+        if let Some(ref name) = self.config.circuit_breaker_events_header {
+            let mut changes = Vec::new();
+
+            while let Some(event) = queue.pop() {
+                if let Event::CircuitBreaker(change) = event {
+                    changes.push(format!(
+                        "{}:{}->{}",
+                        change.subgraph_name,
+                        change.previous_state.as_str(),
+                        change.state.as_str()
+                    ));
+                }
+            }
+
+            if !changes.is_empty() {
+                headers.append(name.as_str(), changes.join(","));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_graphql_subgraph_response(
+        &mut self,
+        _: &AuthorizedOperationContext,
+        subgraph_name: &str,
+        status: StatusCode,
+        headers: &mut Headers,
+    ) -> Result<(), Error> {
+        let config = &self.config.on_subgraph_response;
+
+        if config.reject_status == Some(status.as_u16()) {
+            return Err(Error::new(format!(
+                "Subgraph {subgraph_name} responded with status {}",
+                status.as_u16()
+            )));
+        }
+
+        if let Some(ref name) = config.required_header
+            && !headers.has(name.as_str())
+        {
+            return Err(Error::new(format!(
+                "Subgraph {subgraph_name} responded without the {name} header"
+            )));
+        }
+
+        if let Some(ref header) = config.set_header {
+            headers.set(header.name.as_str(), [header.value.as_str()]);
+        }
+
+        if let Some(ref name) = config.remove_header {
+            headers.remove(name.as_str());
+        }
+
+        Ok(())
+    }
+
+    fn on_graphql_response(&mut self, _: &RequestContext, body: &mut Vec<u8>) -> Result<(), Error> {
+        if let Some(ref redact) = self.config.on_graphql_response.redact {
+            let redacted = String::from_utf8_lossy(body).replace(redact.as_str(), "[REDACTED]");
+            *body = redacted.into_bytes();
+        }
+
+        Ok(())
+    }
+}
@@ -293,6 +293,49 @@ impl McpHttpClient {
             },
         }
     }
+
+    pub async fn list_resources(&mut self) -> McpResponse<rmcp::model::ListResourcesResult> {
+        let result = self.client.list_resources(None).await.unwrap();
+        McpResponse::Result { result }
+    }
+
+    pub async fn list_resource_templates(&mut self) -> McpResponse<rmcp::model::ListResourceTemplatesResult> {
+        let result = self.client.list_resource_templates(None).await.unwrap();
+        McpResponse::Result { result }
+    }
+
+    pub async fn read_resource(&mut self, uri: &str) -> McpResponse<rmcp::model::ReadResourceResult> {
+        let result = self
+            .client
+            .read_resource(rmcp::model::ReadResourceRequestParam { uri: uri.into() })
+            .await
+            .unwrap();
+        McpResponse::Result { result }
+    }
+
+    pub async fn list_prompts(&mut self) -> McpResponse<rmcp::model::ListPromptsResult> {
+        let result = self.client.list_prompts(None).await.unwrap();
+        McpResponse::Result { result }
+    }
+
+    pub async fn get_prompt(
+        &mut self,
+        name: &'static str,
+        arguments: serde_json::Value,
+    ) -> McpResponse<rmcp::model::GetPromptResult> {
+        let result = self
+            .client
+            .get_prompt(rmcp::model::GetPromptRequestParam {
+                name: name.into(),
+                arguments: match arguments {
+                    serde_json::Value::Object(map) => Some(map),
+                    _ => panic!("bad arguments to get_prompt"),
+                },
+            })
+            .await
+            .unwrap();
+        McpResponse::Result { result }
+    }
 }
 
 pub struct McpStream {
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use bytes::Bytes;
 use engine::GraphqlError;
 use engine_schema::{GraphqlSubgraph, VirtualSubgraph};
 use event_queue::EventQueue;
@@ -23,6 +24,10 @@ impl GatewayHooksExtension for GatewayTestExtensions {
 }
 
 impl EngineHooksExtension<engine::EngineOperationContext> for EngineTestExtensions {
+    fn has_graphql_subgraph_response_hook(&self) -> bool {
+        self.wasm.has_graphql_subgraph_response_hook()
+    }
+
     async fn on_graphql_subgraph_request<'r>(
         &self,
         context: engine::EngineOperationContext,
@@ -40,4 +45,22 @@ impl EngineHooksExtension<engine::EngineOperationContext> for EngineTestExtensio
     ) -> Result<http::HeaderMap, GraphqlError> {
         self.wasm.on_virtual_subgraph_request(context, subgraph, headers).await
     }
+
+    async fn on_graphql_subgraph_response(
+        &self,
+        context: engine::EngineOperationContext,
+        subgraph: GraphqlSubgraph<'_>,
+        parts: http::response::Parts,
+    ) -> Result<http::response::Parts, GraphqlError> {
+        self.wasm.on_graphql_subgraph_response(context, subgraph, parts).await
+    }
+
+    async fn on_graphql_response(
+        &self,
+        event_queue: Arc<EventQueue>,
+        hooks_context: Arc<[u8]>,
+        body: Bytes,
+    ) -> Result<Bytes, GraphqlError> {
+        self.wasm.on_graphql_response(event_queue, hooks_context, body).await
+    }
 }
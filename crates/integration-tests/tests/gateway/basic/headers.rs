@@ -1213,3 +1213,128 @@ fn header_regex_remove_should_remove_duplicates() {
     }
     "#);
 }
+
+#[test]
+fn header_insert_template() {
+    let response = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                [[headers]]
+                rule = "insert"
+                name = "x-client"
+                value = "client-{{ header.x-client-name }}"
+
+                [[subgraphs.echo.headers]]
+                rule = "insert"
+                name = "x-operation"
+                value = "{{ operation.type }}:{{ operation.name }}"
+                "#,
+            )
+            .build()
+            .await;
+
+        engine
+            .post(r#"query Hello { client: header(name: "x-client") operation: header(name: "x-operation") }"#)
+            .header("x-client-name", "web")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "client": "client-web",
+        "operation": "query:Hello"
+      }
+    }
+    "#);
+}
+
+#[test]
+fn header_insert_template_with_missing_variable() {
+    let response = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                [[headers]]
+                rule = "insert"
+                name = "x-client"
+                value = "client-{{ header.x-client-name }}"
+
+                [[headers]]
+                rule = "insert"
+                name = "x-operation"
+                value = "{{ operation.name }}"
+                "#,
+            )
+            .build()
+            .await;
+
+        engine
+            .post(r#"query Hello { client: header(name: "x-client") operation: header(name: "x-operation") }"#)
+            .await
+    });
+
+    // The operation isn't known yet when applying the global rules.
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "client": null,
+        "operation": null
+      }
+    }
+    "#);
+}
+
+#[test]
+fn header_rule_conditions() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                [[headers]]
+                rule = "insert"
+                name = "x-anonymous"
+                value = "true"
+                when = { not = { header_present = "authorization" } }
+
+                [[subgraphs.echo.headers]]
+                rule = "forward"
+                name = "x-debug"
+                when = { all = [{ operation_type = "query" }, { header_present = "x-debug-enabled" }] }
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = r#"query { anonymous: header(name: "x-anonymous") debug: header(name: "x-debug") }"#;
+
+        let response = engine.post(QUERY).header("x-debug", "1").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "anonymous": "true",
+            "debug": null
+          }
+        }
+        "#);
+
+        let response = engine
+            .post(QUERY)
+            .header("authorization", "Bearer token")
+            .header("x-debug", "1")
+            .header("x-debug-enabled", "yes")
+            .await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "anonymous": null,
+            "debug": "1"
+          }
+        }
+        "#);
+    });
+}
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use graphql_mocks::Stateful;
+use integration_tests::{gateway::Gateway, runtime};
+
+#[test]
+fn circuit_breaker_opens_and_recovers() {
+    runtime().block_on(async move {
+        let config = indoc::indoc! {r#"
+            [subgraphs.stateful.circuit_breaker]
+            enabled = true
+            minimum_requests = 2
+            failure_rate_threshold = 0.5
+            half_open_after = "1s"
+        "#};
+
+        let engine = Gateway::builder()
+            .with_subgraph(Stateful::default())
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        for _ in 0..2 {
+            let response = engine.post("query { incrementAndFailIfLessThan(n: 2) }").await;
+            assert_eq!(response.errors().len(), 1);
+        }
+
+        // The breaker is open, the subgraph isn't called anymore.
+        let response = engine.post("query { value }").await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": null,
+          "errors": [
+            {
+              "message": "Subgraph 'stateful' is unavailable.",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 9
+                }
+              ],
+              "path": [
+                "value"
+              ],
+              "extensions": {
+                "code": "SUBGRAPH_REQUEST_ERROR"
+              }
+            }
+          ]
+        }
+        "#);
+
+        // A probe is let through after a while and closes the breaker again.
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        let response = engine.post("query { value }").await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "value": 2
+          }
+        }
+        "#);
+
+        let response = engine.post("query { value }").await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "value": 2
+          }
+        }
+        "#);
+    })
+}
+
+#[test]
+fn circuit_breaker_disabled_by_default() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder().with_subgraph(Stateful::default()).build().await;
+
+        for _ in 0..30 {
+            let response = engine.post("query { incrementAndFailIfLessThan(n: 30) }").await;
+            assert_eq!(response.errors().len(), 1);
+        }
+
+        let response = engine.post("query { value }").await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "value": 30
+          }
+        }
+        "#);
+    })
+}
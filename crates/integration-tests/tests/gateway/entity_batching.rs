@@ -0,0 +1,86 @@
+use graphql_mocks::{FederatedAccountsSchema, FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{gateway::Gateway, runtime};
+
+// Both `User` and `Product` entities are fetched from the reviews subgraph.
+const QUERY: &str = r"query { me { username reviews { body } } topProducts { name reviews { body } } }";
+
+#[test]
+fn concurrent_entity_requests_are_batched() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedAccountsSchema::default())
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .build()
+            .await;
+
+        let expected = engine.post(QUERY).await.into_data();
+
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedAccountsSchema::default())
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .with_toml_config(
+                r#"
+                [subgraphs.reviews.entity_batching]
+                enabled = true
+                max_wait = "200ms"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await.into_data();
+        assert_eq!(response, expected);
+
+        let reviews = engine.subgraph::<FederatedReviewsSchema>();
+        assert_eq!(reviews.drain_received_batch_sizes().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(reviews.drain_received_requests().count(), 2);
+    })
+}
+
+#[test]
+fn batches_are_limited_in_size() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedAccountsSchema::default())
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .with_toml_config(
+                r#"
+                [subgraphs.reviews.entity_batching]
+                enabled = true
+                max_size = 1
+                max_wait = "200ms"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+        assert!(response.errors().is_empty(), "{response:#?}");
+
+        let reviews = engine.subgraph::<FederatedReviewsSchema>();
+        assert_eq!(reviews.drain_received_batch_sizes().count(), 0);
+        assert_eq!(reviews.drain_received_requests().count(), 2);
+    })
+}
+
+#[test]
+fn entity_batching_is_disabled_by_default() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedAccountsSchema::default())
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+        assert!(response.errors().is_empty(), "{response:#?}");
+
+        let reviews = engine.subgraph::<FederatedReviewsSchema>();
+        assert_eq!(reviews.drain_received_batch_sizes().count(), 0);
+        assert_eq!(reviews.drain_received_requests().count(), 2);
+    })
+}
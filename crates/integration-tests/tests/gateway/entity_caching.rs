@@ -4,6 +4,7 @@ use graphql_mocks::{ErrorSchema, FederatedInventorySchema, FederatedProductsSche
 use integration_tests::{gateway::Gateway, runtime};
 use serde_json::json;
 
+mod invalidation;
 mod redis;
 mod subgraph_cache_control;
 
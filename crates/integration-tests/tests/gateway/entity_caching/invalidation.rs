@@ -0,0 +1,124 @@
+use graphql_mocks::{FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{gateway::Gateway, runtime};
+use serde_json::json;
+
+const CONFIG: &str = r#"
+    [entity_caching]
+    enabled = true
+
+    [entity_caching.invalidation]
+    enabled = true
+    secret = "s3cr3t"
+"#;
+
+fn invalidation_request(secret: &str, body: serde_json::Value) -> http::Request<Vec<u8>> {
+    http::Request::builder()
+        .uri("http://localhost/entity-cache/invalidate")
+        .method(http::Method::POST)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .header(http::header::AUTHORIZATION, format!("Bearer {secret}"))
+        .body(serde_json::to_vec(&body).unwrap())
+        .unwrap()
+}
+
+#[test]
+fn purge_by_subgraph() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        const QUERY: &str = r"query { topProducts { upc name price } }";
+
+        engine.post(QUERY).await.into_data();
+        engine.post(QUERY).await.into_data();
+
+        let response = engine
+            .raw_execute(invalidation_request("s3cr3t", json!({"subgraphs": ["products"]})))
+            .await;
+        assert_eq!(response.status(), 204);
+
+        engine.post(QUERY).await.into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    });
+}
+
+#[test]
+fn purge_single_entity() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .with_subgraph(FederatedInventorySchema::default())
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        const QUERY: &str = "{ topProducts { upc reviews { id body } } }";
+
+        engine.post(QUERY).await.into_data();
+        engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+
+        let response = engine
+            .raw_execute(invalidation_request(
+                "s3cr3t",
+                json!({"entities": [{"type": "Product", "key": {"upc": "top-2"}}]}),
+            ))
+            .await;
+        assert_eq!(response.status(), 204);
+
+        engine.post(QUERY).await.into_data();
+
+        // Only the purged entity is fetched again.
+        insta::assert_json_snapshot!(engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>(), @r#"
+        [
+          {
+            "query": "query($var0: [_Any!]!) { _entities(representations: $var0) { ... on Product { reviews { id body } } } }",
+            "operationName": null,
+            "variables": {
+              "var0": [
+                {
+                  "__typename": "Product",
+                  "upc": "top-2"
+                }
+              ]
+            },
+            "extensions": {}
+          }
+        ]
+        "#);
+    });
+}
+
+#[test]
+fn purge_requires_the_secret() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        const QUERY: &str = r"query { topProducts { upc } }";
+
+        engine.post(QUERY).await.into_data();
+
+        let response = engine
+            .raw_execute(invalidation_request("wrong", json!({"subgraphs": ["products"]})))
+            .await;
+        assert_eq!(response.status(), 401);
+
+        engine.post(QUERY).await.into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+    });
+}
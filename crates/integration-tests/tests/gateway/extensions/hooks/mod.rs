@@ -2,3 +2,4 @@ mod sdk17;
 mod sdk18;
 mod sdk19;
 mod sdk21;
+mod sdk24;
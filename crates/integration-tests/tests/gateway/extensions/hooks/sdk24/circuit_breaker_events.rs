@@ -0,0 +1,38 @@
+use graphql_mocks::Stateful;
+use integration_tests::{gateway::Gateway, runtime};
+
+#[test]
+fn circuit_breaker_state_changes_are_sent_to_hooks() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_toml_config(
+                r#"
+                [subgraphs.stateful.circuit_breaker]
+                enabled = true
+                minimum_requests = 2
+                failure_rate_threshold = 0.5
+                half_open_after = "1h"
+
+                [extensions.hooks-24.config]
+                circuit_breaker_events_header = "x-circuit-breaker"
+                "#,
+            )
+            .with_extension("hooks-24")
+            .with_subgraph(Stateful::default())
+            .build()
+            .await;
+
+        let response = engine.post("query { incrementAndFailIfLessThan(n: 2) }").await;
+        assert!(response.headers.get("x-circuit-breaker").is_none());
+
+        // The second failure reaches the threshold and opens the breaker.
+        let response = engine.post("query { incrementAndFailIfLessThan(n: 2) }").await;
+        assert_eq!(
+            response.headers.get("x-circuit-breaker").unwrap(),
+            "stateful:closed->open"
+        );
+
+        let response = engine.post("query { value }").await;
+        assert!(response.headers.get("x-circuit-breaker").is_none());
+    })
+}
@@ -0,0 +1,4 @@
+mod circuit_breaker_events;
+mod on_graphql_response;
+mod on_subgraph_response;
+mod override_labels;
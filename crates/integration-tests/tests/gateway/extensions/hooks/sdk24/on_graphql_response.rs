@@ -0,0 +1,68 @@
+use graphql_mocks::EchoSchema;
+use indoc::indoc;
+use integration_tests::{gateway::Gateway, runtime};
+
+#[test]
+fn graphql_response_is_redacted() {
+    runtime().block_on(async move {
+        let config = indoc! {r#"
+            [[headers]]
+            rule = "forward"
+            name = "x-secret"
+
+            [extensions.hooks-24.config]
+            on_graphql_response.redact = "top-secret"
+        "#};
+
+        let gateway = Gateway::builder()
+            .with_toml_config(config)
+            .with_extension("hooks-24")
+            .with_subgraph(EchoSchema::default())
+            .build()
+            .await;
+
+        let response = gateway
+            .post(r#"query { header(name: "x-secret") }"#)
+            .header("x-secret", "top-secret")
+            .await;
+
+        insta::assert_snapshot!(response, @r#"
+        {
+          "data": {
+            "header": "[REDACTED]"
+          }
+        }
+        "#);
+    })
+}
+
+#[test]
+fn graphql_response_is_left_unchanged_by_default() {
+    runtime().block_on(async move {
+        let config = indoc! {r#"
+            [[headers]]
+            rule = "forward"
+            name = "x-secret"
+        "#};
+
+        let gateway = Gateway::builder()
+            .with_toml_config(config)
+            .with_extension("hooks-24")
+            .with_subgraph(EchoSchema::default())
+            .build()
+            .await;
+
+        let response = gateway
+            .post(r#"query { header(name: "x-secret") }"#)
+            .header("x-secret", "top-secret")
+            .await;
+
+        insta::assert_snapshot!(response, @r#"
+        {
+          "data": {
+            "header": "top-secret"
+          }
+        }
+        "#);
+    })
+}
@@ -0,0 +1,149 @@
+use graphql_mocks::EchoSchema;
+use indoc::indoc;
+use integration_tests::{gateway::Gateway, runtime};
+
+#[test]
+fn subgraph_response_is_passed_through() {
+    runtime().block_on(async move {
+        let config = indoc! {r#"
+            [extensions.hooks-24.config]
+            on_subgraph_response.reject_status = 500
+            on_subgraph_response.required_header = "content-type"
+        "#};
+
+        let gateway = Gateway::builder()
+            .with_toml_config(config)
+            .with_extension("hooks-24")
+            .with_subgraph(EchoSchema::default())
+            .build()
+            .await;
+
+        let response = gateway.post(r#"query { header(name: "x-test") }"#).await;
+        insta::assert_snapshot!(response, @r#"
+        {
+          "data": {
+            "header": null
+          }
+        }
+        "#);
+    })
+}
+
+#[test]
+fn subgraph_response_rejected_on_status() {
+    runtime().block_on(async move {
+        let config = indoc! {r#"
+            [extensions.hooks-24.config]
+            on_subgraph_response.reject_status = 200
+        "#};
+
+        let gateway = Gateway::builder()
+            .with_toml_config(config)
+            .with_extension("hooks-24")
+            .with_subgraph(EchoSchema::default())
+            .build()
+            .await;
+
+        let response = gateway.post(r#"query { header(name: "x-test") }"#).await;
+        insta::assert_snapshot!(response, @r#"
+        {
+          "data": {
+            "header": null
+          },
+          "errors": [
+            {
+              "message": "Subgraph echo responded with status 200",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 9
+                }
+              ],
+              "path": [
+                "header"
+              ],
+              "extensions": {
+                "code": "EXTENSION_ERROR"
+              }
+            }
+          ]
+        }
+        "#);
+    })
+}
+
+#[test]
+fn subgraph_response_rejected_on_missing_header() {
+    runtime().block_on(async move {
+        let config = indoc! {r#"
+            [extensions.hooks-24.config]
+            on_subgraph_response.required_header = "x-missing"
+        "#};
+
+        let gateway = Gateway::builder()
+            .with_toml_config(config)
+            .with_extension("hooks-24")
+            .with_subgraph(EchoSchema::default())
+            .build()
+            .await;
+
+        let response = gateway.post(r#"query { header(name: "x-test") }"#).await;
+        insta::assert_snapshot!(response, @r#"
+        {
+          "data": {
+            "header": null
+          },
+          "errors": [
+            {
+              "message": "Subgraph echo responded without the x-missing header",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 9
+                }
+              ],
+              "path": [
+                "header"
+              ],
+              "extensions": {
+                "code": "EXTENSION_ERROR"
+              }
+            }
+          ]
+        }
+        "#);
+    })
+}
+
+#[test]
+fn subgraph_response_headers_are_modified() {
+    runtime().block_on(async move {
+        let config = indoc! {r#"
+            [[response_headers]]
+            rule = "propagate"
+            name = "x-custom"
+
+            [[response_headers]]
+            rule = "propagate"
+            name = "x-hook"
+
+            [extensions.hooks-24.config]
+            on_subgraph_response.set_header = { name = "x-hook", value = "added" }
+            on_subgraph_response.remove_header = "x-custom"
+        "#};
+
+        let gateway = Gateway::builder()
+            .with_toml_config(config)
+            .with_extension("hooks-24")
+            .with_subgraph(EchoSchema::default())
+            .build()
+            .await;
+
+        let response = gateway
+            .post(r#"query { responseHeader(name: "x-custom", value: "kekw") }"#)
+            .await;
+
+        assert_eq!(response.headers.get("x-hook").unwrap(), "added");
+        assert!(response.headers.get("x-custom").is_none());
+    })
+}
@@ -0,0 +1,53 @@
+use integration_tests::{
+    gateway::{Gateway, GraphqlResponse},
+    runtime,
+};
+
+use crate::gateway::subgraphs::progressive_override::execute_overridden_product_query;
+
+async fn execute(config: &str) -> GraphqlResponse {
+    let gateway = Gateway::builder().with_toml_config(config).with_extension("hooks-24");
+    execute_overridden_product_query(gateway, "new-pricing").await
+}
+
+#[test]
+fn label_enabled_by_on_request_uses_the_overriding_subgraph() {
+    let response = runtime().block_on(execute(
+        r#"
+        [extensions.hooks-24.config]
+        override_labels = ["new-pricing"]
+        "#,
+    ));
+
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "product": {
+          "id": "1",
+          "price": 20
+        }
+      }
+    }
+    "#);
+}
+
+#[test]
+fn other_labels_keep_the_overridden_subgraph() {
+    let response = runtime().block_on(execute(
+        r#"
+        [extensions.hooks-24.config]
+        override_labels = ["other-label"]
+        "#,
+    ));
+
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "product": {
+          "id": "1",
+          "price": 10
+        }
+      }
+    }
+    "#);
+}
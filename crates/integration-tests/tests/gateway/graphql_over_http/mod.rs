@@ -2,6 +2,7 @@ mod application_graphql_response_json;
 mod application_json;
 mod batch;
 mod cbor;
+mod multipart;
 
 use engine::GraphqlError;
 use graphql_mocks::{FakeGithubSchema, Stateful};
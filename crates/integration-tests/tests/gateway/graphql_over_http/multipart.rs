@@ -0,0 +1,319 @@
+use graphql_mocks::{FileUploadSchema, dynamic::DynamicSchema};
+use integration_tests::{gateway::Gateway, runtime};
+
+const BOUNDARY: &str = "test-boundary";
+
+const CONFIG: &str = r#"
+    [file_uploads]
+    enabled = true
+"#;
+
+fn multipart_request(
+    operations: serde_json::Value,
+    map: serde_json::Value,
+    files: &[(&str, &str)],
+) -> http::Request<Vec<u8>> {
+    let mut body = String::new();
+    for (name, content) in [("operations", operations.to_string()), ("map", map.to_string())] {
+        body.push_str(&format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{content}\r\n"
+        ));
+    }
+    for (name, content) in files {
+        body.push_str(&format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"; filename=\"{name}.txt\"\r\nContent-Type: text/plain\r\n\r\n{content}\r\n"
+        ));
+    }
+    body.push_str(&format!("--{BOUNDARY}--\r\n"));
+
+    http::Request::builder()
+        .uri("http://localhost/graphql")
+        .method(http::Method::POST)
+        .header(http::header::ACCEPT, "application/json")
+        .header(
+            http::header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .header("x-grafbase-csrf-protection", "1")
+        .body(body.into_bytes())
+        .unwrap()
+}
+
+#[test]
+fn single_file() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FileUploadSchema::default())
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let response = engine
+            .raw_execute(multipart_request(
+                serde_json::json!({
+                    "query": "mutation($file: Upload!) { upload(file: $file) { filename contentType content } }",
+                    "variables": { "file": null }
+                }),
+                serde_json::json!({ "0": ["variables.file"] }),
+                &[("0", "Hello world!")],
+            ))
+            .await;
+        let status = response.status();
+        let body: serde_json::Value = serde_json::from_slice(&response.into_body()).unwrap();
+        insta::assert_json_snapshot!(body, @r#"
+        {
+          "data": {
+            "upload": {
+              "filename": "0.txt",
+              "contentType": "text/plain",
+              "content": "Hello world!"
+            }
+          }
+        }
+        "#);
+        assert_eq!(status, 200);
+    })
+}
+
+#[test]
+fn multiple_files() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FileUploadSchema::default())
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let response = engine
+            .raw_execute(multipart_request(
+                serde_json::json!({
+                    "query": "mutation($files: [Upload!]!) { uploadMany(files: $files) { filename content } }",
+                    "variables": { "files": [null, null] }
+                }),
+                serde_json::json!({ "first": ["variables.files.0"], "second": ["variables.files.1"] }),
+                &[("first", "a"), ("second", "b")],
+            ))
+            .await;
+        let status = response.status();
+        let body: serde_json::Value = serde_json::from_slice(&response.into_body()).unwrap();
+        insta::assert_json_snapshot!(body, @r#"
+        {
+          "data": {
+            "uploadMany": [
+              {
+                "filename": "first.txt",
+                "content": "a"
+              },
+              {
+                "filename": "second.txt",
+                "content": "b"
+              }
+            ]
+          }
+        }
+        "#);
+        assert_eq!(status, 200);
+    })
+}
+
+#[test]
+fn disabled_by_default() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FileUploadSchema::default())
+            .build()
+            .await;
+
+        let response = engine
+            .raw_execute(multipart_request(
+                serde_json::json!({
+                    "query": "mutation($file: Upload!) { upload(file: $file) { content } }",
+                    "variables": { "file": null }
+                }),
+                serde_json::json!({ "0": ["variables.file"] }),
+                &[("0", "Hello world!")],
+            ))
+            .await;
+        let status = response.status();
+        let body: serde_json::Value = serde_json::from_slice(&response.into_body()).unwrap();
+        insta::assert_json_snapshot!(body, @r#"
+        {
+          "errors": [
+            {
+              "message": "Missing or invalid Content-Type header. You must specify one of: 'application/json', 'application/cbor'",
+              "extensions": {
+                "code": "BAD_REQUEST"
+              }
+            }
+          ]
+        }
+        "#);
+        assert_eq!(status, 415);
+    })
+}
+
+#[test]
+fn invalid_map_path() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FileUploadSchema::default())
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let response = engine
+            .raw_execute(multipart_request(
+                serde_json::json!({
+                    "query": "mutation($file: Upload!) { upload(file: $file) { content } }",
+                    "variables": { "file": null }
+                }),
+                serde_json::json!({ "0": ["query"] }),
+                &[("0", "Hello world!")],
+            ))
+            .await;
+        let status = response.status();
+        let body: serde_json::Value = serde_json::from_slice(&response.into_body()).unwrap();
+        insta::assert_json_snapshot!(body, @r#"
+        {
+          "errors": [
+            {
+              "message": "Multipart request failure: File path 'query' in the 'map' field must point to a variable",
+              "extensions": {
+                "code": "BAD_REQUEST"
+              }
+            }
+          ]
+        }
+        "#);
+        assert_eq!(status, 400);
+    })
+}
+
+#[test]
+fn file_used_by_multiple_subgraphs() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FileUploadSchema::default())
+            .with_subgraph(
+                DynamicSchema::builder(
+                    r#"
+                    scalar Upload
+
+                    type Query {
+                        other: Boolean
+                    }
+
+                    type Mutation {
+                        archive(file: Upload!): Boolean
+                    }
+                    "#,
+                )
+                .into_subgraph("archive"),
+            )
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let response = engine
+            .raw_execute(multipart_request(
+                serde_json::json!({
+                    "query": "mutation($file: Upload!) { upload(file: $file) { content } archive(file: $file) }",
+                    "variables": { "file": null }
+                }),
+                serde_json::json!({ "0": ["variables.file"] }),
+                &[("0", "Hello world!")],
+            ))
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(&response.into_body()).unwrap();
+        insta::assert_json_snapshot!(body, @r#"
+        {
+          "errors": [
+            {
+              "message": "An uploaded file cannot be sent to multiple subgraphs, it is used by both 'file-upload' and 'archive'",
+              "extensions": {
+                "code": "BAD_REQUEST"
+              }
+            }
+          ]
+        }
+        "#);
+    })
+}
+
+#[test]
+fn missing_csrf_header() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FileUploadSchema::default())
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let mut request = multipart_request(
+            serde_json::json!({
+                "query": "mutation($file: Upload!) { upload(file: $file) { content } }",
+                "variables": { "file": null }
+            }),
+            serde_json::json!({ "0": ["variables.file"] }),
+            &[("0", "Hello world!")],
+        );
+        request.headers_mut().remove("x-grafbase-csrf-protection");
+
+        let response = engine.raw_execute(request).await;
+        let status = response.status();
+        let body: serde_json::Value = serde_json::from_slice(&response.into_body()).unwrap();
+        insta::assert_json_snapshot!(body, @r#"
+        {
+          "errors": [
+            {
+              "message": "Multipart requests must include the 'X-Grafbase-CSRF-Protection' header to prevent cross-site request forgery.",
+              "extensions": {
+                "code": "BAD_REQUEST"
+              }
+            }
+          ]
+        }
+        "#);
+        assert_eq!(status, 403);
+    })
+}
+
+#[test]
+fn uploads_bypass_the_response_cache() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FileUploadSchema::default())
+            .with_toml_config(
+                r#"
+                [file_uploads]
+                enabled = true
+
+                [response_caching]
+                enabled = true
+                ttl = "30s"
+                "#,
+            )
+            .build()
+            .await;
+
+        let mut contents = Vec::new();
+        for content in ["first", "second"] {
+            let response = engine
+                .raw_execute(multipart_request(
+                    serde_json::json!({
+                        "query": "query($file: Upload!) { read(file: $file) { content } }",
+                        "variables": { "file": null }
+                    }),
+                    serde_json::json!({ "0": ["variables.file"] }),
+                    &[("0", content)],
+                ))
+                .await;
+            assert_eq!(response.status(), 200);
+            let body: serde_json::Value = serde_json::from_slice(&response.into_body()).unwrap();
+            contents.push(body["data"]["read"]["content"].clone());
+        }
+
+        assert_eq!(contents, [serde_json::json!("first"), serde_json::json!("second")]);
+        assert_eq!(engine.drain_graphql_requests_sent_to::<FileUploadSchema>().len(), 2);
+    })
+}
@@ -35,6 +35,55 @@ fn server_info() {
       "result": {
         "protocolVersion": "2025-03-26",
         "capabilities": {
+          "prompts": {},
+          "resources": {},
+          "tools": {}
+        },
+        "serverInfo": {
+          "name": "rmcp",
+          "version": "0.6.4"
+        },
+        "instructions": null
+      }
+    }
+    "#);
+}
+
+#[test]
+fn server_info_with_resource_subscriptions() {
+    let server_info = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph_sdl(
+                "x",
+                r#"
+                type Query {
+                    user: String
+                }
+            "#,
+            )
+            .with_toml_config(
+                r#"
+            [mcp]
+            enabled = true
+            resource_subscriptions = true
+            "#,
+            )
+            .build()
+            .await;
+
+        let stream = engine.mcp_http("/mcp").await;
+        stream.server_info()
+    });
+
+    insta::assert_json_snapshot!(&server_info, @r#"
+    {
+      "result": {
+        "protocolVersion": "2025-03-26",
+        "capabilities": {
+          "prompts": {},
+          "resources": {
+            "subscribe": true
+          },
           "tools": {}
         },
         "serverInfo": {
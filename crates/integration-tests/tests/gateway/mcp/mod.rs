@@ -2,5 +2,8 @@ mod api_use_case;
 mod basic;
 mod execute;
 mod introspect;
+mod operations;
+mod prompts;
+mod resources;
 mod search;
 mod verify;
@@ -0,0 +1,290 @@
+use std::io::Write as _;
+
+use graphql_mocks::dynamic::DynamicSchema;
+use integration_tests::{TestTrustedDocument, gateway::Gateway, runtime};
+use runtime::trusted_documents_client::TrustedDocumentsEnforcementMode;
+use serde_json::json;
+
+const SCHEMA: &str = r#"
+    type Query {
+        user(id: ID!, filter: UserFilter): User
+    }
+
+    type Mutation {
+        updateUser(name: String!): User
+    }
+
+    input UserFilter {
+        active: Boolean
+        role: Role
+    }
+
+    enum Role {
+        ADMIN
+        USER
+    }
+
+    type User {
+        id: ID!
+        name: String!
+    }
+"#;
+
+const OPERATIONS: &str = r#"
+# Finds a user by id.
+query FindUser($id: ID!, $filter: UserFilter) {
+    user(id: $id, filter: $filter) {
+        name
+    }
+}
+
+mutation UpdateUser($name: String!) {
+    updateUser(name: $name) {
+        name
+    }
+}
+"#;
+
+fn operations_file(content: &str) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    file
+}
+
+fn subgraph() -> graphql_mocks::dynamic::DynamicSubgraph {
+    DynamicSchema::builder(SCHEMA)
+        .with_resolver("Query", "user", json!({"id": "1", "name": "Alice"}))
+        .into_subgraph("x")
+}
+
+#[test]
+fn operations_are_exposed_as_tools() {
+    let file = operations_file(OPERATIONS);
+
+    let tools = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_toml_config(format!(
+                r#"
+                [mcp]
+                enabled = true
+                tools = "operations"
+
+                [[mcp.operations]]
+                path = "{}"
+                "#,
+                file.path().display()
+            ))
+            .build()
+            .await;
+
+        let mut stream = engine.mcp_http("/mcp").await;
+        stream.list_tools().await
+    });
+
+    insta::assert_json_snapshot!(&tools, @r#"
+    {
+      "result": {
+        "tools": [
+          {
+            "name": "find_user",
+            "description": "Finds a user by id.",
+            "inputSchema": {
+              "type": "object",
+              "properties": {
+                "id": {
+                  "type": "string"
+                },
+                "filter": {
+                  "type": "object",
+                  "properties": {
+                    "active": {
+                      "type": "boolean"
+                    },
+                    "role": {
+                      "type": "string",
+                      "enum": [
+                        "ADMIN",
+                        "USER"
+                      ]
+                    }
+                  },
+                  "required": []
+                }
+              },
+              "required": [
+                "id"
+              ]
+            },
+            "annotations": {
+              "readOnlyHint": true
+            }
+          }
+        ]
+      }
+    }
+    "#);
+}
+
+#[test]
+fn tool_descriptions_are_the_comments_preceding_the_operation() {
+    let file = operations_file(
+        r#"
+        # Finds a user
+        #   by id.
+        query
+            FindUser($id: ID!) {
+            user(id: $id) { name }
+        }
+
+        # Separated from the operation by an empty line.
+
+        query UserName { user(id: "1") { name } } # Trailing comment.
+        query UserId { user(id: "1") { id } }
+        "#,
+    );
+
+    let tools = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_toml_config(format!(
+                r#"
+                [mcp]
+                enabled = true
+                tools = "operations"
+
+                [[mcp.operations]]
+                path = "{}"
+                "#,
+                file.path().display()
+            ))
+            .build()
+            .await;
+
+        let mut stream = engine.mcp_http("/mcp").await;
+        stream.list_tools().await
+    });
+
+    let tools = serde_json::to_value(&tools).unwrap();
+    let descriptions = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| json!({ "name": tool["name"], "description": tool["description"] }))
+        .collect::<Vec<_>>();
+
+    insta::assert_json_snapshot!(&descriptions, @r#"
+    [
+      {
+        "name": "find_user",
+        "description": "Finds a user\nby id."
+      },
+      {
+        "name": "user_name",
+        "description": "Executes the GraphQL query `UserName`."
+      },
+      {
+        "name": "user_id",
+        "description": "Executes the GraphQL query `UserId`."
+      }
+    ]
+    "#);
+}
+
+#[test]
+fn execute_operation_tool() {
+    let file = operations_file(OPERATIONS);
+
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_toml_config(format!(
+                r#"
+                [mcp]
+                enabled = true
+                tools = "operations"
+
+                [[mcp.operations]]
+                path = "{}"
+                "#,
+                file.path().display()
+            ))
+            .build()
+            .await;
+
+        let mut stream = engine.mcp_http("/mcp").await;
+        let response = stream.call_tool("find_user", json!({"id": "1"})).await;
+
+        insta::assert_json_snapshot!(&response, @r#"
+        {
+          "result": {
+            "content": [
+              {
+                "data": {
+                  "user": {
+                    "name": "Alice"
+                  }
+                }
+              }
+            ],
+            "is_error": false
+          }
+        }
+        "#);
+    });
+}
+
+#[test]
+fn execute_trusted_document_operation_tool() {
+    const DOCUMENT: &str = "query FindUser($id: ID!) { user(id: $id) { name } }";
+    let file = operations_file(DOCUMENT);
+
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_mock_trusted_documents(
+                TrustedDocumentsEnforcementMode::Enforce,
+                vec![TestTrustedDocument {
+                    branch_id: "my-branch-id",
+                    client_name: "assistant",
+                    document_id: "find-user",
+                    document_text: DOCUMENT,
+                }],
+            )
+            .with_toml_config(format!(
+                r#"
+                [mcp]
+                enabled = true
+                tools = "operations"
+                client_name = "assistant"
+
+                [[mcp.operations]]
+                path = "{}"
+                document_id = "find-user"
+                "#,
+                file.path().display()
+            ))
+            .build()
+            .await;
+
+        let mut stream = engine.mcp_http("/mcp").await;
+        let response = stream.call_tool("find_user", json!({"id": "1"})).await;
+
+        insta::assert_json_snapshot!(&response, @r#"
+        {
+          "result": {
+            "content": [
+              {
+                "data": {
+                  "user": {
+                    "name": "Alice"
+                  }
+                }
+              }
+            ],
+            "is_error": false
+          }
+        }
+        "#);
+    });
+}
@@ -0,0 +1,91 @@
+use integration_tests::{gateway::Gateway, runtime};
+use serde_json::json;
+
+const CONFIG: &str = r#"
+    [mcp]
+    enabled = true
+
+    [[mcp.prompts]]
+    name = "find_customer"
+    description = "Find a customer by email"
+    template = "Find the customer with the email {{email}} and summarize their last {{ count }} orders."
+
+    [[mcp.prompts.arguments]]
+    name = "email"
+    description = "Email of the customer"
+    required = true
+
+    [[mcp.prompts.arguments]]
+    name = "count"
+"#;
+
+#[test]
+fn list_prompts() {
+    let prompts = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph_sdl("x", "type Query { hello: String }")
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let mut stream = engine.mcp_http("/mcp").await;
+        stream.list_prompts().await
+    });
+
+    insta::assert_json_snapshot!(&prompts, @r#"
+    {
+      "result": {
+        "prompts": [
+          {
+            "name": "find_customer",
+            "description": "Find a customer by email",
+            "arguments": [
+              {
+                "name": "email",
+                "description": "Email of the customer",
+                "required": true
+              },
+              {
+                "name": "count",
+                "required": false
+              }
+            ]
+          }
+        ]
+      }
+    }
+    "#);
+}
+
+#[test]
+fn get_prompt() {
+    let prompt = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph_sdl("x", "type Query { hello: String }")
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let mut stream = engine.mcp_http("/mcp").await;
+        stream
+            .get_prompt("find_customer", json!({"email": "alice@example.com", "count": 3}))
+            .await
+    });
+
+    insta::assert_json_snapshot!(&prompt, @r#"
+    {
+      "result": {
+        "description": "Find a customer by email",
+        "messages": [
+          {
+            "role": "user",
+            "content": {
+              "type": "text",
+              "text": "Find the customer with the email alice@example.com and summarize their last 3 orders."
+            }
+          }
+        ]
+      }
+    }
+    "#);
+}
@@ -0,0 +1,139 @@
+use std::io::Write as _;
+
+use integration_tests::{gateway::Gateway, runtime};
+
+const SCHEMA: &str = r#"
+    type Query {
+        user(id: ID!): User
+    }
+
+    type User {
+        id: ID!
+        name: String!
+    }
+"#;
+
+const OPERATIONS: &str = r#"# Finds a user by id.
+query FindUser($id: ID!) {
+    user(id: $id) {
+        name
+    }
+}
+"#;
+
+#[test]
+fn list_resources() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(OPERATIONS.as_bytes()).unwrap();
+
+    let (resources, templates) = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph_sdl("x", SCHEMA)
+            .with_toml_config(format!(
+                r#"
+                [mcp]
+                enabled = true
+
+                [[mcp.operations]]
+                path = "{}"
+                "#,
+                file.path().display()
+            ))
+            .build()
+            .await;
+
+        let mut stream = engine.mcp_http("/mcp").await;
+        (stream.list_resources().await, stream.list_resource_templates().await)
+    });
+
+    insta::assert_json_snapshot!(&resources, @r#"
+    {
+      "result": {
+        "resources": [
+          {
+            "uri": "graphql://schema",
+            "name": "schema",
+            "description": "The complete GraphQL SDL of the API.",
+            "mimeType": "application/graphql"
+          },
+          {
+            "uri": "graphql://operations/find_user",
+            "name": "find_user",
+            "description": "Finds a user by id.",
+            "mimeType": "application/graphql"
+          }
+        ]
+      }
+    }
+    "#);
+
+    insta::assert_json_snapshot!(&templates, @r#"
+    {
+      "result": {
+        "resourceTemplates": [
+          {
+            "uriTemplate": "graphql://types/{name}",
+            "name": "type",
+            "description": "The GraphQL SDL and documentation of a single type.",
+            "mimeType": "application/graphql"
+          }
+        ]
+      }
+    }
+    "#);
+}
+
+#[test]
+fn read_resources() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(OPERATIONS.as_bytes()).unwrap();
+
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph_sdl("x", SCHEMA)
+            .with_toml_config(format!(
+                r#"
+                [mcp]
+                enabled = true
+
+                [[mcp.operations]]
+                path = "{}"
+                "#,
+                file.path().display()
+            ))
+            .build()
+            .await;
+
+        let mut stream = engine.mcp_http("/mcp").await;
+
+        let response = stream.read_resource("graphql://types/User").await;
+        insta::assert_json_snapshot!(&response, @r#"
+        {
+          "result": {
+            "contents": [
+              {
+                "uri": "graphql://types/User",
+                "mimeType": "text",
+                "text": "type User {\n  id: ID!\n  name: String!\n}"
+              }
+            ]
+          }
+        }
+        "#);
+
+        let response = stream.read_resource("graphql://operations/find_user").await;
+        insta::assert_json_snapshot!(&response, @r##"
+        {
+          "result": {
+            "contents": [
+              {
+                "uri": "graphql://operations/find_user",
+                "mimeType": "text",
+                "text": "# Finds a user by id.\nquery FindUser($id: ID!) {\n    user(id: $id) {\n        name\n    }\n}\n"
+              }
+            ]
+          }
+        }
+        "##);
+    });
+}
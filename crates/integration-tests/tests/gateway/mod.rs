@@ -1,10 +1,12 @@
 mod apq;
 mod basic;
+mod circuit_breaker;
 mod complexity_control;
 mod composite;
 mod compression;
 mod config;
 mod deser;
+mod entity_batching;
 mod entity_caching;
 mod extensions;
 mod graphql_over_http;
@@ -14,7 +16,10 @@ mod issues;
 mod mcp;
 mod message_signing;
 mod mtls;
+mod rate_limiting;
+mod response_caching;
 mod response_extensions;
+mod response_headers;
 mod router;
 mod subgraph_retries;
 mod subgraphs;
@@ -0,0 +1,141 @@
+use integration_tests::{gateway::Gateway, runtime};
+
+use crate::gateway::complexity_control::ComplexitySchema;
+
+const CONFIG: &str = r#"
+[gateway.rate_limit.complexity]
+limit = 150
+duration = "1h"
+key = { header = "x-client-id" }
+"#;
+
+fn header(response: &integration_tests::gateway::GraphqlResponse, name: &str) -> String {
+    response.headers.get(name).unwrap().to_str().unwrap().to_string()
+}
+
+#[test]
+fn complexity_points_are_counted_per_client() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(ComplexitySchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        const QUERY: &str = "query { expensiveField }";
+
+        let response = engine.post(QUERY).header("x-client-id", "a").await;
+        assert_eq!(response.status, 200);
+        assert_eq!(header(&response, "x-ratelimit-limit"), "150");
+        assert_eq!(header(&response, "x-ratelimit-remaining"), "50");
+
+        let response = engine.post(QUERY).header("x-client-id", "a").await;
+        assert_eq!(response.status, 429);
+        assert_eq!(header(&response, "x-ratelimit-remaining"), "50");
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "errors": [
+            {
+              "message": "Rate limited",
+              "extensions": {
+                "code": "RATE_LIMITED"
+              }
+            }
+          ]
+        }
+        "#);
+
+        // Cheaper operations still fit in the remaining budget.
+        let response = engine.post("query { cheapField }").header("x-client-id", "a").await;
+        assert_eq!(response.status, 200);
+        assert_eq!(header(&response, "x-ratelimit-remaining"), "49");
+
+        // Other clients have their own budget.
+        let response = engine.post(QUERY).header("x-client-id", "b").await;
+        assert_eq!(response.status, 200);
+        assert_eq!(header(&response, "x-ratelimit-remaining"), "50");
+    });
+}
+
+#[test]
+fn no_rate_limit_headers_by_default() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder().with_subgraph(ComplexitySchema).build().await;
+
+        let response = engine.post("query { expensiveField }").await;
+
+        assert_eq!(response.status, 200);
+        assert!(response.headers.get("x-ratelimit-remaining").is_none());
+    });
+}
+
+#[test]
+fn rules_are_applied_per_client() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(ComplexitySchema)
+            .with_toml_config(
+                r#"
+                [[gateway.rate_limit.rules]]
+                name = "per-tenant"
+                limit = 1
+                duration = "1h"
+                key = { header = "x-tenant-id" }
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "query { cheapField }";
+
+        let response = engine.post(QUERY).header("x-tenant-id", "a").await;
+        assert_eq!(response.status, 200);
+
+        let response = engine.post(QUERY).header("x-tenant-id", "a").await;
+        assert_eq!(response.status, 429);
+        let retry_after = header(&response, "retry-after").parse::<u64>().unwrap();
+        assert!(retry_after > 0 && retry_after <= 3600);
+
+        let response = engine.post(QUERY).header("x-tenant-id", "b").await;
+        assert_eq!(response.status, 200);
+
+        // Requests without a tenant don't share a single budget.
+        for _ in 0..2 {
+            let response = engine.post(QUERY).await;
+            assert_eq!(response.status, 200);
+        }
+    });
+}
+
+#[test]
+fn rules_can_be_restricted_to_a_subgraph() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(ComplexitySchema)
+            .with_toml_config(
+                r#"
+                [[gateway.rate_limit.rules]]
+                name = "per-tenant-complexity"
+                limit = 1
+                duration = "1h"
+                key = { header = "x-tenant-id" }
+                subgraph = "complexity"
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "query { cheapField }";
+
+        let response = engine.post(QUERY).header("x-tenant-id", "a").await;
+        assert!(response.errors().is_empty());
+
+        // The operation itself isn't limited, only the subgraph request is.
+        let response = engine.post(QUERY).header("x-tenant-id", "a").await;
+        assert_eq!(response.status, 200);
+        assert!(!response.errors().is_empty());
+
+        let response = engine.post(QUERY).header("x-tenant-id", "b").await;
+        assert!(response.errors().is_empty());
+    });
+}
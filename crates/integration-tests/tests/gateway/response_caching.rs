@@ -0,0 +1,426 @@
+use std::time::Duration;
+
+use engine::ErrorResponse;
+use graphql_mocks::{ErrorSchema, FederatedProductsSchema};
+use headers::{CacheControl, HeaderMapExt};
+use integration_tests::{
+    gateway::{AuthenticationExt, AuthenticationTestExtension, Gateway},
+    runtime,
+};
+use runtime::extension::{PublicMetadataEndpoint, Token};
+use serde_json::json;
+use wiremock::{Mock, MockServer, ResponseTemplate, matchers::method};
+
+#[test]
+fn second_request_is_served_from_the_cache() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                ttl = "30s"
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = r"query { topProducts { upc name price } }";
+
+        let first_response = engine.post(QUERY).await;
+        let second_response = engine.post(QUERY).await;
+
+        let cache_control = first_response.headers.typed_get::<CacheControl>().unwrap();
+        assert_eq!(cache_control.max_age(), Some(Duration::from_secs(30)));
+        assert!(cache_control.public());
+
+        let cache_control = second_response.headers.typed_get::<CacheControl>().unwrap();
+        assert!(cache_control.max_age().unwrap() <= Duration::from_secs(30));
+
+        assert_eq!(first_response.into_data(), second_response.into_data());
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+    });
+}
+
+#[test]
+fn disabled_by_default() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .build()
+            .await;
+
+        const QUERY: &str = r"query { topProducts { upc } }";
+
+        let response = engine.post(QUERY).await;
+        engine.post(QUERY).await;
+
+        assert!(response.headers.typed_get::<CacheControl>().is_none());
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    });
+}
+
+#[test]
+fn variables_partition_the_cache() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = r"query ($upc: String!) { product(upc: $upc) { name } }";
+
+        let first = engine.post(QUERY).variables(json!({"upc": "top-1"})).await.into_data();
+        let second = engine.post(QUERY).variables(json!({"upc": "top-2"})).await.into_data();
+        let third = engine.post(QUERY).variables(json!({"upc": "top-1"})).await.into_data();
+
+        assert_ne!(first, second);
+        assert_eq!(first, third);
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    });
+}
+
+#[test]
+fn configured_headers_partition_the_cache() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                key.headers = ["x-tenant"]
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = r"query { topProducts { upc } }";
+
+        engine.post(QUERY).header("x-tenant", "a").await.into_data();
+        engine.post(QUERY).header("x-tenant", "b").await.into_data();
+        engine.post(QUERY).header("x-tenant", "b").await.into_data();
+        // Headers outside of the key don't matter.
+        engine
+            .post(QUERY)
+            .header("x-tenant", "a")
+            .header("x-other", "c")
+            .await
+            .into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    });
+}
+
+#[test]
+fn responses_with_errors_are_not_cached() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(ErrorSchema::default())
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "query { brokenField(error: \"blah\") }";
+
+        let first_response = engine.post(QUERY).await;
+        engine.post(QUERY).await;
+
+        assert!(!first_response.errors().is_empty());
+        assert!(first_response.headers.typed_get::<CacheControl>().unwrap().no_store());
+
+        assert_eq!(engine.drain_graphql_requests_sent_to::<ErrorSchema>().len(), 2);
+    });
+}
+
+async fn mock_subgraph(response: ResponseTemplate) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(response)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn max_age(headers: &http::HeaderMap) -> Option<Duration> {
+    headers.typed_get::<CacheControl>().unwrap().max_age()
+}
+
+#[test]
+fn cache_control_hints_restrict_the_max_age() {
+    runtime().block_on(async move {
+        let mock_server = mock_subgraph(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "short": "short",
+                "long": "long",
+                "obj": { "id": "1" },
+                "inherited": { "id": "2" },
+                "me": "me"
+            }
+        })))
+        .await;
+        let url = mock_server.uri();
+
+        let engine = Gateway::builder()
+            .with_federated_sdl(format!(
+                r#"
+                type Query
+                    @join__type(graph: A)
+                {{
+                    short: String @cacheControl(maxAge: 10)
+                    long: String
+                    obj: Obj
+                    inherited: Obj @cacheControl(inheritMaxAge: true)
+                    me: String @cacheControl(scope: PRIVATE)
+                }}
+
+                type Obj
+                    @join__type(graph: A)
+                    @cacheControl(maxAge: 5)
+                {{
+                    id: ID!
+                }}
+
+                enum join__Graph
+                {{
+                    A @join__graph(name: "a", url: "{url}")
+                }}
+                "#
+            ))
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                ttl = "30s"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post("query { long }").await;
+        assert_eq!(max_age(&response.headers), Some(Duration::from_secs(30)));
+
+        let response = engine.post("query { short long }").await;
+        assert_eq!(max_age(&response.headers), Some(Duration::from_secs(10)));
+
+        let response = engine.post("query { obj { id } }").await;
+        assert_eq!(max_age(&response.headers), Some(Duration::from_secs(5)));
+
+        let response = engine.post("query { inherited { id } }").await;
+        assert_eq!(max_age(&response.headers), Some(Duration::from_secs(30)));
+
+        // Private responses can't be cached for anonymous users.
+        let response = engine.post("query { me }").await;
+        assert!(response.headers.typed_get::<CacheControl>().unwrap().no_store());
+        engine.post("query { me }").await;
+
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 6);
+    });
+}
+
+#[test]
+fn subgraph_cache_control_restricts_the_max_age() {
+    runtime().block_on(async move {
+        let body = json!({ "data": { "id": "abc" } });
+
+        for (cache_control, expected_max_age, expected_requests) in [
+            ("max-age=5", Some(Duration::from_secs(5)), 1),
+            ("public, max-age=3600", Some(Duration::from_secs(30)), 1),
+            ("private, max-age=5", None, 2),
+            ("no-store", None, 2),
+        ] {
+            let mock_server = mock_subgraph(
+                ResponseTemplate::new(200)
+                    .insert_header("cache-control", cache_control)
+                    .set_body_json(&body),
+            )
+            .await;
+            let url = mock_server.uri();
+
+            let engine = Gateway::builder()
+                .with_federated_sdl(format!(
+                    r#"
+                    type Query
+                        @join__type(graph: A)
+                    {{
+                        id: ID!
+                    }}
+
+                    enum join__Graph
+                    {{
+                        A @join__graph(name: "a", url: "{url}")
+                    }}
+                    "#
+                ))
+                .with_toml_config(
+                    r#"
+                    [response_caching]
+                    enabled = true
+                    ttl = "30s"
+                    "#,
+                )
+                .build()
+                .await;
+
+            let response = engine.post("query { id }").await;
+            engine.post("query { id }").await;
+
+            let header = response.headers.typed_get::<CacheControl>().unwrap();
+            assert_eq!(header.max_age(), expected_max_age, "{cache_control}");
+            assert_eq!(header.no_store(), expected_max_age.is_none(), "{cache_control}");
+            assert_eq!(
+                mock_server.received_requests().await.unwrap().len(),
+                expected_requests,
+                "{cache_control}"
+            );
+        }
+    });
+}
+
+#[test]
+fn mutations_bypass_the_cache() {
+    runtime().block_on(async move {
+        let mock_server = mock_subgraph(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "increment": 1 }
+        })))
+        .await;
+        let url = mock_server.uri();
+
+        let engine = Gateway::builder()
+            .with_federated_sdl(format!(
+                r#"
+                type Query
+                    @join__type(graph: A)
+                {{
+                    id: ID!
+                }}
+
+                type Mutation
+                    @join__type(graph: A)
+                {{
+                    increment: Int!
+                }}
+
+                enum join__Graph
+                {{
+                    A @join__graph(name: "a", url: "{url}")
+                }}
+                "#
+            ))
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post("mutation { increment }").await;
+        engine.post("mutation { increment }").await;
+
+        assert!(response.headers.typed_get::<CacheControl>().unwrap().no_store());
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+    });
+}
+
+/// Authenticates with the `sub` claim taken from the `x-user` header.
+struct UserHeaderAuth;
+
+#[async_trait::async_trait]
+impl AuthenticationTestExtension for UserHeaderAuth {
+    async fn authenticate(&self, headers: &http::HeaderMap) -> Result<Token, ErrorResponse> {
+        Ok(match headers.get("x-user").and_then(|value| value.to_str().ok()) {
+            Some(user) => Token::Bytes(serde_json::to_vec(&json!({ "sub": user })).unwrap().into()),
+            None => Token::Anonymous,
+        })
+    }
+
+    async fn public_metadata_endpoints(&self) -> Vec<PublicMetadataEndpoint> {
+        vec![]
+    }
+}
+
+#[test]
+fn authenticated_requests_are_partitioned_on_jwt_claims() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_extension(AuthenticationExt::new(UserHeaderAuth))
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                key.jwt_claims = ["sub"]
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = r"query { topProducts { upc } }";
+
+        engine.post(QUERY).header("x-user", "alice").await.into_data();
+        engine.post(QUERY).header("x-user", "bob").await.into_data();
+        engine.post(QUERY).header("x-user", "alice").await.into_data();
+        engine.post(QUERY).await.into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            3
+        );
+    });
+}
+
+#[test]
+fn authenticated_requests_bypass_the_cache_without_jwt_claims() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_extension(AuthenticationExt::new(UserHeaderAuth))
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = r"query { topProducts { upc } }";
+
+        let response = engine.post(QUERY).header("x-user", "alice").await;
+        engine.post(QUERY).header("x-user", "alice").await;
+
+        assert!(response.headers.typed_get::<CacheControl>().unwrap().no_store());
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    });
+}
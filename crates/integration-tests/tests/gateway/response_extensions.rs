@@ -384,6 +384,138 @@ fn grafbase_extension_on_ill_formed_graphql_over_http_request() {
     })
 }
 
+#[test]
+fn explain_query_plan_without_executing() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FakeGithubSchema::default())
+            .with_toml_config(
+                r#"
+            [telemetry.exporters.response_extension]
+            trace_id = false
+            query_plan = false
+            explain = true
+            "#,
+            )
+            .build()
+            .await;
+
+        let response = engine
+            .post("query { serverVersion }")
+            .header("x-grafbase-telemetry", "yes")
+            .header("x-grafbase-explain", "yes")
+            .await;
+
+        insta::assert_json_snapshot!(response, {
+            ".extensions.grafbase.explain.solverCost" => "[cost]",
+        }, @r#"
+        {
+          "data": null,
+          "extensions": {
+            "grafbase": {
+              "explain": {
+                "solverCost": "[cost]",
+                "fetches": [
+                  {
+                    "id": 0,
+                    "resolver": {
+                      "__typename": "GraphqlResolver",
+                      "subgraphName": "github",
+                      "request": {
+                        "query": "query { serverVersion }"
+                      }
+                    },
+                    "entity": "Query",
+                    "estimatedComplexity": 0,
+                    "dependsOn": []
+                  }
+                ],
+                "dot": "digraph {\n    0 [label=\"Root#github\\nQuery\"];\n}"
+              }
+            }
+          }
+        }
+        "#);
+
+        assert!(engine.drain_graphql_requests_sent_to::<FakeGithubSchema>().is_empty());
+    })
+}
+
+#[test]
+fn explain_mutation_over_sse_without_executing() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(
+                DynamicSchema::builder(
+                    r#"
+                    type Query {
+                        posts: [String!]
+                    }
+
+                    type Mutation {
+                        createPost(title: String!): Int
+                    }
+                    "#,
+                )
+                .with_resolver("Mutation", "createPost", serde_json::json!(1))
+                .into_subgraph("posts"),
+            )
+            .with_toml_config(
+                r#"
+            [telemetry.exporters.response_extension]
+            trace_id = false
+            query_plan = false
+            explain = true
+            "#,
+            )
+            .build()
+            .await;
+
+        let response = engine
+            .post(r#"mutation { createPost(title: "Hello") }"#)
+            .header("x-grafbase-telemetry", "yes")
+            .header("x-grafbase-explain", "yes")
+            .into_sse_stream()
+            .await
+            .collect()
+            .await;
+
+        let [message] = response.messages.as_slice() else {
+            panic!("Expected a single message: {:#?}", response.messages);
+        };
+
+        assert_eq!(message["data"], serde_json::Value::Null);
+        assert_eq!(
+            message["extensions"]["grafbase"]["explain"]["fetches"][0]["resolver"]["subgraphName"],
+            "posts"
+        );
+        assert!(engine.drain_graphql_requests_sent_to_by_name("posts").is_empty());
+    })
+}
+
+#[test]
+fn explain_is_disabled_by_default() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FakeGithubSchema::default())
+            .build()
+            .await;
+
+        let response = engine
+            .post("query { serverVersion }")
+            .header("x-grafbase-explain", "yes")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "#);
+    })
+}
+
 #[test]
 fn complex_query_plan() {
     runtime().block_on(async move {
@@ -0,0 +1,162 @@
+use graphql_mocks::EchoSchema;
+use integration_tests::{gateway::Gateway, runtime};
+
+const QUERY: &str = r#"query { responseHeader(name: "X-Custom", value: "kekw") }"#;
+
+#[test]
+fn subgraph_response_headers_are_not_propagated_by_default() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder().with_subgraph(EchoSchema::default()).build().await;
+
+        let response = engine.post(QUERY).await;
+
+        assert!(response.headers.get("x-custom").is_none());
+    });
+}
+
+#[test]
+fn propagate_subgraph_response_header() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                [[response_headers]]
+                rule = "propagate"
+                name = "x-custom"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+
+        assert_eq!(response.headers.get("x-custom").unwrap(), "kekw");
+    });
+}
+
+#[test]
+fn propagate_subgraph_response_header_with_rename() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                [[response_headers]]
+                rule = "propagate"
+                pattern = "^x-cus"
+                rename = "x-renamed"
+                merge = "append"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+
+        assert!(response.headers.get("x-custom").is_none());
+        assert_eq!(response.headers.get("x-renamed").unwrap(), "kekw");
+    });
+}
+
+#[test]
+fn subgraph_rules_override_global_ones() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                [[response_headers]]
+                rule = "propagate"
+                pattern = ".*"
+
+                [[subgraphs.echo.response_headers]]
+                rule = "remove"
+                name = "x-custom"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+
+        assert!(response.headers.get("x-custom").is_none());
+    });
+}
+
+#[test]
+fn propagated_headers_are_served_from_the_response_cache() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+
+                [[response_headers]]
+                rule = "propagate"
+                name = "x-custom"
+                "#,
+            )
+            .build()
+            .await;
+
+        engine.post(QUERY).await;
+        let response = engine.post(QUERY).await;
+
+        assert_eq!(response.headers.get("x-custom").unwrap(), "kekw");
+        assert_eq!(engine.drain_graphql_requests_sent_to::<EchoSchema>().len(), 1);
+    });
+}
+
+#[test]
+fn responses_propagating_cookies_are_not_cached() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+
+                [[response_headers]]
+                rule = "propagate"
+                name = "set-cookie"
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = r#"query { responseHeader(name: "Set-Cookie", value: "session=1") }"#;
+
+        engine.post(QUERY).await;
+        let response = engine.post(QUERY).await;
+
+        assert_eq!(response.headers.get("set-cookie").unwrap(), "session=1");
+        assert_eq!(engine.drain_graphql_requests_sent_to::<EchoSchema>().len(), 2);
+    });
+}
+
+#[test]
+fn hop_by_hop_headers_are_never_propagated() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                [[response_headers]]
+                rule = "propagate"
+                pattern = ".*"
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = r#"query { responseHeader(name: "Keep-Alive", value: "timeout=5") }"#;
+
+        let response = engine.post(QUERY).await;
+
+        assert!(response.headers.get("keep-alive").is_none());
+    });
+}
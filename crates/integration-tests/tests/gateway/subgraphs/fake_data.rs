@@ -0,0 +1,126 @@
+use graphql_mocks::{Schema as _, dynamic::DynamicSchema};
+use integration_tests::{gateway::Gateway, runtime};
+
+const PRODUCTS_SDL: &str = r#"
+    extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@key"])
+
+    enum Kind {
+        HAT
+        SHOE
+    }
+
+    type Product @key(fields: "id") {
+        id: ID!
+        name: String!
+        kind: Kind!
+    }
+
+    type Query {
+        products: [Product!]!
+    }
+"#;
+
+const REVIEWS_SDL: &str = r#"
+    extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@key"])
+
+    type Product @key(fields: "id") {
+        id: ID!
+        reviews: [Review!]!
+    }
+
+    type Review {
+        rating: Int!
+        body: String
+    }
+"#;
+
+#[test]
+fn fake_data_is_deterministic_and_resolves_entities() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(
+                DynamicSchema::builder(PRODUCTS_SDL)
+                    .with_fake_data()
+                    .into_subgraph("products"),
+            )
+            .with_subgraph(
+                DynamicSchema::builder(REVIEWS_SDL)
+                    .with_fake_data()
+                    .into_subgraph("reviews"),
+            )
+            .build()
+            .await;
+
+        let query = "query { products { id name kind reviews { rating body } } }";
+        let response = engine.post(query).await;
+
+        assert!(response.errors().is_empty(), "{response}");
+
+        let products = response["data"]["products"].as_array().unwrap();
+        assert!(!products.is_empty());
+
+        for product in products {
+            assert!(product["id"].is_string());
+            assert!(product["name"].as_str().unwrap().starts_with("name "));
+            assert!(["HAT", "SHOE"].contains(&product["kind"].as_str().unwrap()));
+
+            let reviews = product["reviews"].as_array().unwrap();
+            assert!(!reviews.is_empty());
+            assert!(reviews.iter().all(|review| review["rating"].is_i64()));
+        }
+
+        assert_eq!(response.into_value(), engine.post(query).await.into_value());
+    });
+}
+
+#[test]
+fn an_entity_gets_the_same_data_whichever_field_reaches_it() {
+    runtime().block_on(async move {
+        let schema = DynamicSchema::builder(PRODUCTS_SDL).with_fake_data().finish();
+
+        let products = schema
+            .execute(
+                Vec::new(),
+                async_graphql::Request::new("query { products { __typename id name kind } }"),
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data
+            .into_json()
+            .unwrap()["products"]
+            .clone();
+
+        let representations = products
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|product| serde_json::json!({ "__typename": "Product", "id": product["id"] }))
+            .collect::<Vec<_>>();
+
+        let request = async_graphql::Request::new(
+            r#"
+            query($representations: [_Any!]!) {
+                _entities(representations: $representations) {
+                    ... on Product { __typename id name kind }
+                }
+            }
+            "#,
+        )
+        .variables(async_graphql::Variables::from_json(
+            serde_json::json!({ "representations": representations }),
+        ));
+
+        let entities = schema
+            .execute(Vec::new(), request)
+            .await
+            .into_result()
+            .unwrap()
+            .data
+            .into_json()
+            .unwrap()["_entities"]
+            .clone();
+
+        assert_eq!(products, entities);
+    });
+}
@@ -0,0 +1,498 @@
+use graphql_mocks::{FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{gateway::Gateway, runtime};
+
+#[test]
+fn defer_fields_from_another_subgraph() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .build()
+            .await;
+
+        let response = engine
+            .post(
+                r#"
+                query {
+                    topProducts {
+                        name
+                        ... @defer(label: "reviews") {
+                            reviews {
+                                body
+                            }
+                        }
+                    }
+                }
+                "#,
+            )
+            .into_multipart_stream()
+            .await
+            .collect()
+            .await;
+
+        insta::assert_json_snapshot!(response.messages, @r###"
+        [
+          {
+            "data": {
+              "topProducts": [
+                {
+                  "name": "Trilby"
+                },
+                {
+                  "name": "Fedora"
+                },
+                {
+                  "name": "Boater"
+                },
+                {
+                  "name": "Jeans"
+                },
+                {
+                  "name": "Pink Jeans"
+                }
+              ]
+            },
+            "hasNext": true
+          },
+          {
+            "incremental": [
+              {
+                "data": {
+                  "reviews": [
+                    {
+                      "body": "A highly effective form of birth control."
+                    }
+                  ]
+                },
+                "path": [
+                  "topProducts",
+                  0
+                ],
+                "label": "reviews"
+              },
+              {
+                "data": {
+                  "reviews": [
+                    {
+                      "body": "Fedoras are one of the most fashionable hats around and can look great with a variety of outfits."
+                    }
+                  ]
+                },
+                "path": [
+                  "topProducts",
+                  1
+                ],
+                "label": "reviews"
+              },
+              {
+                "data": {
+                  "reviews": [
+                    {
+                      "body": "This is the last straw. Hat you will wear. 11/10"
+                    }
+                  ]
+                },
+                "path": [
+                  "topProducts",
+                  2
+                ],
+                "label": "reviews"
+              },
+              {
+                "data": {
+                  "reviews": []
+                },
+                "path": [
+                  "topProducts",
+                  3
+                ],
+                "label": "reviews"
+              },
+              {
+                "data": {
+                  "reviews": [
+                    {
+                      "body": "Beautiful Pink, my parrot loves it. Definitely recommend!"
+                    }
+                  ]
+                },
+                "path": [
+                  "topProducts",
+                  4
+                ],
+                "label": "reviews"
+              }
+            ],
+            "hasNext": true
+          },
+          {
+            "hasNext": false
+          }
+        ]
+        "###);
+    })
+}
+
+#[test]
+fn defer_is_ignored_when_disabled() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .build()
+            .await;
+
+        let response = engine
+            .post(
+                r#"
+                query {
+                    topProducts {
+                        name
+                        ... @defer(if: false) {
+                            price
+                        }
+                    }
+                }
+                "#,
+            )
+            .into_multipart_stream()
+            .await
+            .collect()
+            .await;
+
+        insta::assert_json_snapshot!(response.messages, @r###"
+        [
+          {
+            "data": {
+              "topProducts": [
+                {
+                  "name": "Trilby",
+                  "price": 11
+                },
+                {
+                  "name": "Fedora",
+                  "price": 22
+                },
+                {
+                  "name": "Boater",
+                  "price": 33
+                },
+                {
+                  "name": "Jeans",
+                  "price": 44
+                },
+                {
+                  "name": "Pink Jeans",
+                  "price": 55
+                }
+              ]
+            }
+          }
+        ]
+        "###);
+    })
+}
+
+#[test]
+fn stream_list() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .build()
+            .await;
+
+        let response = engine
+            .post(
+                r#"
+                query {
+                    topProducts @stream(initialCount: 2, label: "products") {
+                        name
+                    }
+                }
+                "#,
+            )
+            .into_multipart_stream()
+            .await
+            .collect()
+            .await;
+
+        insta::assert_json_snapshot!(response.messages, @r###"
+        [
+          {
+            "data": {
+              "topProducts": [
+                {
+                  "name": "Trilby"
+                },
+                {
+                  "name": "Fedora"
+                }
+              ]
+            },
+            "hasNext": true
+          },
+          {
+            "incremental": [
+              {
+                "items": [
+                  {
+                    "name": "Boater"
+                  },
+                  {
+                    "name": "Jeans"
+                  },
+                  {
+                    "name": "Pink Jeans"
+                  }
+                ],
+                "path": [
+                  "topProducts",
+                  2
+                ],
+                "label": "products"
+              }
+            ],
+            "hasNext": true
+          },
+          {
+            "hasNext": false
+          }
+        ]
+        "###);
+    })
+}
+
+#[test]
+fn defer_nested_fields_from_the_same_subgraph() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .build()
+            .await;
+
+        let response = engine
+            .post(
+                r#"
+                query {
+                    topProducts {
+                        name
+                        ... @defer(label: "price") {
+                            price
+                        }
+                    }
+                }
+                "#,
+            )
+            .into_multipart_stream()
+            .await
+            .collect()
+            .await;
+
+        insta::assert_json_snapshot!(response.messages, @r###"
+        [
+          {
+            "data": {
+              "topProducts": [
+                {
+                  "name": "Trilby"
+                },
+                {
+                  "name": "Fedora"
+                },
+                {
+                  "name": "Boater"
+                },
+                {
+                  "name": "Jeans"
+                },
+                {
+                  "name": "Pink Jeans"
+                }
+              ]
+            },
+            "hasNext": true
+          },
+          {
+            "incremental": [
+              {
+                "data": {
+                  "price": 11
+                },
+                "path": [
+                  "topProducts",
+                  0
+                ],
+                "label": "price"
+              },
+              {
+                "data": {
+                  "price": 22
+                },
+                "path": [
+                  "topProducts",
+                  1
+                ],
+                "label": "price"
+              },
+              {
+                "data": {
+                  "price": 33
+                },
+                "path": [
+                  "topProducts",
+                  2
+                ],
+                "label": "price"
+              },
+              {
+                "data": {
+                  "price": 44
+                },
+                "path": [
+                  "topProducts",
+                  3
+                ],
+                "label": "price"
+              },
+              {
+                "data": {
+                  "price": 55
+                },
+                "path": [
+                  "topProducts",
+                  4
+                ],
+                "label": "price"
+              }
+            ],
+            "hasNext": true
+          },
+          {
+            "hasNext": false
+          }
+        ]
+        "###);
+    })
+}
+
+#[test]
+fn stream_list_over_sse() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .build()
+            .await;
+
+        let response = engine
+            .post(
+                r#"
+                query {
+                    topProducts @stream(initialCount: 4) {
+                        name
+                    }
+                }
+                "#,
+            )
+            .into_sse_stream()
+            .await
+            .collect()
+            .await;
+
+        insta::assert_json_snapshot!(response.messages, @r###"
+        [
+          {
+            "data": {
+              "topProducts": [
+                {
+                  "name": "Trilby"
+                },
+                {
+                  "name": "Fedora"
+                },
+                {
+                  "name": "Boater"
+                },
+                {
+                  "name": "Jeans"
+                }
+              ]
+            },
+            "hasNext": true
+          },
+          {
+            "incremental": [
+              {
+                "items": [
+                  {
+                    "name": "Pink Jeans"
+                  }
+                ],
+                "path": [
+                  "topProducts",
+                  4
+                ]
+              }
+            ],
+            "hasNext": true
+          },
+          {
+            "hasNext": false
+          }
+        ]
+        "###);
+    })
+}
+
+#[test]
+fn stream_with_negative_initial_count() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .build()
+            .await;
+
+        let response = engine
+            .post("query { topProducts @stream(initialCount: -1) { name } }")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Argument 'initialCount' of directive '@stream' must be non-negative, found -1.",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 29
+                }
+              ],
+              "extensions": {
+                "code": "OPERATION_VALIDATION_ERROR"
+              }
+            }
+          ]
+        }
+        "###);
+
+        let response = engine
+            .post("query($count: Int!) { topProducts @stream(initialCount: $count) { name } }")
+            .variables(serde_json::json!({ "count": -1 }))
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Argument 'initialCount' of directive '@stream' must be non-negative, found -1.",
+              "extensions": {
+                "code": "OPERATION_VALIDATION_ERROR"
+              }
+            }
+          ]
+        }
+        "###);
+    })
+}
@@ -1,6 +1,9 @@
+mod fake_data;
+mod incremental_delivery;
 mod interface_object;
 mod not_reachable;
 mod overrride;
+pub(crate) mod progressive_override;
 mod provides;
 mod requires;
 mod shared_root;
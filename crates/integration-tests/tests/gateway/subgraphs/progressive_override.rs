@@ -0,0 +1,101 @@
+use graphql_mocks::dynamic::{DynamicSchema, EntityResolverContext};
+use integration_tests::{
+    gateway::{Gateway, GatewayBuilder, GraphqlResponse},
+    runtime,
+};
+use serde_json::json;
+
+/// Queries a product whose price, 10 in subgraph `a`, is overridden by subgraph `b` with the
+/// given label to 20.
+pub(crate) async fn execute_overridden_product_query(gateway: GatewayBuilder, label: &str) -> GraphqlResponse {
+    let gateway = gateway
+        .with_subgraph(
+            DynamicSchema::builder(
+                r#"
+                extend schema
+                  @link(url: "https://specs.apollo.dev/federation/v2.7", import: ["@key"])
+
+                type Query {
+                    product: Product!
+                }
+
+                type Product @key(fields: "id") {
+                    id: ID!
+                    price: Int!
+                }
+                "#,
+            )
+            .with_resolver("Query", "product", json!({"id": "1", "price": 10}))
+            .into_subgraph("a"),
+        )
+        .with_subgraph(
+            DynamicSchema::builder(format!(
+                r#"
+                extend schema
+                  @link(url: "https://specs.apollo.dev/federation/v2.7", import: ["@key", "@override"])
+
+                type Product @key(fields: "id") {{
+                    id: ID!
+                    price: Int! @override(from: "a", label: "{label}")
+                }}
+                "#
+            ))
+            .with_entity_resolver("Product", |_: EntityResolverContext<'_>| {
+                Some(json!({"id": "1", "price": 20}))
+            })
+            .into_subgraph("b"),
+        )
+        .build()
+        .await;
+
+    gateway.post("query { product { id price } }").await
+}
+
+async fn execute_with_label(label: &str) -> GraphqlResponse {
+    execute_overridden_product_query(Gateway::builder(), label).await
+}
+
+#[test]
+fn percent_100_always_uses_the_overriding_subgraph() {
+    let response = runtime().block_on(execute_with_label("percent(100)"));
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "product": {
+          "id": "1",
+          "price": 20
+        }
+      }
+    }
+    "#);
+}
+
+#[test]
+fn percent_0_always_uses_the_overridden_subgraph() {
+    let response = runtime().block_on(execute_with_label("percent(0)"));
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "product": {
+          "id": "1",
+          "price": 10
+        }
+      }
+    }
+    "#);
+}
+
+#[test]
+fn custom_label_is_disabled_by_default() {
+    let response = runtime().block_on(execute_with_label("my-label"));
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "product": {
+          "id": "1",
+          "price": 10
+        }
+      }
+    }
+    "#);
+}
@@ -1,6 +1,8 @@
 #![deny(unused_crate_dependencies)]
 use grafbase_workspace_hack as _;
 
+mod prompts;
+mod resources;
 mod server;
 mod tools;
 
@@ -15,7 +17,10 @@ use engine::{ContractAwareEngine, Runtime};
 use gateway_config::ModelControlProtocolConfig;
 use rmcp::transport::{
     sse_server::{SseServer, SseServerConfig},
-    streamable_http_server::{StreamableHttpServerConfig, StreamableHttpService, session::never::NeverSessionManager},
+    streamable_http_server::{
+        StreamableHttpServerConfig, StreamableHttpService,
+        session::{local::LocalSessionManager, never::NeverSessionManager},
+    },
 };
 use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
@@ -28,20 +33,32 @@ pub fn router<R: Runtime>(
 ) -> (Router, Option<CancellationToken>) {
     match config.transport {
         gateway_config::McpTransport::StreamingHttp => {
-            let execute_mutations = config.execute_mutations;
+            let mcp_server = server::McpServer::new(engine.clone(), config).unwrap();
 
-            let mcp_server = server::McpServer::new(engine.clone(), execute_mutations).unwrap();
+            let server_config = StreamableHttpServerConfig {
+                sse_keep_alive: Some(Duration::from_secs(5)),
+                stateful_mode: config.resource_subscriptions,
+            };
 
-            let service = StreamableHttpService::new(
-                move || Ok(mcp_server.clone()),
-                Arc::new(NeverSessionManager::default()),
-                StreamableHttpServerConfig {
-                    sse_keep_alive: Some(Duration::from_secs(5)),
-                    stateful_mode: false,
-                },
-            );
+            // Sessions must be kept for resource subscriptions: the notifications are sent
+            // later on the session's standalone SSE stream.
+            let router = if config.resource_subscriptions {
+                let service = StreamableHttpService::new(
+                    move || Ok(mcp_server.new_session()),
+                    Arc::new(LocalSessionManager::default()),
+                    server_config,
+                );
+                Router::new().route_service(&config.path, service)
+            } else {
+                let service = StreamableHttpService::new(
+                    move || Ok(mcp_server.new_session()),
+                    Arc::new(NeverSessionManager::default()),
+                    server_config,
+                );
+                Router::new().route_service(&config.path, service)
+            };
 
-            (Router::new().route_service(&config.path, service), None)
+            (router, None)
         }
         gateway_config::McpTransport::Sse => {
             let (sse_server, router) = SseServer::new(SseServerConfig {
@@ -53,10 +70,8 @@ pub fn router<R: Runtime>(
                 sse_keep_alive: Some(Duration::from_secs(5)),
             });
 
-            let execute_mutations = config.execute_mutations;
-
-            let mcp_server = server::McpServer::new(engine.clone(), execute_mutations).unwrap();
-            let ct = sse_server.with_service(move || mcp_server.clone());
+            let mcp_server = server::McpServer::new(engine.clone(), config).unwrap();
+            let ct = sse_server.with_service(move || mcp_server.new_session());
 
             (router, Some(ct))
         }
@@ -0,0 +1,111 @@
+use gateway_config::McpPromptConfig;
+use rmcp::model::{
+    ErrorCode, ErrorData, GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+};
+
+/// A prompt template from the configuration, arguments are inserted with `{{argument_name}}`.
+pub(crate) struct PromptTemplate {
+    config: McpPromptConfig,
+}
+
+impl PromptTemplate {
+    pub(crate) fn load_all(configs: &[McpPromptConfig]) -> anyhow::Result<Vec<Self>> {
+        let mut prompts: Vec<Self> = Vec::with_capacity(configs.len());
+
+        for config in configs {
+            if prompts.iter().any(|prompt| prompt.name() == config.name) {
+                anyhow::bail!("Multiple MCP prompts are named '{}'", config.name);
+            }
+
+            for placeholder in placeholders(&config.template) {
+                if !config.arguments.iter().any(|argument| argument.name == placeholder) {
+                    anyhow::bail!(
+                        "The MCP prompt '{}' uses the undeclared argument '{placeholder}'",
+                        config.name
+                    );
+                }
+            }
+
+            prompts.push(Self { config: config.clone() });
+        }
+
+        Ok(prompts)
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub(crate) fn to_prompt(&self) -> Prompt {
+        let arguments = self
+            .config
+            .arguments
+            .iter()
+            .map(|argument| PromptArgument {
+                name: argument.name.clone(),
+                title: None,
+                description: argument.description.clone(),
+                required: Some(argument.required),
+            })
+            .collect::<Vec<_>>();
+
+        Prompt::new(
+            &self.config.name,
+            self.config.description.as_deref(),
+            (!arguments.is_empty()).then_some(arguments),
+        )
+    }
+
+    pub(crate) fn render(&self, arguments: Option<JsonObject>) -> Result<GetPromptResult, ErrorData> {
+        let arguments = arguments.unwrap_or_default();
+
+        for argument in &self.config.arguments {
+            if argument.required && !arguments.contains_key(&argument.name) {
+                return Err(ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("Missing required argument '{}'", argument.name),
+                    None,
+                ));
+            }
+        }
+
+        let mut text = String::with_capacity(self.config.template.len());
+        let mut rest = self.config.template.as_str();
+        while let Some((before, name, after)) = next_placeholder(rest) {
+            text.push_str(before);
+            match arguments.get(name) {
+                Some(serde_json::Value::String(value)) => text.push_str(value),
+                Some(serde_json::Value::Null) | None => {}
+                Some(value) => text.push_str(&value.to_string()),
+            }
+            rest = after;
+        }
+        text.push_str(rest);
+
+        Ok(GetPromptResult {
+            description: self.config.description.clone(),
+            messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+        })
+    }
+}
+
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    let mut rest = template;
+    std::iter::from_fn(move || {
+        let (_, name, after) = next_placeholder(rest)?;
+        rest = after;
+        Some(name)
+    })
+}
+
+/// Splits the template around the next `{{name}}`, returning the text before it, the trimmed
+/// name and the text after it.
+fn next_placeholder(template: &str) -> Option<(&str, &str, &str)> {
+    let start = template.find("{{")?;
+    let end = template[start..].find("}}")? + start;
+    Some((
+        &template[..start],
+        template[start + 2..end].trim(),
+        &template[end + 2..],
+    ))
+}
@@ -0,0 +1,287 @@
+use std::sync::{Arc, Mutex, Weak};
+
+use engine::Schema;
+use futures::future::BoxFuture;
+use fxhash::FxHashSet;
+use http::request::Parts;
+use rmcp::{
+    Peer, RoleServer,
+    model::{
+        AnnotateAble as _, ErrorCode, ErrorData, RawResource, RawResourceTemplate, ReadResourceResult, Resource,
+        ResourceContents, ResourceTemplate, ResourceUpdatedNotificationParam,
+    },
+};
+use serde_json::json;
+use tokio_stream::{StreamExt as _, wrappers::WatchStream};
+
+use crate::EngineWatcher;
+
+const SCHEMA_URI: &str = "graphql://schema";
+const TYPE_URI_PREFIX: &str = "graphql://types/";
+const OPERATION_URI_PREFIX: &str = "graphql://operations/";
+const GRAPHQL_MIME_TYPE: &str = "application/graphql";
+
+/// The GraphQL schema, its types and the configured operations exposed as MCP resources.
+pub(crate) struct Resources {
+    schemas: Box<dyn SchemaSource>,
+    operations: Vec<OperationDocument>,
+}
+
+pub(crate) struct OperationDocument {
+    pub name: String,
+    pub description: String,
+    pub content: String,
+}
+
+trait SchemaSource: Send + Sync + 'static {
+    fn get(&self, parts: Parts) -> BoxFuture<'_, Result<Arc<Schema>, ErrorData>>;
+}
+
+impl<R: engine::Runtime> SchemaSource for EngineWatcher<R> {
+    fn get(&self, parts: Parts) -> BoxFuture<'_, Result<Arc<Schema>, ErrorData>> {
+        let engine = self.borrow().clone();
+        Box::pin(async move {
+            engine
+                .get_schema(&parts)
+                .await
+                .map_err(|err| ErrorData::new(ErrorCode::INTERNAL_ERROR, err.into_owned(), None))
+        })
+    }
+}
+
+impl Resources {
+    pub(crate) fn new(engine: &EngineWatcher<impl engine::Runtime>, operations: Vec<OperationDocument>) -> Self {
+        Self {
+            schemas: Box::new(engine.clone()),
+            operations,
+        }
+    }
+
+    pub(crate) fn list(&self) -> Vec<Resource> {
+        let mut schema = RawResource::new(SCHEMA_URI, "schema");
+        schema.description = Some("The complete GraphQL SDL of the API.".into());
+        schema.mime_type = Some(GRAPHQL_MIME_TYPE.into());
+
+        std::iter::once(schema)
+            .chain(self.operations.iter().map(|operation| {
+                let mut resource =
+                    RawResource::new(format!("{OPERATION_URI_PREFIX}{}", operation.name), &operation.name);
+                resource.description = Some(operation.description.clone());
+                resource.mime_type = Some(GRAPHQL_MIME_TYPE.into());
+                resource
+            }))
+            .map(|resource| resource.no_annotation())
+            .collect()
+    }
+
+    pub(crate) fn templates(&self) -> Vec<ResourceTemplate> {
+        let template: RawResourceTemplate = serde_json::from_value(json!({
+            "uriTemplate": format!("{TYPE_URI_PREFIX}{{name}}"),
+            "name": "type",
+            "description": "The GraphQL SDL and documentation of a single type.",
+            "mimeType": GRAPHQL_MIME_TYPE,
+        }))
+        .expect("valid resource template");
+
+        vec![template.no_annotation()]
+    }
+
+    pub(crate) async fn read(&self, parts: Parts, uri: String) -> Result<ReadResourceResult, ErrorData> {
+        let text = if uri == SCHEMA_URI {
+            self.schemas.get(parts).await?.to_sdl()
+        } else if let Some(name) = uri.strip_prefix(TYPE_URI_PREFIX) {
+            let schema = self.schemas.get(parts).await?;
+            match schema.type_definition_by_name(name) {
+                Some(definition) if !definition.is_inaccessible() => definition.to_string(),
+                _ => return Err(not_found(&uri)),
+            }
+        } else if let Some(name) = uri.strip_prefix(OPERATION_URI_PREFIX)
+            && let Some(operation) = self.operations.iter().find(|operation| operation.name == name)
+        {
+            operation.content.clone()
+        } else {
+            return Err(not_found(&uri));
+        };
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(text, uri)],
+        })
+    }
+}
+
+fn not_found(uri: &str) -> ErrorData {
+    ErrorData::new(ErrorCode::RESOURCE_NOT_FOUND, format!("Unknown resource '{uri}'"), None)
+}
+
+/// Resources subscribed to by a single MCP session.
+#[derive(Default)]
+pub(crate) struct Subscriptions {
+    peer: Mutex<Option<Peer<RoleServer>>>,
+    uris: Mutex<FxHashSet<String>>,
+}
+
+impl Subscriptions {
+    pub(crate) fn subscribe(&self, peer: Peer<RoleServer>, uri: String) {
+        *self.peer.lock().unwrap() = Some(peer);
+        self.uris.lock().unwrap().insert(uri);
+    }
+
+    pub(crate) fn unsubscribe(&self, uri: &str) {
+        self.uris.lock().unwrap().remove(uri);
+    }
+}
+
+/// Keeps track of the sessions to notify them when a new schema is loaded.
+#[derive(Default)]
+pub(crate) struct SubscriptionRegistry {
+    sessions: Mutex<Vec<Weak<Subscriptions>>>,
+}
+
+impl SubscriptionRegistry {
+    pub(crate) fn register(&self, subscriptions: &Arc<Subscriptions>) {
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|session| session.strong_count() > 0);
+        sessions.push(Arc::downgrade(subscriptions));
+    }
+
+    /// Notifies the subscribers of schema resources whenever the engine is reloaded with a
+    /// different schema.
+    pub(crate) fn watch_schema_updates<R: engine::Runtime>(self: &Arc<Self>, engine: &EngineWatcher<R>) {
+        let registry = Arc::downgrade(self);
+        let mut current_hash = engine.borrow().no_contract.schema.hash;
+        let mut stream = WatchStream::from_changes(engine.clone());
+
+        tokio::spawn(async move {
+            while let Some(engine) = stream.next().await {
+                if engine.no_contract.schema.hash == current_hash {
+                    continue;
+                }
+                current_hash = engine.no_contract.schema.hash;
+
+                let Some(registry) = registry.upgrade() else {
+                    break;
+                };
+                registry.notify_schema_update().await;
+            }
+        });
+    }
+
+    async fn notify_schema_update(&self) {
+        let sessions = {
+            let mut sessions = self.sessions.lock().unwrap();
+            sessions.retain(|session| session.strong_count() > 0);
+            sessions.iter().filter_map(Weak::upgrade).collect::<Vec<_>>()
+        };
+
+        for session in sessions {
+            let Some(peer) = session.peer.lock().unwrap().clone() else {
+                continue;
+            };
+            let uris = session
+                .uris
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|uri| *uri == SCHEMA_URI || uri.starts_with(TYPE_URI_PREFIX))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            for uri in uris {
+                if let Err(err) = peer
+                    .notify_resource_updated(ResourceUpdatedNotificationParam { uri })
+                    .await
+                {
+                    tracing::debug!("Failed to notify MCP client of a resource update: {err}");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rmcp::{
+        ClientHandler, RoleClient, ServerHandler, ServiceExt as _,
+        model::ResourceUpdatedNotificationParam,
+        service::{NotificationContext, RunningService},
+    };
+    use tokio::sync::mpsc;
+
+    use super::{SCHEMA_URI, SubscriptionRegistry, Subscriptions};
+
+    struct Server;
+
+    impl ServerHandler for Server {}
+
+    struct Client(mpsc::UnboundedSender<String>);
+
+    impl ClientHandler for Client {
+        async fn on_resource_updated(
+            &self,
+            ResourceUpdatedNotificationParam { uri }: ResourceUpdatedNotificationParam,
+            _: NotificationContext<RoleClient>,
+        ) {
+            self.0.send(uri).unwrap();
+        }
+    }
+
+    async fn connect() -> (
+        RunningService<rmcp::RoleServer, Server>,
+        RunningService<RoleClient, Client>,
+        mpsc::UnboundedReceiver<String>,
+    ) {
+        let (client_sender, server_receiver) = futures::channel::mpsc::unbounded();
+        let (server_sender, client_receiver) = futures::channel::mpsc::unbounded();
+        let (updates_sender, updates) = mpsc::unbounded_channel();
+
+        let (server, client) = tokio::join!(
+            Server.serve((server_sender, server_receiver)),
+            Client(updates_sender).serve((client_sender, client_receiver)),
+        );
+
+        (server.unwrap(), client.unwrap(), updates)
+    }
+
+    #[tokio::test]
+    async fn subscribers_are_notified_of_schema_updates() {
+        let (server, _client, mut updates) = connect().await;
+        let registry = SubscriptionRegistry::default();
+
+        let subscriptions = Arc::new(Subscriptions::default());
+        registry.register(&subscriptions);
+        subscriptions.subscribe(server.peer().clone(), SCHEMA_URI.to_string());
+        subscriptions.subscribe(server.peer().clone(), "graphql://types/User".to_string());
+        subscriptions.subscribe(server.peer().clone(), "graphql://operations/GetUser".to_string());
+
+        registry.notify_schema_update().await;
+
+        let mut notified = vec![updates.recv().await.unwrap(), updates.recv().await.unwrap()];
+        notified.sort();
+        assert_eq!(notified, ["graphql://schema", "graphql://types/User"]);
+
+        // Operations don't depend on the schema.
+        subscriptions.unsubscribe(SCHEMA_URI);
+        subscriptions.unsubscribe("graphql://types/User");
+        registry.notify_schema_update().await;
+        assert!(updates.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn closed_sessions_are_not_notified() {
+        let (server, _client, mut updates) = connect().await;
+        let registry = SubscriptionRegistry::default();
+
+        let subscriptions = Arc::new(Subscriptions::default());
+        registry.register(&subscriptions);
+        subscriptions.subscribe(server.peer().clone(), SCHEMA_URI.to_string());
+        drop(subscriptions);
+
+        registry.notify_schema_update().await;
+
+        assert!(updates.try_recv().is_err());
+        assert!(registry.sessions.lock().unwrap().is_empty());
+    }
+}
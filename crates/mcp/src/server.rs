@@ -2,47 +2,123 @@ use std::sync::Arc;
 
 use crate::{
     EngineWatcher,
-    tools::{ExecuteTool, IntrospectTool, RmcpTool, SearchTool},
+    prompts::PromptTemplate,
+    resources::{OperationDocument, Resources, SubscriptionRegistry, Subscriptions},
+    tools::{ExecuteTool, IntrospectTool, OperationTool, RmcpTool, SearchTool, request_parts},
 };
+use gateway_config::{McpTools, ModelControlProtocolConfig};
 use rmcp::{
     RoleServer, ServerHandler,
     model::{
-        CallToolRequestParam, CallToolResult, ErrorCode, ErrorData, Implementation, ListToolsResult,
-        PaginatedRequestParam, ProtocolVersion, ServerCapabilities, ServerInfo,
+        CallToolRequestParam, CallToolResult, ErrorCode, ErrorData, GetPromptRequestParam, GetPromptResult,
+        Implementation, ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, ListToolsResult,
+        PaginatedRequestParam, ProtocolVersion, ReadResourceRequestParam, ReadResourceResult, ServerCapabilities,
+        ServerInfo, SubscribeRequestParam, UnsubscribeRequestParam,
     },
     service::RequestContext,
 };
 
+/// One instance is created for each MCP session, sharing everything but its resource
+/// subscriptions.
 #[derive(Clone)]
-pub(crate) struct McpServer(Arc<McpServerInner>);
+pub(crate) struct McpServer {
+    inner: Arc<McpServerInner>,
+    subscriptions: Arc<Subscriptions>,
+}
 
 pub(crate) struct McpServerInner {
     info: ServerInfo,
     tools: Vec<Box<dyn RmcpTool>>,
+    resources: Resources,
+    prompts: Vec<PromptTemplate>,
+    registry: Arc<SubscriptionRegistry>,
 }
 
 impl std::ops::Deref for McpServer {
     type Target = McpServerInner;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.inner
     }
 }
 
 impl McpServer {
-    pub(crate) fn new(engine: EngineWatcher<impl engine::Runtime>, execute_mutations: bool) -> anyhow::Result<Self> {
-        Ok(Self(Arc::new(McpServerInner {
-            info: ServerInfo {
-                protocol_version: ProtocolVersion::LATEST,
-                capabilities: ServerCapabilities::builder().enable_tools().build(),
-                server_info: Implementation::from_build_env(),
-                instructions: None,
-            },
-            tools: vec![
-                Box::new(IntrospectTool::new(&engine)),
-                Box::new(SearchTool::new(&engine, execute_mutations)?),
-                Box::new(ExecuteTool::new(&engine, execute_mutations)),
-            ],
-        })))
+    pub(crate) fn new(
+        engine: EngineWatcher<impl engine::Runtime>,
+        config: &ModelControlProtocolConfig,
+    ) -> anyhow::Result<Self> {
+        let execute_mutations = config.execute_mutations;
+        let mut tools: Vec<Box<dyn RmcpTool>> = Vec::new();
+
+        if config.tools == McpTools::All {
+            tools.push(Box::new(IntrospectTool::new(&engine)));
+            tools.push(Box::new(SearchTool::new(&engine, execute_mutations)?));
+            tools.push(Box::new(ExecuteTool::new(&engine, execute_mutations)));
+        }
+
+        let mut operations = Vec::new();
+        for tool in OperationTool::load_all(
+            &engine,
+            execute_mutations,
+            config.client_name.as_deref(),
+            &config.operations,
+        )? {
+            if tools.iter().any(|existing| existing.name() == tool.name()) {
+                anyhow::bail!("The operation tool '{}' conflicts with a built-in tool", tool.name());
+            }
+            operations.push(OperationDocument {
+                name: tool.name().to_string(),
+                description: tool.description().to_string(),
+                content: tool.document().to_string(),
+            });
+            tools.push(Box::new(tool));
+        }
+
+        let registry = Arc::new(SubscriptionRegistry::default());
+        if config.resource_subscriptions {
+            registry.watch_schema_updates(&engine);
+        }
+
+        let capabilities = if config.resource_subscriptions {
+            ServerCapabilities::builder()
+                .enable_prompts()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .enable_tools()
+                .build()
+        } else {
+            ServerCapabilities::builder()
+                .enable_prompts()
+                .enable_resources()
+                .enable_tools()
+                .build()
+        };
+
+        Ok(Self {
+            inner: Arc::new(McpServerInner {
+                info: ServerInfo {
+                    protocol_version: ProtocolVersion::LATEST,
+                    capabilities,
+                    server_info: Implementation::from_build_env(),
+                    instructions: None,
+                },
+                tools,
+                resources: Resources::new(&engine, operations),
+                prompts: PromptTemplate::load_all(&config.prompts)?,
+                registry,
+            }),
+            subscriptions: Default::default(),
+        })
+    }
+
+    /// Creates the server handling a new MCP session.
+    pub(crate) fn new_session(&self) -> Self {
+        let subscriptions = Arc::new(Subscriptions::default());
+        self.registry.register(&subscriptions);
+
+        Self {
+            inner: self.inner.clone(),
+            subscriptions,
+        }
     }
 }
 
@@ -77,4 +153,79 @@ impl ServerHandler for McpServer {
             None,
         ))
     }
+
+    async fn list_resources(
+        &self,
+        _: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        Ok(ListResourcesResult {
+            next_cursor: None,
+            resources: self.resources.list(),
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, ErrorData> {
+        Ok(ListResourceTemplatesResult {
+            next_cursor: None,
+            resource_templates: self.resources.templates(),
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        mut ctx: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        self.resources.read(request_parts(&mut ctx), uri).await
+    }
+
+    async fn subscribe(
+        &self,
+        SubscribeRequestParam { uri }: SubscribeRequestParam,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        self.subscriptions.subscribe(ctx.peer, uri);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        UnsubscribeRequestParam { uri }: UnsubscribeRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        self.subscriptions.unsubscribe(&uri);
+        Ok(())
+    }
+
+    async fn list_prompts(
+        &self,
+        _: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        Ok(ListPromptsResult {
+            next_cursor: None,
+            prompts: self.prompts.iter().map(PromptTemplate::to_prompt).collect(),
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParam { name, arguments }: GetPromptRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        if let Some(prompt) = self.prompts.iter().find(|prompt| prompt.name() == name) {
+            return prompt.render(arguments);
+        }
+
+        Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("Unknown prompt '{name}'"),
+            None,
+        ))
+    }
 }
@@ -4,7 +4,6 @@ use engine::mcp::{McpRequestContext, McpResponseExtension};
 use engine_operation::RawVariables;
 use http::request::Parts;
 use rmcp::model::{CallToolResult, Content};
-use serde::Serialize as _;
 
 use super::{Tool, sdl::PartialSdl};
 use crate::EngineWatcher;
@@ -66,7 +65,7 @@ impl<R: engine::Runtime> Tool for ExecuteTool<R> {
     }
 
     async fn call(&self, parts: Parts, parameters: Self::Parameters) -> anyhow::Result<CallToolResult> {
-        let EngineResponse { json, mcp } = self.execute(parts, parameters).await?;
+        let EngineResponse { json, mcp } = execute(&self.engine, self.execute_mutations, parts, parameters).await?;
         let mut content = vec![Content::text(String::from_utf8(json).unwrap())];
         if let Some(McpResponseExtension { schema, mut site_ids }) = mcp
             && !site_ids.is_empty()
@@ -96,9 +95,9 @@ impl<R: engine::Runtime> Tool for ExecuteTool<R> {
     }
 }
 
-struct EngineResponse {
-    json: Vec<u8>,
-    mcp: Option<McpResponseExtension>,
+pub(super) struct EngineResponse {
+    pub json: Vec<u8>,
+    pub mcp: Option<McpResponseExtension>,
 }
 
 impl<R: engine::Runtime> ExecuteTool<R> {
@@ -108,34 +107,38 @@ impl<R: engine::Runtime> ExecuteTool<R> {
             execute_mutations,
         }
     }
+}
 
-    async fn execute(&self, mut parts: Parts, request: Request) -> anyhow::Result<EngineResponse> {
-        let engine = self.engine.borrow().clone();
-        let mut body = Vec::new();
-        let mut serializer = minicbor_serde::Serializer::new(&mut body);
-
-        // Necessary for serde_json::Value which serializes `Null` as unit rather than none...
-        serializer.serialize_unit_as_null(true);
-        request.serialize(&mut serializer)?;
-        let body = async move { Ok(body.into()) };
-
-        parts.method = http::Method::POST;
-        parts
-            .headers
-            .insert("Content-Type", http::HeaderValue::from_static("application/cbor"));
-        parts
-            .headers
-            .insert("Accept", http::HeaderValue::from_static("application/json"));
-        parts.extensions.insert(McpRequestContext {
-            execute_mutations: self.execute_mutations,
-        });
-
-        let http_request = http::Request::from_parts(parts, body);
-        let mut response = engine.execute(http_request).await;
-        let mcp = response.extensions_mut().remove();
-        Ok(EngineResponse {
-            json: response.into_body().into_bytes().unwrap().into(),
-            mcp,
-        })
-    }
+/// Executes a GraphQL request through the engine, like any other client would.
+pub(super) async fn execute<R: engine::Runtime>(
+    engine: &EngineWatcher<R>,
+    execute_mutations: bool,
+    mut parts: Parts,
+    request: impl serde::Serialize,
+) -> anyhow::Result<EngineResponse> {
+    let engine = engine.borrow().clone();
+    let mut body = Vec::new();
+    let mut serializer = minicbor_serde::Serializer::new(&mut body);
+
+    // Necessary for serde_json::Value which serializes `Null` as unit rather than none...
+    serializer.serialize_unit_as_null(true);
+    request.serialize(&mut serializer)?;
+    let body = async move { Ok(body.into()) };
+
+    parts.method = http::Method::POST;
+    parts
+        .headers
+        .insert("Content-Type", http::HeaderValue::from_static("application/cbor"));
+    parts
+        .headers
+        .insert("Accept", http::HeaderValue::from_static("application/json"));
+    parts.extensions.insert(McpRequestContext { execute_mutations });
+
+    let http_request = http::Request::from_parts(parts, body);
+    let mut response = engine.execute(http_request).await;
+    let mcp = response.extensions_mut().remove();
+    Ok(EngineResponse {
+        json: response.into_body().into_bytes().unwrap().into(),
+        mcp,
+    })
 }
@@ -1,5 +1,6 @@
 mod execute;
 mod introspect;
+mod operation;
 mod sdl;
 mod search;
 
@@ -7,6 +8,7 @@ pub use execute::*;
 use futures::future::BoxFuture;
 use http::request::Parts;
 pub use introspect::*;
+pub use operation::*;
 pub use search::*;
 use std::borrow::Cow;
 
@@ -58,10 +60,7 @@ impl<T: Tool> RmcpTool for T {
         mut ctx: RequestContext<RoleServer>,
         parameters: Option<JsonObject>,
     ) -> BoxFuture<'_, Result<CallToolResult, ErrorData>> {
-        let parts = ctx
-            .extensions
-            .remove::<Parts>()
-            .unwrap_or_else(|| http::Request::builder().body(Vec::<u8>::new()).unwrap().into_parts().0);
+        let parts = request_parts(&mut ctx);
 
         Box::pin(async move {
             let parameters: T::Parameters =
@@ -75,6 +74,13 @@ impl<T: Tool> RmcpTool for T {
     }
 }
 
+/// HTTP request parts of the MCP request, forwarded to the engine.
+pub(crate) fn request_parts(ctx: &mut RequestContext<RoleServer>) -> Parts {
+    ctx.extensions
+        .remove::<Parts>()
+        .unwrap_or_else(|| http::Request::builder().body(Vec::<u8>::new()).unwrap().into_parts().0)
+}
+
 struct SdlAndErrors {
     sdl: String,
     errors: Vec<String>,
@@ -0,0 +1,324 @@
+use std::sync::Arc;
+
+use convert_case::{Case, Casing as _};
+use cynic_parser::common::{OperationType, WrappingType};
+use engine::Schema;
+use engine_schema::{ScalarType, TypeDefinition, Wrapping};
+use futures::future::BoxFuture;
+use gateway_config::McpOperationsConfig;
+use rmcp::{
+    RoleServer,
+    model::{CallToolResult, Content, ErrorCode, ErrorData, JsonObject, ToolAnnotations},
+    service::RequestContext,
+};
+use serde_json::json;
+
+use super::{RmcpTool, execute::execute, request_parts};
+use crate::EngineWatcher;
+
+// Recursive input objects are only described up to this depth.
+const MAX_INPUT_OBJECT_DEPTH: usize = 8;
+
+/// A named operation from a GraphQL document exposed as its own tool. Agents only provide the
+/// variables, the document itself can't be changed.
+pub struct OperationTool<R: engine::Runtime> {
+    engine: EngineWatcher<R>,
+    execute_mutations: bool,
+    name: String,
+    description: String,
+    operation_name: String,
+    is_mutation: bool,
+    variables: Vec<Variable>,
+    document: Arc<Document>,
+}
+
+struct Document {
+    content: String,
+    id: Option<String>,
+    client_name: Option<http::HeaderValue>,
+}
+
+struct Variable {
+    name: String,
+    type_name: String,
+    wrapping: Wrapping,
+    has_default_value: bool,
+}
+
+#[derive(serde::Serialize)]
+struct OperationRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<&'a str>,
+    #[serde(rename = "operationName")]
+    operation_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    doc_id: Option<&'a str>,
+    variables: JsonObject,
+}
+
+impl<R: engine::Runtime> OperationTool<R> {
+    /// Loads every named query, and mutations if allowed, of the configured documents.
+    pub fn load_all(
+        engine: &EngineWatcher<R>,
+        execute_mutations: bool,
+        client_name: Option<&str>,
+        configs: &[McpOperationsConfig],
+    ) -> anyhow::Result<Vec<Self>> {
+        let client_name = client_name.map(http::HeaderValue::from_str).transpose()?;
+        let mut tools = Vec::new();
+
+        for McpOperationsConfig { path, document_id } in configs {
+            let content = std::fs::read_to_string(path)
+                .map_err(|err| anyhow::anyhow!("Failed to read the operations at {}: {err}", path.display()))?;
+            let parsed = cynic_parser::parse_executable_document(&content)
+                .map_err(|err| anyhow::anyhow!("Failed to parse the operations at {}: {err}", path.display()))?;
+
+            let mut operations = Vec::new();
+            for operation in parsed.operations() {
+                let Some(operation_name) = operation.name() else {
+                    tracing::warn!("Ignoring an unnamed operation in {}", path.display());
+                    continue;
+                };
+
+                let is_mutation = match operation.operation_type() {
+                    OperationType::Query => false,
+                    OperationType::Mutation if execute_mutations => true,
+                    OperationType::Mutation | OperationType::Subscription => continue,
+                };
+
+                let variables = operation
+                    .variable_definitions()
+                    .map(|variable| {
+                        let ty = variable.ty();
+                        let mut wrapping = Wrapping::default();
+                        // cynic-parser provides the wrappers from outermost to innermost.
+                        for wrapper in ty.wrappers().collect::<Vec<_>>().into_iter().rev() {
+                            wrapping = match wrapper {
+                                WrappingType::NonNull => wrapping.non_null(),
+                                WrappingType::List => wrapping.list(),
+                            };
+                        }
+
+                        Variable {
+                            name: variable.name().to_string(),
+                            type_name: ty.name().to_string(),
+                            wrapping,
+                            has_default_value: variable.default_value().is_some(),
+                        }
+                    })
+                    .collect();
+
+                // Named operations always start with their operation type.
+                let description = operation
+                    .operation_type_span()
+                    .and_then(|span| leading_comments(&content, span.start))
+                    .unwrap_or_else(|| {
+                        format!(
+                            "Executes the GraphQL {} `{operation_name}`.",
+                            if is_mutation { "mutation" } else { "query" }
+                        )
+                    });
+
+                operations.push((operation_name.to_string(), is_mutation, description, variables));
+            }
+
+            let document = Arc::new(Document {
+                content,
+                id: document_id.clone(),
+                client_name: client_name.clone(),
+            });
+
+            for (operation_name, is_mutation, description, variables) in operations {
+                let name = operation_name.to_case(Case::Snake);
+                if tools.iter().any(|tool: &Self| tool.name == name) {
+                    anyhow::bail!("Multiple operations are exposed as the tool '{name}'");
+                }
+
+                tools.push(Self {
+                    engine: engine.clone(),
+                    execute_mutations,
+                    name,
+                    description,
+                    operation_name,
+                    is_mutation,
+                    variables,
+                    document: document.clone(),
+                });
+            }
+        }
+
+        Ok(tools)
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The GraphQL document defining the operation.
+    pub fn document(&self) -> &str {
+        &self.document.content
+    }
+
+    fn input_schema(&self, schema: &Schema) -> JsonObject {
+        let mut properties = JsonObject::new();
+        let mut required = Vec::new();
+
+        for variable in &self.variables {
+            let definition = schema.type_definition_by_name(&variable.type_name);
+            properties.insert(
+                variable.name.clone(),
+                input_type_schema(definition, variable.wrapping, 0),
+            );
+            if variable.wrapping.is_non_null() && !variable.has_default_value {
+                required.push(variable.name.clone());
+            }
+        }
+
+        let mut input_schema = JsonObject::new();
+        input_schema.insert("type".into(), json!("object"));
+        input_schema.insert("properties".into(), properties.into());
+        input_schema.insert("required".into(), required.into());
+        input_schema
+    }
+}
+
+impl<R: engine::Runtime> RmcpTool for OperationTool<R> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn to_tool(&self) -> rmcp::model::Tool {
+        let engine = self.engine.borrow().clone();
+        let annotations = if self.is_mutation {
+            ToolAnnotations::new().destructive(true).open_world(true)
+        } else {
+            ToolAnnotations::new().read_only(true)
+        };
+
+        rmcp::model::Tool::new(
+            self.name.clone(),
+            self.description.clone(),
+            self.input_schema(&engine.no_contract.schema),
+        )
+        .annotate(annotations)
+    }
+
+    fn call(
+        &self,
+        mut ctx: RequestContext<RoleServer>,
+        parameters: Option<JsonObject>,
+    ) -> BoxFuture<'_, Result<CallToolResult, ErrorData>> {
+        let mut parts = request_parts(&mut ctx);
+
+        Box::pin(async move {
+            let document = &self.document;
+            if let Some(client_name) = &document.client_name {
+                parts.headers.insert("x-grafbase-client-name", client_name.clone());
+            }
+
+            // Trusted documents are executed by id when we know it, otherwise the engine matches
+            // the document text.
+            let request = OperationRequest {
+                query: document.id.is_none().then_some(document.content.as_str()),
+                operation_name: &self.operation_name,
+                doc_id: document.id.as_deref(),
+                variables: parameters.unwrap_or_default(),
+            };
+
+            match execute(&self.engine, self.execute_mutations, parts, request).await {
+                Ok(response) => Ok(CallToolResult {
+                    content: vec![Content::text(String::from_utf8_lossy(&response.json).into_owned())],
+                    structured_content: None,
+                    is_error: Some(false),
+                    meta: None,
+                }),
+                Err(err) => Err(ErrorData::new(ErrorCode::INTERNAL_ERROR, err.to_string(), None)),
+            }
+        })
+    }
+}
+
+fn input_type_schema(definition: Option<TypeDefinition<'_>>, wrapping: Wrapping, depth: usize) -> serde_json::Value {
+    if let Some(inner) = wrapping.without_list() {
+        return json!({
+            "type": "array",
+            "items": input_type_schema(definition, inner, depth),
+        });
+    }
+
+    let Some(definition) = definition else {
+        return json!({});
+    };
+
+    let mut schema = match definition {
+        TypeDefinition::Scalar(scalar) => match scalar.as_ref().ty {
+            ScalarType::String => json!({ "type": "string" }),
+            ScalarType::Int => json!({ "type": "integer" }),
+            ScalarType::Float => json!({ "type": "number" }),
+            ScalarType::Boolean => json!({ "type": "boolean" }),
+            ScalarType::Unknown => json!({}),
+        },
+        TypeDefinition::Enum(enm) => json!({
+            "type": "string",
+            "enum": enm.values().map(|value| value.name()).collect::<Vec<_>>(),
+        }),
+        TypeDefinition::InputObject(input_object) if depth < MAX_INPUT_OBJECT_DEPTH => {
+            let mut properties = JsonObject::new();
+            let mut required = Vec::new();
+            for field in input_object.input_fields() {
+                let ty = field.ty();
+                let mut field_schema = input_type_schema(Some(ty.definition()), ty.wrapping, depth + 1);
+                if let (Some(description), Some(object)) = (field.description(), field_schema.as_object_mut()) {
+                    object.insert("description".into(), description.into());
+                }
+                properties.insert(field.name().to_string(), field_schema);
+                if ty.wrapping.is_non_null() && field.default_value().is_none() {
+                    required.push(field.name());
+                }
+            }
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            })
+        }
+        TypeDefinition::InputObject(_) => json!({ "type": "object" }),
+        TypeDefinition::Interface(_) | TypeDefinition::Object(_) | TypeDefinition::Union(_) => json!({}),
+    };
+
+    if let Some(description) = definition.description()
+        && let Some(object) = schema.as_object_mut()
+        && !object.contains_key("description")
+    {
+        object.insert("description".into(), description.into());
+    }
+
+    schema
+}
+
+/// The comments on the lines right before an operation definition, which starts at `offset` in
+/// the document, are used as the description of its tool.
+fn leading_comments(document: &str, offset: usize) -> Option<String> {
+    let (preceding_lines, line_start) = document[..offset]
+        .rsplit_once('\n')
+        .unwrap_or(("", &document[..offset]));
+
+    // Something else precedes the definition on its own line.
+    if !line_start.trim().is_empty() {
+        return None;
+    }
+
+    let mut comments = preceding_lines
+        .lines()
+        .rev()
+        .map_while(|line| line.trim().strip_prefix('#'))
+        .map(str::trim)
+        .collect::<Vec<_>>();
+
+    if comments.is_empty() {
+        return None;
+    }
+
+    comments.reverse();
+    Some(comments.join("\n"))
+}
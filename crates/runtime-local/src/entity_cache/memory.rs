@@ -2,6 +2,7 @@ use std::time::Instant;
 
 use bytes::Bytes;
 use futures_util::{FutureExt, future::BoxFuture};
+use runtime::entity_cache::CacheTag;
 use tracing::{Instrument, field::Empty};
 
 pub struct InMemoryEntityCache {
@@ -12,6 +13,7 @@ pub struct InMemoryEntityCache {
 struct CacheValue {
     data: Bytes,
     expires_at: Instant,
+    tags: Vec<CacheTag>,
 }
 
 impl InMemoryEntityCache {
@@ -39,16 +41,34 @@ impl InMemoryEntityCache {
         name: &str,
         bytes: std::borrow::Cow<'_, [u8]>,
         expiration_ttl: std::time::Duration,
+        tags: &[CacheTag],
     ) -> anyhow::Result<()> {
         self.inner.insert(
             name.to_string(),
             CacheValue {
                 data: bytes.into_owned().into(),
                 expires_at: Instant::now() + expiration_ttl,
+                tags: tags.to_vec(),
             },
         );
         Ok(())
     }
+
+    async fn purge(&self, tags: &[CacheTag]) -> anyhow::Result<()> {
+        // Collecting first, invalidating while iterating could deadlock on the shard locks.
+        let names = self
+            .inner
+            .iter()
+            .filter(|entry| entry.value().tags.iter().any(|tag| tags.contains(tag)))
+            .map(|entry| entry.key().clone())
+            .collect::<Vec<_>>();
+
+        for name in names {
+            self.inner.invalidate(&name);
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for InMemoryEntityCache {
@@ -89,8 +109,14 @@ impl runtime::entity_cache::EntityCache for InMemoryEntityCache {
         name: &'a str,
         bytes: std::borrow::Cow<'a, [u8]>,
         expiration_ttl: std::time::Duration,
+        tags: &'a [CacheTag],
     ) -> BoxFuture<'a, anyhow::Result<()>> {
         let cache_span = tracing::info_span!("entity cache put");
-        Box::pin(self.put(name, bytes, expiration_ttl).instrument(cache_span))
+        Box::pin(self.put(name, bytes, expiration_ttl, tags).instrument(cache_span))
+    }
+
+    fn purge<'a>(&'a self, tags: &'a [CacheTag]) -> BoxFuture<'a, anyhow::Result<()>> {
+        let cache_span = tracing::info_span!("entity cache purge");
+        Box::pin(self.purge(tags).instrument(cache_span))
     }
 }
@@ -2,6 +2,7 @@ use bytes::Bytes;
 use deadpool::managed::Object;
 use futures_util::{FutureExt, future::BoxFuture};
 use redis::{AsyncCommands, SetOptions};
+use runtime::entity_cache::CacheTag;
 use tracing::{Instrument, field::Empty};
 
 use crate::redis::{Manager, Pool};
@@ -29,16 +30,64 @@ impl RedisEntityCache {
         name: &str,
         bytes: std::borrow::Cow<'_, [u8]>,
         expiration_ttl: std::time::Duration,
+        tags: &[CacheTag],
     ) -> anyhow::Result<()> {
         let mut connection = self.connection().await?;
+        let key = self.key(name);
         let options = SetOptions::default().with_expiration(self.expiry_time(expiration_ttl));
-        Ok(connection.set_options(self.key(name), bytes.as_ref(), options).await?)
+
+        if tags.is_empty() {
+            return Ok(connection.set_options(key, bytes.as_ref(), options).await?);
+        }
+
+        // Each tag is a set of the keys tagged with it. The set must live at least as long as its
+        // longest-lived member, so the expiry is only ever extended. EXPIRE NX and GT require
+        // Redis 7 or later.
+        //
+        // The pipeline isn't a transaction as the keys may be in different slots of a cluster.
+        let ttl = expiration_ttl.as_secs().max(1) as i64;
+        let mut pipeline = redis::pipe();
+        pipeline.set_options(&key, bytes.as_ref(), options).ignore();
+
+        for tag in tags {
+            let tag_key = self.tag_key(tag);
+            pipeline.cmd("SADD").arg(&tag_key).arg(&key).ignore();
+            pipeline.cmd("EXPIRE").arg(&tag_key).arg(ttl).arg("NX").ignore();
+            pipeline.cmd("EXPIRE").arg(&tag_key).arg(ttl).arg("GT").ignore();
+        }
+
+        Ok(pipeline.query_async::<()>(&mut *connection).await?)
+    }
+
+    async fn purge(&self, tags: &[CacheTag]) -> anyhow::Result<()> {
+        let mut connection = self.connection().await?;
+
+        for tag in tags {
+            let tag_key = self.tag_key(tag);
+            let keys: Vec<String> = connection.smembers(&tag_key).await?;
+
+            // Keys are deleted one by one, a multi-key DEL fails on a cluster when they don't
+            // share the same slot.
+            let mut pipeline = redis::pipe();
+            for key in keys {
+                pipeline.del(key).ignore();
+            }
+            pipeline.del(&tag_key).ignore();
+
+            pipeline.query_async::<()>(&mut *connection).await?;
+        }
+
+        Ok(())
     }
 
     fn key(&self, name: &str) -> String {
         format!("{}-{name}", self.key_prefix)
     }
 
+    fn tag_key(&self, tag: &CacheTag) -> String {
+        format!("{}-tag-{tag}", self.key_prefix)
+    }
+
     fn expiry_time(&self, duration: std::time::Duration) -> redis::SetExpiry {
         if duration.as_secs() > 60 {
             redis::SetExpiry::PX(duration.as_millis() as u64)
@@ -90,8 +139,14 @@ impl runtime::entity_cache::EntityCache for RedisEntityCache {
         name: &'a str,
         bytes: std::borrow::Cow<'a, [u8]>,
         expiration_ttl: std::time::Duration,
+        tags: &'a [CacheTag],
     ) -> BoxFuture<'a, anyhow::Result<()>> {
         let cache_span = tracing::info_span!("entity cache put");
-        Box::pin(self.put(name, bytes, expiration_ttl).instrument(cache_span))
+        Box::pin(self.put(name, bytes, expiration_ttl, tags).instrument(cache_span))
+    }
+
+    fn purge<'a>(&'a self, tags: &'a [CacheTag]) -> BoxFuture<'a, anyhow::Result<()>> {
+        let cache_span = tracing::info_span!("entity cache purge");
+        Box::pin(self.purge(tags).instrument(cache_span))
     }
 }
@@ -1,6 +1,8 @@
+mod load_balancing;
 mod signing;
 mod traffic_shaping;
 
+use std::borrow::Cow;
 use std::future::Future;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -18,9 +20,9 @@ use gateway_config::Config;
 use rapidhash::fast::RapidHashMap;
 use reqwest::{Certificate, Identity, RequestBuilder};
 use reqwest_eventsource::RequestBuilderExt;
-use runtime::fetch::{FetchError, FetchRequest, FetchResult, Fetcher, WebsocketRequest};
+use runtime::fetch::{FetchError, FetchRequest, FetchResult, Fetcher, SubgraphEndpointsHealth, WebsocketRequest};
 
-use crate::fetch::traffic_shaping::TrafficShaping;
+use crate::fetch::{load_balancing::LoadBalancers, traffic_shaping::TrafficShaping};
 
 const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(5);
 const ENABLE_HICKORY_DNS: bool = true;
@@ -30,6 +32,7 @@ pub struct NativeFetcherInner {
     signer: signing::RequestSigner,
     dedicated_clients: FxHashMap<GraphqlSubgraphId, reqwest::Client>,
     traffic_shaping: traffic_shaping::TrafficShaping,
+    load_balancers: LoadBalancers,
 }
 
 #[derive(Clone)]
@@ -52,12 +55,25 @@ impl NativeFetcher {
         let signer = signing::RequestSigner::new(config, &name_to_id)?;
         let dedicated_clients = generate_dedicated_http_clients(config, &name_to_id)?;
 
-        Ok(NativeFetcher(Arc::new(NativeFetcherInner {
+        let fetcher = NativeFetcher(Arc::new(NativeFetcherInner {
             client: client_builder().build()?,
             signer,
             dedicated_clients,
             traffic_shaping: TrafficShaping::new(&config.traffic_shaping),
-        })))
+            load_balancers: LoadBalancers::new(config, schema),
+        }));
+
+        // The health checks stop once the fetcher is dropped, on a schema reload for example.
+        for (subgraph_id, balancer) in fetcher.load_balancers.iter() {
+            if balancer.active_health_check().is_some() {
+                tokio::spawn(load_balancing::run_active_health_check(
+                    Arc::downgrade(&fetcher.0),
+                    *subgraph_id,
+                ));
+            }
+        }
+
+        Ok(fetcher)
     }
 }
 
@@ -84,7 +100,28 @@ struct FetchResponse {
 }
 
 impl NativeFetcherInner {
-    async fn execute(&self, fetch_req: FetchRequest<'_>) -> FetchResponse {
+    async fn execute(&self, mut fetch_req: FetchRequest<'_>) -> FetchResponse {
+        let Some(endpoint) = self
+            .load_balancers
+            .get(fetch_req.subgraph_id)
+            .and_then(|balancer| balancer.select(&fetch_req.url))
+        else {
+            return self.send(fetch_req).await;
+        };
+
+        fetch_req.url = Cow::Owned(endpoint.url().clone());
+        let response = self.send(fetch_req).await;
+
+        let success = response
+            .result
+            .as_ref()
+            .is_ok_and(|response| !response.status().is_server_error());
+        endpoint.record(success);
+
+        response
+    }
+
+    async fn send(&self, fetch_req: FetchRequest<'_>) -> FetchResponse {
         let mut info = SubgraphResponse::builder();
 
         let subgraph_id = fetch_req.subgraph_id;
@@ -233,6 +270,10 @@ impl Fetcher for NativeFetcher {
                 .map(|item| item.map_err(|err| FetchError::from(err.to_string()))))
         }
     }
+
+    fn endpoints_health(&self) -> Vec<SubgraphEndpointsHealth> {
+        self.load_balancers.health()
+    }
 }
 
 fn into_reqwest(request: FetchRequest<'_>) -> reqwest::Request {
@@ -0,0 +1,460 @@
+use std::{
+    sync::{
+        Arc, Mutex, Weak,
+        atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use engine::Schema;
+use engine_schema::GraphqlSubgraphId;
+use fxhash::FxHashMap;
+use gateway_config::{
+    ActiveHealthCheckConfig, Config, LoadBalancingConfig, LoadBalancingStrategy, PassiveHealthCheckConfig,
+};
+use reqwest::Url;
+use runtime::fetch::{EndpointHealth, FetchError, SubgraphEndpointsHealth};
+
+use super::NativeFetcherInner;
+
+const HEALTH_CHECK_QUERY: &str = r#"{"query":"{ __typename }"}"#;
+
+pub(super) struct LoadBalancers {
+    by_subgraph: FxHashMap<GraphqlSubgraphId, Arc<LoadBalancer>>,
+}
+
+impl LoadBalancers {
+    pub fn new(config: &Config, schema: &Schema) -> Self {
+        let by_subgraph = schema
+            .graphql_subgraphs()
+            .filter_map(|subgraph| {
+                let config = config.subgraphs.get(subgraph.name())?.load_balancing.as_ref()?;
+                if config.endpoints.is_empty() {
+                    return None;
+                }
+
+                let mut balancer = LoadBalancer::new(subgraph.name(), subgraph.url().clone(), config);
+                balancer.probe_headers = engine::subgraph_probe_headers(schema, subgraph.id);
+                Some((subgraph.id, Arc::new(balancer)))
+            })
+            .collect();
+
+        Self { by_subgraph }
+    }
+
+    pub fn get(&self, subgraph_id: GraphqlSubgraphId) -> Option<&Arc<LoadBalancer>> {
+        self.by_subgraph.get(&subgraph_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&GraphqlSubgraphId, &Arc<LoadBalancer>)> {
+        self.by_subgraph.iter()
+    }
+
+    pub fn health(&self) -> Vec<SubgraphEndpointsHealth> {
+        let mut health = self
+            .by_subgraph
+            .values()
+            .map(|balancer| balancer.health())
+            .collect::<Vec<_>>();
+        health.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        health
+    }
+}
+
+pub(super) struct LoadBalancer {
+    subgraph_name: String,
+    /// Requests sent to another URL, changed by a hook for example, are left untouched.
+    subgraph_url: Url,
+    strategy: LoadBalancingStrategy,
+    passive_health_check: PassiveHealthCheckConfig,
+    active_health_check: ActiveHealthCheckConfig,
+    /// Headers of the active health checks, from the subgraph header rules.
+    probe_headers: http::HeaderMap,
+    endpoints: Vec<Endpoint>,
+    next: AtomicUsize,
+}
+
+struct Endpoint {
+    url: Url,
+    weight: u32,
+    inflight: AtomicUsize,
+    consecutive_failures: AtomicU32,
+    ejected_until: Mutex<Option<Instant>>,
+    active_probe: Mutex<ProbeState>,
+    probe_healthy: AtomicBool,
+}
+
+#[derive(Default)]
+struct ProbeState {
+    consecutive_failures: u32,
+    consecutive_successes: u32,
+}
+
+impl Endpoint {
+    fn is_healthy(&self, now: Instant) -> bool {
+        let ejected = self.ejected_until.lock().unwrap().is_some_and(|until| now < until);
+        !ejected && self.probe_healthy.load(Ordering::Relaxed)
+    }
+}
+
+impl LoadBalancer {
+    fn new(subgraph_name: &str, subgraph_url: Url, config: &LoadBalancingConfig) -> Self {
+        Self {
+            subgraph_name: subgraph_name.to_string(),
+            subgraph_url,
+            strategy: config.strategy,
+            passive_health_check: config.passive_health_check,
+            active_health_check: config.health_check,
+            probe_headers: http::HeaderMap::new(),
+            endpoints: config
+                .endpoints
+                .iter()
+                .map(|endpoint| Endpoint {
+                    url: endpoint.url.clone(),
+                    weight: endpoint.weight.max(1),
+                    inflight: AtomicUsize::new(0),
+                    consecutive_failures: AtomicU32::new(0),
+                    ejected_until: Mutex::new(None),
+                    active_probe: Mutex::new(ProbeState::default()),
+                    probe_healthy: AtomicBool::new(true),
+                })
+                .collect(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn active_health_check(&self) -> Option<ActiveHealthCheckConfig> {
+        Some(self.active_health_check).filter(|config| config.enabled)
+    }
+
+    /// Chooses the endpoint of a request to the subgraph URL. If every endpoint is unhealthy, all
+    /// of them are considered again rather than failing the request.
+    pub fn select(&self, url: &Url) -> Option<SelectedEndpoint<'_>> {
+        if url != &self.subgraph_url {
+            return None;
+        }
+
+        let now = Instant::now();
+        let mut candidates = (0..self.endpoints.len())
+            .filter(|&ix| self.endpoints[ix].is_healthy(now))
+            .collect::<Vec<_>>();
+
+        if candidates.is_empty() {
+            candidates.extend(0..self.endpoints.len());
+        }
+
+        let next = self.next.fetch_add(1, Ordering::Relaxed);
+        let index = match self.strategy {
+            LoadBalancingStrategy::RoundRobin => candidates[next % candidates.len()],
+            LoadBalancingStrategy::LeastInflight => {
+                // Rotating the starting point spreads the requests among equally loaded endpoints.
+                let offset = next % candidates.len();
+                candidates[offset..]
+                    .iter()
+                    .chain(&candidates[..offset])
+                    .copied()
+                    .min_by_key(|&ix| self.endpoints[ix].inflight.load(Ordering::Relaxed))
+                    .expect("at least one endpoint")
+            }
+            LoadBalancingStrategy::Weighted => {
+                let total_weight = candidates
+                    .iter()
+                    .map(|&ix| self.endpoints[ix].weight as usize)
+                    .sum::<usize>();
+                let mut point = next % total_weight;
+                candidates
+                    .iter()
+                    .copied()
+                    .find(|&ix| {
+                        let weight = self.endpoints[ix].weight as usize;
+                        if point < weight {
+                            true
+                        } else {
+                            point -= weight;
+                            false
+                        }
+                    })
+                    .expect("point within the total weight")
+            }
+        };
+
+        self.endpoints[index].inflight.fetch_add(1, Ordering::Relaxed);
+        Some(SelectedEndpoint { balancer: self, index })
+    }
+
+    fn record_request(&self, index: usize, success: bool) {
+        if !self.passive_health_check.enabled {
+            return;
+        }
+
+        let endpoint = &self.endpoints[index];
+        if success {
+            endpoint.consecutive_failures.store(0, Ordering::Relaxed);
+            return;
+        }
+
+        let failures = endpoint.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= self.passive_health_check.max_failures {
+            endpoint.consecutive_failures.store(0, Ordering::Relaxed);
+            *endpoint.ejected_until.lock().unwrap() =
+                Some(Instant::now() + self.passive_health_check.ejection_duration);
+
+            tracing::warn!(
+                "Ejecting endpoint {} of subgraph {} after {failures} failed requests",
+                endpoint.url,
+                self.subgraph_name
+            );
+        }
+    }
+
+    fn record_probe(&self, index: usize, success: bool) {
+        let endpoint = &self.endpoints[index];
+        let mut probe = endpoint.active_probe.lock().unwrap();
+
+        if success {
+            probe.consecutive_failures = 0;
+            probe.consecutive_successes += 1;
+            if probe.consecutive_successes >= self.active_health_check.healthy_threshold
+                && !endpoint.probe_healthy.swap(true, Ordering::Relaxed)
+            {
+                tracing::info!(
+                    "Endpoint {} of subgraph {} is healthy again",
+                    endpoint.url,
+                    self.subgraph_name
+                );
+            }
+        } else {
+            probe.consecutive_successes = 0;
+            probe.consecutive_failures += 1;
+            if probe.consecutive_failures >= self.active_health_check.unhealthy_threshold
+                && endpoint.probe_healthy.swap(false, Ordering::Relaxed)
+            {
+                tracing::warn!(
+                    "Ejecting endpoint {} of subgraph {} after failed health checks",
+                    endpoint.url,
+                    self.subgraph_name
+                );
+            }
+        }
+    }
+
+    fn health(&self) -> SubgraphEndpointsHealth {
+        let now = Instant::now();
+
+        SubgraphEndpointsHealth {
+            name: self.subgraph_name.clone(),
+            endpoints: self
+                .endpoints
+                .iter()
+                .map(|endpoint| EndpointHealth {
+                    url: endpoint.url.to_string(),
+                    healthy: endpoint.is_healthy(now),
+                    inflight_requests: endpoint.inflight.load(Ordering::Relaxed),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Endpoint chosen for a request, counted as in flight until dropped.
+pub(super) struct SelectedEndpoint<'a> {
+    balancer: &'a LoadBalancer,
+    index: usize,
+}
+
+impl SelectedEndpoint<'_> {
+    pub fn url(&self) -> &Url {
+        &self.balancer.endpoints[self.index].url
+    }
+
+    /// Network errors and 5xx responses count as failures for the passive health check.
+    pub fn record(self, success: bool) {
+        self.balancer.record_request(self.index, success);
+    }
+}
+
+impl Drop for SelectedEndpoint<'_> {
+    fn drop(&mut self) {
+        self.balancer.endpoints[self.index]
+            .inflight
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Probes every endpoint of the balancer at the configured interval until the fetcher owning it
+/// is dropped. Probes are sent like regular subgraph requests, with the subgraph TLS settings and
+/// message signing.
+pub(super) async fn run_active_health_check(fetcher: Weak<NativeFetcherInner>, subgraph_id: GraphqlSubgraphId) {
+    let Some(config) = fetcher
+        .upgrade()
+        .and_then(|fetcher| fetcher.load_balancers.get(subgraph_id)?.active_health_check())
+    else {
+        return;
+    };
+
+    let mut interval = tokio::time::interval(config.interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let Some(fetcher) = fetcher.upgrade() else {
+            return;
+        };
+        let Some(balancer) = fetcher.load_balancers.get(subgraph_id) else {
+            return;
+        };
+
+        let probes = balancer.endpoints.iter().map(|endpoint| {
+            probe(
+                &fetcher,
+                subgraph_id,
+                &balancer.probe_headers,
+                &endpoint.url,
+                config.timeout,
+            )
+        });
+
+        for (index, success) in futures_util::future::join_all(probes).await.into_iter().enumerate() {
+            balancer.record_probe(index, success);
+        }
+    }
+}
+
+async fn probe(
+    fetcher: &NativeFetcherInner,
+    subgraph_id: GraphqlSubgraphId,
+    headers: &http::HeaderMap,
+    url: &Url,
+    timeout: Duration,
+) -> bool {
+    let mut request = reqwest::Request::new(http::Method::POST, url.clone());
+    *request.headers_mut() = headers.clone();
+    request.headers_mut().insert(
+        http::header::CONTENT_TYPE,
+        http::HeaderValue::from_static("application/json"),
+    );
+    *request.body_mut() = Some(HEALTH_CHECK_QUERY.into());
+    *request.timeout_mut() = Some(timeout);
+
+    let result = match fetcher.signer.sign(subgraph_id, request).await {
+        Ok(request) => fetcher
+            .client(subgraph_id)
+            .execute(request)
+            .await
+            .map_err(FetchError::from),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(response) => response.status().is_success(),
+        Err(err) => {
+            tracing::debug!("Health check of {url} failed: {err}");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gateway_config::SubgraphEndpointConfig;
+
+    use super::*;
+
+    fn balancer(strategy: LoadBalancingStrategy, weights: &[u32]) -> LoadBalancer {
+        let config = LoadBalancingConfig {
+            endpoints: weights
+                .iter()
+                .enumerate()
+                .map(|(ix, weight)| SubgraphEndpointConfig {
+                    url: format!("http://replica-{ix}/graphql").parse().unwrap(),
+                    weight: *weight,
+                })
+                .collect(),
+            strategy,
+            passive_health_check: PassiveHealthCheckConfig {
+                enabled: true,
+                max_failures: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        LoadBalancer::new("products", "http://products/graphql".parse().unwrap(), &config)
+    }
+
+    fn pick(balancer: &LoadBalancer) -> String {
+        let url = "http://products/graphql".parse().unwrap();
+        balancer.select(&url).unwrap().url().host_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn round_robin() {
+        let balancer = balancer(LoadBalancingStrategy::RoundRobin, &[1, 1]);
+
+        let picks = (0..4).map(|_| pick(&balancer)).collect::<Vec<_>>();
+        assert_eq!(picks, ["replica-0", "replica-1", "replica-0", "replica-1"]);
+    }
+
+    #[test]
+    fn weighted() {
+        let balancer = balancer(LoadBalancingStrategy::Weighted, &[3, 1]);
+
+        let picks = (0..4).map(|_| pick(&balancer)).collect::<Vec<_>>();
+        assert_eq!(picks, ["replica-0", "replica-0", "replica-0", "replica-1"]);
+    }
+
+    #[test]
+    fn least_inflight() {
+        let balancer = balancer(LoadBalancingStrategy::LeastInflight, &[1, 1]);
+        let url = "http://products/graphql".parse().unwrap();
+
+        let first = balancer.select(&url).unwrap();
+        assert_eq!(first.url().host_str(), Some("replica-0"));
+
+        // replica-0 is still busy.
+        assert_eq!(pick(&balancer), "replica-1");
+        assert_eq!(pick(&balancer), "replica-1");
+    }
+
+    #[test]
+    fn other_urls_are_not_balanced() {
+        let balancer = balancer(LoadBalancingStrategy::RoundRobin, &[1, 1]);
+        let url = "http://elsewhere/graphql".parse().unwrap();
+
+        assert!(balancer.select(&url).is_none());
+    }
+
+    #[test]
+    fn passive_health_check_ejects_failing_endpoints() {
+        let balancer = balancer(LoadBalancingStrategy::RoundRobin, &[1, 1]);
+        let url = "http://products/graphql".parse().unwrap();
+
+        for _ in 0..2 {
+            let endpoint = balancer.select(&url).unwrap();
+            assert_eq!(endpoint.url().host_str(), Some("replica-0"));
+            endpoint.record(false);
+
+            balancer.select(&url).unwrap().record(true);
+        }
+
+        let picks = (0..3).map(|_| pick(&balancer)).collect::<Vec<_>>();
+        assert_eq!(picks, ["replica-1", "replica-1", "replica-1"]);
+
+        let health = balancer.health();
+        assert!(!health.endpoints[0].healthy);
+        assert!(health.endpoints[1].healthy);
+    }
+
+    #[test]
+    fn every_endpoint_is_used_when_none_is_healthy() {
+        let balancer = balancer(LoadBalancingStrategy::RoundRobin, &[1]);
+        let url = "http://products/graphql".parse().unwrap();
+
+        balancer.select(&url).unwrap().record(false);
+        balancer.select(&url).unwrap().record(false);
+
+        assert!(!balancer.health().endpoints[0].healthy);
+        assert_eq!(pick(&balancer), "replica-0");
+    }
+}
@@ -1,3 +1,34 @@
 pub mod in_memory;
 #[cfg(feature = "redis")]
 pub mod redis;
+
+use gateway_config::{RateLimitClientKey, RateLimitRule};
+use runtime::rate_limiting::{RateLimitKey, RateLimiterContext};
+
+/// Rules without a subgraph apply to every operation, the others only to the requests sent to
+/// their subgraph.
+fn rule_applies_to(rule: &RateLimitRule, key: &RateLimitKey<'_>) -> bool {
+    match (key, &rule.subgraph) {
+        (RateLimitKey::Operation, None) => true,
+        (RateLimitKey::Subgraph(name), Some(subgraph)) => name.as_ref() == subgraph,
+        _ => false,
+    }
+}
+
+/// Identifies the client a limit applies to. Requests without the configured identifier aren't
+/// limited by it, rather than sharing a single budget between all of them.
+fn client_id(key: &RateLimitClientKey, context: &dyn RateLimiterContext) -> Option<String> {
+    match key {
+        RateLimitClientKey::Ip => context.ip().map(|ip| ip.to_string()),
+        RateLimitClientKey::Header(name) => http::HeaderName::from_bytes(name.as_bytes())
+            .ok()
+            .and_then(|name| context.header(name))
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        RateLimitClientKey::JwtClaim(claim) => context.jwt_claim(claim).map(|value| match value {
+            serde_json::Value::String(value) => value.clone(),
+            value => value.to_string(),
+        }),
+        RateLimitClientKey::OperationName => context.graphql_operation_name().map(str::to_string),
+    }
+}
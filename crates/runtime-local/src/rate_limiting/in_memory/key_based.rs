@@ -1,19 +1,151 @@
 use std::num::NonZeroU32;
 use std::sync::Arc;
+use std::time::Duration;
 use std::{collections::HashMap, sync::RwLock};
 
 use futures_util::FutureExt;
 use futures_util::future::BoxFuture;
-use gateway_config::{Config, GraphRateLimit};
-use governor::Quota;
+use gateway_config::{ComplexityRateLimit, Config, GraphRateLimit, RateLimitRule};
+use governor::{
+    NotUntil, Quota,
+    clock::{Clock, DefaultClock},
+    middleware::StateInformationMiddleware,
+    state::keyed::DefaultKeyedStateStore,
+};
 
-use runtime::rate_limiting::{Error, RateLimitKey, RateLimiter, RateLimiterContext};
+use runtime::rate_limiting::{Error, RateLimitBudget, RateLimitKey, RateLimiter, RateLimiterContext};
 use tokio::sync::watch;
 
 type Limiters = HashMap<RateLimitKey<'static>, governor::DefaultKeyedRateLimiter<usize>>;
 
+/// How often the state of the clients which weren't seen recently is dropped.
+const RETAIN_RECENT_INTERVAL: Duration = Duration::from_secs(60);
+
 pub struct InMemoryRateLimiter {
     limiters: Arc<RwLock<Limiters>>,
+    clients: Arc<RwLock<ClientLimiters>>,
+}
+
+/// Limiters keeping a separate state for each client.
+#[derive(Default)]
+struct ClientLimiters {
+    budgets: Option<ClientBudgets>,
+    rules: Vec<RuleLimiter>,
+}
+
+impl ClientLimiters {
+    fn new(config: &Config) -> Self {
+        let Some(rate_limit) = config.gateway.rate_limit.as_ref() else {
+            return Self::default();
+        };
+
+        Self {
+            budgets: rate_limit.complexity.as_ref().and_then(ClientBudgets::new),
+            rules: rate_limit.rules.iter().filter_map(RuleLimiter::new).collect(),
+        }
+    }
+
+    /// Keyed limiters keep a state for every client ever seen, so we regularly drop the ones
+    /// which are back to their full capacity.
+    fn retain_recent(&self) {
+        if let Some(budgets) = &self.budgets {
+            budgets.limiter.retain_recent();
+            budgets.limiter.shrink_to_fit();
+        }
+
+        for rule in &self.rules {
+            rule.limiter.retain_recent();
+            rule.limiter.shrink_to_fit();
+        }
+    }
+}
+
+struct RuleLimiter {
+    config: RateLimitRule,
+    limiter: governor::DefaultKeyedRateLimiter<String>,
+}
+
+impl RuleLimiter {
+    fn new(config: &RateLimitRule) -> Option<Self> {
+        let Some(limit) = NonZeroU32::new(config.limit.min(u32::MAX as usize) as u32) else {
+            tracing::error!("the limit of the rate limit rule {} cannot be zero", config.name);
+            return None;
+        };
+
+        let Some(quota) = Quota::with_period(config.duration / limit.get()) else {
+            tracing::error!(
+                "the limit of the rate limit rule {} is too high for its duration",
+                config.name
+            );
+            return None;
+        };
+
+        Some(Self {
+            config: config.clone(),
+            limiter: governor::RateLimiter::keyed(quota.allow_burst(limit)),
+        })
+    }
+}
+
+/// Complexity budgets of every client, replenished continuously over the configured duration.
+struct ClientBudgets {
+    config: ComplexityRateLimit,
+    limit: NonZeroU32,
+    limiter: governor::RateLimiter<String, DefaultKeyedStateStore<String>, DefaultClock, StateInformationMiddleware>,
+}
+
+impl ClientBudgets {
+    fn new(config: &ComplexityRateLimit) -> Option<Self> {
+        let Some(limit) = NonZeroU32::new(config.limit.min(u32::MAX as usize) as u32) else {
+            tracing::error!("the complexity rate limit cannot be zero");
+            return None;
+        };
+
+        let Some(quota) = Quota::with_period(config.duration / limit.get()) else {
+            tracing::error!("the complexity limit is too high for the defined duration");
+            return None;
+        };
+
+        let limiter =
+            governor::RateLimiter::keyed(quota.allow_burst(limit)).with_middleware::<StateInformationMiddleware>();
+
+        Some(Self {
+            config: config.clone(),
+            limit,
+            limiter,
+        })
+    }
+
+    fn consume(&self, client: String, cost: usize) -> Result<RateLimitBudget, Error> {
+        let limit = self.limit.get() as usize;
+        // Every operation costs at least one point.
+        let cost = NonZeroU32::new(cost.min(u32::MAX as usize) as u32).unwrap_or(NonZeroU32::MIN);
+        let replenish_interval = self.config.duration / self.limit.get();
+
+        let budget = |remaining: usize| RateLimitBudget {
+            limit,
+            remaining,
+            reset: replenish_interval * (limit - remaining.min(limit)) as u32,
+        };
+
+        match self.limiter.check_key_n(&client, cost) {
+            Ok(Ok(snapshot)) => Ok(budget(snapshot.remaining_burst_capacity() as usize)),
+            Ok(Err(not_until)) => {
+                let wait = not_until.wait_time_from(DefaultClock::default().now());
+                let missing = wait.as_nanos().div_ceil(replenish_interval.as_nanos().max(1)) as usize;
+
+                Err(Error::ExceededBudget(budget(
+                    (cost.get() as usize).saturating_sub(missing),
+                )))
+            }
+            // The operation costs more than the whole budget, it can never succeed.
+            Err(_) => Err(Error::ExceededBudget(RateLimitBudget {
+                limit,
+                remaining: 0,
+                reset: self.config.duration,
+            })),
+        }
+    }
 }
 
 /// Load the rate limit configuration for global and subgraph level settings.
@@ -47,7 +179,9 @@ impl InMemoryRateLimiter {
         }
 
         let limiters = Arc::new(RwLock::new(limiters));
-        RateLimiter::new(Self { limiters })
+        let clients = Arc::new(RwLock::new(ClientLimiters::default()));
+
+        RateLimiter::new(Self { limiters, clients })
     }
 
     pub fn runtime_with_watcher(mut config: watch::Receiver<Config>) -> RateLimiter {
@@ -66,12 +200,32 @@ impl InMemoryRateLimiter {
         let limiters = Arc::new(RwLock::new(limiters));
         let limiters_copy = Arc::downgrade(&limiters);
 
+        let clients = Arc::new(RwLock::new(ClientLimiters::new(&config.borrow())));
+        let clients_copy = Arc::downgrade(&clients);
+
+        let retained_clients = Arc::downgrade(&clients);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RETAIN_RECENT_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                let Some(clients) = retained_clients.upgrade() else {
+                    break;
+                };
+
+                clients.read().unwrap().retain_recent();
+            }
+        });
+
         tokio::spawn(async move {
             while let Ok(()) = config.changed().await {
-                let Some(limiters) = limiters_copy.upgrade() else {
+                let (Some(limiters), Some(clients)) = (limiters_copy.upgrade(), clients_copy.upgrade()) else {
                     break;
                 };
 
+                *clients.write().unwrap() = ClientLimiters::new(&config.borrow());
+
                 let mut limiters = limiters.write().unwrap();
                 limiters.clear();
 
@@ -86,7 +240,7 @@ impl InMemoryRateLimiter {
             }
         });
 
-        RateLimiter::new(Self { limiters })
+        RateLimiter::new(Self { limiters, clients })
     }
 }
 
@@ -104,20 +258,54 @@ fn create_limiter(rate_limit_config: GraphRateLimit) -> Option<governor::Default
     Some(governor::RateLimiter::keyed(Quota::per_second(quota)))
 }
 
+fn exceeded_capacity(not_until: NotUntil<<DefaultClock as Clock>::Instant>) -> Error {
+    Error::ExceededCapacity {
+        retry_after: Some(not_until.wait_time_from(DefaultClock::default().now())),
+    }
+}
+
 impl runtime::rate_limiting::RateLimiterInner for InMemoryRateLimiter {
     fn limit<'a>(&'a self, context: &'a dyn RateLimiterContext) -> BoxFuture<'a, Result<(), Error>> {
         async {
             let Some(key) = context.key() else { return Ok(()) };
-            let limiters = self.limiters.read().unwrap();
 
-            if let Some(rate_limiter) = limiters.get(key) {
-                rate_limiter
-                    .check_key(&usize::MIN)
-                    .map_err(|_err| Error::ExceededCapacity)?;
+            if let Some(rate_limiter) = self.limiters.read().unwrap().get(key) {
+                rate_limiter.check_key(&usize::MIN).map_err(exceeded_capacity)?;
             };
 
+            let clients = self.clients.read().unwrap();
+
+            for rule in clients
+                .rules
+                .iter()
+                .filter(|rule| crate::rate_limiting::rule_applies_to(&rule.config, key))
+            {
+                if let Some(client) = crate::rate_limiting::client_id(&rule.config.key, context) {
+                    rule.limiter.check_key(&client).map_err(exceeded_capacity)?;
+                }
+            }
+
             Ok(())
         }
         .boxed()
     }
+
+    fn limit_complexity<'a>(
+        &'a self,
+        context: &'a dyn RateLimiterContext,
+        cost: usize,
+    ) -> BoxFuture<'a, Result<Option<RateLimitBudget>, Error>> {
+        async move {
+            let clients = self.clients.read().unwrap();
+            let Some(budgets) = clients.budgets.as_ref() else {
+                return Ok(None);
+            };
+
+            let Some(client) = crate::rate_limiting::client_id(&budgets.config.key, context) else {
+                return Ok(None);
+            };
+            budgets.consume(client, cost).map(Some)
+        }
+        .boxed()
+    }
 }
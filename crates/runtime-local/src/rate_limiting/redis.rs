@@ -1,12 +1,16 @@
-use std::time::{Duration, SystemTime};
+use std::{
+    sync::LazyLock,
+    time::{Duration, SystemTime},
+};
 
+use base64::{Engine, prelude::BASE64_URL_SAFE_NO_PAD};
 use futures_util::future::BoxFuture;
-use gateway_config::{Config, GraphRateLimit};
+use gateway_config::{ComplexityRateLimit, Config, RateLimitRule};
 use grafbase_telemetry::otel::opentelemetry::{
     KeyValue,
     metrics::{Histogram, Meter},
 };
-use runtime::rate_limiting::{Error, RateLimitKey, RateLimiter, RateLimiterContext};
+use runtime::rate_limiting::{Error, RateLimitBudget, RateLimitKey, RateLimiter, RateLimiterContext};
 use tokio::sync::watch;
 use tracing::{Instrument, field::Empty};
 
@@ -86,15 +90,77 @@ impl RedisRateLimiter {
             RateLimitKey::Subgraph(graph) => {
                 format!("{}:subgraph:rate_limit:{graph}:{bucket}", self.key_prefix)
             }
+            RateLimitKey::Operation => {
+                format!("{}:rate_limit:operation:{bucket}", self.key_prefix)
+            }
         }
     }
 
+    fn generate_rule_key(&self, bucket: u64, rule: &RateLimitRule, client: &str) -> String {
+        format!(
+            "{}:rate_limit:rule:{}:{}:{bucket}",
+            self.key_prefix,
+            rule.name,
+            client_segment(client)
+        )
+    }
+
     fn record_duration(&self, duration: Duration, status: RedisStatus) {
         let attributes = vec![KeyValue::new("grafbase.redis.status", status.as_str())];
         self.latencies.record(duration.as_millis() as u64, &attributes);
     }
 
-    async fn limit_inner(&self, key: &RateLimitKey<'_>, config: GraphRateLimit) -> Result<(), Error> {
+    async fn limit_inner(&self, limit: usize, duration: Duration, key: impl Fn(u64) -> String) -> Result<(), Error> {
+        let consumption = self.consume(limit, duration, 1, key).await?;
+
+        if consumption.accepted {
+            Ok(())
+        } else {
+            Err(Error::ExceededCapacity {
+                retry_after: Some(consumption.reset),
+            })
+        }
+    }
+
+    async fn limit_complexity_inner(
+        &self,
+        client: String,
+        config: ComplexityRateLimit,
+        cost: usize,
+    ) -> Result<RateLimitBudget, Error> {
+        // Every operation costs at least one point.
+        let consumption = self
+            .consume(config.limit, config.duration, cost.max(1), |bucket| {
+                format!(
+                    "{}:rate_limit:complexity:{}:{bucket}",
+                    self.key_prefix,
+                    client_segment(&client)
+                )
+            })
+            .await?;
+
+        let budget = RateLimitBudget {
+            limit: config.limit,
+            remaining: consumption.remaining,
+            reset: consumption.reset,
+        };
+
+        if consumption.accepted {
+            Ok(budget)
+        } else {
+            Err(Error::ExceededBudget(budget))
+        }
+    }
+
+    /// Checks the capacity left in the current window and consumes `cost` from it in a single
+    /// script, so concurrent requests can't all pass the check before any of them is counted.
+    async fn consume(
+        &self,
+        limit: usize,
+        duration: Duration,
+        cost: usize,
+        key: impl Fn(u64) -> String,
+    ) -> Result<Consumption, Error> {
         let now = SystemTime::now();
 
         let current_ts = match now.duration_since(SystemTime::UNIX_EPOCH) {
@@ -113,49 +179,33 @@ impl RedisRateLimiter {
             }
         };
 
-        let duration_ns = config.duration.as_nanos() as u64;
-        let current_bucket = current_ts - current_ts % duration_ns;
-        let previous_bucket = current_bucket - duration_ns;
+        let window = Window::at(current_ts, duration);
 
-        let bucket_percentage = (current_ts % duration_ns) as f64 / duration_ns as f64;
-
-        // The counter key for the current window.
-        let current_bucket = self.generate_key(current_bucket, key);
-        // The counter key for the previous window.
-        let previous_bucket = self.generate_key(previous_bucket, key);
-
-        // We execute multiple commands in one pipelined query to be _fast_.
-        let mut pipe = redis::pipe();
-
-        // Marks the start of an atomic block. The get and incr are guaranteed to run atomically.
-        pipe.atomic();
-
-        pipe.cmd("GET").arg(&previous_bucket);
-        pipe.cmd("GET").arg(&current_bucket);
+        let mut invocation = CONSUME_SCRIPT.prepare_invoke();
+        invocation
+            // The counter key for the previous window.
+            .key(key(window.previous_bucket))
+            // The counter key for the current window.
+            .key(key(window.current_bucket))
+            .arg(limit)
+            .arg(cost)
+            .arg(window.elapsed)
+            // Deletes the counter once it can't be part of the count anymore.
+            .arg((duration.as_secs() * 2).max(1));
 
         let start = SystemTime::now();
-        let result = pipe.query_async::<(Option<u64>, Option<u64>)>(&mut *conn).await;
+        let result = invocation.invoke_async::<(u64, u64)>(&mut *conn).await;
         let duration = SystemTime::now().duration_since(start).unwrap_or_default();
 
-        // Execute the whole pipeline in one multiplexed request.
         match result {
-            Ok((previous_count, current_count)) => {
+            Ok((accepted, remaining)) => {
                 self.record_duration(duration, RedisStatus::Success);
 
-                let previous_count = previous_count.unwrap_or_default().min(config.limit as u64);
-                let current_count = current_count.unwrap_or_default().min(config.limit as u64);
-
-                // Sum is a percentage what is left from the previous window, and the count of the
-                // current window.
-                let average = previous_count as f64 * (1.0 - bucket_percentage) + current_count as f64;
-
-                if average < config.limit as f64 {
-                    tokio::spawn(incr_counter(self.pool.clone(), current_bucket, config.duration));
-
-                    Ok(())
-                } else {
-                    Err(Error::ExceededCapacity)
-                }
+                Ok(Consumption {
+                    accepted: accepted == 1,
+                    remaining: remaining as usize,
+                    reset: window.reset,
+                })
             }
             Err(e) => {
                 self.record_duration(duration, RedisStatus::Error);
@@ -166,32 +216,70 @@ impl RedisRateLimiter {
     }
 }
 
-async fn incr_counter(pool: Pool, current_bucket: String, expire: Duration) -> Result<(), Error> {
-    let mut conn = match pool.get().await {
-        Ok(conn) => conn,
-        Err(error) => {
-            tracing::error!("error fetching a Redis connection: {error}");
-            return Err(Error::Internal(String::from("rate limit")));
-        }
-    };
-
-    let mut pipe = redis::pipe();
-    pipe.atomic();
-
-    pipe.cmd("INCR").arg(&current_bucket);
+/// Client ids come from headers or claims and may contain the `:` separating the key segments,
+/// so they're encoded to keep two clients from sharing a counter.
+fn client_segment(client: &str) -> String {
+    BASE64_URL_SAFE_NO_PAD.encode(client)
+}
 
-    // Sets the timeout to the set. This will delete the data after the duration if we do not modify the value.
-    pipe.cmd("EXPIRE")
-        .arg(&current_bucket)
-        .arg(expire.as_secs() * 2)
-        .ignore();
+/// Where a point in time falls in the fixed windows of a rate limit.
+struct Window {
+    current_bucket: u64,
+    previous_bucket: u64,
+    /// Elapsed fraction of the current window.
+    elapsed: f64,
+    /// Time left until the current window ends, after which its counter only partially counts.
+    reset: Duration,
+}
 
-    if let Err(e) = pipe.query_async::<(u64,)>(&mut *conn).await {
-        tracing::error!("error with Redis query: {e}");
-        return Err(Error::Internal(String::from("rate limit")));
+impl Window {
+    fn at(timestamp_ns: u64, duration: Duration) -> Self {
+        let duration_ns = (duration.as_nanos() as u64).max(1);
+        let elapsed_ns = timestamp_ns % duration_ns;
+        let current_bucket = timestamp_ns - elapsed_ns;
+
+        Window {
+            current_bucket,
+            previous_bucket: current_bucket.saturating_sub(duration_ns),
+            elapsed: elapsed_ns as f64 / duration_ns as f64,
+            reset: Duration::from_nanos(duration_ns - elapsed_ns),
+        }
     }
+}
 
-    Ok(())
+/// The total count is the count of the current window added with the part of the previous window
+/// that still overlaps the sliding one. The cost is only added to the current window if it fits
+/// in the remaining capacity.
+///
+/// KEYS: previous window counter, current window counter
+/// ARGV: limit, cost, elapsed fraction of the current window, counter expiration in seconds
+static CONSUME_SCRIPT: LazyLock<redis::Script> = LazyLock::new(|| {
+    redis::Script::new(
+        r#"
+        local limit = tonumber(ARGV[1])
+        local cost = tonumber(ARGV[2])
+        local previous_count = math.min(tonumber(redis.call("GET", KEYS[1]) or "0"), limit)
+        local current_count = math.min(tonumber(redis.call("GET", KEYS[2]) or "0"), limit)
+        local count = previous_count * (1 - tonumber(ARGV[3])) + current_count
+        local remaining = math.max(math.floor(limit - count), 0)
+
+        if cost > remaining then
+            return { 0, remaining }
+        end
+
+        redis.call("INCRBY", KEYS[2], cost)
+        redis.call("EXPIRE", KEYS[2], ARGV[4])
+        return { 1, remaining - cost }
+        "#,
+    )
+});
+
+struct Consumption {
+    accepted: bool,
+    /// Capacity left in the window after this consumption.
+    remaining: usize,
+    /// Time until the current window ends.
+    reset: Duration,
 }
 
 impl runtime::rate_limiting::RateLimiterInner for RedisRateLimiter {
@@ -200,25 +288,32 @@ impl runtime::rate_limiting::RateLimiterInner for RedisRateLimiter {
             return Box::pin(async { Ok(()) });
         };
 
-        let config = match key {
-            RateLimitKey::Global => self
-                .config_watcher
-                .borrow()
+        let (config, rules) = {
+            let config = self.config_watcher.borrow();
+
+            let shared = match key {
+                RateLimitKey::Global => config.gateway.rate_limit.as_ref().and_then(|rt| rt.global),
+                RateLimitKey::Subgraph(name) => config.subgraphs.get(name.as_ref()).and_then(|sb| sb.rate_limit),
+                RateLimitKey::Operation => None,
+            };
+
+            let rules = config
                 .gateway
                 .rate_limit
-                .as_ref()
-                .and_then(|rt| rt.global),
-            RateLimitKey::Subgraph(name) => self
-                .config_watcher
-                .borrow()
-                .subgraphs
-                .get(name.as_ref())
-                .and_then(|sb| sb.rate_limit),
+                .iter()
+                .flat_map(|rt| rt.rules.iter())
+                .filter(|rule| crate::rate_limiting::rule_applies_to(rule, key))
+                .filter_map(|rule| {
+                    crate::rate_limiting::client_id(&rule.key, context).map(|client| (rule.clone(), client))
+                })
+                .collect::<Vec<_>>();
+
+            (shared, rules)
         };
 
-        let Some(config) = config else {
+        if config.is_none() && rules.is_empty() {
             return Box::pin(async { Ok(()) });
-        };
+        }
 
         let span = tracing::info_span!("rate limit", "subgraph.name" = Empty);
 
@@ -226,6 +321,72 @@ impl runtime::rate_limiting::RateLimiterInner for RedisRateLimiter {
             span.record("subgraph.name", subgraph.as_ref());
         }
 
-        Box::pin(self.limit_inner(key, config).instrument(span))
+        Box::pin(
+            async move {
+                if let Some(config) = config {
+                    self.limit_inner(config.limit, config.duration, |bucket| self.generate_key(bucket, key))
+                        .await?;
+                }
+
+                for (rule, client) in rules {
+                    self.limit_inner(rule.limit, rule.duration, |bucket| {
+                        self.generate_rule_key(bucket, &rule, &client)
+                    })
+                    .await?;
+                }
+
+                Ok(())
+            }
+            .instrument(span),
+        )
+    }
+
+    fn limit_complexity<'a>(
+        &'a self,
+        context: &'a dyn RateLimiterContext,
+        cost: usize,
+    ) -> BoxFuture<'a, Result<Option<RateLimitBudget>, Error>> {
+        let config = self
+            .config_watcher
+            .borrow()
+            .gateway
+            .rate_limit
+            .as_ref()
+            .and_then(|rt| rt.complexity.clone());
+
+        let Some(config) = config else {
+            return Box::pin(async { Ok(None) });
+        };
+
+        let Some(client) = crate::rate_limiting::client_id(&config.key, context) else {
+            return Box::pin(async { Ok(None) });
+        };
+        let span = tracing::info_span!("rate limit complexity");
+
+        Box::pin(async move { self.limit_complexity_inner(client, config, cost).await.map(Some) }.instrument(span))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_is_the_time_left_in_the_window() {
+        let window = Window::at(
+            Duration::from_millis(1_003_500).as_nanos() as u64,
+            Duration::from_secs(10),
+        );
+
+        assert_eq!(window.current_bucket, Duration::from_secs(1_000).as_nanos() as u64);
+        assert_eq!(window.previous_bucket, Duration::from_secs(990).as_nanos() as u64);
+        assert_eq!(window.elapsed, 0.35);
+        assert_eq!(window.reset, Duration::from_millis(6_500));
+    }
+
+    #[test]
+    fn client_segment_has_no_separator() {
+        assert!(!client_segment("tenant:1:42").contains(':'));
+        assert_ne!(client_segment("tenant:1"), client_segment("tenant"));
     }
 }
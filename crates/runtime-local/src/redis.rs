@@ -67,6 +67,18 @@ impl RedisPoolFactory {
     }
 }
 
+/// Checks that a connection can be established and answers to a `PING`.
+pub async fn ping(pool: &Pool) -> anyhow::Result<()> {
+    let mut connection = pool
+        .get()
+        .await
+        .map_err(|error| anyhow::anyhow!("error fetching a redis connection: {error}"))?;
+
+    redis::cmd("PING").query_async::<()>(&mut *connection).await?;
+
+    Ok(())
+}
+
 fn new_pool(url: &str, tls_config: Option<RedisTlsConfig<'_>>) -> anyhow::Result<Pool> {
     let tls_config = match tls_config {
         Some(tls) => {
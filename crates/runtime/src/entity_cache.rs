@@ -1,19 +1,24 @@
-use std::{borrow::Cow, time::Duration};
+use std::{borrow::Cow, fmt, time::Duration};
 
 use bytes::Bytes;
-use futures_util::{future::BoxFuture, FutureExt};
+use futures_util::{FutureExt, future::BoxFuture};
 
 /// A simplified cache trait with just enough features to handle entity caching
 pub trait EntityCache: Send + Sync {
     fn get<'a>(&'a self, name: &'a str) -> BoxFuture<'a, anyhow::Result<Option<Bytes>>>;
 
-    /// Put an entry into the store, with an optional expiry TTL.
+    /// Put an entry into the store, with an optional expiry TTL. The tags can later be used to
+    /// purge the entry before it expires.
     fn put<'a>(
         &'a self,
         name: &'a str,
         bytes: Cow<'a, [u8]>,
         expiration_ttl: Duration,
+        tags: &'a [CacheTag],
     ) -> BoxFuture<'a, anyhow::Result<()>>;
+
+    /// Remove all entries with at least one of the given tags.
+    fn purge<'a>(&'a self, tags: &'a [CacheTag]) -> BoxFuture<'a, anyhow::Result<()>>;
 }
 
 impl EntityCache for () {
@@ -26,7 +31,81 @@ impl EntityCache for () {
         _name: &'a str,
         _bytes: Cow<'a, [u8]>,
         _expiration_ttl: Duration,
+        _tags: &'a [CacheTag],
     ) -> BoxFuture<'a, anyhow::Result<()>> {
         futures_util::future::ready(Ok(())).boxed()
     }
+
+    fn purge<'a>(&'a self, _tags: &'a [CacheTag]) -> BoxFuture<'a, anyhow::Result<()>> {
+        futures_util::future::ready(Ok(())).boxed()
+    }
+}
+
+/// Tag attached to a cache entry, allowing it to be purged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CacheTag {
+    /// Every entry fetched from this subgraph.
+    Subgraph(String),
+    /// Every entity of this type.
+    EntityType(String),
+    /// A single entity, identified by its type and key.
+    Entity { type_name: String, key: String },
+    /// Tag provided by the subgraph with the `Cache-Tag` response header.
+    Custom(String),
+}
+
+impl CacheTag {
+    /// Tag of a single entity. The key is the JSON object of the key fields, with the same tag
+    /// being generated regardless of the field order.
+    pub fn entity(type_name: impl Into<String>, key: &serde_json::Value) -> Self {
+        let mut canonical = String::new();
+        write_canonical_json(&mut canonical, key);
+
+        CacheTag::Entity {
+            type_name: type_name.into(),
+            key: canonical,
+        }
+    }
+}
+
+impl fmt::Display for CacheTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheTag::Subgraph(name) => write!(f, "subgraph:{name}"),
+            CacheTag::EntityType(name) => write!(f, "type:{name}"),
+            CacheTag::Entity { type_name, key } => write!(f, "entity:{type_name}:{key}"),
+            CacheTag::Custom(tag) => write!(f, "tag:{tag}"),
+        }
+    }
+}
+
+fn write_canonical_json(out: &mut String, value: &serde_json::Value) {
+    match value {
+        serde_json::Value::Object(fields) => {
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            fields.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+            out.push('{');
+            for (i, (name, value)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String(name.clone()).to_string());
+                out.push(':');
+                write_canonical_json(out, value);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(out, value);
+            }
+            out.push(']');
+        }
+        value => out.push_str(&value.to_string()),
+    }
 }
@@ -1,5 +1,6 @@
 use std::{borrow::Cow, future::Future, sync::Arc};
 
+use bytes::Bytes;
 use engine_schema::{GraphqlSubgraph, VirtualSubgraph};
 use error::{ErrorResponse, GraphqlError};
 use event_queue::EventQueue;
@@ -12,6 +13,8 @@ pub struct OnRequest {
     pub event_queue: Arc<EventQueue>,
     // Arc for Wasmtime because we can't return an non 'static value from a function.
     pub hooks_context: Arc<[u8]>,
+    /// Custom progressive override labels, `@override(label: "...")`, to enable for this request.
+    pub override_labels: Vec<String>,
 }
 
 pub trait GatewayHooksExtension: Clone + Send + Sync + 'static {
@@ -45,4 +48,24 @@ pub trait EngineHooksExtension<OperationContext>: Send + Sync + 'static {
         subgraph: VirtualSubgraph<'_>,
         headers: http::HeaderMap,
     ) -> impl Future<Output = Result<http::HeaderMap, GraphqlError>> + Send;
+
+    /// Whether `on_graphql_subgraph_response` does anything. If not, the subgraph response headers
+    /// aren't kept for it.
+    fn has_graphql_subgraph_response_hook(&self) -> bool;
+
+    /// Called once a GraphQL subgraph responded, after all retries.
+    fn on_graphql_subgraph_response(
+        &self,
+        context: OperationContext,
+        subgraph: GraphqlSubgraph<'_>,
+        parts: response::Parts,
+    ) -> impl Future<Output = Result<response::Parts, GraphqlError>> + Send;
+
+    /// Called with the serialized body of complete, non-streaming, GraphQL responses.
+    fn on_graphql_response(
+        &self,
+        event_queue: Arc<EventQueue>,
+        hooks_context: Arc<[u8]>,
+        body: Bytes,
+    ) -> impl Future<Output = Result<Bytes, GraphqlError>> + Send;
 }
@@ -3,7 +3,7 @@ use std::{borrow::Cow, future::Future, time::Duration};
 use bytes::Bytes;
 use engine_schema::GraphqlSubgraphId;
 use event_queue::SubgraphResponseBuilder;
-use futures_util::{stream::BoxStream, Stream, StreamExt, TryFutureExt};
+use futures_util::{Stream, StreamExt, TryFutureExt, stream::BoxStream};
 use http::Response;
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -71,6 +71,20 @@ pub struct WebsocketRequest<'a, Body> {
     pub timeout: Duration,
 }
 
+/// Health of the endpoints across which the requests of a subgraph are balanced.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SubgraphEndpointsHealth {
+    pub name: String,
+    pub endpoints: Vec<EndpointHealth>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EndpointHealth {
+    pub url: String,
+    pub healthy: bool,
+    pub inflight_requests: usize,
+}
+
 pub trait Fetcher: Send + Sync + 'static {
     fn fetch(
         &self,
@@ -89,6 +103,11 @@ pub trait Fetcher: Send + Sync + 'static {
     ) -> impl Future<Output = FetchResult<impl Stream<Item = FetchResult<serde_json::Value>> + Send + 'static>> + Send
     where
         T: serde::Serialize + Send;
+
+    /// Only subgraphs balanced across multiple endpoints are reported.
+    fn endpoints_health(&self) -> Vec<SubgraphEndpointsHealth> {
+        Vec::new()
+    }
 }
 
 pub mod dynamic {
@@ -117,6 +136,10 @@ pub mod dynamic {
         ) -> FetchResult<BoxStream<'static, FetchResult<serde_json::Value>>> {
             unreachable!()
         }
+
+        fn endpoints_health(&self) -> Vec<SubgraphEndpointsHealth> {
+            Vec::new()
+        }
     }
 
     #[derive(Clone)]
@@ -172,6 +195,10 @@ pub mod dynamic {
                 })
                 .await
         }
+
+        fn endpoints_health(&self) -> Vec<SubgraphEndpointsHealth> {
+            self.0.endpoints_health()
+        }
     }
 
     struct DynWrapper<T>(T);
@@ -204,5 +231,9 @@ pub mod dynamic {
                 .map_ok(|stream| stream.boxed())
                 .await
         }
+
+        fn endpoints_health(&self) -> Vec<SubgraphEndpointsHealth> {
+            self.0.endpoints_health()
+        }
     }
 }
@@ -0,0 +1,8 @@
+/// Reachability of an external dependency of the engine, a Redis server for example.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DependencyHealth {
+    pub name: &'static str,
+    pub reachable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -1,18 +1,35 @@
 use std::borrow::Cow;
 use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
-use futures_util::future::BoxFuture;
 use futures_util::FutureExt;
+use futures_util::future::BoxFuture;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Too many requests")]
-    ExceededCapacity,
+    ExceededCapacity {
+        /// Time after which the request would be accepted, if known.
+        retry_after: Option<Duration>,
+    },
+    #[error("Complexity budget exceeded")]
+    ExceededBudget(RateLimitBudget),
     #[error("internal error: {0}")]
     Internal(String),
 }
 
+impl Error {
+    /// Time after which a rate limited request would be accepted, if known.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::ExceededCapacity { retry_after } => *retry_after,
+            Error::ExceededBudget(budget) => Some(budget.reset),
+            Error::Internal(_) => None,
+        }
+    }
+}
+
 pub trait RateLimiterContext: Send + Sync {
     fn header(&self, name: http::HeaderName) -> Option<&http::HeaderValue>;
     fn graphql_operation_name(&self) -> Option<&str>;
@@ -24,8 +41,27 @@ pub trait RateLimiterContext: Send + Sync {
     }
 }
 
+/// Complexity points left to a client in the current window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitBudget {
+    pub limit: usize,
+    pub remaining: usize,
+    /// Time until the budget is fully replenished.
+    pub reset: Duration,
+}
+
 pub trait RateLimiterInner: Send + Sync {
     fn limit<'a>(&'a self, context: &'a dyn RateLimiterContext) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Consumes `cost` complexity points from the budget of the client identified by the context.
+    /// Returns `None` if no complexity rate limit is configured.
+    fn limit_complexity<'a>(
+        &'a self,
+        _context: &'a dyn RateLimiterContext,
+        _cost: usize,
+    ) -> BoxFuture<'a, Result<Option<RateLimitBudget>, Error>> {
+        async { Ok(None) }.boxed()
+    }
 }
 
 impl RateLimiterInner for () {
@@ -57,6 +93,8 @@ impl RateLimiter {
 pub enum RateLimitKey<'a> {
     Global,
     Subgraph(Cow<'a, str>),
+    /// A single operation, limited by the rules keyed on the client.
+    Operation,
 }
 
 impl<'a> From<&'a str> for RateLimitKey<'a> {
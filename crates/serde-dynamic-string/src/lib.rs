@@ -10,6 +10,10 @@ use regex::Regex;
 use serde::Serialize;
 use serde_with::DeserializeFromStr;
 
+/// Scopes of the variables resolved at request time by the gateway, such as `{{ jwt.sub }}` in a
+/// header rule. They're kept as is by [DynamicString::with_request_scopes].
+const REQUEST_SCOPES: &[&str] = &["jwt", "header", "client", "operation"];
+
 /// A wrapper type for Serde structures that can be (de-)serialized from a string.
 /// If wrapping a type with this wrapper, one can pass values through env vars with the syntax
 /// "{{ env.FOO }}".
@@ -31,16 +35,15 @@ where
     pub fn into_inner(self) -> T {
         self.0
     }
-}
 
-impl<T> FromStr for DynamicString<T>
-where
-    T::Err: std::error::Error,
-    T: FromStr + AsRef<str> + Default + Write + Clone,
-{
-    type Err = String;
+    /// Like parsing the string, but keeps the variables resolved at request time, such as
+    /// `{{ jwt.sub }}`, instead of rejecting them. Only for values the gateway renders for each
+    /// request, like header rule values.
+    pub fn with_request_scopes(string: &str) -> Result<Self, String> {
+        Self::parse(string, true)
+    }
 
-    fn from_str(string: &str) -> Result<Self, Self::Err> {
+    fn parse(string: &str, keep_request_scopes: bool) -> Result<Self, String> {
         /// Matches any "{{ something }}"
         fn re() -> &'static Regex {
             static RE: OnceLock<Regex> = OnceLock::new();
@@ -57,17 +60,26 @@ where
             let key = captures.get(1).unwrap().as_str();
             let path = key.split('.');
 
-            if let Some(("env", variable_name)) = path.collect_tuple() {
-                // this is true if we have data between the current and the last match
-                // e.g. `{{ env.FOO }} {{ env.BAR }}`
-                //                    ^ we get this string
-                if overall_match.start() > last_end {
-                    match T::from_str(&string[last_end..overall_match.start()]) {
-                        Ok(value) => result.write_str(value.as_ref()).expect("must succeed"),
-                        Err(e) => errors.push(e.to_string()),
-                    }
+            // this is true if we have data between the current and the last match
+            // e.g. `{{ env.FOO }} {{ env.BAR }}`
+            //                    ^ we get this string
+            if overall_match.start() > last_end {
+                match T::from_str(&string[last_end..overall_match.start()]) {
+                    Ok(value) => result.write_str(value.as_ref()).expect("must succeed"),
+                    Err(e) => errors.push(e.to_string()),
                 }
+            }
 
+            if keep_request_scopes
+                && key
+                    .split_once('.')
+                    .is_some_and(|(scope, _)| REQUEST_SCOPES.contains(&scope))
+            {
+                match T::from_str(overall_match.as_str()) {
+                    Ok(value) => result.write_str(value.as_ref()).expect("must succeed"),
+                    Err(e) => errors.push(e.to_string()),
+                }
+            } else if let Some(("env", variable_name)) = path.collect_tuple() {
                 // fetches the value from the environment
                 match std::env::var(variable_name) {
                     Ok(ref value) => match T::from_str(value) {
@@ -100,6 +112,18 @@ where
     }
 }
 
+impl<T> FromStr for DynamicString<T>
+where
+    T::Err: std::error::Error,
+    T: FromStr + AsRef<str> + Default + Write + Clone,
+{
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Self::parse(string, false)
+    }
+}
+
 impl<T> AsRef<str> for DynamicString<T>
 where
     T::Err: std::error::Error,
@@ -255,6 +279,21 @@ mod tests {
         });
     }
 
+    #[test]
+    fn request_scope_is_kept() {
+        temp_env::with_var("FOOBAR", Some("some_value"), || {
+            let result = DynamicString::<String>::with_request_scopes("{{ env.FOOBAR }}:{{ jwt.sub }}").unwrap();
+            assert_eq!("some_value:{{ jwt.sub }}", result.as_ref());
+        });
+    }
+
+    #[test]
+    fn request_scope_is_rejected_by_default() {
+        let error = "{{ jwt.sub }}".parse::<DynamicString<String>>().unwrap_err();
+
+        insta::assert_snapshot!(&error, @"right now only variables scoped with 'env.' are supported: `jwt.sub`");
+    }
+
     #[test]
     fn non_env_scope() {
         let error = "{{ meow.FOO }}".parse::<DynamicString<String>>().unwrap_err();
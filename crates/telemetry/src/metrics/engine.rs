@@ -14,6 +14,7 @@ pub struct EngineMetrics {
     operation_latency: Histogram<u64>,
     subgraph_latency: Histogram<u64>,
     subgraph_retries: Counter<u64>,
+    subgraph_circuit_breaker_transitions: Counter<u64>,
     subgraph_request_body_size: Histogram<u64>,
     subgraph_response_body_size: Histogram<u64>,
     subgraph_requests_inflight: UpDownCounter<i64>,
@@ -48,6 +49,13 @@ pub struct SubgraphRequestRetryAttributes {
     pub aborted: bool,
 }
 
+#[derive(Debug)]
+pub struct SubgraphCircuitBreakerAttributes {
+    pub name: String,
+    pub from: &'static str,
+    pub to: &'static str,
+}
+
 #[derive(Debug)]
 pub struct SubgraphRequestBodySizeAttributes {
     pub name: String,
@@ -99,6 +107,9 @@ impl EngineMetrics {
                 .with_unit("ms")
                 .build(),
             subgraph_retries: meter.u64_counter("graphql.subgraph.request.retries").build(),
+            subgraph_circuit_breaker_transitions: meter
+                .u64_counter("graphql.subgraph.circuit_breaker.transitions")
+                .build(),
             subgraph_request_body_size: meter.u64_histogram("graphql.subgraph.request.body.size").build(),
             subgraph_response_body_size: meter.u64_histogram("graphql.subgraph.response.body.size").build(),
             subgraph_requests_inflight: meter.i64_up_down_counter("graphql.subgraph.request.inflight").build(),
@@ -200,6 +211,19 @@ impl EngineMetrics {
         self.subgraph_retries.add(1, &attributes);
     }
 
+    pub fn record_subgraph_circuit_breaker_transition(
+        &self,
+        SubgraphCircuitBreakerAttributes { name, from, to }: SubgraphCircuitBreakerAttributes,
+    ) {
+        let attributes = [
+            KeyValue::new("graphql.subgraph.name", name),
+            KeyValue::new("graphql.subgraph.circuit_breaker.from", from),
+            KeyValue::new("graphql.subgraph.circuit_breaker.to", to),
+        ];
+
+        self.subgraph_circuit_breaker_transitions.add(1, &attributes);
+    }
+
     pub fn record_subgraph_request_size(
         &self,
         SubgraphRequestBodySizeAttributes { name }: SubgraphRequestBodySizeAttributes,
@@ -6,6 +6,10 @@ pub mod layer;
 pub mod logs;
 /// metrics related otel functions
 pub mod metrics;
+/// Prometheus metrics exporter
+pub mod prometheus;
+/// Sampling decided once the request has finished.
+mod tail_sampling;
 /// For creation of a tracing provider.
 pub mod traces;
 
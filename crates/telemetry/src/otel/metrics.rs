@@ -62,6 +62,14 @@ pub(super) fn build_meter_provider(
         provider = attach_reader(config, provider)?;
     }
 
+    if let Some(config) = config.metrics_prometheus_config() {
+        let reader = PeriodicReader::builder(super::prometheus::PrometheusExporter::global())
+            .with_interval(config.refresh_interval)
+            .build();
+
+        provider = provider.with_reader(reader);
+    }
+
     if let Some(config) = config.grafbase_otlp_config() {
         provider = attach_reader(config, provider)?;
     }
@@ -0,0 +1,380 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Write as _},
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::{
+    error::OTelSdkResult,
+    metrics::{
+        Temporality,
+        data::{AggregatedMetrics, Metric, MetricData, ResourceMetrics},
+        exporter::PushMetricExporter,
+    },
+};
+
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+static SNAPSHOT: OnceLock<Arc<Mutex<String>>> = OnceLock::new();
+
+/// The metrics in the Prometheus text exposition format, as of the last export. Returns `None`
+/// if the Prometheus exporter isn't enabled.
+pub fn snapshot() -> Option<String> {
+    SNAPSHOT.get().map(|snapshot| snapshot.lock().unwrap().clone())
+}
+
+/// Renders the cumulative metrics in the Prometheus text exposition format at every export. The
+/// result is served by the gateway through [snapshot].
+#[derive(Debug)]
+pub struct PrometheusExporter {
+    snapshot: Arc<Mutex<String>>,
+}
+
+impl PrometheusExporter {
+    /// There is a single meter provider in the process, so a single exporter.
+    pub fn global() -> Self {
+        Self {
+            snapshot: SNAPSHOT.get_or_init(Default::default).clone(),
+        }
+    }
+}
+
+impl PushMetricExporter for PrometheusExporter {
+    async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
+        let text = render(metrics);
+        *self.snapshot.lock().unwrap() = text;
+        Ok(())
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        Ok(())
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        Ok(())
+    }
+
+    fn temporality(&self) -> Temporality {
+        Temporality::Cumulative
+    }
+}
+
+struct Family {
+    ty: &'static str,
+    help: String,
+    samples: String,
+}
+
+fn render(metrics: &ResourceMetrics) -> String {
+    // Resource attributes, like the service name, are added as labels to every sample.
+    let resource_labels = metrics
+        .resource()
+        .iter()
+        .map(|(key, value)| (sanitize_name(key.as_str()), value.to_string()))
+        .collect::<Vec<_>>();
+
+    let mut families = BTreeMap::<String, Family>::new();
+    for scope in metrics.scope_metrics() {
+        for metric in scope.metrics() {
+            let name = metric_name(metric);
+            let family = families.entry(name.clone()).or_insert_with(|| Family {
+                ty: "untyped",
+                help: metric.description().to_string(),
+                samples: String::new(),
+            });
+
+            match metric.data() {
+                AggregatedMetrics::F64(data) => render_data(&name, family, &resource_labels, data),
+                AggregatedMetrics::U64(data) => render_data(&name, family, &resource_labels, data),
+                AggregatedMetrics::I64(data) => render_data(&name, family, &resource_labels, data),
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for (name, family) in families {
+        if !family.help.is_empty() {
+            writeln!(out, "# HELP {name} {}", escape_help(&family.help)).ok();
+        }
+        writeln!(out, "# TYPE {name} {}", family.ty).ok();
+        out.push_str(&family.samples);
+    }
+
+    out
+}
+
+fn render_data<T: Display + Copy>(
+    name: &str,
+    family: &mut Family,
+    resource_labels: &[(String, String)],
+    data: &MetricData<T>,
+) {
+    let out = &mut family.samples;
+
+    match data {
+        MetricData::Gauge(gauge) => {
+            family.ty = "gauge";
+            for point in gauge.data_points() {
+                let labels = labels(resource_labels, point.attributes(), None);
+                writeln!(out, "{name}{labels} {}", point.value()).ok();
+            }
+        }
+        MetricData::Sum(sum) => {
+            family.ty = if sum.is_monotonic() { "counter" } else { "gauge" };
+            for point in sum.data_points() {
+                let labels = labels(resource_labels, point.attributes(), None);
+                writeln!(out, "{name}{labels} {}", point.value()).ok();
+            }
+        }
+        MetricData::Histogram(histogram) => {
+            family.ty = "histogram";
+            for point in histogram.data_points() {
+                let mut cumulative = 0;
+                for (bound, count) in point.bounds().zip(point.bucket_counts()) {
+                    cumulative += count;
+                    let labels = labels(resource_labels, point.attributes(), Some(&bound.to_string()));
+                    writeln!(out, "{name}_bucket{labels} {cumulative}").ok();
+                }
+                render_histogram_totals(
+                    out,
+                    name,
+                    resource_labels,
+                    point.attributes(),
+                    point.count(),
+                    point.sum(),
+                );
+            }
+        }
+        MetricData::ExponentialHistogram(histogram) => {
+            family.ty = "histogram";
+            for point in histogram.data_points() {
+                // Prometheus text format has no exponential buckets, so they're converted to
+                // regular ones. Bucket `index` holds values within (base^index, base^(index + 1)].
+                let base = 2f64.powf(2f64.powi(-(point.scale() as i32)));
+                let mut cumulative = point.zero_count() + point.negative_bucket().counts().sum::<u64>();
+                let offset = point.positive_bucket().offset();
+
+                for (i, count) in point.positive_bucket().counts().enumerate() {
+                    if count == 0 {
+                        continue;
+                    }
+                    cumulative += count;
+                    let bound = base.powi(offset + i as i32 + 1);
+                    let labels = labels(resource_labels, point.attributes(), Some(&bound.to_string()));
+                    writeln!(out, "{name}_bucket{labels} {cumulative}").ok();
+                }
+                render_histogram_totals(
+                    out,
+                    name,
+                    resource_labels,
+                    point.attributes(),
+                    point.count(),
+                    point.sum(),
+                );
+            }
+        }
+    }
+}
+
+fn render_histogram_totals<'a, T: Display>(
+    out: &mut String,
+    name: &str,
+    resource_labels: &[(String, String)],
+    attributes: impl Iterator<Item = &'a KeyValue> + Clone,
+    count: impl Display,
+    sum: T,
+) {
+    let bucket_labels = labels(resource_labels, attributes.clone(), Some("+Inf"));
+    writeln!(out, "{name}_bucket{bucket_labels} {count}").ok();
+
+    let labels = labels(resource_labels, attributes, None);
+    writeln!(out, "{name}_sum{labels} {sum}").ok();
+    writeln!(out, "{name}_count{labels} {count}").ok();
+}
+
+fn labels<'a>(
+    resource_labels: &[(String, String)],
+    attributes: impl Iterator<Item = &'a KeyValue>,
+    le: Option<&str>,
+) -> String {
+    let mut out = String::new();
+    let labels = resource_labels
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .chain(attributes.map(|kv| (sanitize_name(kv.key.as_str()), kv.value.to_string())))
+        .chain(le.map(|le| ("le".to_string(), le.to_string())));
+
+    for (key, value) in labels {
+        out.push(if out.is_empty() { '{' } else { ',' });
+        write!(out, "{key}=\"{}\"", escape_label_value(&value)).ok();
+    }
+    if !out.is_empty() {
+        out.push('}');
+    }
+
+    out
+}
+
+/// Follows the OpenTelemetry to Prometheus naming conventions: `http.server.request.duration`
+/// with unit `ms` becomes `http_server_request_duration_milliseconds`.
+fn metric_name(metric: &Metric) -> String {
+    let mut name = sanitize_name(metric.name());
+
+    let unit = match metric.unit() {
+        "ms" => Some("milliseconds"),
+        "s" => Some("seconds"),
+        "By" => Some("bytes"),
+        _ => None,
+    };
+    if let Some(unit) = unit
+        && !name.ends_with(unit)
+    {
+        name.push('_');
+        name.push_str(unit);
+    }
+
+    let is_counter = match metric.data() {
+        AggregatedMetrics::F64(MetricData::Sum(sum)) => sum.is_monotonic(),
+        AggregatedMetrics::U64(MetricData::Sum(sum)) => sum.is_monotonic(),
+        AggregatedMetrics::I64(MetricData::Sum(sum)) => sum.is_monotonic(),
+        _ => false,
+    };
+    if is_counter && !name.ends_with("_total") {
+        name.push_str("_total");
+    }
+
+    name
+}
+
+fn sanitize_name(name: &str) -> String {
+    let mut name = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect::<String>();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry::{KeyValue, metrics::MeterProvider as _};
+    use opentelemetry_sdk::{
+        Resource,
+        metrics::{PeriodicReader, SdkMeterProvider},
+    };
+
+    use super::*;
+
+    fn render_with(record: impl FnOnce(&opentelemetry::metrics::Meter)) -> String {
+        let exporter = PrometheusExporter {
+            snapshot: Default::default(),
+        };
+        let snapshot = exporter.snapshot.clone();
+
+        let provider = SdkMeterProvider::builder()
+            .with_resource(
+                Resource::builder_empty()
+                    .with_attribute(KeyValue::new("service.name", "gateway"))
+                    .build(),
+            )
+            .with_reader(PeriodicReader::builder(exporter).build())
+            .build();
+
+        record(&provider.meter("test"));
+        provider.force_flush().unwrap();
+
+        snapshot.lock().unwrap().clone()
+    }
+
+    #[test]
+    fn counters_and_gauges() {
+        let text = render_with(|meter| {
+            let counter = meter
+                .u64_counter("graphql.operation.cache.hit")
+                .with_description("Number of operation cache hits")
+                .build();
+            counter.add(2, &[KeyValue::new("graphql.operation.type", "query")]);
+            counter.add(1, &[KeyValue::new("graphql.operation.type", "query")]);
+
+            let inflight = meter.i64_up_down_counter("graphql.subgraph.request.inflight").build();
+            inflight.add(3, &[]);
+            inflight.add(-1, &[]);
+
+            meter.u64_gauge("pool.connections").build().record(7, &[]);
+        });
+
+        insta::assert_snapshot!(text, @r#"
+        # HELP graphql_operation_cache_hit_total Number of operation cache hits
+        # TYPE graphql_operation_cache_hit_total counter
+        graphql_operation_cache_hit_total{service_name="gateway",graphql_operation_type="query"} 3
+        # TYPE graphql_subgraph_request_inflight gauge
+        graphql_subgraph_request_inflight{service_name="gateway"} 2
+        # TYPE pool_connections gauge
+        pool_connections{service_name="gateway"} 7
+        "#);
+    }
+
+    #[test]
+    fn histograms() {
+        let text = render_with(|meter| {
+            let histogram = meter
+                .f64_histogram("http.server.request.duration")
+                .with_unit("ms")
+                .with_boundaries(vec![10.0, 100.0])
+                .build();
+
+            for value in [5.0, 50.0, 500.0] {
+                histogram.record(value, &[]);
+            }
+        });
+
+        insta::assert_snapshot!(text, @r#"
+        # TYPE http_server_request_duration_milliseconds histogram
+        http_server_request_duration_milliseconds_bucket{service_name="gateway",le="10"} 1
+        http_server_request_duration_milliseconds_bucket{service_name="gateway",le="100"} 2
+        http_server_request_duration_milliseconds_bucket{service_name="gateway",le="+Inf"} 3
+        http_server_request_duration_milliseconds_sum{service_name="gateway"} 555
+        http_server_request_duration_milliseconds_count{service_name="gateway"} 3
+        "#);
+    }
+
+    #[test]
+    fn label_values_and_help_are_escaped() {
+        let text = render_with(|meter| {
+            meter
+                .u64_gauge("cache.entries")
+                .with_description("Entries\nin the \\ cache")
+                .build()
+                .record(1, &[KeyValue::new("path", "C:\\cache \"main\"\nsecond")]);
+        });
+
+        insta::assert_snapshot!(text, @r##"
+        # HELP cache_entries Entries\nin the \\ cache
+        # TYPE cache_entries gauge
+        cache_entries{service_name="gateway",path="C:\\cache \"main\"\nsecond"} 1
+        "##);
+    }
+
+    #[test]
+    fn names_are_sanitized() {
+        assert_eq!(
+            sanitize_name("http.server.request-duration"),
+            "http_server_request_duration"
+        );
+        assert_eq!(sanitize_name("graphql_operations"), "graphql_operations");
+        assert_eq!(sanitize_name("2xx.responses"), "_2xx_responses");
+        assert_eq!(sanitize_name("café"), "caf_");
+    }
+}
@@ -0,0 +1,583 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::Duration,
+};
+
+use gateway_config::{TailSamplingConfig, TracingConfig};
+use opentelemetry::{
+    Context, Value,
+    trace::{Status, TraceId},
+};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    trace::{Span, SpanData, SpanProcessor},
+};
+
+use crate::span::kind::GrafbaseSpanKind;
+
+/// Buffers the spans of each trace until its HTTP request span ends, and only then decides
+/// whether the whole trace is forwarded to the exporters. Head sampling must keep every trace for
+/// this to work, the configured sampling ratio is applied here instead.
+#[derive(Debug)]
+pub(super) struct TailSamplingProcessor {
+    config: TailSamplingConfig,
+    default_sampling: f64,
+    processors: Vec<Box<dyn SpanProcessor>>,
+    traces: Mutex<Traces>,
+}
+
+#[derive(Debug, Default)]
+struct Traces {
+    pending: HashMap<TraceId, Vec<SpanData>>,
+    pending_order: VecDeque<TraceId>,
+    /// Spans can end after the request span, for example background work, so we keep the recent
+    /// decisions around.
+    decided: HashMap<TraceId, bool>,
+    decided_order: VecDeque<TraceId>,
+}
+
+impl Traces {
+    fn decide(&mut self, trace_id: TraceId, keep: bool, max_buffered_traces: usize) {
+        self.decided.insert(trace_id, keep);
+        self.decided_order.push_back(trace_id);
+
+        if self.decided_order.len() > max_buffered_traces
+            && let Some(oldest) = self.decided_order.pop_front()
+        {
+            self.decided.remove(&oldest);
+        }
+    }
+}
+
+impl TailSamplingProcessor {
+    pub(super) fn new(config: &TracingConfig, tail_sampling: &TailSamplingConfig) -> Self {
+        Self {
+            config: tail_sampling.clone(),
+            default_sampling: config.sampling,
+            processors: Vec::new(),
+            traces: Default::default(),
+        }
+    }
+
+    pub(super) fn with_processor(mut self, processor: impl SpanProcessor + 'static) -> Self {
+        self.processors.push(Box::new(processor));
+        self
+    }
+
+    fn export(&self, spans: Vec<SpanData>) {
+        for span in spans {
+            if let Some((last, others)) = self.processors.split_last() {
+                for processor in others {
+                    processor.on_end(span.clone());
+                }
+                last.on_end(span);
+            }
+        }
+    }
+
+    /// Traces evicted from the buffer, for example background work, may lack the HTTP request
+    /// span. The latency threshold and client name rules don't apply to them.
+    fn should_keep(&self, trace_id: TraceId, spans: &[SpanData]) -> bool {
+        let root = spans.iter().find(|span| is_kind(span, GrafbaseSpanKind::HttpRequest));
+
+        if self.config.keep_errors
+            && spans.iter().any(|span| {
+                (is_kind(span, GrafbaseSpanKind::HttpRequest) || is_kind(span, GrafbaseSpanKind::GraphqlOperation))
+                    && (matches!(span.status, Status::Error { .. })
+                        || i64_attribute(span, "graphql.response.errors.count").is_some_and(|count| count > 0))
+            })
+        {
+            return true;
+        }
+
+        if self.config.keep_subgraph_failures
+            && spans.iter().any(|span| {
+                is_kind(span, GrafbaseSpanKind::SubgraphGraphqlRequest) && matches!(span.status, Status::Error { .. })
+            })
+        {
+            return true;
+        }
+
+        if let Some(threshold) = self.config.latency_threshold
+            && let Some(root) = root
+            && root.end_time.duration_since(root.start_time).unwrap_or_default() >= threshold
+        {
+            return true;
+        }
+
+        let operation_name = spans
+            .iter()
+            .find_map(|span| str_attribute(span, "graphql.operation.name"));
+        let client_name = root.and_then(|root| str_attribute(root, "http.request.header.x-grafbase-client-name"));
+
+        let sampling = self
+            .config
+            .rules
+            .iter()
+            .find(|rule| {
+                rule.operation_name
+                    .as_deref()
+                    .is_none_or(|name| operation_name.as_deref() == Some(name))
+                    && rule
+                        .client_name
+                        .as_deref()
+                        .is_none_or(|name| client_name.as_deref() == Some(name))
+            })
+            .map(|rule| rule.sampling)
+            .unwrap_or(self.default_sampling);
+
+        is_sampled(trace_id, sampling)
+    }
+}
+
+impl SpanProcessor for TailSamplingProcessor {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        for processor in &self.processors {
+            processor.on_start(span, cx);
+        }
+    }
+
+    fn on_end(&self, span: SpanData) {
+        let trace_id = span.span_context.trace_id();
+        let mut traces = self.traces.lock().unwrap();
+
+        if let Some(keep) = traces.decided.get(&trace_id).copied() {
+            drop(traces);
+            if keep {
+                self.export(vec![span]);
+            }
+            return;
+        }
+
+        if !is_kind(&span, GrafbaseSpanKind::HttpRequest) {
+            let mut evicted = None;
+
+            if !traces.pending.contains_key(&trace_id) {
+                traces.pending_order.push_back(trace_id);
+                if traces.pending_order.len() > self.config.max_buffered_traces
+                    && let Some(oldest) = traces.pending_order.pop_front()
+                {
+                    evicted = traces.pending.remove(&oldest).map(|spans| (oldest, spans));
+                }
+            }
+            traces.pending.entry(trace_id).or_default().push(span);
+
+            // The evicted trace may never get a request span, so it's decided now.
+            let evicted = evicted.filter(|(oldest, spans)| {
+                let keep = self.should_keep(*oldest, spans);
+                traces.decide(*oldest, keep, self.config.max_buffered_traces);
+                keep
+            });
+            drop(traces);
+
+            if let Some((_, spans)) = evicted {
+                self.export(spans);
+            }
+            return;
+        }
+
+        let mut spans = traces.pending.remove(&trace_id).unwrap_or_default();
+        traces.pending_order.retain(|id| *id != trace_id);
+        spans.push(span);
+
+        let keep = self.should_keep(trace_id, &spans);
+        traces.decide(trace_id, keep, self.config.max_buffered_traces);
+        drop(traces);
+
+        if keep {
+            self.export(spans);
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.processors
+            .iter()
+            .map(|processor| processor.force_flush())
+            .fold(Ok(()), |result, other| result.and(other))
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        let pending = {
+            let mut traces = self.traces.lock().unwrap();
+            traces.pending_order.clear();
+            std::mem::take(&mut traces.pending)
+        };
+
+        for (trace_id, spans) in pending {
+            if self.should_keep(trace_id, &spans) {
+                self.export(spans);
+            }
+        }
+
+        self.processors
+            .iter()
+            .map(|processor| processor.shutdown_with_timeout(timeout))
+            .fold(Ok(()), |result, other| result.and(other))
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        for processor in &mut self.processors {
+            processor.set_resource(resource);
+        }
+    }
+}
+
+fn attribute<'a>(span: &'a SpanData, key: &str) -> Option<&'a Value> {
+    span.attributes
+        .iter()
+        .find(|kv| kv.key.as_str() == key)
+        .map(|kv| &kv.value)
+}
+
+fn str_attribute(span: &SpanData, key: &str) -> Option<String> {
+    attribute(span, key).map(|value| value.as_str().into_owned())
+}
+
+fn i64_attribute(span: &SpanData, key: &str) -> Option<i64> {
+    match attribute(span, key)? {
+        Value::I64(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn is_kind(span: &SpanData, kind: GrafbaseSpanKind) -> bool {
+    attribute(span, "grafbase.kind").is_some_and(|value| value.as_str() == kind.as_ref())
+}
+
+/// Same deterministic decision as the `TraceIdRatioBased` sampler, so all gateways sharing a
+/// trace agree.
+fn is_sampled(trace_id: TraceId, ratio: f64) -> bool {
+    if ratio >= 1.0 {
+        return true;
+    }
+    let bytes = trace_id.to_bytes();
+    let low = u64::from_be_bytes(bytes[8..16].try_into().unwrap()) >> 1;
+    let threshold = (ratio.max(0.0) * (1u64 << 63) as f64) as u64;
+    low < threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Duration, SystemTime},
+    };
+
+    use gateway_config::{SamplingRuleConfig, TailSamplingConfig};
+    use opentelemetry::{
+        Context, KeyValue,
+        trace::{Span as _, Status, TraceId, Tracer as _, TracerProvider as _},
+    };
+    use opentelemetry_sdk::{
+        error::OTelSdkResult,
+        trace::{SdkTracerProvider, Span, SpanData, SpanProcessor},
+    };
+
+    use super::{TailSamplingProcessor, is_sampled};
+    use crate::span::kind::GrafbaseSpanKind;
+
+    const SAMPLED: u128 = 1 << 64;
+    const NOT_SAMPLED: u128 = u128::MAX;
+
+    #[derive(Debug, Clone, Default)]
+    struct Exported(Arc<Mutex<Vec<SpanData>>>);
+
+    impl Exported {
+        fn names(&self) -> Vec<String> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|span| span.name.to_string())
+                .collect()
+        }
+    }
+
+    impl SpanProcessor for Exported {
+        fn on_start(&self, _: &mut Span, _: &Context) {}
+
+        fn on_end(&self, span: SpanData) {
+            self.0.lock().unwrap().push(span);
+        }
+
+        fn force_flush(&self) -> OTelSdkResult {
+            Ok(())
+        }
+
+        fn shutdown_with_timeout(&self, _: Duration) -> OTelSdkResult {
+            Ok(())
+        }
+    }
+
+    struct Harness {
+        provider: SdkTracerProvider,
+        exported: Exported,
+    }
+
+    impl Harness {
+        fn new(config: TailSamplingConfig, default_sampling: f64) -> Self {
+            let exported = Exported::default();
+
+            let processor = TailSamplingProcessor {
+                config,
+                default_sampling,
+                processors: Vec::new(),
+                traces: Default::default(),
+            }
+            .with_processor(exported.clone());
+
+            let provider = SdkTracerProvider::builder().with_span_processor(processor).build();
+
+            Self { provider, exported }
+        }
+
+        fn end_span(&self, trace_id: u128, name: &'static str, kind: GrafbaseSpanKind, attributes: Vec<KeyValue>) {
+            self.end_span_with(trace_id, name, kind, attributes, Status::Unset, Duration::ZERO);
+        }
+
+        fn end_span_with(
+            &self,
+            trace_id: u128,
+            name: &'static str,
+            kind: GrafbaseSpanKind,
+            mut attributes: Vec<KeyValue>,
+            status: Status,
+            duration: Duration,
+        ) {
+            let tracer = self.provider.tracer("tail-sampling-test");
+            attributes.push(KeyValue::new("grafbase.kind", kind.as_ref().to_string()));
+
+            let end_time = SystemTime::now();
+            let mut span = tracer
+                .span_builder(name)
+                .with_trace_id(TraceId::from_bytes(trace_id.to_be_bytes()))
+                .with_start_time(end_time - duration)
+                .with_attributes(attributes)
+                .with_status(status)
+                .start(&tracer);
+
+            span.end_with_timestamp(end_time);
+        }
+    }
+
+    fn config() -> TailSamplingConfig {
+        TailSamplingConfig::default()
+    }
+
+    #[test]
+    fn sampling_is_deterministic_on_the_trace_id() {
+        assert!(is_sampled(TraceId::from_bytes(NOT_SAMPLED.to_be_bytes()), 1.0));
+        assert!(!is_sampled(TraceId::from_bytes(SAMPLED.to_be_bytes()), 0.0));
+
+        assert!(is_sampled(TraceId::from_bytes(SAMPLED.to_be_bytes()), 0.01));
+        assert!(!is_sampled(TraceId::from_bytes(NOT_SAMPLED.to_be_bytes()), 0.99));
+
+        let half = TraceId::from_bytes((1_u128 << 63).to_be_bytes());
+        assert!(is_sampled(half, 0.6));
+        assert!(!is_sampled(half, 0.4));
+    }
+
+    #[test]
+    fn spans_are_buffered_until_the_request_span_ends() {
+        let harness = Harness::new(config(), 1.0);
+
+        harness.end_span(SAMPLED, "operation", GrafbaseSpanKind::GraphqlOperation, Vec::new());
+        assert!(harness.exported.names().is_empty());
+
+        harness.end_span(SAMPLED, "request", GrafbaseSpanKind::HttpRequest, Vec::new());
+        assert_eq!(harness.exported.names(), ["operation", "request"]);
+    }
+
+    #[test]
+    fn unsampled_traces_are_dropped() {
+        let harness = Harness::new(config(), 0.5);
+
+        harness.end_span(NOT_SAMPLED, "operation", GrafbaseSpanKind::GraphqlOperation, Vec::new());
+        harness.end_span(NOT_SAMPLED, "request", GrafbaseSpanKind::HttpRequest, Vec::new());
+
+        assert!(harness.exported.names().is_empty());
+    }
+
+    #[test]
+    fn traces_with_errors_are_kept() {
+        let harness = Harness::new(config(), 0.0);
+
+        harness.end_span(
+            1,
+            "operation",
+            GrafbaseSpanKind::GraphqlOperation,
+            vec![KeyValue::new("graphql.response.errors.count", 2_i64)],
+        );
+        harness.end_span(1, "request", GrafbaseSpanKind::HttpRequest, Vec::new());
+
+        assert_eq!(harness.exported.names(), ["operation", "request"]);
+
+        let harness = Harness::new(
+            TailSamplingConfig {
+                keep_errors: false,
+                ..config()
+            },
+            0.0,
+        );
+
+        harness.end_span(
+            1,
+            "operation",
+            GrafbaseSpanKind::GraphqlOperation,
+            vec![KeyValue::new("graphql.response.errors.count", 2_i64)],
+        );
+        harness.end_span(1, "request", GrafbaseSpanKind::HttpRequest, Vec::new());
+
+        assert!(harness.exported.names().is_empty());
+    }
+
+    #[test]
+    fn traces_with_subgraph_failures_are_kept() {
+        let harness = Harness::new(config(), 0.0);
+
+        harness.end_span_with(
+            1,
+            "subgraph",
+            GrafbaseSpanKind::SubgraphGraphqlRequest,
+            Vec::new(),
+            Status::error("connection refused"),
+            Duration::ZERO,
+        );
+        harness.end_span(1, "request", GrafbaseSpanKind::HttpRequest, Vec::new());
+
+        assert_eq!(harness.exported.names(), ["subgraph", "request"]);
+    }
+
+    #[test]
+    fn slow_traces_are_kept() {
+        let harness = Harness::new(
+            TailSamplingConfig {
+                latency_threshold: Some(Duration::from_secs(1)),
+                ..config()
+            },
+            0.0,
+        );
+
+        harness.end_span_with(
+            1,
+            "slow",
+            GrafbaseSpanKind::HttpRequest,
+            Vec::new(),
+            Status::Unset,
+            Duration::from_secs(2),
+        );
+        harness.end_span(2, "fast", GrafbaseSpanKind::HttpRequest, Vec::new());
+
+        assert_eq!(harness.exported.names(), ["slow"]);
+    }
+
+    #[test]
+    fn first_matching_rule_overrides_the_default_sampling() {
+        let harness = Harness::new(
+            TailSamplingConfig {
+                rules: vec![
+                    SamplingRuleConfig {
+                        operation_name: Some("Checkout".into()),
+                        client_name: None,
+                        sampling: 1.0,
+                    },
+                    SamplingRuleConfig {
+                        operation_name: None,
+                        client_name: Some("mobile".into()),
+                        sampling: 1.0,
+                    },
+                ],
+                ..config()
+            },
+            0.0,
+        );
+
+        for (trace_id, operation_name) in [(1, "Checkout"), (2, "Browse")] {
+            harness.end_span(
+                trace_id,
+                operation_name,
+                GrafbaseSpanKind::GraphqlOperation,
+                vec![KeyValue::new("graphql.operation.name", operation_name)],
+            );
+            harness.end_span(trace_id, "request", GrafbaseSpanKind::HttpRequest, Vec::new());
+        }
+
+        harness.end_span(
+            3,
+            "mobile-request",
+            GrafbaseSpanKind::HttpRequest,
+            vec![KeyValue::new("http.request.header.x-grafbase-client-name", "mobile")],
+        );
+
+        assert_eq!(harness.exported.names(), ["Checkout", "request", "mobile-request"]);
+    }
+
+    #[test]
+    fn late_spans_follow_the_trace_decision() {
+        let harness = Harness::new(config(), 0.5);
+
+        harness.end_span(SAMPLED, "kept-request", GrafbaseSpanKind::HttpRequest, Vec::new());
+        harness.end_span(
+            NOT_SAMPLED,
+            "dropped-request",
+            GrafbaseSpanKind::HttpRequest,
+            Vec::new(),
+        );
+
+        harness.end_span(SAMPLED, "kept-late", GrafbaseSpanKind::GraphqlOperation, Vec::new());
+        harness.end_span(
+            NOT_SAMPLED,
+            "dropped-late",
+            GrafbaseSpanKind::GraphqlOperation,
+            Vec::new(),
+        );
+
+        assert_eq!(harness.exported.names(), ["kept-request", "kept-late"]);
+    }
+
+    #[test]
+    fn evicted_traces_without_request_span_are_decided() {
+        let harness = Harness::new(
+            TailSamplingConfig {
+                max_buffered_traces: 1,
+                ..config()
+            },
+            0.5,
+        );
+
+        harness.end_span(SAMPLED, "background", GrafbaseSpanKind::GraphqlOperation, Vec::new());
+        harness.end_span(NOT_SAMPLED, "dropped", GrafbaseSpanKind::GraphqlOperation, Vec::new());
+        assert_eq!(harness.exported.names(), ["background"]);
+
+        harness.end_span(
+            SAMPLED,
+            "background-late",
+            GrafbaseSpanKind::GraphqlOperation,
+            Vec::new(),
+        );
+        assert_eq!(harness.exported.names(), ["background", "background-late"]);
+
+        harness.end_span(1, "other", GrafbaseSpanKind::GraphqlOperation, Vec::new());
+        harness.end_span(
+            NOT_SAMPLED,
+            "dropped-late",
+            GrafbaseSpanKind::GraphqlOperation,
+            Vec::new(),
+        );
+        assert_eq!(harness.exported.names(), ["background", "background-late"]);
+    }
+
+    #[test]
+    fn pending_traces_are_decided_on_shutdown() {
+        let harness = Harness::new(config(), 0.5);
+
+        harness.end_span(SAMPLED, "background", GrafbaseSpanKind::GraphqlOperation, Vec::new());
+        harness.end_span(NOT_SAMPLED, "dropped", GrafbaseSpanKind::GraphqlOperation, Vec::new());
+        assert!(harness.exported.names().is_empty());
+
+        harness.provider.shutdown().unwrap();
+        assert_eq!(harness.exported.names(), ["background"]);
+    }
+}
@@ -9,6 +9,7 @@ use opentelemetry_sdk::{
     },
 };
 
+use super::tail_sampling::TailSamplingProcessor;
 use crate::{
     config::{BatchExportConfig, TelemetryConfig},
     error::TracingError,
@@ -22,7 +23,12 @@ pub(super) fn build_trace_provider<I>(
 where
     I: IdGenerator + 'static,
 {
-    let base_sampler = Sampler::TraceIdRatioBased(config.tracing.sampling);
+    // With tail sampling every trace is recorded, the ratio is applied once the request finishes.
+    let base_sampler = if config.tracing.tail_sampling.is_some() {
+        Sampler::AlwaysOn
+    } else {
+        Sampler::TraceIdRatioBased(config.tracing.sampling)
+    };
     let mut builder = TracerProviderBuilder::default().with_id_generator(id_generator);
 
     if config.tracing.parent_based_sampler {
@@ -41,9 +47,30 @@ where
 }
 
 fn setup_exporters(
-    mut tracer_provider_builder: TracerProviderBuilder,
+    tracer_provider_builder: TracerProviderBuilder,
     config: &TelemetryConfig,
 ) -> Result<TracerProviderBuilder, TracingError> {
+    let processors = build_span_processors(config)?;
+
+    let Some(tail_sampling) = &config.tracing.tail_sampling else {
+        return Ok(processors
+            .into_iter()
+            .fold(tracer_provider_builder, |builder, processor| {
+                builder.with_span_processor(processor)
+            }));
+    };
+
+    let tail_sampling_processor = processors.into_iter().fold(
+        TailSamplingProcessor::new(&config.tracing, tail_sampling),
+        |tail_sampling, processor| tail_sampling.with_processor(processor),
+    );
+
+    Ok(tracer_provider_builder.with_span_processor(tail_sampling_processor))
+}
+
+fn build_span_processors(config: &TelemetryConfig) -> Result<Vec<BatchSpanProcessor>, TracingError> {
+    let mut processors = Vec::new();
+
     // stdout
     if let Some(stdout_exporter) = config.tracing_stdout_config() {
         let span_processor = build_batched_span_processor(
@@ -52,7 +79,7 @@ fn setup_exporters(
             opentelemetry_stdout::SpanExporter::default(),
         );
 
-        tracer_provider_builder = tracer_provider_builder.with_span_processor(span_processor);
+        processors.push(span_processor);
     }
 
     use super::exporter::{build_metadata, build_tls_config};
@@ -118,7 +145,7 @@ fn setup_exporters(
             span_exporter,
         );
 
-        tracer_provider_builder = tracer_provider_builder.with_span_processor(span_processor);
+        processors.push(span_processor);
     }
 
     if let Some(config) = config.grafbase_otlp_config() {
@@ -126,10 +153,10 @@ fn setup_exporters(
 
         let span_processor = build_batched_span_processor(config.timeout(), &config.batch_export(), span_exporter);
 
-        tracer_provider_builder = tracer_provider_builder.with_span_processor(span_processor);
+        processors.push(span_processor);
     }
 
-    Ok(tracer_provider_builder)
+    Ok(processors)
 }
 
 fn build_batched_span_processor(
@@ -64,6 +64,7 @@ impl HooksExtensionInstance for super::ExtensionInstanceSince0_17_0 {
                     contract_key: None,
                     event_queue,
                     hooks_context: Default::default(),
+                    override_labels: Vec::new(),
                 }),
                 Err(err) => Err(self
                     .store
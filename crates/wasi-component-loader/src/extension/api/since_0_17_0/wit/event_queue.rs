@@ -9,7 +9,7 @@ impl Host for InstanceState {}
 impl HostEventQueue for InstanceState {
     async fn pop(&mut self, self_: Resource<EventQueueResource>) -> wasmtime::Result<Option<Event>> {
         let this = self.resources.get(&self_)?;
-        Ok(this.pop().map(Into::into))
+        Ok(std::iter::from_fn(|| this.pop()).find_map(convert_event))
     }
 
     async fn drop(&mut self, res: Resource<EventQueueResource>) -> wasmtime::Result<()> {
@@ -19,15 +19,17 @@ impl HostEventQueue for InstanceState {
     }
 }
 
-impl From<event_queue::Event> for Event {
-    fn from(value: event_queue::Event) -> Self {
-        match value {
-            event_queue::Event::Operation(op) => Event::Operation(op.into()),
-            event_queue::Event::Subgraph(subgraph) => Event::Subgraph(subgraph.into()),
-            event_queue::Event::Http(http) => Event::Http(http.into()),
-            event_queue::Event::Extension(ext) => Event::Extension(ext.into()),
-        }
-    }
+/// Circuit breaker events have no counterpart in this SDK version and are skipped.
+fn convert_event(value: event_queue::Event) -> Option<Event> {
+    let event = match value {
+        event_queue::Event::Operation(op) => Event::Operation(op.into()),
+        event_queue::Event::Subgraph(subgraph) => Event::Subgraph(subgraph.into()),
+        event_queue::Event::Http(http) => Event::Http(http.into()),
+        event_queue::Event::Extension(ext) => Event::Extension(ext.into()),
+        event_queue::Event::CircuitBreaker(_) => return None,
+    };
+
+    Some(event)
 }
 
 impl From<event_queue::ExecutedOperation> for ExecutedOperation {
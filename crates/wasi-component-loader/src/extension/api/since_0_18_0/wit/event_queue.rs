@@ -11,12 +11,15 @@ impl Host for InstanceState {}
 
 impl HostEventQueue for InstanceState {
     async fn pop(&mut self, self_: Resource<EventQueueResource>) -> wasmtime::Result<Option<Event>> {
-        let this = self.resources.get(&self_)?;
+        let this = self.resources.get(&self_)?.clone();
 
-        match this.pop() {
-            Some(event) => Ok(Some(self.convert_event_0_18_0(event)?)),
-            None => Ok(None),
+        while let Some(event) = this.pop() {
+            if let Some(event) = self.convert_event_0_18_0(event)? {
+                return Ok(Some(event));
+            }
         }
+
+        Ok(None)
     }
 
     async fn drop(&mut self, res: Resource<EventQueueResource>) -> wasmtime::Result<()> {
@@ -27,15 +30,17 @@ impl HostEventQueue for InstanceState {
 }
 
 impl InstanceState {
-    fn convert_event_0_18_0(&mut self, event: event_queue::Event) -> wasmtime::Result<Event> {
+    /// Circuit breaker events have no counterpart in this SDK version and are skipped.
+    fn convert_event_0_18_0(&mut self, event: event_queue::Event) -> wasmtime::Result<Option<Event>> {
         let event = match event {
             event_queue::Event::Operation(op) => Event::Operation(op.into()),
             event_queue::Event::Subgraph(event) => self.convert_subgraph_event_0_18_0(event)?,
             event_queue::Event::Http(http) => Event::Http(http.into()),
             event_queue::Event::Extension(ext) => Event::Extension(ext.into()),
+            event_queue::Event::CircuitBreaker(_) => return Ok(None),
         };
 
-        Ok(event)
+        Ok(Some(event))
     }
 
     fn convert_subgraph_event_0_18_0(
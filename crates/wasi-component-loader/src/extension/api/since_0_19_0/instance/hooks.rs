@@ -60,6 +60,7 @@ impl HooksExtensionInstance for super::ExtensionInstanceSince0_19_0 {
                         contract_key,
                         event_queue,
                         hooks_context: Default::default(),
+                        override_labels: Vec::new(),
                     })
                 }
                 Err(err) => Err(self
@@ -8,12 +8,15 @@ impl Host for InstanceState {}
 
 impl HostEventQueue for InstanceState {
     async fn pop(&mut self, self_: Resource<EventQueueResource>) -> wasmtime::Result<Option<Event>> {
-        let this = self.resources.get(&self_)?;
+        let this = self.resources.get(&self_)?.clone();
 
-        match this.pop() {
-            Some(event) => Ok(Some(super::event_types::convert_event(self, event)?)),
-            None => Ok(None),
+        while let Some(event) = this.pop() {
+            if let Some(event) = super::event_types::convert_event(self, event)? {
+                return Ok(Some(event));
+            }
         }
+
+        Ok(None)
     }
 
     async fn drop(&mut self, res: Resource<EventQueueResource>) -> wasmtime::Result<()> {
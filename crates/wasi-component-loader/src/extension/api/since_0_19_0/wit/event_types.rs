@@ -4,15 +4,17 @@ pub use super::grafbase::sdk::event_types::*;
 
 impl Host for InstanceState {}
 
-pub(crate) fn convert_event(state: &mut InstanceState, event: event_queue::Event) -> wasmtime::Result<Event> {
+/// Circuit breaker events have no counterpart in the SDK event types and are skipped.
+pub(crate) fn convert_event(state: &mut InstanceState, event: event_queue::Event) -> wasmtime::Result<Option<Event>> {
     let event = match event {
         event_queue::Event::Operation(op) => Event::Operation(op.into()),
         event_queue::Event::Subgraph(event) => convert_subgraph_event(state, event)?,
         event_queue::Event::Http(http) => Event::Http(http.into()),
         event_queue::Event::Extension(ext) => Event::Extension(ext.into()),
+        event_queue::Event::CircuitBreaker(_) => return Ok(None),
     };
 
-    Ok(event)
+    Ok(Some(event))
 }
 
 fn convert_subgraph_event(
@@ -56,6 +56,7 @@ impl HooksExtensionInstance for super::ExtensionInstanceSince0_21_0 {
                         contract_key,
                         event_queue,
                         hooks_context: context.into(),
+                        override_labels: Vec::new(),
                     })
                 }
                 Err(err) => Err(self
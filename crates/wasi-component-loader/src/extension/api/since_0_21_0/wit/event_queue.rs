@@ -46,11 +46,13 @@ pub fn add_to_linker_impl(linker: &mut wasmtime::component::Linker<InstanceState
         move |mut caller: wasmtime::StoreContextMut<'_, InstanceState>, (event_queue,): (Resource<EventQueue>,)| {
             Box::new(async move {
                 let state = caller.data_mut();
-                let event_queue = state.resources.get(&event_queue)?;
-                match event_queue.pop() {
-                    Some(event) => Ok((Some(event_types::convert_event(state, event)?),)),
-                    None => Ok((None,)),
+                let event_queue = state.resources.get(&event_queue)?.clone();
+                while let Some(event) = event_queue.pop() {
+                    if let Some(event) = event_types::convert_event(state, event)? {
+                        return Ok((Some(event),));
+                    }
                 }
+                Ok((None,))
             })
         },
     )?;
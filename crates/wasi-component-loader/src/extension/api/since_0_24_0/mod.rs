@@ -0,0 +1,3 @@
+mod instance;
+pub mod wit;
+pub use instance::SdkPre0_24_0;
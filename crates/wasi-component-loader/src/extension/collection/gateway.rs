@@ -26,6 +26,10 @@ pub struct GatewayWasmExtensionsInner {
     pub(crate) engine: wasmtime::Engine,
     pub(crate) hooks: Option<Pool>,
     pub(crate) hooks_event_filter: Option<event_queue::EventFilter>,
+    /// Whether the hooks extension may implement `on_graphql_subgraph_response`.
+    pub(crate) hooks_on_graphql_subgraph_response: bool,
+    /// Whether the hooks extension implements `on_graphql_response`.
+    pub(crate) hooks_on_graphql_response: bool,
     pub(crate) authentication: Vec<Pool>,
 }
 
@@ -36,6 +40,8 @@ impl Default for GatewayWasmExtensionsInner {
             engine,
             hooks: None,
             hooks_event_filter: None,
+            hooks_on_graphql_subgraph_response: false,
+            hooks_on_graphql_response: false,
             authentication: Vec::new(),
         }
     }
@@ -57,6 +63,8 @@ impl GatewayWasmExtensions {
             engine,
             hooks: None,
             hooks_event_filter: None,
+            hooks_on_graphql_subgraph_response: false,
+            hooks_on_graphql_response: false,
             authentication: Vec::new(),
         };
 
@@ -65,7 +73,10 @@ impl GatewayWasmExtensions {
         for config in extension_configs {
             let manifiest = &extension_catalog[config.id].manifest;
             match &manifiest.r#type {
-                extension_catalog::Type::Hooks(HooksType { event_filter }) => {
+                extension_catalog::Type::Hooks(HooksType {
+                    event_filter,
+                    on_graphql_response,
+                }) => {
                     if inner.hooks.is_some() {
                         return Err(wasmtime::Error::msg(
                             "Multiple hooks extensions found in the configuration, but only one is allowed.",
@@ -75,6 +86,10 @@ impl GatewayWasmExtensions {
                         .as_ref()
                         .or(manifiest.legacy_event_filter.as_ref())
                         .map(convert_event_filter);
+                    // Older SDKs don't have the GraphQL response hooks.
+                    let has_graphql_response_hooks = config.sdk_version >= semver::Version::new(0, 24, 0);
+                    inner.hooks_on_graphql_subgraph_response = has_graphql_response_hooks;
+                    inner.hooks_on_graphql_response = has_graphql_response_hooks && *on_graphql_response;
                     inner.hooks = Some(
                         Pool::new(
                             &inner.engine,
@@ -113,6 +128,7 @@ fn convert_event_filter(filter: &extension_catalog::EventFilter) -> event_queue:
                     extension_catalog::EventType::SubgraphRequest => event_queue::EventFilterType::SubgraphRequest,
                     extension_catalog::EventType::HttpRequest => event_queue::EventFilterType::HttpRequest,
                     extension_catalog::EventType::Extension => event_queue::EventFilterType::Extension,
+                    extension_catalog::EventType::CircuitBreaker => event_queue::EventFilterType::CircuitBreaker,
                 });
             }
             event_queue::EventFilter::Types(out)
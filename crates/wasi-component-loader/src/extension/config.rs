@@ -39,7 +39,7 @@ pub(super) fn load_extensions_config(
     let mut wasm_extensions = Vec::with_capacity(extension_catalog.len());
 
     let can_skip_sending_events = extension_catalog.iter().all(|ext| match &ext.manifest.r#type {
-        extension_catalog::Type::Hooks(HooksType { event_filter }) => event_filter
+        extension_catalog::Type::Hooks(HooksType { event_filter, .. }) => event_filter
             .as_ref()
             .or(ext.manifest.legacy_event_filter.as_ref())
             .map(|event_filter| match event_filter {
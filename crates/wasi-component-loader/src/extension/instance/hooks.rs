@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use bytes::Bytes;
 use engine::EngineOperationContext;
 use engine_error::{ErrorResponse, GraphqlError};
 use engine_schema::{GraphqlSubgraph, VirtualSubgraph};
@@ -19,6 +20,7 @@ pub(crate) trait HooksExtensionInstance {
             contract_key: None,
             event_queue: Arc::new(event_queue),
             hooks_context: Default::default(),
+            override_labels: Vec::new(),
         }))))
     }
 
@@ -51,4 +53,22 @@ pub(crate) trait HooksExtensionInstance {
     ) -> BoxFuture<'a, wasmtime::Result<Result<http::HeaderMap, GraphqlError>>> {
         Box::pin(std::future::ready(Ok(Ok(headers))))
     }
+
+    fn on_graphql_subgraph_response<'a>(
+        &'a mut self,
+        ctx: EngineOperationContext,
+        subgraph: GraphqlSubgraph<'a>,
+        parts: http::response::Parts,
+    ) -> BoxFuture<'a, wasmtime::Result<Result<http::response::Parts, GraphqlError>>> {
+        Box::pin(std::future::ready(Ok(Ok(parts))))
+    }
+
+    fn on_graphql_response(
+        &mut self,
+        event_queue: Arc<EventQueue>,
+        hooks_context: Arc<[u8]>,
+        body: Bytes,
+    ) -> BoxFuture<'_, wasmtime::Result<Result<Bytes, GraphqlError>>> {
+        Box::pin(std::future::ready(Ok(Ok(body))))
+    }
 }
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use bytes::Bytes;
 use engine::EngineOperationContext;
 use engine_error::{ErrorResponse, GraphqlError};
 use engine_schema::{GraphqlSubgraph, VirtualSubgraph};
@@ -21,6 +22,7 @@ impl GatewayHooksExtension for GatewayWasmExtensions {
                 contract_key: None,
                 event_queue: Arc::new(event_queue),
                 hooks_context: Default::default(),
+                override_labels: Vec::new(),
             });
         };
 
@@ -48,6 +50,10 @@ impl GatewayHooksExtension for GatewayWasmExtensions {
 }
 
 impl EngineHooksExtension<EngineOperationContext> for EngineWasmExtensions {
+    fn has_graphql_subgraph_response_hook(&self) -> bool {
+        self.gateway_extensions.hooks_on_graphql_subgraph_response
+    }
+
     async fn on_graphql_subgraph_request<'r>(
         &self,
         context: EngineOperationContext,
@@ -81,4 +87,41 @@ impl EngineHooksExtension<EngineOperationContext> for EngineWasmExtensions {
 
         wasmsafe!(instance.on_virtual_subgraph_request(context, subgraph, headers).await)
     }
+
+    async fn on_graphql_subgraph_response(
+        &self,
+        context: EngineOperationContext,
+        subgraph: GraphqlSubgraph<'_>,
+        parts: response::Parts,
+    ) -> Result<response::Parts, GraphqlError> {
+        let Some(pool) = self.gateway_extensions.hooks.as_ref() else {
+            return Ok(parts);
+        };
+        let mut instance = pool.get().await.map_err(|e| {
+            tracing::error!("Failed to get instance from pool: {e}");
+            GraphqlError::internal_extension_error()
+        })?;
+
+        wasmsafe!(instance.on_graphql_subgraph_response(context, subgraph, parts).await)
+    }
+
+    async fn on_graphql_response(
+        &self,
+        event_queue: Arc<EventQueue>,
+        hooks_context: Arc<[u8]>,
+        body: Bytes,
+    ) -> Result<Bytes, GraphqlError> {
+        if !self.gateway_extensions.hooks_on_graphql_response {
+            return Ok(body);
+        }
+        let Some(pool) = self.gateway_extensions.hooks.as_ref() else {
+            return Ok(body);
+        };
+        let mut instance = pool.get().await.map_err(|e| {
+            tracing::error!("Failed to get instance from pool: {e}");
+            GraphqlError::internal_extension_error()
+        })?;
+
+        wasmsafe!(instance.on_graphql_response(event_queue, hooks_context, body).await)
+    }
 }
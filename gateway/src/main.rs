@@ -37,9 +37,10 @@ fn main() -> anyhow::Result<()> {
             if subgraph.introspection_url.is_some()
                 || subgraph.introspection_headers.is_some()
                 || subgraph.schema_path.is_some()
+                || subgraph.mock
             {
                 tracing::warn!(
-                    "Subgraph {name} has introspection_url, introspection_headers or schema_path set. They're ignored in the federated gateway."
+                    "Subgraph {name} has introspection_url, introspection_headers, schema_path or mock set. They're ignored in the federated gateway."
                 );
             }
         }
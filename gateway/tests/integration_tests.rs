@@ -21,7 +21,7 @@ use duct::{Handle, cmd};
 use futures_util::future::BoxFuture;
 use futures_util::{Future, FutureExt};
 use http::{HeaderMap, StatusCode};
-use indoc::indoc;
+use indoc::{formatdoc, indoc};
 use tempfile::tempdir;
 use tokio::time::Instant;
 use tokio::{runtime::Runtime, time::sleep};
@@ -1091,6 +1091,315 @@ fn health_custom_listener() {
     });
 }
 
+#[test]
+fn health_reports_load_balanced_subgraphs() {
+    let config = r#"
+        [health]
+        include_subgraph_endpoints = true
+
+        [subgraphs.accounts.load_balancing]
+        endpoints = [
+            { url = "http://127.0.0.1:46697/a" },
+            { url = "http://127.0.0.1:46697/b" },
+        ]
+    "#;
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/health");
+
+        let response = client.client().get(url).send().await.unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let body: serde_json::Value = response.json().await.unwrap();
+
+        insta::assert_json_snapshot!(&body, @r#"
+        {
+          "status": "healthy",
+          "subgraphs": [
+            {
+              "name": "accounts",
+              "endpoints": [
+                {
+                  "url": "http://127.0.0.1:46697/a",
+                  "healthy": true,
+                  "inflight_requests": 0
+                },
+                {
+                  "url": "http://127.0.0.1:46697/b",
+                  "healthy": true,
+                  "inflight_requests": 0
+                }
+              ]
+            }
+          ]
+        }
+        "#);
+    });
+}
+
+#[test]
+fn active_health_checks_send_subgraph_headers() {
+    let server = runtime().block_on(async {
+        let server = wiremock::MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/a"))
+            .and(header("x-probe", "yes"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": {"__typename": "Query"}})),
+            )
+            .mount(&server)
+            .await;
+
+        server
+    });
+
+    let config = formatdoc! {r#"
+        [health]
+        include_subgraph_endpoints = true
+
+        [subgraphs.accounts.load_balancing]
+        endpoints = [
+            {{ url = "{uri}/a" }},
+            {{ url = "{uri}/b" }},
+        ]
+
+        [subgraphs.accounts.load_balancing.health_check]
+        enabled = true
+        interval = "100ms"
+        unhealthy_threshold = 1
+
+        [[subgraphs.accounts.headers]]
+        rule = "insert"
+        name = "x-probe"
+        value = "yes"
+    "#, uri = server.uri()};
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        sleep(Duration::from_millis(500)).await;
+
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/health");
+
+        let response = client.client().get(url).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body: serde_json::Value = response.json().await.unwrap();
+        let endpoints = &body["subgraphs"][0]["endpoints"];
+
+        // Only the endpoint answering to probes with the header is healthy.
+        assert_eq!(endpoints[0]["healthy"], true);
+        assert_eq!(endpoints[1]["healthy"], false);
+
+        drop(server);
+    });
+}
+
+#[test]
+fn health_hides_load_balanced_subgraphs_by_default() {
+    let config = r#"
+        [subgraphs.accounts.load_balancing]
+        endpoints = [
+            { url = "http://127.0.0.1:46697/a" },
+            { url = "http://127.0.0.1:46697/b" },
+        ]
+    "#;
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/health");
+
+        let response = client.client().get(url).send().await.unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let body: serde_json::Value = response.json().await.unwrap();
+
+        insta::assert_json_snapshot!(&body, @r#"
+        {
+          "status": "healthy"
+        }
+        "#);
+    });
+}
+
+#[test]
+fn health_liveness_and_readiness() {
+    let schema = load_schema("big");
+
+    with_static_server("", &schema, None, None, |client| async move {
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+
+        url.set_path("/health/live");
+        let response = client.client().get(url.clone()).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body: serde_json::Value = response.json().await.unwrap();
+        insta::assert_json_snapshot!(&body, @r#"
+        {
+          "status": "healthy"
+        }
+        "#);
+
+        url.set_path("/health/ready");
+        let response = client.client().get(url).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body: serde_json::Value = response.json().await.unwrap();
+        insta::assert_json_snapshot!(&body, @r#"
+        {
+          "status": "ready",
+          "dependencies": [],
+          "extensions": []
+        }
+        "#);
+    });
+}
+
+#[test]
+fn readiness_fails_with_unreachable_redis() {
+    let config = indoc! {r#"
+        [entity_caching]
+        enabled = true
+        storage = "redis"
+
+        [entity_caching.redis]
+        url = "redis://127.0.0.1:1"
+    "#};
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+
+        url.set_path("/health/live");
+        let response = client.client().get(url.clone()).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        url.set_path("/health/ready");
+        let response = client.client().get(url).send().await.unwrap();
+        assert_eq!(response.status(), 503);
+
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["status"], "not_ready");
+        assert_eq!(body["dependencies"][0]["name"], "entity_cache");
+        assert_eq!(body["dependencies"][0]["reachable"], false);
+        assert!(body["dependencies"][0]["error"].is_string());
+    });
+}
+
+#[test]
+fn readiness_probes_subgraphs() {
+    let server = runtime().block_on(async {
+        let server = wiremock::MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(header("x-probe", "yes"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": {"__typename": "Query"}})),
+            )
+            // The second readiness check reuses the results of the first probe.
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        server
+    });
+
+    let config = formatdoc! {r#"
+        [health.subgraph_probe]
+        enabled = true
+        interval = "1h"
+
+        [subgraphs.accounts]
+        url = "{}"
+
+        [[subgraphs.accounts.headers]]
+        rule = "insert"
+        name = "x-probe"
+        value = "yes"
+    "#, server.uri()};
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/health/ready");
+
+        for _ in 0..2 {
+            let response = client.client().get(url.clone()).send().await.unwrap();
+            // Unavailable subgraphs don't make the gateway unready.
+            assert_eq!(response.status(), 200);
+
+            let body: serde_json::Value = response.json().await.unwrap();
+            let subgraphs = body["subgraphs"].as_array().unwrap();
+            let healthy = |name: &str| {
+                subgraphs
+                    .iter()
+                    .find(|subgraph| subgraph["name"] == name)
+                    .map(|subgraph| subgraph["healthy"].as_bool().unwrap())
+            };
+
+            assert_eq!(healthy("accounts"), Some(true));
+            assert_eq!(healthy("products"), Some(false));
+            assert_eq!(healthy("reviews"), Some(false));
+        }
+
+        server.verify().await;
+    });
+}
+
+#[test]
+fn trusted_documents_manifest() {
+    let manifest_dir = tempdir().unwrap();
+    let manifest_path = manifest_dir.path().join("web.json");
+
+    fs::write(
+        &manifest_path,
+        r#"{ "format": "apollo-persisted-query-manifest", "version": 1, "operations": [{ "id": "abc", "body": "query { __typename }", "name": "Typename", "type": "query" }] }"#,
+    )
+    .unwrap();
+
+    let config = formatdoc! {r#"
+        [trusted_documents]
+        enabled = true
+        enforced = true
+
+        [trusted_documents.manifests]
+        web = "{}"
+    "#, manifest_path.display()};
+
+    let schema = load_schema("tiny");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let send = |doc_id: &'static str| {
+            client
+                .client()
+                .post(client.endpoint())
+                .header("x-grafbase-client-name", "web")
+                .json(&serde_json::json!({ "doc_id": doc_id }))
+                .send()
+        };
+
+        let response: serde_json::Value = send("abc").await.unwrap().json().await.unwrap();
+        assert_eq!(response, serde_json::json!({ "data": { "__typename": "Query" } }));
+
+        let response: serde_json::Value = send("def").await.unwrap().json().await.unwrap();
+        assert_eq!(
+            response["errors"][0]["message"],
+            serde_json::json!("Unknown trusted document id: 'def'")
+        );
+    });
+}
+
 #[test]
 fn schema_file_hot_reload() {
     let config = indoc! {r#"
@@ -14,6 +14,7 @@ use crate::{Client, clickhouse_client, load_schema, runtime, with_static_server}
 
 mod object_storage;
 mod operation;
+mod prometheus;
 mod request;
 
 const METRICS_DELAY: Duration = Duration::from_secs(6);
@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use indoc::{formatdoc, indoc};
+
+use crate::{load_schema, with_static_server};
+
+#[test]
+fn scrape_prometheus_endpoint() {
+    let config = indoc! {r#"
+        [telemetry]
+        service_name = "prometheus-test"
+
+        [telemetry.metrics.exporters.prometheus]
+        enabled = true
+        refresh_interval = "100ms"
+    "#};
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let response: serde_json::Value = client.gql("{ __typename }").send().await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "__typename": "Query"
+          }
+        }
+        "#);
+
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/metrics");
+
+        // The metrics are rendered at every refresh interval.
+        let mut body = String::new();
+
+        for _ in 0..50 {
+            let response = client.client().get(url.clone()).send().await.unwrap();
+
+            assert_eq!(response.status(), 200);
+            assert_eq!(
+                response.headers()["content-type"],
+                "text/plain; version=0.0.4; charset=utf-8"
+            );
+
+            body = response.text().await.unwrap();
+
+            if body.contains("graphql_operation_duration_milliseconds_count") {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        assert!(
+            body.contains("# TYPE graphql_operation_duration_milliseconds histogram"),
+            "{body}"
+        );
+        assert!(
+            body.contains("# TYPE http_server_request_duration_milliseconds histogram"),
+            "{body}"
+        );
+
+        let bucket = body
+            .lines()
+            .find(|line| {
+                line.starts_with("graphql_operation_duration_milliseconds_bucket{") && line.contains("le=\"+Inf\"")
+            })
+            .unwrap_or_else(|| panic!("{body}"));
+
+        assert!(bucket.contains("service_name=\"prometheus-test\""), "{bucket}");
+    });
+}
+
+#[test]
+fn prometheus_endpoint_on_its_own_listener() {
+    let listen = crate::listen_address();
+    let config = formatdoc! {r#"
+        [telemetry.metrics.exporters.prometheus]
+        enabled = true
+        listen = "{listen}"
+        path = "/prometheus"
+        refresh_interval = "100ms"
+    "#};
+
+    let schema = load_schema("big");
+
+    with_static_server(config.as_str(), &schema, None, None, |client| async move {
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/prometheus");
+
+        // Not served by the gateway itself.
+        let response = client.client().get(url).send().await.unwrap();
+        assert_eq!(response.status(), 404);
+
+        let url = format!("http://{listen}/prometheus");
+        let mut response = None;
+
+        for _ in 0..50 {
+            if let Ok(ok) = client.client().get(&url).send().await {
+                response = Some(ok);
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        let response = response.expect("the metrics listener should be up");
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/plain; version=0.0.4; charset=utf-8"
+        );
+    });
+}